@@ -127,6 +127,8 @@ type UpdateInfo = {
   asset_url?: string
   asset_name?: string
   changelog?: string
+  asset_size?: number
+  asset_sha256?: string
   [key: string]: any
 }
 
@@ -560,7 +562,9 @@ function App() {
     try {
       const path = await invoke('download_update', {
         assetUrl: updateInfo.asset_url,
-        assetName: updateInfo.asset_name
+        assetName: updateInfo.asset_name,
+        expectedSize: updateInfo.asset_size ?? null,
+        expectedSha256: updateInfo.asset_sha256 ?? null
       }) as string;
       setDownloadedUpdatePath(path);
     } catch (error) {
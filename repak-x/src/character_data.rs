@@ -80,6 +80,14 @@ static CHARACTER_CACHE: Lazy<Arc<RwLock<CharacterDataCache>>> = Lazy::new(|| {
     Arc::new(RwLock::new(CharacterDataCache::default()))
 });
 
+// Path ID patterns used by identify_mod_from_paths, compiled once
+static SKIN_ID_REGEX: Lazy<regex_lite::Regex> = Lazy::new(|| {
+    regex_lite::Regex::new(r"(\d{7})").unwrap()
+});
+static HERO_ID_REGEX: Lazy<regex_lite::Regex> = Lazy::new(|| {
+    regex_lite::Regex::new(r"/(?:Hero|Characters?)/(\d{4})/").unwrap()
+});
+
 // ============================================================================
 // CHARACTER ID LOOKUP
 // ============================================================================
@@ -717,14 +725,11 @@ pub fn identify_mod_from_paths(file_paths: &[String]) -> Option<(String, String)
     
     // Look for skin ID patterns in file paths
     // Common patterns: /1011001/, /Hero/1011/, etc.
-    let skin_id_regex = regex_lite::Regex::new(r"(\d{7})").unwrap();
-    let hero_id_regex = regex_lite::Regex::new(r"/(?:Hero|Characters?)/(\d{4})/").unwrap();
-    
     let cache = CHARACTER_CACHE.read().unwrap();
     
     for path in file_paths {
         // Try to find exact skin ID
-        for caps in skin_id_regex.captures_iter(path) {
+        for caps in SKIN_ID_REGEX.captures_iter(path) {
             if let Some(m) = caps.get(1) {
                 let potential_id = m.as_str();
                 if let Some(skin) = cache.by_skin_id.get(potential_id) {
@@ -734,7 +739,7 @@ pub fn identify_mod_from_paths(file_paths: &[String]) -> Option<(String, String)
         }
         
         // Try to find character ID
-        if let Some(caps) = hero_id_regex.captures(path) {
+        if let Some(caps) = HERO_ID_REGEX.captures(path) {
             if let Some(m) = caps.get(1) {
                 let char_id = m.as_str();
                 // Find any skin with this character ID
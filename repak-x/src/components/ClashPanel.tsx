@@ -87,7 +87,7 @@ const ClashPanel = ({ clashes, mods = [], onSetPriority, onClose }: ClashPanelPr
                                                     {mod && (
                                                         <div className="clash-priority-wrapper">
                                                             <NumberInput
-                                                                value={mod.priority || 0}
+                                                                value={Math.min(mod.priority || 0, 99)}
                                                                 min={0}
                                                                 max={99}
                                                                 onChange={(val) => onSetPriority && onSetPriority(path, val)}
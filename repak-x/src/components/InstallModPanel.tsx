@@ -26,6 +26,9 @@ type ModInput = {
   auto_fix_texture?: boolean
   auto_fix_serialize_size?: boolean
   auto_force_legacy?: boolean
+  predicted_output_name?: string
+  predicted_format?: 'iostore' | 'legacy_pak'
+  estimated_output_size?: number
   [key: string]: any
 }
 
@@ -34,6 +37,8 @@ type ModSetting = {
   fixSerializeSize: boolean
   toRepak: boolean
   forceLegacy: boolean
+  isPatch: boolean
+  preserveCompression: boolean
   compression: string
   usmapPath: string
   customName: string
@@ -183,6 +188,8 @@ const buildInitialSettings = (mods: ModInput[] = []): Record<number, ModSetting>
       fixSerializeSize: canApplyPatches ? (mod.auto_fix_serialize_size || false) : false,
       toRepak: effectiveToRepak,
       forceLegacy: mod.contains_uassets === false ? true : (mod.auto_force_legacy || false),
+      isPatch: true,
+      preserveCompression: false,
       compression: 'Oodle',
       usmapPath: '',
       customName: '',
@@ -301,6 +308,8 @@ export default function InstallModPanel({ mods, allTags, folders = [], onCreateT
       ...modSettings[idx],
       toRepak: isRepakLocked(mod) ? false : (modSettings[idx]?.toRepak || false),
       forceLegacy: modSettings[idx]?.forceLegacy || false,
+      isPatch: modSettings[idx]?.isPatch ?? true,
+      preserveCompression: modSettings[idx]?.preserveCompression || false,
       installSubfolder: modSettings[idx]?.installSubfolder || ''
     }))
     onInstall(modsToInstall)
@@ -403,6 +412,13 @@ export default function InstallModPanel({ mods, allTags, folders = [], onCreateT
                         )}
                       </div>
 
+                      {mod.predicted_output_name && (
+                        <div className="install-mod-card__preview">
+                          → will install as {mod.predicted_output_name}
+                          {(modSettings[idx]?.forceLegacy || mod.predicted_format === 'legacy_pak') ? ' (.pak)' : ' (.pak + .utoc/.ucas)'}
+                        </div>
+                      )}
+
 
                       <div className="install-mod-card__tags">
                         <div className="install-mod-card__row">
@@ -519,6 +535,44 @@ export default function InstallModPanel({ mods, allTags, folders = [], onCreateT
                           </div>
                         </Switch>
 
+                        <Switch
+                          size="md"
+                          color="primary"
+                          checked={modSettings[idx]?.isPatch ?? true}
+                          onChange={(value) => updateModSetting(idx, 'isPatch', value)}
+                          className={`install-toggle patch-toggle ${(modSettings[idx]?.isPatch ?? true) ? 'active' : ''}`}
+                          title="Patch paks (_9999999_P) override game files and other mods. Turn off for full replacements or new content that shouldn't override anything"
+                        >
+                          <div className="install-toggle__text">
+                            <span className="install-toggle__label">Patch Pak</span>
+                            <span className="install-toggle__hint">
+                              {(modSettings[idx]?.isPatch ?? true)
+                                ? 'Installed as _9999999_P, overrides other mods'
+                                : 'Base pak: plain name, loads before all patch paks so any of them overrides it'}
+                            </span>
+                          </div>
+                        </Switch>
+
+                        {!mod.is_dir && (
+                          <Switch
+                            size="md"
+                            color="primary"
+                            checked={modSettings[idx]?.preserveCompression || false}
+                            onChange={(value) => updateModSetting(idx, 'preserveCompression', value)}
+                            className={`install-toggle compression-toggle ${modSettings[idx]?.preserveCompression ? 'active' : ''}`}
+                            title="Repack with the source pak's compression instead of Oodle. Applies to legacy PAK output; IoStore bundles are always Oodle"
+                          >
+                            <div className="install-toggle__text">
+                              <span className="install-toggle__label">Keep Compression</span>
+                              <span className="install-toggle__hint">
+                                {modSettings[idx]?.preserveCompression
+                                  ? "Using the source pak's codec"
+                                  : 'Repacked with Oodle'}
+                              </span>
+                            </div>
+                          </Switch>
+                        )}
+
                         {mod.contains_uassets !== false && (
                           <Switch
                             size="md"
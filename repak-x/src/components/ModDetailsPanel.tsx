@@ -29,6 +29,7 @@ type ModDetailsData = {
   mod_type?: string
   category?: string
   character_name?: string
+  skin_name?: string | null
   additional_categories?: string[]
   has_blueprint?: boolean
   is_iostore?: boolean
@@ -48,6 +49,28 @@ export default function ModDetailsPanel({ mod, initialDetails, onClose, characte
   const [details, setDetails] = useState<ModDetailsData | null>(initialDetails || null)
   const [loading, setLoading] = useState(!initialDetails)
   const [error, setError] = useState<string | null>(null)
+  const [pakVersion, setPakVersion] = useState<string | null>(null)
+  const [supportedVersions, setSupportedVersions] = useState<string[]>([])
+
+  useEffect(() => {
+    invoke<string[]>('list_supported_versions')
+      .then(setSupportedVersions)
+      .catch(() => setSupportedVersions([]))
+  }, [])
+
+  useEffect(() => {
+    let cancelled = false
+    setPakVersion(null)
+    if (!mod) return
+
+    invoke<string>('detect_pak_version', { path: mod.path })
+      .then(version => { if (!cancelled) setPakVersion(version) })
+      .catch(err => console.warn('Failed to detect pak version:', err))
+
+    return () => {
+      cancelled = true
+    }
+  }, [mod])
 
   useEffect(() => {
     let cancelled = false
@@ -179,6 +202,7 @@ export default function ModDetailsPanel({ mod, initialDetails, onClose, characte
                       <img src={getHeroImage(details.character_name, characterData)} alt="" />
                     )}
                     {details.character_name}
+                    {details.skin_name && details.skin_name !== 'Unknown Skin' && ` — ${details.skin_name}`}
                   </div>
                 )}
                 {details.mod_type?.startsWith('Multiple Heroes') && (
@@ -268,6 +292,14 @@ export default function ModDetailsPanel({ mod, initialDetails, onClose, characte
                 <span className="detail-label">Size:</span>
                 <span className="detail-value">{formatFileSize(details.total_size ?? 0)}</span>
               </div>
+              {pakVersion && (
+                <div className="detail-item">
+                  <span className="detail-label">Pak Version:</span>
+                  <Tooltip title={supportedVersions.length > 0 ? `Supported: ${supportedVersions.join(', ')}` : ''}>
+                    <span className="detail-value">{pakVersion}</span>
+                  </Tooltip>
+                </div>
+              )}
               {mod.folder_id && (
                 <div className="detail-item">
                   <span className="detail-label">Folder:</span>
@@ -493,11 +493,11 @@ const ModItem = memo(function ModItem({
                 <span className="mod-size">{formatFileSize(mod.file_size ?? 0)}</span>
                 <div className="actions-right">
                     <NumberInput
-                        value={mod.priority || 0}
+                        value={Math.min(mod.priority || 0, 7)}
                         min={0}
                         max={7}
                         onChange={(newPriority) => handleSetPriority(mod.path, newPriority)}
-                        disabled={gameRunning}
+                        disabled={gameRunning || !/_P\.[^.]+$/i.test(mod.path)}
                     />
                     <div className="mod-switch-wrapper" onClick={(e) => e.stopPropagation()} >
                         <Switch title={mod.enabled ? 'Disable mod' : 'Enable mod'}
@@ -7,7 +7,7 @@ import Checkbox from './ui/Checkbox'
 import { LuFolderInput } from "react-icons/lu"
 import { RiSparkling2Fill } from "react-icons/ri"
 import { CgPerformance } from "react-icons/cg"
-import { MdRefresh, MdArticle } from "react-icons/md"
+import { MdRefresh, MdArticle, MdTexture } from "react-icons/md"
 import { FaDiscord } from "react-icons/fa"
 import { RiGraduationCapFill } from "react-icons/ri"
 import { BsKeyboardFill } from "react-icons/bs"
@@ -73,6 +73,7 @@ export default function SettingsPanel({ settings, onSave, onClose, theme, setThe
   const [parallelProcessing, setLocalParallelProcessing] = useState(settings.parallelProcessing || false);
   const [holdToDelete, setHoldToDelete] = useState(settings.holdToDelete !== false);
   const [enableDrp, setEnableDrp] = useState(settings.enableDrp !== false);
+  const [autoStripMipmaps, setAutoStripMipmaps] = useState(false);
   const [usmapStatus, setUsmapStatus] = useState('');
   const [showRatMode, setShowRatMode] = useState(false);
 
@@ -95,7 +96,15 @@ export default function SettingsPanel({ settings, onSave, onClose, theme, setThe
     }
   }, [usmapStatus]);
 
+  useEffect(() => {
+    invoke<boolean>('get_auto_strip_mipmaps')
+      .then(setAutoStripMipmaps)
+      .catch((error) => console.error('Failed to load auto strip mipmaps setting:', error));
+  }, []);
+
   const handleSave = () => {
+    invoke('set_auto_strip_mipmaps', { enabled: autoStripMipmaps })
+      .catch((error) => console.error('Failed to save auto strip mipmaps setting:', error));
     onSave({
       globalUsmap,
       hideSuffix,
@@ -329,6 +338,26 @@ export default function SettingsPanel({ settings, onSave, onClose, theme, setThe
             </div>
           </div>
 
+          <div className="setting-section">
+            <h3>Installation</h3>
+            <div className="setting-group">
+              <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+                <div style={{ display: 'flex', alignItems: 'center' }}>
+                  <MdTexture style={{ marginRight: '8px', color: accentColor }} />
+                  <span style={{ fontWeight: 'normal', opacity: 0.9 }}>Always Strip Mipmaps on Texture Mods</span>
+                </div>
+                <Switch style={{ marginTop: '0.5rem' }}
+                  checked={autoStripMipmaps}
+                  onChange={(checked: boolean) => setAutoStripMipmaps(checked)}
+                />
+              </div>
+              <p style={{ fontSize: '0.8rem', opacity: 0.6, marginLeft: '24px', marginTop: '-0.8rem' }}>
+                Forces the texture fix on every mod with textures, even if you leave it unchecked. Smaller files and less
+                streaming stutter, but textures lose their lower-resolution mips and can look grainy from a distance.
+              </p>
+            </div>
+          </div>
+
           <div className="setting-section">
             <h3>Experimental</h3>
             <div className="setting-group">
@@ -1,19 +1,19 @@
 pub mod install_mod_logic;
 
-use crate::install_mod::install_mod_logic::archives::{extract_zip, extract_rar, extract_7z};
-use crate::uasset_detection::{detect_texture_files, detect_static_mesh_files};
+use crate::install_mod::install_mod_logic::archives::{extract_zip, extract_rar, extract_7z, prepare_kept_extraction_dir};
+use crate::uasset_detection::detect_texture_and_static_mesh_files;
 use crate::utils::{collect_files, get_current_pak_characteristics};
 use crate::utoc_utils::read_utoc;
-use log::{debug, error};
+use log::{debug, error, info, warn};
 use repak::utils::AesKey;
 use repak::Compression::Oodle;
 use repak::{Compression, PakReader};
 use std::fs::File;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::LazyLock;
-use tempfile::tempdir;
+use std::sync::{Arc, LazyLock, RwLock};
+use tempfile::{tempdir, TempDir};
 use walkdir::WalkDir;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -47,6 +47,19 @@ pub struct InstallableMod {
     /// Force legacy PAK format instead of IoStore conversion
     /// Used for Audio/Config mods that don't need IoStore processing
     pub force_legacy_pak: bool,
+    /// Rename the installed .pak to .bak_repak so the mod is staged disabled
+    #[serde(default)]
+    pub install_disabled: bool,
+    /// Install as a patch pak (`_9999999_P` name). Base paks keep the plain name, are mounted
+    /// before every patch pak and so never override other mods
+    #[serde(default = "default_is_patch")]
+    pub is_patch: bool,
+    /// Encrypt only the pak index when repacking (source pak used index-only encryption)
+    #[serde(default)]
+    pub encrypt_index_only: bool,
+    /// Repack legacy paks with the source pak's own compression instead of `compression`
+    #[serde(default)]
+    pub preserve_source_compression: bool,
     /// Subfolder within the mods directory to install into (empty = root)
     pub install_subfolder: String,
     /// Enable parallel processing for batch operations (texture stripping, etc.)
@@ -55,6 +68,36 @@ pub struct InstallableMod {
     /// Enable obfuscation (encrypts IoStore with game's AES key to block extraction tools like FModel)
     #[serde(default)]
     pub obfuscate: bool,
+    /// Target IoStore container (TOC) version for the repack (None = toolkit default)
+    #[serde(default)]
+    pub iostore_version: Option<u8>,
+    /// Temp directory an archive was extracted into. Shared by every mod found in that archive
+    /// and removed once the last of them is dropped (i.e. after installation finishes)
+    #[serde(skip)]
+    pub temp_dir: Option<Arc<TempDir>>,
+}
+
+pub fn default_is_patch() -> bool {
+    true
+}
+
+impl InstallableMod {
+    /// Whether the install repacks this mod into a legacy pak (the only output `mount_point`
+    /// applies to); IoStore conversion and as-is copies keep their own mount point
+    pub fn writes_legacy_pak(&self) -> bool {
+        !self.iostore
+            && (self.repak || self.is_dir)
+            && (self.force_legacy_pak || self.mod_type == "Audio" || self.mod_type == "Movies")
+    }
+
+    /// Compression for a repacked legacy pak: the source pak's methods when preserving them
+    /// (empty = uncompressed), otherwise `compression`
+    pub fn output_compression(&self) -> Vec<Compression> {
+        match &self.reader {
+            Some(reader) if self.preserve_source_compression => reader.compression(),
+            _ => vec![self.compression],
+        }
+    }
 }
 
 impl Default for InstallableMod {
@@ -81,13 +124,24 @@ impl Default for InstallableMod {
             enabled: true,
             contains_uassets: true, // Default to true for safety
             force_legacy_pak: false,
+            install_disabled: false,
+            is_patch: true,
+            encrypt_index_only: false,
+            preserve_source_compression: false,
             install_subfolder: String::new(),
             parallel_processing: false,
             obfuscate: false,
+            iostore_version: None,
+            temp_dir: None,
         }
     }
 }
 
+/// Extensions that make a folder count as mod content (packages, maps and Wwise audio)
+pub fn is_content_file_ext(ext: &str) -> bool {
+    matches!(ext, "uasset" | "uexp" | "ubulk" | "umap" | "bnk" | "wem")
+}
+
 /// Returns true if the file list contains any UAsset-related files
 /// (.uasset, .uexp, .ubulk, .umap)
 pub fn contains_uasset_files(files: &[String]) -> bool {
@@ -100,11 +154,70 @@ pub fn contains_uasset_files(files: &[String]) -> bool {
     })
 }
 
-pub static AES_KEY: LazyLock<AesKey> = LazyLock::new(|| {
-    AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
-        .expect("Unable to initialise AES_KEY")
+/// Returns true if the pak is the stub half of an IoStore mod: it holds no asset data of its
+/// own, only chunk-name placeholders, while the real data lives in .ucas. An empty pak has no
+/// marker to go on, so it only counts as a stub through `is_iostore_stub_at`.
+pub fn is_iostore_stub(pak: &PakReader) -> bool {
+    let files = pak.files();
+    if files.is_empty() || contains_uasset_files(&files) {
+        return false;
+    }
+    files.iter().all(|f| {
+        let name = f.rsplit('/').next().unwrap_or(f);
+        name.eq_ignore_ascii_case("chunknames")
+            || pak.get_file_entry(f).map(|e| e.uncompressed == 0).unwrap_or(false)
+    })
+}
+
+/// `is_iostore_stub` for a pak on disk, which also accepts an empty pak sitting next to its
+/// .utoc (enabled or disabled)
+pub fn is_iostore_stub_at(pak: &PakReader, pak_path: &Path) -> bool {
+    is_iostore_stub(pak)
+        || (pak.files().is_empty()
+            && (pak_path.with_extension("utoc").exists() || pak_path.with_extension("utoc_disabled").exists()))
+}
+
+/// Pak encryption mode: "none", "index" (only the index is encrypted) or "full"
+pub fn pak_encryption_mode(pak: &PakReader) -> &'static str {
+    match (pak.encrypted_index(), pak.encrypted_data()) {
+        (_, true) => "full",
+        (true, false) => "index",
+        (false, false) => "none",
+    }
+}
+
+/// Returns true if only the pak index is encrypted and entry data is stored in the clear
+pub fn is_index_only_encrypted(pak: &PakReader) -> bool {
+    pak_encryption_mode(pak) == "index"
+}
+
+/// AES key the game shipped with, used until a different key is configured (e.g. by detect_game_aes_key)
+pub const DEFAULT_AES_KEY_HEX: &str = "0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74";
+
+/// Hex and parsed form of the key every pak/IoStore operation uses
+static AES_KEY: LazyLock<RwLock<(String, AesKey)>> = LazyLock::new(|| {
+    let key = AesKey::from_str(DEFAULT_AES_KEY_HEX).expect("Unable to initialise AES_KEY");
+    RwLock::new((DEFAULT_AES_KEY_HEX.to_string(), key))
 });
 
+/// The AES key currently in use
+pub fn current_aes_key() -> AesKey {
+    AES_KEY.read().unwrap().1.clone()
+}
+
+/// The AES key currently in use, as the hex string UAssetTool expects
+pub fn current_aes_key_hex() -> String {
+    AES_KEY.read().unwrap().0.clone()
+}
+
+/// Switch every subsequent pak/IoStore operation to a different AES key
+pub fn set_aes_key(hex: &str) -> Result<(), String> {
+    let hex = hex.trim().trim_start_matches("0x").to_uppercase();
+    let key = AesKey::from_str(&hex).map_err(|e| format!("Invalid AES key: {}", e))?;
+    *AES_KEY.write().unwrap() = (hex, key);
+    Ok(())
+}
+
 fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
     let mut new_mods = Vec::<InstallableMod>::new();
     let mut processed_mods = std::collections::HashSet::new();
@@ -143,10 +256,12 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                 let has_uassets = contains_uasset_files(&files);
 
                 // Try to open PAK for reader (optional - may fail for obfuscated mods)
-                let reader = repak::PakBuilder::new()
-                    .key(AES_KEY.clone().0)
-                    .reader(&mut BufReader::new(File::open(&file_path).unwrap()))
-                    .ok();
+                let reader = File::open(file_path).ok().and_then(|file| {
+                    repak::PakBuilder::new()
+                        .key(current_aes_key().0)
+                        .reader(&mut BufReader::new(file))
+                        .ok()
+                });
 
                 let installable_mod = InstallableMod {
                     mod_name: mod_base_name,
@@ -170,9 +285,16 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
             }
             // This is a standalone .pak file
             else {
+                let file = match File::open(file_path) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        warn!("Skipping unreadable pak {}: {}", file_path.display(), e);
+                        continue;
+                    }
+                };
                 let builder = repak::PakBuilder::new()
-                    .key(AES_KEY.clone().0)
-                    .reader(&mut BufReader::new(File::open(file_path).unwrap()));
+                    .key(current_aes_key().0)
+                    .reader(&mut BufReader::new(file));
 
                 if let Ok(builder) = builder {
                     let files = builder.files();
@@ -184,8 +306,7 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                     let is_audio_or_movie = modtype.contains("Audio") || modtype.contains("Movies");
                     
                     // Auto-detect texture files (mesh patching is handled automatically by UAssetTool)
-                    let auto_fix_textures = detect_texture_files(&files);
-                    let auto_fix_static_mesh = detect_static_mesh_files(&files);
+                    let (auto_fix_textures, auto_fix_static_mesh) = detect_texture_and_static_mesh_files(&files);
 
                     let installable_mod = InstallableMod {
                         mod_name: mod_base_name,
@@ -194,6 +315,7 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                         fix_textures: auto_fix_textures,
                         fix_serialsize_header: auto_fix_static_mesh,
                         is_dir: false,
+                        encrypt_index_only: is_index_only_encrypted(&builder),
                         reader: Some(builder),
                         mod_path: file_path.to_path_buf(),
                         mount_point: "../../../".to_string(),
@@ -235,7 +357,7 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                     if collect_files(&mut content_files, &entry_path).is_ok() {
                         for file in &content_files {
                             if let Some(ext) = file.extension().and_then(|s| s.to_str()) {
-                                if ext == "uasset" || ext == "uexp" || ext == "ubulk" || ext == "bnk" || ext == "wem" {
+                                if is_content_file_ext(ext) {
                                     has_content = true;
                                     break;
                                 }
@@ -262,8 +384,7 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                         let is_audio_or_movies = modtype.contains("Audio") || modtype.contains("Movies");
                         
                         // Auto-detect texture files (mesh patching is handled automatically by UAssetTool)
-                        let auto_fix_textures = detect_texture_files(&file_strings);
-                        let auto_fix_static_mesh = detect_static_mesh_files(&file_strings);
+                        let (auto_fix_textures, auto_fix_static_mesh) = detect_texture_and_static_mesh_files(&file_strings);
                         
                         let installable_mod = InstallableMod {
                             mod_name,
@@ -298,7 +419,7 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                 let has_content = content_files.iter().any(|f| {
                     f.extension()
                         .and_then(|s| s.to_str())
-                        .map(|ext| ext == "uasset" || ext == "uexp" || ext == "ubulk" || ext == "bnk" || ext == "wem")
+                        .map(is_content_file_ext)
                         .unwrap_or(false)
                 });
                 
@@ -319,8 +440,7 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
                     let modtype = get_current_pak_characteristics(file_strings.clone());
                     let has_uassets = contains_uasset_files(&file_strings);
                     let is_audio_or_movies = modtype.contains("Audio") || modtype.contains("Movies");
-                    let auto_fix_textures = detect_texture_files(&file_strings);
-                    let auto_fix_static_mesh = detect_static_mesh_files(&file_strings);
+                    let (auto_fix_textures, auto_fix_static_mesh) = detect_texture_and_static_mesh_files(&file_strings);
                     
                     let installable_mod = InstallableMod {
                         mod_name,
@@ -351,8 +471,12 @@ fn find_mods_from_archive(path: &str) -> Vec<InstallableMod> {
     new_mods
 }
 
-fn map_to_mods_internal(paths: &[PathBuf]) -> Vec<InstallableMod> {
+fn map_to_mods_internal(
+    paths: &[PathBuf],
+    keep_extracted_root: Option<&Path>,
+) -> (Vec<InstallableMod>, Vec<(PathBuf, String)>) {
     let mut extensible_vec: Vec<InstallableMod> = Vec::new();
+    let mut skipped: Vec<(PathBuf, String)> = Vec::new();
     let mut installable_mods = paths
         .iter()
         .map(|path| {
@@ -389,15 +513,29 @@ fn map_to_mods_internal(paths: &[PathBuf]) -> Vec<InstallableMod> {
                     has_uassets = contains_uasset_files(&files);
                     
                     // Try to open PAK for reader (optional - may fail for obfuscated mods)
-                    pak = repak::PakBuilder::new()
-                        .key(AES_KEY.clone().0)
-                        .reader(&mut BufReader::new(File::open(path.clone()).unwrap()))
-                        .ok();
+                    pak = File::open(path).ok().and_then(|file| {
+                        repak::PakBuilder::new()
+                            .key(current_aes_key().0)
+                            .reader(&mut BufReader::new(file))
+                            .ok()
+                    });
                 } else {
+                    let file = File::open(path).map_err(|e| {
+                        error!("Unable to open pak file {}: {}", path.display(), e);
+                        repak::Error::Io(e)
+                    })?;
                     let builder = repak::PakBuilder::new()
-                        .key(AES_KEY.clone().0)
-                        .reader(&mut BufReader::new(File::open(path.clone()).unwrap()));
+                        .key(current_aes_key().0)
+                        .reader(&mut BufReader::new(file));
                     match builder {
+                        Ok(builder) if is_iostore_stub(&builder) => {
+                            let msg = format!(
+                                "{} is an IoStore mod but its .utoc/.ucas files are missing; copy them next to the .pak",
+                                path.display()
+                            );
+                            error!("{}", msg);
+                            return Err(repak::Error::Io(std::io::Error::other(msg)));
+                        }
                         Ok(builder) => {
                             pak = Some(builder.clone());
                             
@@ -408,8 +546,7 @@ fn map_to_mods_internal(paths: &[PathBuf]) -> Vec<InstallableMod> {
                             has_uassets = contains_uasset_files(&files);
                             
                             // Auto-detect texture files (mesh patching is handled automatically by UAssetTool)
-                            auto_fix_textures = detect_texture_files(&files);
-                            auto_fix_static_mesh = detect_static_mesh_files(&files);
+                            (auto_fix_textures, auto_fix_static_mesh) = detect_texture_and_static_mesh_files(&files);
                         }
                         Err(e) => {
                             error!("Error reading pak file: {}", e);
@@ -431,31 +568,48 @@ fn map_to_mods_internal(paths: &[PathBuf]) -> Vec<InstallableMod> {
                 has_uassets = contains_uasset_files(&files);
                 
                 // Auto-detect texture files (mesh patching is handled automatically by UAssetTool)
-                auto_fix_textures = detect_texture_files(&files);
-                auto_fix_static_mesh = detect_static_mesh_files(&files);
+                (auto_fix_textures, auto_fix_static_mesh) = detect_texture_and_static_mesh_files(&files);
             }
 
             if is_archive {
                 modtype = "Archive".to_string();
-                let tempdir = tempdir()
-                    .unwrap()
-                    .path()
-                    .as_os_str()
-                    .to_str()
-                    .unwrap()
-                    .to_string();
-
-                if extension == "zip" {
-                    extract_zip(path.to_str().unwrap(), &tempdir).expect("Unable to extract zip archive")
+                // Keep the TempDir guard alive alongside the mods that point into it;
+                // dropping it here would delete the directory before it's scanned/installed.
+                // In keep_extracted mode, extract to a persistent folder instead so it can be inspected.
+                let (temp_guard, tempdir) = match keep_extracted_root {
+                    Some(root) => {
+                        let dir = prepare_kept_extraction_dir(root, path)?;
+                        info!("Keeping extracted archive contents at: {}", dir.display());
+                        (None, dir.to_string_lossy().to_string())
+                    }
+                    None => {
+                        let guard = Arc::new(tempdir()?);
+                        let dir = guard.path().to_string_lossy().to_string();
+                        (Some(guard), dir)
+                    }
+                };
+
+                let extract_result = if extension == "zip" {
+                    extract_zip(path.to_str().unwrap(), &tempdir)
                 } else if extension == "rar" {
-                    extract_rar(path.to_str().unwrap(), &tempdir).expect("Unable to extract rar archive")
-                } else if extension == "7z" {
-                    extract_7z(path.to_str().unwrap(), &tempdir).expect("Unable to extract 7z archive")
-                }
+                    extract_rar(path.to_str().unwrap(), &tempdir)
+                } else {
+                    extract_7z(path.to_str().unwrap(), &tempdir)
+                };
 
-                // Now find pak files / iostore mods and turn them into installable mods
-                let mut new_mods = find_mods_from_archive(&tempdir);
-                extensible_vec.append(&mut new_mods);
+                match extract_result {
+                    Ok(()) => {
+                        // Now find pak files / iostore mods and turn them into installable mods
+                        let mut new_mods = find_mods_from_archive(&tempdir);
+                        for new_mod in new_mods.iter_mut() {
+                            new_mod.temp_dir = temp_guard.clone();
+                        }
+                        extensible_vec.append(&mut new_mods);
+                    }
+                    Err(e) => {
+                        error!("Unable to extract archive {}: {}", path.display(), e);
+                    }
+                }
             }
 
             // Determine if we should repak this mod
@@ -470,6 +624,7 @@ fn map_to_mods_internal(paths: &[PathBuf]) -> Vec<InstallableMod> {
                 fix_textures: auto_fix_textures,
                 fix_serialsize_header: auto_fix_static_mesh,
                 is_dir,
+                encrypt_index_only: pak.as_ref().is_some_and(is_index_only_encrypted),
                 reader: pak,
                 mod_path: path.clone(),
                 mount_point: "../../../".to_string(),
@@ -481,21 +636,63 @@ fn map_to_mods_internal(paths: &[PathBuf]) -> Vec<InstallableMod> {
                 ..Default::default()
             })
         })
-        .filter_map(|x: Result<InstallableMod, repak::Error>| x.ok())
+        .zip(paths)
+        .filter_map(|(x, path): (Result<InstallableMod, repak::Error>, &PathBuf)| match x {
+            Ok(installable) => Some(installable),
+            Err(e) => {
+                skipped.push((path.clone(), e.to_string()));
+                None
+            }
+        })
         .filter(|x| !x.is_archived)
         .collect::<Vec<_>>();
 
     installable_mods.extend(extensible_vec);
 
     debug!("Install mods: {:?}", installable_mods);
-    installable_mods
+    (installable_mods, skipped)
 }
 
-pub fn map_paths_to_mods(paths: &[PathBuf]) -> Vec<InstallableMod> {
-    let installable_mods = map_to_mods_internal(paths);
+/// Map dropped paths to installable mods. When `keep_extracted_root` is set, archives are
+/// extracted into a persistent folder under it instead of a temp dir, so their contents can be inspected
+pub fn map_paths_to_mods(paths: &[PathBuf], keep_extracted_root: Option<&Path>) -> Vec<InstallableMod> {
+    let (installable_mods, _) = map_to_mods_internal(paths, keep_extracted_root);
     installable_mods
 }
 
+/// Like [`map_paths_to_mods`], but also returns the input paths that couldn't be read
+/// (locked, unreadable or not a valid pak) together with the reason, so callers can report them
+pub fn map_paths_to_mods_with_skipped(
+    paths: &[PathBuf],
+    keep_extracted_root: Option<&Path>,
+) -> (Vec<InstallableMod>, Vec<(PathBuf, String)>) {
+    map_to_mods_internal(paths, keep_extracted_root)
+}
+
+/// Validate a user-supplied install subfolder so it can only point inside the mods directory.
+/// Rejects absolute paths, drive letters / prefixes and `..` components; returns the folder
+/// normalized to forward slashes with empty and `.` components dropped ("" = mods root).
+pub fn sanitize_subfolder(subfolder: &str) -> Result<String, String> {
+    let trimmed = subfolder.trim();
+    if trimmed.starts_with('/') || trimmed.starts_with('\\') {
+        return Err(format!("Install folder must be relative to the mods directory: {}", subfolder));
+    }
+
+    let mut parts = Vec::new();
+    for part in trimmed.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => return Err(format!("Install folder may not contain '..': {}", subfolder)),
+            // Drive letters ("C:") and anything else with a colon could escape on Windows
+            _ if part.contains(':') => {
+                return Err(format!("Install folder must be relative to the mods directory: {}", subfolder))
+            }
+            _ => parts.push(part),
+        }
+    }
+    Ok(parts.join("/"))
+}
+
 // Egui-specific function - stubbed out for Tauri
 #[allow(dead_code)]
 pub fn map_dropped_file_to_mods(_dropped_files: &[PathBuf]) -> Vec<InstallableMod> {
@@ -503,3 +700,175 @@ pub fn map_dropped_file_to_mods(_dropped_files: &[PathBuf]) -> Vec<InstallableMo
     // Original egui version uses egui::DroppedFile
     unimplemented!("This function is only available in the egui version")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufWriter, Write};
+
+    fn write_test_pak(path: &std::path::Path) {
+        let file = File::create(path).unwrap();
+        let mut writer = repak::PakBuilder::new().writer(
+            BufWriter::new(file),
+            repak::Version::V11,
+            "../../../".to_string(),
+            None,
+        );
+        writer
+            .write_file("Marvel/Content/Marvel/Characters/1011/SK_Test.uasset", false, b"test")
+            .unwrap();
+        writer.write_index().unwrap();
+    }
+
+    #[test]
+    fn test_map_only_pak_is_a_level_mod() {
+        let work_dir = tempdir().unwrap();
+        let pak_path = work_dir.path().join("ArenaRework.pak");
+        let file = File::create(&pak_path).unwrap();
+        let mut writer = repak::PakBuilder::new().writer(
+            BufWriter::new(file),
+            repak::Version::V11,
+            "../../../".to_string(),
+            None,
+        );
+        for entry in [
+            "Marvel/Content/Marvel/Maps/Arena/Arena_Main.umap",
+            "Marvel/Content/Marvel/Maps/Arena/Arena_Main.uexp",
+            "Marvel/Content/Marvel/Maps/Arena/Arena_Main_BuiltData.uasset",
+        ] {
+            writer.write_file(entry, false, b"test").unwrap();
+        }
+        writer.write_index().unwrap();
+
+        let mods = map_paths_to_mods(&[pak_path], None);
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].mod_type, "Map/Level");
+        assert!(mods[0].contains_uassets);
+        assert!(is_content_file_ext("umap"));
+    }
+
+    #[test]
+    fn test_empty_pak_is_only_a_stub_next_to_its_utoc() {
+        let work_dir = tempdir().unwrap();
+        let open = |path: &std::path::Path| {
+            repak::PakBuilder::new().reader(&mut BufReader::new(File::open(path).unwrap())).unwrap()
+        };
+        let write_pak = |path: &std::path::Path, entries: &[&str]| {
+            let mut writer = repak::PakBuilder::new().writer(
+                BufWriter::new(File::create(path).unwrap()),
+                repak::Version::V11,
+                "../../../".to_string(),
+                None,
+            );
+            for entry in entries {
+                writer.write_file(entry, false, b"").unwrap();
+            }
+            writer.write_index().unwrap();
+        };
+
+        let empty = work_dir.path().join("Empty_9999999_P.pak");
+        write_pak(&empty, &[]);
+        assert!(!is_iostore_stub(&open(&empty)));
+        assert!(!is_iostore_stub_at(&open(&empty), &empty));
+        File::create(empty.with_extension("utoc")).unwrap();
+        assert!(is_iostore_stub_at(&open(&empty), &empty));
+
+        let stub = work_dir.path().join("Stub_9999999_P.pak");
+        write_pak(&stub, &["chunknames"]);
+        assert!(is_iostore_stub(&open(&stub)));
+    }
+
+    #[test]
+    fn test_mount_point_applies_only_to_legacy_output() {
+        let mut installable = InstallableMod { repak: true, mod_type: "Mesh".to_string(), ..Default::default() };
+        assert!(!installable.writes_legacy_pak());
+        installable.force_legacy_pak = true;
+        assert!(installable.writes_legacy_pak());
+        installable.iostore = true;
+        assert!(!installable.writes_legacy_pak());
+    }
+
+    #[test]
+    fn test_sanitize_subfolder_rejects_escapes() {
+        assert!(sanitize_subfolder("../../evil").is_err());
+        assert!(sanitize_subfolder("Skins/../../evil").is_err());
+        assert!(sanitize_subfolder("C:\\foo").is_err());
+        assert!(sanitize_subfolder("C:foo").is_err());
+        assert!(sanitize_subfolder("/etc").is_err());
+        assert!(sanitize_subfolder("\\\\server\\share").is_err());
+    }
+
+    #[test]
+    fn test_sanitize_subfolder_normalizes() {
+        assert_eq!(sanitize_subfolder("").unwrap(), "");
+        assert_eq!(sanitize_subfolder("Skins").unwrap(), "Skins");
+        assert_eq!(sanitize_subfolder("Skins\\Hulk/./").unwrap(), "Skins/Hulk");
+    }
+
+    #[test]
+    fn test_map_paths_discovers_paks_in_zip() {
+        let work_dir = tempdir().unwrap();
+        let pak_path = work_dir.path().join("TestMod.pak");
+        write_test_pak(&pak_path);
+
+        let zip_path = work_dir.path().join("TestMod.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        zip.start_file("TestMod.pak", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&std::fs::read(&pak_path).unwrap()).unwrap();
+        zip.finish().unwrap();
+
+        let mods = map_paths_to_mods(&[zip_path], None);
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].mod_name, "TestMod");
+        assert!(mods[0].mod_path.exists(), "extracted pak should still exist after mapping");
+
+        // The extraction dir lives exactly as long as the mods that reference it
+        let extracted = mods[0].mod_path.clone();
+        drop(mods);
+        assert!(!extracted.exists());
+    }
+    #[test]
+    fn test_install_reports_an_outcome_per_mod() {
+        use install_mod_logic::{install_mods_in_viewport, ModInstallOutcome};
+        use std::sync::atomic::{AtomicBool, AtomicI32};
+
+        let source_dir = tempdir().unwrap();
+        let game_dir = tempdir().unwrap();
+        let complete = source_dir.path().join("Complete.pak");
+        write_test_pak(&complete);
+        std::fs::write(complete.with_extension("utoc"), b"utoc").unwrap();
+        std::fs::write(complete.with_extension("ucas"), b"ucas").unwrap();
+        // No .utoc/.ucas next to this one, so copying the container fails
+        let broken = source_dir.path().join("Broken.pak");
+        write_test_pak(&broken);
+
+        let iostore_mod = |path: &std::path::Path, name: &str| InstallableMod {
+            mod_name: name.to_string(),
+            mod_type: "Mesh".to_string(),
+            mod_path: path.to_path_buf(),
+            iostore: true,
+            ..Default::default()
+        };
+        let mut mods = vec![
+            iostore_mod(&complete, "Complete"),
+            iostore_mod(&broken, "Broken"),
+            InstallableMod { enabled: false, ..iostore_mod(&complete, "Unchecked") },
+            InstallableMod { install_disabled: true, ..iostore_mod(&complete, "Staged") },
+        ];
+
+        let outcomes = install_mods_in_viewport(
+            &mut mods,
+            game_dir.path(),
+            &AtomicI32::new(0),
+            &AtomicBool::new(false),
+            &|_| {},
+        );
+
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[0], ModInstallOutcome::Installed(game_dir.path().join(format!("{}.pak", mods[0].mod_name))));
+        assert_eq!(outcomes[1], ModInstallOutcome::Failed);
+        assert_eq!(outcomes[2], ModInstallOutcome::Skipped);
+        assert_eq!(outcomes[3], ModInstallOutcome::Installed(game_dir.path().join(format!("{}.bak_repak", mods[3].mod_name))));
+    }
+}
@@ -1,11 +1,13 @@
 pub mod archives;
 pub mod iotoc;
 pub mod pak_files;
+pub mod session_log;
 
 use crate::install_mod::InstallableMod;
 use iotoc::convert_to_iostore_directory;
 use log::{error, info, warn};
 use pak_files::create_repak_from_pak;
+use session_log::log_mod_line;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
@@ -16,7 +18,7 @@ use serde_json;
 use regex_lite::Regex;
 
 /// Recursively copy a directory and all its contents to a destination
-fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+pub(crate) fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
     if !dst.exists() {
         fs::create_dir_all(dst)?;
     }
@@ -76,6 +78,26 @@ pub fn normalize_mod_base_name(name: &str, min_nines: usize) -> String {
     format!("{}_{}_P", base, new_nines)
 }
 
+/// Mod name with the patch pak suffix ("_999..._P") removed, for base paks
+pub fn base_pak_name(name: &str) -> String {
+    let base = name.strip_suffix("_P").unwrap_or(name);
+    let re = Regex::new(r"^(.+)_9+$").unwrap();
+    match re.captures(base) {
+        Some(caps) => caps[1].to_string(),
+        None => base.to_string(),
+    }
+}
+
+/// Output file stem for a mod: patch paks get the "_999..._P" suffix with at least
+/// `min_nines` nines, base paks keep the plain name
+pub fn installed_base_name(installable_mod: &InstallableMod, min_nines: usize) -> String {
+    if installable_mod.is_patch {
+        normalize_mod_base_name(&installable_mod.mod_name, min_nines)
+    } else {
+        base_pak_name(&installable_mod.mod_name)
+    }
+}
+
 pub fn record_installed_tags(base_name: &str, tags: &Vec<String>) {
     if tags.is_empty() { return; }
     let mut cfg_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -100,15 +122,73 @@ pub fn record_installed_tags(base_name: &str, tags: &Vec<String>) {
     let _ = fs::write(&path, serde_json::to_string_pretty(&map).unwrap());
 }
 
+/// Rename a freshly installed `<base>.pak` to the disabled `.bak_repak` name when the mod was
+/// requested to be installed disabled. Goes through toggling, so .utoc/.ucas companions are
+/// disabled along with the pak. Returns where the installed pak ended up.
+fn apply_install_disabled(installable_mod: &InstallableMod, output_directory: &Path, base: &str) -> PathBuf {
+    let pak_path = output_directory.join(format!("{}.pak", base));
+    if !installable_mod.install_disabled {
+        return pak_path;
+    }
+    match crate::toggle_mod_file(&pak_path) {
+        Ok(_) => {
+            info!("Installed {} disabled", base);
+            log_mod_line(base, "installed disabled");
+            pak_path.with_extension("bak_repak")
+        }
+        Err(e) => {
+            error!("Failed to disable installed mod {}: {}", pak_path.display(), e);
+            log_mod_line(base, &format!("ERROR: failed to disable installed mod: {}", e));
+            pak_path
+        }
+    }
+}
+
+/// What `install_mods_in_viewport` did with one mod, in the same order as its `mods`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModInstallOutcome {
+    /// Written to this path (`.pak`, or `.bak_repak` when installed disabled)
+    Installed(PathBuf),
+    Failed,
+    /// Not enabled for install, or the install was stopped before reaching it
+    Skipped,
+}
+
+/// Structured progress update for a single mod's install pipeline.
+/// Emitted once per stage (extracting → detecting → fixing_textures → repacking → writing → done)
+/// so the frontend can render per-mod progress instead of a single global percentage.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct InstallStage {
+    pub mod_name: String,
+    pub stage: String,
+    pub progress_within_stage: f32,
+}
+
+/// Helper to build and forward an `InstallStage` to the reporter callback.
+/// Stage transitions are also written to the session install log.
+pub fn report_stage(on_stage: &dyn Fn(InstallStage), mod_name: &str, stage: &str, progress_within_stage: f32) {
+    // Only log stage boundaries; intermediate progress would flood the file
+    if progress_within_stage == 0.0 || progress_within_stage >= 1.0 {
+        log_mod_line(mod_name, &format!("stage={} progress={:.2}", stage, progress_within_stage));
+    }
+    on_stage(InstallStage {
+        mod_name: mod_name.to_string(),
+        stage: stage.to_string(),
+        progress_within_stage,
+    });
+}
+
 pub fn install_mods_in_viewport(
     mods: &mut [InstallableMod],
     mod_directory: &Path,
     installed_mods_ptr: &AtomicI32,
     stop_thread: &AtomicBool,
-) {
+    on_stage: &dyn Fn(InstallStage),
+) -> Vec<ModInstallOutcome> {
     let mut type_tracker: HashMap<String, usize> = HashMap::new();
+    let mut outcomes = vec![ModInstallOutcome::Skipped; mods.len()];
 
-    for installable_mod in mods.iter_mut() {
+    for (mod_idx, installable_mod) in mods.iter_mut().enumerate() {
         let min_nines = if installable_mod.enabled {
              let count = type_tracker.entry(installable_mod.mod_type.clone()).or_insert(0);
              let n = 7 + *count;
@@ -119,7 +199,7 @@ pub fn install_mods_in_viewport(
         };
 
         // Ensure naming suffix consistency up-front for all flows
-        installable_mod.mod_name = normalize_mod_base_name(&installable_mod.mod_name, min_nines);
+        installable_mod.mod_name = installed_base_name(installable_mod, min_nines);
         
         if !installable_mod.enabled {
             continue;
@@ -130,6 +210,23 @@ pub fn install_mods_in_viewport(
             break;
         }
 
+        log_mod_line(
+            &installable_mod.mod_name,
+            &format!(
+                "source={} iostore={} is_dir={} repak={} fix_textures={} fix_serialsize={} force_legacy={} mount_point={} subfolder='{}'",
+                installable_mod.mod_path.display(),
+                installable_mod.iostore,
+                installable_mod.is_dir,
+                installable_mod.repak,
+                installable_mod.fix_textures,
+                installable_mod.fix_serialsize_header,
+                installable_mod.force_legacy_pak,
+                installable_mod.mount_point,
+                installable_mod.install_subfolder,
+            ),
+        );
+        report_stage(on_stage, &installable_mod.mod_name, "detecting", 0.0);
+
         // Determine the actual output directory (base + subfolder if specified)
         let output_directory = if installable_mod.install_subfolder.is_empty() {
             mod_directory.to_path_buf()
@@ -139,6 +236,9 @@ pub fn install_mods_in_viewport(
             if !subfolder_path.exists() {
                 if let Err(e) = fs::create_dir_all(&subfolder_path) {
                     error!("Failed to create subfolder '{}': {}", installable_mod.install_subfolder, e);
+                    log_mod_line(&installable_mod.mod_name, &format!("ERROR: failed to create subfolder '{}': {}", installable_mod.install_subfolder, e));
+                    report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                    outcomes[mod_idx] = ModInstallOutcome::Failed;
                     continue;
                 }
                 info!("Created install subfolder: {}", subfolder_path.display());
@@ -153,33 +253,56 @@ pub fn install_mods_in_viewport(
             let ucas_path = installable_mod.mod_path.with_extension("ucas");
 
             // Ensure output names follow suffix rule
-            let base = normalize_mod_base_name(&installable_mod.mod_name, 7);
+            let base = installed_base_name(installable_mod, 7);
             let dests = vec![
                 (pak_path, format!("{}.pak", base)),
                 (utoc_path, format!("{}.utoc", base)),
                 (ucas_path, format!("{}.ucas", base)),
             ];
 
-            for (src, dest_name) in dests {
+            report_stage(on_stage, &installable_mod.mod_name, "detecting", 1.0);
+            let dest_count = dests.len() as f32;
+            let mut copy_failed = false;
+            for (idx, (src, dest_name)) in dests.into_iter().enumerate() {
+                report_stage(on_stage, &installable_mod.mod_name, "writing", idx as f32 / dest_count);
                 if let Err(e) = std::fs::copy(&src, output_directory.join(&dest_name)) {
                     error!("Unable to copy file {:?}: {:?}", src, e);
+                    log_mod_line(&installable_mod.mod_name, &format!("ERROR: unable to copy {:?}: {:?}", src, e));
+                    copy_failed = true;
                 }
             }
+            if copy_failed {
+                report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Failed;
+                continue;
+            }
+            let installed = apply_install_disabled(installable_mod, &output_directory, &base);
             // Record tags for pickup by main app
             record_installed_tags(&base, &installable_mod.custom_tags);
+            report_stage(on_stage, &installable_mod.mod_name, "done", 1.0);
+            outcomes[mod_idx] = ModInstallOutcome::Installed(installed);
             continue;
         }
 
+        report_stage(on_stage, &installable_mod.mod_name, "detecting", 1.0);
+
         if installable_mod.repak {
             if let Err(e) = create_repak_from_pak(
                 installable_mod,
                 output_directory.clone(),
                 installed_mods_ptr,
+                on_stage,
             ) {
                 error!("Failed to create repak from pak: {}", e);
+                log_mod_line(&installable_mod.mod_name, &format!("ERROR: {}", e));
+                report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Failed;
             } else {
-                let base = normalize_mod_base_name(&installable_mod.mod_name, 7);
+                let base = installed_base_name(installable_mod, 7);
+                let installed = apply_install_disabled(installable_mod, &output_directory, &base);
                 record_installed_tags(&base, &installable_mod.custom_tags);
+                report_stage(on_stage, &installable_mod.mod_name, "done", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Installed(installed);
             }
         }
 
@@ -190,11 +313,20 @@ pub fn install_mods_in_viewport(
                 "Copying mod instead of repacking: {}",
                 installable_mod.mod_name
             );
-            let base = normalize_mod_base_name(&installable_mod.mod_name, 7);
-            std::fs::copy(&installable_mod.mod_path, output_directory.join(format!("{}.pak", &base)))
-            .unwrap();
+            let base = installed_base_name(installable_mod, 7);
+            report_stage(on_stage, &installable_mod.mod_name, "writing", 0.0);
+            if let Err(e) = std::fs::copy(&installable_mod.mod_path, output_directory.join(format!("{}.pak", &base))) {
+                error!("Unable to copy file {:?}: {:?}", installable_mod.mod_path, e);
+                log_mod_line(&installable_mod.mod_name, &format!("ERROR: unable to copy {:?}: {:?}", installable_mod.mod_path, e));
+                report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Failed;
+                continue;
+            }
+            let installed = apply_install_disabled(installable_mod, &output_directory, &base);
             record_installed_tags(&base, &installable_mod.custom_tags);
             installed_mods_ptr.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            report_stage(on_stage, &installable_mod.mod_name, "done", 1.0);
+            outcomes[mod_idx] = ModInstallOutcome::Installed(installed);
             continue;
         }
 
@@ -204,6 +336,9 @@ pub fn install_mods_in_viewport(
                 Ok(dir) => dir,
                 Err(e) => {
                     error!("Failed to create temp directory: {}", e);
+                    log_mod_line(&installable_mod.mod_name, &format!("ERROR: failed to create temp directory: {}", e));
+                    report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                    outcomes[mod_idx] = ModInstallOutcome::Failed;
                     continue;
                 }
             };
@@ -211,10 +346,15 @@ pub fn install_mods_in_viewport(
             
             // Copy all files from source to temp
             let source_path = PathBuf::from(&installable_mod.mod_path);
+            report_stage(on_stage, &installable_mod.mod_name, "extracting", 0.0);
             if let Err(e) = copy_dir_recursive(&source_path, &temp_path) {
                 error!("Failed to copy mod files to temp directory: {}", e);
+                log_mod_line(&installable_mod.mod_name, &format!("ERROR: failed to copy mod files to temp directory: {}", e));
+                report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Failed;
                 continue;
             }
+            report_stage(on_stage, &installable_mod.mod_name, "extracting", 1.0);
             info!("Copied mod files to temp directory for processing");
             
             let res = convert_to_iostore_directory(
@@ -222,15 +362,25 @@ pub fn install_mods_in_viewport(
                 output_directory.clone(),
                 temp_path,
                 installed_mods_ptr,
+                on_stage,
             );
             // temp_dir is automatically cleaned up when it goes out of scope
             if let Err(e) = res {
                 error!("Failed to create repak from pak: {}", e);
+                log_mod_line(&installable_mod.mod_name, &format!("ERROR: {}", e));
+                report_stage(on_stage, &installable_mod.mod_name, "failed", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Failed;
             } else {
                 info!("Installed mod: {}", installable_mod.mod_name);
+                let base = installed_base_name(installable_mod, 7);
+                let installed = apply_install_disabled(installable_mod, &output_directory, &base);
+                record_installed_tags(&base, &installable_mod.custom_tags);
+                report_stage(on_stage, &installable_mod.mod_name, "done", 1.0);
+                outcomes[mod_idx] = ModInstallOutcome::Installed(installed);
             }
         }
     }
     // set i32 to -255 magic value to indicate mod installation is done
     AtomicI32::store(installed_mods_ptr, -255, Ordering::SeqCst);
+    outcomes
 }
@@ -1,8 +1,221 @@
+/// Default cap on total uncompressed bytes extracted from a single archive (16 GiB)
+pub const DEFAULT_MAX_EXTRACTED_BYTES: u64 = 16 * 1024 * 1024 * 1024;
+/// Default cap on number of entries extracted from a single archive
+pub const DEFAULT_MAX_ENTRIES: usize = 100_000;
+
+// Active limits, applied from AppState at startup and whenever the settings change
+static MAX_EXTRACTED_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MAX_EXTRACTED_BYTES);
+static MAX_ENTRIES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_ENTRIES);
+
+// Cancel token of the detection scan currently running, if any. cancel_detection only ever
+// reaches this token, so a late cancel can't leak into a later install's extraction
+static ACTIVE_DETECTION: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+/// Start a detection scan and return its cancel token, to be passed to the extractors
+pub fn begin_detection_scan() -> Arc<AtomicBool> {
+    let token = Arc::new(AtomicBool::new(false));
+    *ACTIVE_DETECTION.lock().unwrap() = Some(token.clone());
+    token
+}
+
+/// Finish a detection scan; later cancel requests no longer reach its token
+pub fn end_detection_scan(token: &Arc<AtomicBool>) {
+    let mut active = ACTIVE_DETECTION.lock().unwrap();
+    if active.as_ref().is_some_and(|t| Arc::ptr_eq(t, token)) {
+        *active = None;
+    }
+}
+
+/// Request cancellation of the running detection scan. Returns false when none is running
+pub fn request_cancel_extraction() -> bool {
+    match ACTIVE_DETECTION.lock().unwrap().as_ref() {
+        Some(token) => {
+            token.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+fn check_cancelled(archive_name: &str, cancel: Option<&AtomicBool>) -> io::Result<()> {
+    if cancel.is_some_and(|c| c.load(Ordering::SeqCst)) {
+        return Err(io::Error::new(
+            io::ErrorKind::Interrupted,
+            format!("Extraction of '{}' was cancelled", archive_name),
+        ));
+    }
+    Ok(())
+}
+
+// Most recent persistent extraction folder (keep_extracted mode), for reveal_last_extraction
+static LAST_KEPT_EXTRACTION: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Prepare a persistent extraction folder `<root>/<archive stem>` for inspection,
+/// clearing any leftovers from a previous extraction of the same archive
+pub fn prepare_kept_extraction_dir(root: &Path, archive_path: &Path) -> io::Result<PathBuf> {
+    let stem = archive_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "archive".to_string());
+    let dir = root.join(stem);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    std::fs::create_dir_all(&dir)?;
+    *LAST_KEPT_EXTRACTION.lock().unwrap() = Some(dir.clone());
+    Ok(dir)
+}
+
+/// Path of the most recent persistent extraction, if any
+pub fn last_kept_extraction() -> Option<PathBuf> {
+    LAST_KEPT_EXTRACTION.lock().unwrap().clone()
+}
+
+/// Update the decompression-bomb guard limits. A value of 0 restores the default.
+pub fn set_extraction_limits(max_extracted_bytes: u64, max_entries: usize) {
+    let bytes = if max_extracted_bytes == 0 { DEFAULT_MAX_EXTRACTED_BYTES } else { max_extracted_bytes };
+    let entries = if max_entries == 0 { DEFAULT_MAX_ENTRIES } else { max_entries };
+    MAX_EXTRACTED_BYTES.store(bytes, Ordering::SeqCst);
+    MAX_ENTRIES.store(entries, Ordering::SeqCst);
+}
+
+/// Tracks extracted bytes/entries against the active limits for a single extraction
+struct ExtractionGuard {
+    max_bytes: u64,
+    max_entries: usize,
+}
+
+impl ExtractionGuard {
+    fn new() -> Self {
+        Self {
+            max_bytes: MAX_EXTRACTED_BYTES.load(Ordering::SeqCst),
+            max_entries: MAX_ENTRIES.load(Ordering::SeqCst),
+        }
+    }
+
+    fn check(&self, archive_name: &str, entries: usize, bytes: u64) -> io::Result<()> {
+        if entries > self.max_entries {
+            return Err(io::Error::other(format!(
+                "Archive '{}' has too many entries ({} > limit of {})",
+                archive_name, entries, self.max_entries
+            )));
+        }
+        if bytes > self.max_bytes {
+            return Err(io::Error::other(format!(
+                "Archive '{}' exceeds the extraction size limit ({} > {} bytes)",
+                archive_name, bytes, self.max_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Entry count and declared uncompressed size of a zip archive
+fn zip_totals<R: Read + io::Seek>(archive: &mut ZipArchive<R>) -> (usize, u64) {
+    let entries = archive.len();
+    let bytes = (0..entries)
+        .map(|i| archive.by_index_raw(i).map(|f| f.size()).unwrap_or(0))
+        .sum();
+    (entries, bytes)
+}
+
+/// File count and declared unpacked size of a RAR archive
+fn rar_totals(rar_path: &str) -> io::Result<(usize, u64)> {
+    let mut entries = 0usize;
+    let mut bytes = 0u64;
+    for header in Archive::new(rar_path).open_for_listing().map_err(io::Error::other)? {
+        let header = header.map_err(io::Error::other)?;
+        if header.is_file() {
+            entries += 1;
+            bytes += header.unpacked_size;
+        }
+    }
+    Ok((entries, bytes))
+}
+
+/// File count and declared uncompressed size of a 7z archive, (0, 0) if it can't be listed
+fn sevenz_totals(archive_path: &str) -> (usize, u64) {
+    sevenz_rust2::Archive::open(archive_path)
+        .map(|a| {
+            let files: Vec<_> = a.files.iter().filter(|f| !f.is_directory()).collect();
+            (files.len(), files.iter().map(|f| f.size()).sum())
+        })
+        .unwrap_or((0, 0))
+}
+
+/// Total uncompressed size an archive declares for its contents, read from its listing without
+/// extracting anything. None for files that aren't zip/rar/7z or can't be listed.
+pub fn declared_uncompressed_size(archive_path: &Path) -> Option<u64> {
+    let ext = archive_path.extension()?.to_str()?.to_lowercase();
+    let path = archive_path.to_str()?;
+    match ext.as_str() {
+        "zip" => {
+            let mut archive = ZipArchive::new(File::open(path).ok()?).ok()?;
+            Some(zip_totals(&mut archive).1)
+        }
+        "rar" => rar_totals(path).ok().map(|(_, bytes)| bytes),
+        "7z" => Some(sevenz_totals(path).1),
+        _ => None,
+    }
+}
+
+/// Remove partially extracted content after an aborted extraction
+fn cleanup_output_dir<T>(output_dir: &str, result: io::Result<T>) -> io::Result<T> {
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(output_dir);
+    }
+    result
+}
+
 pub fn extract_zip(zip_path: &str, output_dir: &str) -> io::Result<()> {
+    extract_zip_with_progress(zip_path, output_dir, &|_| {}, None)
+}
+
+/// Progress update for archive extraction, reported once per extracted entry
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ArchiveExtractProgress {
+    pub archive_name: String,
+    pub current_file: String,
+    pub entries_done: usize,
+    pub entries_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+fn archive_display_name(archive_path: &str) -> String {
+    Path::new(archive_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| archive_path.to_string())
+}
+
+/// `cancel` is the detection scan's token (see `begin_detection_scan`); installs pass None
+pub fn extract_zip_with_progress(
+    zip_path: &str,
+    output_dir: &str,
+    on_progress: &dyn Fn(ArchiveExtractProgress),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<()> {
+    cleanup_output_dir(output_dir, extract_zip_inner(zip_path, output_dir, on_progress, cancel))
+}
+
+fn extract_zip_inner(
+    zip_path: &str,
+    output_dir: &str,
+    on_progress: &dyn Fn(ArchiveExtractProgress),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<()> {
     let file = File::open(zip_path)?;
     let mut archive = ZipArchive::new(file)?;
 
-    for i in 0..archive.len() {
+    let archive_name = archive_display_name(zip_path);
+    let guard = ExtractionGuard::new();
+    let (entries_total, bytes_total) = zip_totals(&mut archive);
+    guard.check(&archive_name, entries_total, bytes_total)?;
+    let mut bytes_done = 0u64;
+
+    for i in 0..entries_total {
+        check_cancelled(&archive_name, cancel)?;
         let mut file = archive.by_index(i)?;
         let outpath = Path::new(output_dir).join(file.mangled_name());
 
@@ -15,42 +228,161 @@ pub fn extract_zip(zip_path: &str, output_dir: &str) -> io::Result<()> {
                 }
             }
             let mut outfile = File::create(&outpath)?;
-            io::copy(&mut file, &mut outfile)?;
+            // Declared sizes can lie, so cap the actual stream as well
+            let remaining = guard.max_bytes.saturating_sub(bytes_done);
+            bytes_done += io::copy(&mut (&mut file).take(remaining + 1), &mut outfile)?;
+            guard.check(&archive_name, i + 1, bytes_done)?;
         }
+
+        on_progress(ArchiveExtractProgress {
+            archive_name: archive_name.clone(),
+            current_file: file.name().to_string(),
+            entries_done: i + 1,
+            entries_total,
+            bytes_done,
+            bytes_total,
+        });
     }
 
     Ok(())
 }
 
 use std::fs::File;
-use std::io;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use unrar::Archive;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use zip::ZipArchive;
 
-pub fn extract_rar(rar_path: &str, output_dir: &str) -> Result<(), unrar::error::UnrarError> {
+pub fn extract_rar(rar_path: &str, output_dir: &str) -> io::Result<()> {
+    extract_rar_with_progress(rar_path, output_dir, &|_| {}, None)
+}
+
+pub fn extract_rar_with_progress(
+    rar_path: &str,
+    output_dir: &str,
+    on_progress: &dyn Fn(ArchiveExtractProgress),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<()> {
+    cleanup_output_dir(output_dir, extract_rar_inner(rar_path, output_dir, on_progress, cancel))
+}
+
+fn extract_rar_inner(
+    rar_path: &str,
+    output_dir: &str,
+    on_progress: &dyn Fn(ArchiveExtractProgress),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<()> {
+    let unrar_err = |e: unrar::error::UnrarError| io::Error::other(e);
     let output_dir = Path::new(output_dir);
+    let archive_name = archive_display_name(rar_path);
+    let guard = ExtractionGuard::new();
+
+    // List first so progress has a total to report against
+    let (entries_total, bytes_total) = rar_totals(rar_path)?;
+    guard.check(&archive_name, entries_total, bytes_total)?;
+
+    let mut entries_done = 0usize;
+    let mut bytes_done = 0u64;
     let mut archive =
         Archive::new(rar_path)
-            .open_for_processing()?;
-    while let Some(header) = archive.read_header()? {
+            .open_for_processing()
+            .map_err(unrar_err)?;
+    while let Some(header) = archive.read_header().map_err(unrar_err)? {
+        check_cancelled(&archive_name, cancel)?;
         let filename = header.entry().filename.clone();
         archive = if header.entry().is_file() {
-            header.extract_to(output_dir.join(filename))?
+            // unrar writes the file itself, so refuse an entry before it can push us
+            // past the cap; unrar stops unpacking at the declared size
+            guard.check(
+                &archive_name,
+                entries_done + 1,
+                bytes_done.saturating_add(header.entry().unpacked_size),
+            )?;
+            let out_path = output_dir.join(&filename);
+            let next = header.extract_to(&out_path).map_err(unrar_err)?;
+            entries_done += 1;
+            // Count what actually landed on disk rather than trusting the header
+            bytes_done += std::fs::metadata(&out_path).map(|m| m.len()).unwrap_or(0);
+            if let Err(e) = guard.check(&archive_name, entries_done, bytes_done) {
+                let _ = std::fs::remove_file(&out_path);
+                return Err(e);
+            }
+            on_progress(ArchiveExtractProgress {
+                archive_name: archive_name.clone(),
+                current_file: filename.to_string_lossy().to_string(),
+                entries_done,
+                entries_total,
+                bytes_done,
+                bytes_total,
+            });
+            next
         } else {
-            header.skip()?
+            header.skip().map_err(unrar_err)?
         };
     }
     Ok(())
 }
 
 pub fn extract_7z(archive_path: &str, output_dir: &str) -> io::Result<()> {
+    extract_7z_with_progress(archive_path, output_dir, &|_| {}, None)
+}
+
+pub fn extract_7z_with_progress(
+    archive_path: &str,
+    output_dir: &str,
+    on_progress: &dyn Fn(ArchiveExtractProgress),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<()> {
+    cleanup_output_dir(output_dir, extract_7z_inner(archive_path, output_dir, on_progress, cancel))
+}
+
+fn extract_7z_inner(
+    archive_path: &str,
+    output_dir: &str,
+    on_progress: &dyn Fn(ArchiveExtractProgress),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<()> {
     let output_path = Path::new(output_dir);
     std::fs::create_dir_all(output_path)?;
-    
-    // Use sevenz_rust2's decompress_file utility for simple extraction
-    sevenz_rust2::decompress_file(archive_path, output_path)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Failed to extract 7z archive: {}", e)))?;
-    
+
+    let archive_name = archive_display_name(archive_path);
+    let guard = ExtractionGuard::new();
+    let (entries_total, bytes_total) = sevenz_totals(archive_path);
+    guard.check(&archive_name, entries_total, bytes_total)?;
+
+    let mut entries_done = 0usize;
+    let mut bytes_done = 0u64;
+
+    // Write each entry ourselves so the size cap counts decompressed bytes, not the
+    // size the archive claims
+    sevenz_rust2::decompress_file_with_extract_fn(archive_path, output_path, |entry, reader, dest| {
+        check_cancelled(&archive_name, cancel)?;
+        if entry.is_directory() {
+            std::fs::create_dir_all(dest)?;
+            return Ok(true);
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut outfile = io::BufWriter::new(std::fs::File::create(dest)?);
+        let remaining = guard.max_bytes.saturating_sub(bytes_done);
+        bytes_done += io::copy(&mut reader.take(remaining + 1), &mut outfile)?;
+        outfile.flush()?;
+        guard.check(&archive_name, entries_done + 1, bytes_done)?;
+        entries_done += 1;
+        on_progress(ArchiveExtractProgress {
+            archive_name: archive_name.clone(),
+            current_file: entry.name().to_string(),
+            entries_done,
+            entries_total,
+            bytes_done,
+            bytes_total,
+        });
+        Ok(true)
+    })
+    .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Failed to extract 7z archive: {}", e)))?;
+
     Ok(())
-}
\ No newline at end of file
+}
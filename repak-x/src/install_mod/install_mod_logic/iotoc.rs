@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use crate::install_mod::install_mod_logic::pak_files::repak_dir;
+use crate::install_mod::install_mod_logic::{report_stage, InstallStage};
 use crate::install_mod::InstallableMod;
 use crate::uasset_api_integration::batch_convert_textures_to_inline_with_parallel;
 use crate::utils::collect_files;
@@ -13,23 +14,84 @@ use serde::{Deserialize, Serialize};
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
+/// Static Mesh SerializeSize fix, applied to the extracted files before they are packed.
+/// Runs for every output format (legacy pak and IoStore alike)
+fn fix_serialsize_headers(
+    pak: &InstallableMod,
+    to_pak_dir: &Path,
+    on_stage: &dyn Fn(InstallStage),
+) -> Result<(), repak::Error> {
+    if !pak.fix_serialsize_header {
+        return Ok(());
+    }
+
+    report_stage(on_stage, &pak.mod_name, "fixing_serialize_size", 0.0);
+    info!("╔══════════════════════════════════════════════════════════╗");
+    info!("║  STATIC MESH SERIALIZESIZE FIX - STARTING                ║");
+    info!("╚══════════════════════════════════════════════════════════╝");
+    
+    // Check for usmap file (required for unversioned assets) - stored in roaming folder
+    let usmap_full_path = if !pak.usmap_path.is_empty() {
+        // Construct full path to Usmap folder in roaming directory
+        let usmap_dir = dirs::config_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("Repak-X")
+            .join("Usmap");
+        let usmap_file = usmap_dir.join(&pak.usmap_path);
+        if usmap_file.exists() {
+            Some(usmap_file.to_string_lossy().to_string())
+        } else {
+            warn!("USmap file not found in roaming folder: {}", usmap_file.display());
+            None
+        }
+    } else {
+        warn!("No usmap file specified - detection may be limited for unversioned assets");
+        None
+    };
+    
+    let usmap_path = usmap_full_path.as_deref();
+    
+    match process_static_mesh_serializesize(to_pak_dir, usmap_path) {
+        Ok(fixed_count) => {
+            if fixed_count > 0 {
+                info!("✓ Fixed SerializeSize for {} Static Mesh(es)", fixed_count);
+            } else {
+                info!("✓ No Static Mesh SerializeSize fixes needed");
+            }
+        }
+        Err(e) => {
+            error!("✗ Static Mesh SerializeSize fix failed: {}", e);
+            return Err(repak::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("SerializeSize fix failed: {}", e),
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub fn convert_to_iostore_directory(
     pak: &InstallableMod,
     mod_dir: PathBuf,
     to_pak_dir: PathBuf,
     packed_files_count: &AtomicI32,
+    on_stage: &dyn Fn(InstallStage),
 ) -> Result<(), repak::Error> {
     let mod_type = pak.mod_type.clone();
+
+    fix_serialsize_headers(pak, &to_pak_dir, on_stage)?;
     
     // Check for force_legacy_pak flag - skip IoStore conversion entirely
     if pak.force_legacy_pak {
         info!("Force Legacy PAK enabled for '{}'. Skipping IoStore conversion.", pak.mod_name);
+        report_stage(on_stage, &pak.mod_name, "writing", 0.0);
         repak_dir(pak, to_pak_dir, mod_dir, packed_files_count)?;
         return Ok(());
     }
     
     if mod_type == "Audio" || mod_type == "Movies" {
         debug!("{} mod detected. Not creating iostore packages",mod_type);
+        report_stage(on_stage, &pak.mod_name, "writing", 0.0);
         repak_dir(pak, to_pak_dir, mod_dir, packed_files_count)?;
         return Ok(());
     }
@@ -44,52 +106,6 @@ pub fn convert_to_iostore_directory(
     let mut paths = vec![];
     collect_files(&mut paths, &to_pak_dir)?;
 
-    // Static Mesh SerializeSize fix
-    if pak.fix_serialsize_header {
-        info!("╔══════════════════════════════════════════════════════════╗");
-        info!("║  STATIC MESH SERIALIZESIZE FIX - STARTING                ║");
-        info!("╚══════════════════════════════════════════════════════════╝");
-        
-        // Check for usmap file (required for unversioned assets) - stored in roaming folder
-        let usmap_full_path = if !pak.usmap_path.is_empty() {
-            // Construct full path to Usmap folder in roaming directory
-            let usmap_dir = dirs::config_dir()
-                .unwrap_or_else(|| std::path::PathBuf::from("."))
-                .join("Repak-X")
-                .join("Usmap");
-            let usmap_file = usmap_dir.join(&pak.usmap_path);
-            if usmap_file.exists() {
-                Some(usmap_file.to_string_lossy().to_string())
-            } else {
-                warn!("USmap file not found in roaming folder: {}", usmap_file.display());
-                None
-            }
-        } else {
-            warn!("No usmap file specified - detection may be limited for unversioned assets");
-            None
-        };
-        
-        let usmap_path = usmap_full_path.as_deref();
-        
-        match process_static_mesh_serializesize(&to_pak_dir, usmap_path) {
-            Ok(fixed_count) => {
-                if fixed_count > 0 {
-                    info!("✓ Fixed SerializeSize for {} Static Mesh(es)", fixed_count);
-                    info!("   Proceeding with IoStore conversion...");
-                } else {
-                    info!("✓ No Static Mesh SerializeSize fixes needed");
-                }
-            }
-            Err(e) => {
-                error!("✗ Static Mesh SerializeSize fix failed: {}", e);
-                return Err(repak::Error::Io(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("SerializeSize fix failed: {}", e),
-                )));
-            }
-        }
-    }
-
     // Note: Skeletal Mesh patching is now handled automatically by UAssetTool during IoStore conversion
 
     // Process textures using UAssetAPI to convert them to inline format
@@ -97,6 +113,7 @@ pub fn convert_to_iostore_directory(
     // Uses batch processing for much better performance (single UAssetTool process call)
     let processed_textures: std::collections::HashSet<String> = if pak.fix_textures {
         info!("Texture fix enabled for mod: {}", pak.mod_name);
+        report_stage(on_stage, &pak.mod_name, "fixing_textures", 0.0);
         
         // Collect all .uasset files that have corresponding .ubulk files (textures needing conversion)
         let texture_paths: Vec<PathBuf> = paths.iter()
@@ -234,20 +251,24 @@ pub fn convert_to_iostore_directory(
         None
     };
     
+    report_stage(on_stage, &pak.mod_name, "repacking", 0.0);
     info!("Converting to IoStore using UAssetTool...");
     info!("  Input directory: {}", to_pak_dir.display());
     info!("  Output base: {}", output_base.display());
     
-    // parallel_processing toggle: false=50% threads, true=75% threads
+    let aes_key = crate::install_mod::current_aes_key_hex();
     let result = uasset_toolkit::create_mod_iostore(
         &output_base.to_string_lossy(),
         &to_pak_dir.to_string_lossy(),
-        usmap_full_path.as_deref(),
-        Some(&pak.mount_point),
-        Some(true), // Enable compression
-        None, // Use default AES key
-        pak.parallel_processing, // Toggle: false=50%, true=75% CPU threads
-        pak.obfuscate, // Encrypt with game's AES key to block FModel extraction
+        &uasset_toolkit::ModIoStoreOptions {
+            usmap_path: usmap_full_path.as_deref(),
+            mount_point: Some(&pak.mount_point),
+            compress: Some(true),
+            aes_key: Some(&aes_key),
+            parallel: pak.parallel_processing, // Toggle: false=50%, true=75% CPU threads
+            obfuscate: pak.obfuscate, // Encrypt with game's AES key to block FModel extraction
+            container_version: pak.iostore_version, // Match the game's container format (None = toolkit default)
+        },
     ).map_err(|e| repak::Error::Io(std::io::Error::new(
         std::io::ErrorKind::Other,
         format!("IoStore conversion failed: {}", e),
@@ -258,6 +279,7 @@ pub fn convert_to_iostore_directory(
     info!("  UCAS: {}", result.ucas_path);
     info!("  PAK:  {}", result.pak_path);
     info!("  Converted {} assets ({} files)", result.converted_count, result.file_count);
+    report_stage(on_stage, &pak.mod_name, "writing", 1.0);
 
     packed_files_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
     Ok(())
@@ -495,7 +517,7 @@ fn apply_binary_patch(uasset_path: &Path, old_size: i64, new_size: i64) -> Resul
 
 /// Process Static Mesh .uasset files in a directory - fix SerializeSize for Static Meshes ONLY
 /// Uses UAssetAPI to detect asset type before processing
-fn process_static_mesh_serializesize(dir: &Path, usmap_path: Option<&str>) -> Result<usize, Box<dyn std::error::Error>> {
+pub fn process_static_mesh_serializesize(dir: &Path, usmap_path: Option<&str>) -> Result<usize, Box<dyn std::error::Error>> {
     let mut total_fixed = 0;
     let mut uasset_files = Vec::new();
 
@@ -577,3 +599,40 @@ fn collect_uasset_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn serialsize_fix_runs_for_legacy_paks() {
+        let source = tempfile::tempdir().unwrap();
+        let output = tempfile::tempdir().unwrap();
+        let pak = InstallableMod {
+            mod_name: "LegacyMesh_9999999_P".to_string(),
+            mod_type: "Mesh".to_string(),
+            fix_serialsize_header: true,
+            force_legacy_pak: true,
+            ..Default::default()
+        };
+
+        let stages = RefCell::new(Vec::new());
+        let on_stage = |stage: InstallStage| stages.borrow_mut().push(stage.stage);
+        convert_to_iostore_directory(
+            &pak,
+            output.path().to_path_buf(),
+            source.path().to_path_buf(),
+            &AtomicI32::new(0),
+            &on_stage,
+        )
+        .unwrap();
+
+        let stages = stages.into_inner();
+        let fix = stages.iter().position(|s| s == "fixing_serialize_size");
+        let write = stages.iter().position(|s| s == "writing");
+        assert!(fix.is_some(), "SerializeSize fix was skipped: {:?}", stages);
+        assert!(fix < write, "fix must run before the pak is written: {:?}", stages);
+        assert!(output.path().join("LegacyMesh_9999999_P.pak").exists());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::install_mod::{InstallableMod, AES_KEY};
+use crate::install_mod::{current_aes_key, InstallableMod};
 use crate::utils::collect_files;
 use log::{debug, info, error};
 use path_clean::PathClean;
@@ -14,6 +14,7 @@ use std::sync::atomic::AtomicI32;
 use tempfile::tempdir;
 
 use super::iotoc::{convert_to_iostore_directory, process_texture_files};
+use super::{report_stage, InstallStage};
 
 pub fn extract_pak_to_dir(pak: &InstallableMod, install_dir: PathBuf) -> Result<(), repak::Error> {
     let pak_reader = pak.clone().reader.clone().unwrap();
@@ -80,17 +81,21 @@ pub fn create_repak_from_pak(
     pak: &InstallableMod,
     mod_dir: PathBuf,
     packed_files_count: &AtomicI32,
+    on_stage: &dyn Fn(InstallStage),
 ) -> Result<(), repak::Error> {
     // extract the pak first into a temporary dir
     let temp_dir = tempdir().map_err(repak::Error::Io)?;
     let temp_path = temp_dir.path(); // Get the path of the temporary directory
 
+    report_stage(on_stage, &pak.mod_name, "extracting", 0.0);
     extract_pak_to_dir(pak, temp_path.to_path_buf())?;
+    report_stage(on_stage, &pak.mod_name, "extracting", 1.0);
     convert_to_iostore_directory(
         pak,
         mod_dir.clone(),
         temp_path.to_path_buf(),
         packed_files_count,
+        on_stage,
     )?;
     // repak_dir(pak, PathBuf::from(temp_path), mod_dir,packed_files_count)?;
     Ok(())
@@ -167,9 +172,12 @@ pub fn repak_dir(
 
     paths.sort();
 
+    let compression = pak.output_compression();
+    let compress = !compression.is_empty();
     let builder = repak::PakBuilder::new()
-        .compression(vec![pak.compression])
-        .key(AES_KEY.clone().0);
+        .compression(compression)
+        .key(current_aes_key().0)
+        .encrypt_index_only(pak.encrypt_index_only);
 
     let mut pak_writer = builder.writer(
         BufWriter::new(output_file),
@@ -189,7 +197,7 @@ pub fn repak_dir(
                 .expect("failed to convert to slash path");
 
             let entry = entry_builder
-                .build_entry(true, std::fs::read(p).expect("WTF"), rel)
+                .build_entry(compress, std::fs::read(p).expect("WTF"), rel)
                 .expect("Failed to build entry");
             (rel.to_string(), entry)
         })
@@ -206,7 +214,7 @@ pub fn repak_dir(
     let rel_paths_bytes: Vec<u8> = rel_paths.join("\n").into_bytes();
 
     let entry = entry_builder
-        .build_entry(true, rel_paths_bytes, "chunknames")
+        .build_entry(compress, rel_paths_bytes, "chunknames")
         .expect("Failed to build entry");
 
     pak_writer.write_entry("chunknames".to_string(), entry)?;
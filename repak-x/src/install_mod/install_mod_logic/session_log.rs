@@ -0,0 +1,52 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Number of per-session install logs kept on disk; older ones are pruned at startup
+const MAX_SESSION_LOGS: usize = 10;
+
+// Current session's install log, opened once by init_session_log
+static SESSION_LOG: Mutex<Option<(PathBuf, File)>> = Mutex::new(None);
+
+/// Open a fresh `install_<timestamp>.log` for this session in `dir`,
+/// pruning the oldest logs so at most `MAX_SESSION_LOGS` remain
+pub fn init_session_log(dir: &Path) -> std::io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let mut existing: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with("install_") && n.ends_with(".log"))
+                .unwrap_or(false)
+        })
+        .collect();
+    // Timestamped names sort chronologically
+    existing.sort();
+    let excess = (existing.len() + 1).saturating_sub(MAX_SESSION_LOGS);
+    for old in existing.iter().take(excess) {
+        let _ = fs::remove_file(old);
+    }
+
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let path = dir.join(format!("install_{}.log", timestamp));
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    *SESSION_LOG.lock().unwrap() = Some((path.clone(), file));
+    Ok(path)
+}
+
+/// Path of the current session's install log, if logging was initialised
+pub fn session_log_path() -> Option<PathBuf> {
+    SESSION_LOG.lock().unwrap().as_ref().map(|(p, _)| p.clone())
+}
+
+/// Append a line tagged with the mod it belongs to. No-op if the log isn't open.
+pub fn log_mod_line(mod_name: &str, line: &str) {
+    if let Some((_, file)) = SESSION_LOG.lock().unwrap().as_mut() {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+        let _ = writeln!(file, "{} [{}] {}", timestamp, mod_name, line);
+    }
+}
@@ -5,7 +5,44 @@
 //! for privacy protection while maintaining functionality.
 
 use sha2::{Digest, Sha256};
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether peer/host addresses are obfuscated in logs and UI events (on unless the user opts out)
+static OBFUSCATION_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turn address obfuscation on or off for everything that displays P2P addresses
+pub fn set_enabled(enabled: bool) {
+    OBFUSCATION_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    OBFUSCATION_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Format an advertised/peer address for logs and the UI, obfuscating it when enabled.
+/// Hostnames (e.g. relay tunnels) aren't personal addresses and are shown as-is.
+pub fn display_address(addr: &str) -> String {
+    if !is_enabled() {
+        return addr.to_string();
+    }
+    obfuscate_address(addr)
+}
+
+/// Obfuscate an "ip:port" socket address or multiaddress, leaving hostnames untouched
+///
+/// Example: "192.168.1.5:7777" -> "192.xxx.xxx.xxx [a1b2c3]:7777"
+pub fn obfuscate_address(addr: &str) -> String {
+    if let Ok(socket) = addr.parse::<SocketAddr>() {
+        format!("{}:{}", obfuscate_ip(&socket.ip().to_string()), socket.port())
+    } else if addr.starts_with('/') {
+        obfuscate_multiaddr(addr)
+    } else if addr.parse::<IpAddr>().is_ok() {
+        obfuscate_ip(addr)
+    } else {
+        addr.to_string()
+    }
+}
 
 /// Obfuscate an IP address for display purposes
 /// 
@@ -133,6 +170,15 @@ mod tests {
         assert!(obfuscated.contains("tcp/8080"));
     }
 
+    #[test]
+    fn test_obfuscate_address() {
+        let obfuscated = obfuscate_address("203.0.113.7:7777");
+        assert!(obfuscated.starts_with("203.xxx.xxx.xxx ["));
+        assert!(obfuscated.ends_with(":7777"));
+        assert!(!obfuscated.contains("0.113.7"));
+        assert_eq!(obfuscate_address("bore.pub:41234"), "bore.pub:41234");
+    }
+
     #[test]
     fn test_is_private_ip() {
         assert!(is_private_ip("192.168.1.1"));
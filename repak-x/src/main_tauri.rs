@@ -27,6 +27,7 @@ use simplelog::{ColorChoice, CombinedLogger, Config, TermLogger, TerminalMode, W
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{Emitter, Listener, Manager, State, Window};
 use utils::find_marvel_rivals;
@@ -51,6 +52,36 @@ struct P2PState {
     manager: Arc<p2p_manager::UnifiedP2PManager>,
 }
 
+/// Lines kept by the install log buffer, oldest dropped first
+const INSTALL_LOG_BUFFER_LINES: usize = 5000;
+
+/// Every install_log / install_stage event of this app session, so a log can be exported
+/// for a bug report after the install panel is closed
+#[derive(Default)]
+struct InstallLogBuffer {
+    lines: Mutex<std::collections::VecDeque<String>>,
+}
+
+impl InstallLogBuffer {
+    fn push(&self, line: String) {
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == INSTALL_LOG_BUFFER_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(format!("[{}] {}", chrono::Local::now().format("%H:%M:%S"), line));
+    }
+
+    fn snapshot(&self) -> Vec<String> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Dev mode watcher on a mod author's source folder, see watch_source_for_dev
+#[derive(Default)]
+struct DevWatchState {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
 /// Crash monitoring state
 struct CrashMonitorState {
     game_start_time: Mutex<Option<std::time::SystemTime>>,
@@ -64,6 +95,9 @@ struct DiscordState {
 
 #[derive(Default, Serialize, Deserialize)]
 struct AppState {
+    /// Layout version of the persisted state, upgraded by `migrate_state` on load (0 = before versioning)
+    #[serde(default)]
+    schema_version: u32,
     game_path: PathBuf,
     folders: Vec<ModFolder>,
     mod_metadata: Vec<ModMetadata>,
@@ -84,6 +118,79 @@ struct AppState {
     /// Enable obfuscation (encrypts IoStore with game's AES key to block FModel extraction)
     #[serde(default)]
     obfuscate: bool,
+    /// Max total uncompressed bytes extracted from a single archive (0 = built-in default)
+    #[serde(default)]
+    max_extracted_bytes: u64,
+    /// Max number of entries extracted from a single archive (0 = built-in default)
+    #[serde(default)]
+    max_entries: usize,
+    /// Longest single UAssetTool response buffered before the tool is restarted (0 = built-in default)
+    #[serde(default)]
+    max_tool_response_bytes: usize,
+    /// Extract archives to a persistent folder (app_dir/Extracted) instead of a temp dir, for inspection
+    #[serde(default)]
+    keep_extracted: bool,
+    /// Target IoStore container (TOC) version for repacks (None = toolkit's built-in default)
+    #[serde(default)]
+    iostore_version: Option<u8>,
+    /// Folder holding the last backup of the base game's Paks, used by verify_game_paks
+    #[serde(default)]
+    game_paks_backup_dir: Option<PathBuf>,
+    /// Recompress uncompressed / non-Oodle legacy paks with Oodle during quick organize
+    #[serde(default)]
+    recompress_on_organize: bool,
+    /// Classify obvious textures from the uasset header during detection, only asking UAssetTool about ambiguous files
+    #[serde(default)]
+    fast_detection: bool,
+    /// Always strip mipmaps from mods that contain textures, regardless of the per-mod toggle
+    #[serde(default)]
+    auto_strip_mipmaps: bool,
+    /// Maximum P2P downloads running at once; further accepted transfers wait in a queue (0 = default)
+    #[serde(default)]
+    p2p_max_concurrent: usize,
+    /// Install mods received over P2P once their checksums verify, instead of leaving the raw files in ~mods
+    #[serde(default)]
+    auto_install_received: bool,
+    /// Show P2P addresses in full in logs and the UI instead of obfuscating them
+    #[serde(default)]
+    ip_obfuscation_disabled: bool,
+    /// AES key (hex) for pak/IoStore operations when the game no longer uses the bundled one
+    #[serde(default)]
+    aes_key: Option<String>,
+    /// Enabled mods (by .pak path) when the game was last launched from the app, i.e. what it has mounted
+    #[serde(default)]
+    mounted_mods: Option<Vec<PathBuf>>,
+    /// Active test-load isolation, kept until restored so a crash doesn't lose what was enabled
+    #[serde(default)]
+    isolation: Option<IsolateToken>,
+    /// Crash bisection in progress, kept across restarts since every step needs a game launch
+    #[serde(default)]
+    bisection: Option<BisectionSession>,
+    /// Run repair_config on every startup to drop metadata and folders that no longer exist
+    #[serde(default)]
+    repair_config_on_startup: bool,
+    /// UAssetTool executable chosen by the user, overriding the guessed install location
+    #[serde(default)]
+    uasset_tool_path: Option<String>,
+    /// How launch_game handles the launch_record skip-launcher patch
+    #[serde(default)]
+    launcher_skip_mode: LauncherSkipMode,
+    /// Game install folder (holding MarvelRivals_Launcher.exe), found by walking up from game_path
+    #[serde(default)]
+    game_root: Option<PathBuf>,
+}
+
+/// How the launcher is skipped via `launch_record` ("0" skips it, "6" is the game default)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LauncherSkipMode {
+    /// Never touch launch_record
+    Off,
+    /// Skip the launcher for launches from the app, restoring the file once the game is up
+    #[default]
+    PerLaunch,
+    /// Keep the patch in place, also for launches outside the app
+    Always,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -127,6 +234,23 @@ struct ModMetadata {
     folder_id: Option<String>,
     #[serde(default)]
     custom_tags: Vec<String>,
+    /// Where the mod was downloaded from (Nexus page, etc.), http(s) only
+    #[serde(default)]
+    source_url: Option<String>,
+    /// Cover image, copied into the app's thumbnails folder by set_mod_thumbnail
+    #[serde(default)]
+    thumbnail_path: Option<String>,
+}
+
+impl ModMetadata {
+    /// Whether this entry belongs to the mod at `path`, whichever of its enabled/disabled
+    /// names (`.pak`, `.bak_repak`, `.pak_disabled`) either side was recorded under
+    fn matches(&self, path: &Path) -> bool {
+        self.path == path ||
+        self.path.with_extension("pak") == path ||
+        self.path.with_extension("bak_repak") == path ||
+        self.path.with_extension("pak_disabled") == path
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -141,6 +265,15 @@ struct ModEntry {
     // Character/skin info from character_data (dynamically looked up)
     character_name: Option<String>,
     skin_name: Option<String>,
+    source_url: Option<String>,
+    /// Mod type (e.g. "Hulk - Skin"), only resolved by query_mods when filtering on it
+    mod_type: Option<String>,
+    /// Number of files inside the mod (from the .utoc for IoStore), only resolved with resolve_names
+    file_count: Option<usize>,
+    /// IoStore container is encrypted (installed with obfuscation), read from the .utoc header
+    obfuscated: bool,
+    /// Cover image for grid views, only set when the stored file still exists
+    thumbnail_path: Option<String>,
 }
 
 // ============================================================================
@@ -156,8 +289,8 @@ struct DrpSettingsDto {
 }
 
 #[tauri::command]
-async fn get_drp_settings(state: State<'_, Arc<Mutex<AppState>>>) -> Result<DrpSettingsDto, String> {
-    let state = state.lock().unwrap();
+async fn get_drp_settings(state: State<'_, Arc<RwLock<AppState>>>) -> Result<DrpSettingsDto, String> {
+    let state = state.read().await;
     Ok(DrpSettingsDto {
         enable_drp: Some(state.enable_drp),
         accent_color: state.accent_color.clone(),
@@ -167,10 +300,10 @@ async fn get_drp_settings(state: State<'_, Arc<Mutex<AppState>>>) -> Result<DrpS
 #[tauri::command]
 async fn save_drp_settings(
     settings: DrpSettingsDto, 
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
     discord: State<'_, DiscordState>
 ) -> Result<(), String> {
-    let mut state = state.lock().unwrap();
+    let mut state = state.write().await;
     
     // Handle DRP Settings
     if let Some(enabled) = settings.enable_drp {
@@ -215,10 +348,10 @@ async fn save_drp_settings(
 #[tauri::command]
 async fn set_parallel_processing(
     enabled: bool,
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
 ) -> Result<(), String> {
     info!("set_parallel_processing called: enabled={}", enabled);
-    let mut state = state.lock().unwrap();
+    let mut state = state.write().await;
     state.parallel_processing = enabled;
     save_state(&state).map_err(|e| e.to_string())?;
     Ok(())
@@ -226,8 +359,8 @@ async fn set_parallel_processing(
 
 /// Get current parallel processing setting
 #[tauri::command]
-async fn get_parallel_processing(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
-    let state = state.lock().unwrap();
+async fn get_parallel_processing(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
     Ok(state.parallel_processing)
 }
 
@@ -235,10 +368,10 @@ async fn get_parallel_processing(state: State<'_, Arc<Mutex<AppState>>>) -> Resu
 #[tauri::command]
 async fn set_obfuscate(
     enabled: bool,
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
 ) -> Result<(), String> {
     info!("set_obfuscate called: enabled={}", enabled);
-    let mut state = state.lock().unwrap();
+    let mut state = state.write().await;
     state.obfuscate = enabled;
     save_state(&state).map_err(|e| e.to_string())?;
     Ok(())
@@ -246,805 +379,2643 @@ async fn set_obfuscate(
 
 /// Get current obfuscation setting
 #[tauri::command]
-async fn get_obfuscate(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
-    let state = state.lock().unwrap();
+async fn get_obfuscate(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
     Ok(state.obfuscate)
 }
 
+/// Set the IoStore container (TOC) version used when repacking (None = toolkit default)
 #[tauri::command]
-async fn get_game_path(state: State<'_, Arc<Mutex<AppState>>>) -> Result<String, String> {
-    let state = state.lock().unwrap();
-    Ok(state.game_path.to_string_lossy().to_string())
+async fn set_iostore_version(
+    version: Option<u8>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_iostore_version called: version={:?}", version);
+    let mut state = state.write().await;
+    state.iostore_version = version;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
 }
 
+/// Get the configured IoStore container version (None = toolkit default)
 #[tauri::command]
-async fn set_game_path(path: String, state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), String> {
-    let mods_path = PathBuf::from(&path);
-    
-    // Auto-deploy bundled LOD Disabler mod if path exists
-    if mods_path.exists() {
-        match deploy_bundled_lod_mod(&mods_path) {
-            Ok(true) => info!("Auto-deployed bundled LOD Disabler mod"),
-            Ok(false) => info!("Bundled LOD Disabler mod already present or not bundled"),
-            Err(e) => warn!("Failed to auto-deploy LOD Disabler mod: {}", e),
-        }
-    }
-    
-    let mut state = state.lock().unwrap();
-    state.game_path = mods_path;
+async fn get_iostore_version(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Option<u8>, String> {
+    let state = state.read().await;
+    Ok(state.iostore_version)
+}
+
+/// The base game's main IoStore container in the Paks folder
+fn find_game_utoc(paks_dir: &Path) -> Result<PathBuf, String> {
+    let mut game_utocs: Vec<PathBuf> = std::fs::read_dir(paks_dir)
+        .map_err(|e| format!("Failed to read {}: {}", paks_dir.display(), e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("utoc"))
+        .collect();
+    // Prefer the main chunk, which every game build ships
+    game_utocs.sort_by_key(|p| !p.file_name().and_then(|n| n.to_str()).unwrap_or("").starts_with("pakchunk0"));
+    game_utocs.into_iter().next()
+        .ok_or_else(|| format!("No game IoStore containers found in {}", paks_dir.display()))
+}
+
+/// Read the container version from the installed game's own IoStore files and use it for repacks
+#[tauri::command]
+async fn detect_iostore_version(state: State<'_, Arc<RwLock<AppState>>>) -> Result<u8, String> {
+    let game_path = state.read().await.game_path.clone();
+    // game_path is the ~mods folder; the base game containers live in its parent Paks folder
+    let paks_dir = game_path.parent()
+        .ok_or("Game path is not set")?;
+    let utoc = find_game_utoc(paks_dir)?;
+
+    let version = uasset_toolkit::detect_iostore_version(&utoc.to_string_lossy())
+        .map_err(|e| e.to_string())?;
+    info!("Detected IoStore container version {} from {}", version, utoc.display());
+
+    let mut state = state.write().await;
+    state.iostore_version = Some(version);
     save_state(&state).map_err(|e| e.to_string())?;
-    Ok(())
+    Ok(version)
+}
+
+/// Result of comparing a mod's engine versions against the installed game
+#[derive(Debug, Clone, Serialize)]
+struct CompatReport {
+    /// Object versions of a package inside the mod (legacy .pak mods only)
+    mod_package_version: Option<uasset_toolkit::PackageVersion>,
+    /// Object versions of a package from a base-game pak
+    game_package_version: Option<uasset_toolkit::PackageVersion>,
+    /// .utoc container version of the mod (IoStore mods only)
+    mod_container_version: Option<u8>,
+    game_container_version: Option<u8>,
+    /// False when the mod targets a different engine version than the game
+    compatible: bool,
+    warnings: Vec<String>,
+}
+
+/// Object versions of the first .uasset in a legacy pak, None if the pak holds no packages
+/// (e.g. the stub pak of an IoStore container)
+fn read_pak_package_version(pak_path: &Path) -> Result<Option<uasset_toolkit::PackageVersion>, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    let file = File::open(pak_path)
+        .map_err(|e| format!("Failed to open {}: {}", pak_path.display(), e))?;
+    let mut reader = BufReader::new(file);
+    let pak = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .map_err(|e| format!("Failed to read {}: {}", pak_path.display(), e))?;
+    let Some(uasset) = pak.files().into_iter().find(|f| f.to_lowercase().ends_with(".uasset")) else {
+        return Ok(None);
+    };
+    let data = pak.get(&uasset, &mut reader)
+        .map_err(|e| format!("Failed to read {}: {}", uasset, e))?;
+
+    let temp_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let temp_uasset = temp_dir.path().join("package.uasset");
+    std::fs::write(&temp_uasset, data)
+        .map_err(|e| format!("Failed to write {}: {}", uasset, e))?;
+    uasset_toolkit::get_package_version(&temp_uasset.to_string_lossy())
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Object versions of the game's cooked packages, read from the first base-game pak that
+/// still carries legacy packages
+fn read_game_package_version(paks_dir: &Path) -> Option<uasset_toolkit::PackageVersion> {
+    let mut game_paks: Vec<PathBuf> = std::fs::read_dir(paks_dir).ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("pak"))
+        .collect();
+    game_paks.sort();
+    game_paks.iter().find_map(|pak| match read_pak_package_version(pak) {
+        Ok(version) => version,
+        Err(e) => {
+            warn!("Skipping {} for game version detection: {}", pak.display(), e);
+            None
+        }
+    })
 }
 
+/// Compare the engine versions a mod was built with against the installed game, so users get
+/// a heads-up before installing a mod built for an older (or newer) game build
 #[tauri::command]
-async fn auto_detect_game_path(state: State<'_, Arc<Mutex<AppState>>>, window: Window) -> Result<String, String> {
-    match find_marvel_rivals() {
-        Some(game_root) => {
-            // game_path should be the ~mods directory (matching egui behavior)
-            let mods_path = game_root.join("~mods");
-            
-            // Create ~mods directory if it doesn't exist
-            if !mods_path.exists() {
-                if let Err(e) = std::fs::create_dir_all(&mods_path) {
-                    let error_msg = format!("Failed to create ~mods directory: {}", e);
-                    toast_events::emit_game_path_failed(&window, &error_msg);
-                    return Err(error_msg);
-                }
+async fn check_mod_compatibility(
+    mod_path: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<CompatReport, String> {
+    info!("check_mod_compatibility called: {}", mod_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+    let game_path = state.read().await.game_path.clone();
+    let paks_dir = game_path.parent()
+        .ok_or("Game path is not set")?
+        .to_path_buf();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut warnings = Vec::new();
+
+        let utoc = iostore_companion_path(&path, "utoc");
+        let (mod_package_version, mod_container_version) = if utoc.exists() {
+            // Zen packages don't keep the legacy summary, the container version is what we can compare
+            let version = uasset_toolkit::detect_iostore_version(&utoc.to_string_lossy())
+                .map_err(|e| e.to_string())?;
+            (None, Some(version))
+        } else {
+            (read_pak_package_version(&path)?, None)
+        };
+
+        let game_container_version = find_game_utoc(&paks_dir).ok()
+            .and_then(|utoc| uasset_toolkit::detect_iostore_version(&utoc.to_string_lossy()).ok());
+        let game_package_version = if mod_package_version.is_some() {
+            read_game_package_version(&paks_dir)
+        } else {
+            None
+        };
+
+        if let (Some(mod_version), Some(game_version)) = (mod_container_version, game_container_version) {
+            if mod_version != game_version {
+                warnings.push(format!(
+                    "Mod IoStore container version {} differs from the game's ({}); it was likely built for a different game build",
+                    mod_version, game_version
+                ));
             }
-            
-            // Auto-deploy bundled LOD Disabler mod
-            match deploy_bundled_lod_mod(&mods_path) {
-                Ok(true) => info!("Auto-deployed bundled LOD Disabler mod"),
-                Ok(false) => info!("Bundled LOD Disabler mod already present or not bundled"),
-                Err(e) => warn!("Failed to auto-deploy LOD Disabler mod: {}", e),
+        }
+        if let (Some(mod_version), Some(game_version)) = (&mod_package_version, &game_package_version) {
+            if mod_version.file_version_ue5 != game_version.file_version_ue5
+                || mod_version.file_version_ue4 != game_version.file_version_ue4
+            {
+                warnings.push(format!(
+                    "Mod was cooked with engine object version UE4 {} / UE5 {}, the game uses UE4 {} / UE5 {}",
+                    mod_version.file_version_ue4, mod_version.file_version_ue5,
+                    game_version.file_version_ue4, game_version.file_version_ue5
+                ));
+            } else if mod_version.file_version_licensee_ue != game_version.file_version_licensee_ue {
+                warnings.push(format!(
+                    "Mod licensee version {} differs from the game's ({})",
+                    mod_version.file_version_licensee_ue, game_version.file_version_licensee_ue
+                ));
             }
-            
-            let mut state = state.lock().unwrap();
-            state.game_path = mods_path.clone();
-            save_state(&state).map_err(|e| e.to_string())?;
-            Ok(mods_path.to_string_lossy().to_string())
         }
-        None => {
-            let error_msg = "Could not auto-detect Marvel Rivals installation".to_string();
-            toast_events::emit_game_path_failed(&window, &error_msg);
-            Err(error_msg)
+        // Only version mismatches make a mod incompatible, not being able to tell
+        let compatible = warnings.is_empty();
+        if game_container_version.is_none() && game_package_version.is_none() {
+            warnings.push("Could not detect the installed game version".to_string());
         }
-    }
-}
-
-#[tauri::command]
-async fn start_file_watcher(
-    window: Window,
-    state: State<'_, Arc<Mutex<AppState>>>,
-    watcher_state: State<'_, WatcherState>,
-) -> Result<(), String> {
-    let state_guard = state.lock().unwrap();
-    let game_path = state_guard.game_path.clone();
-    drop(state_guard);
 
-    if !game_path.exists() {
-        return Ok(()); // Can't watch non-existent path
-    }
+        for warning in &warnings {
+            warn!("[Compat] {}: {}", path.display(), warning);
+        }
+        Ok(CompatReport {
+            compatible,
+            mod_package_version,
+            game_package_version,
+            mod_container_version,
+            game_container_version,
+            warnings,
+        })
+    })
+    .await
+    .map_err(|e| format!("Compatibility check failed: {}", e))?
+}
+
+/// Textures at or above this size on either side count as 4K
+const IMPACT_4K_TEXTURE_SIZE: i32 = 4096;
+/// Meshes with more triangles than this are flagged as high-poly
+const IMPACT_HIGH_POLY_TRIANGLES: i32 = 100_000;
+/// Scores above these are reported as medium / high impact
+const IMPACT_MEDIUM_SCORE: f64 = 10.0;
+const IMPACT_HIGH_SCORE: f64 = 25.0;
+
+/// Rough estimate of how heavy a mod's textures and meshes are, for a pre-install stutter warning
+#[derive(Debug, Clone, Serialize, Default)]
+struct ImpactReport {
+    /// Heuristic cost: one point per 2K texture (a third more with mipmaps) and per 100k triangles
+    score: f64,
+    /// "low", "medium" or "high"
+    level: String,
+    texture_count: usize,
+    mesh_count: usize,
+    /// Largest texture as (width, height)
+    largest_texture: Option<(i32, i32)>,
+    /// Highest triangle count of any mesh
+    max_triangles: Option<i32>,
+    flags: Vec<String>,
+}
+
+impl ImpactReport {
+    fn from_assets(
+        textures: &[(String, uasset_toolkit::TextureInfo)],
+        meshes: &[(String, uasset_toolkit::MeshInfo)],
+    ) -> Self {
+        let mut report = ImpactReport {
+            texture_count: textures.len(),
+            mesh_count: meshes.len(),
+            ..Default::default()
+        };
 
-    let mut watcher_guard = watcher_state.watcher.lock().unwrap();
-    
-    // Create a new watcher with debouncing
-    let window_clone = window.clone();
-    let last_event_time = Arc::new(Mutex::new(std::time::Instant::now()));
-    
-    let paused = watcher_state.paused.clone();
-    let watcher_result = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
-        match res {
-            Ok(event) => {
-                // Skip events while paused (e.g. during P2P transfer)
-                if paused.load(Ordering::Relaxed) {
-                    return;
-                }
-                // We only care about Create, Remove, Rename, and Modify events (files and directories)
-                match event.kind {
-                    EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_) => {
-                         // Debounce: only emit if 500ms have passed since last event
-                         let mut last_time = last_event_time.lock().unwrap();
-                         let now = std::time::Instant::now();
-                         let elapsed = now.duration_since(*last_time);
-                         
-                         if elapsed.as_millis() >= 500 {
-                             *last_time = now;
-                             window_clone.emit("mods_dir_changed", ()).unwrap_or_else(|e| {
-                                 error!("Failed to emit mods_dir_changed: {}", e);
-                             });
-                         }
-                    },
-                    _ => {}
+        let mut huge_with_mips = 0;
+        let mut huge_without_mips = 0;
+        for (_, info) in textures {
+            let (Some(width), Some(height)) = (info.width, info.height) else {
+                continue;
+            };
+            let has_mips = info.mip_gen_settings.as_deref().is_none_or(|m| m != "NoMipmaps");
+            let mut cost = (width as f64 * height as f64) / (2048.0 * 2048.0);
+            if has_mips {
+                cost *= 4.0 / 3.0;
+            }
+            report.score += cost;
+            if report.largest_texture.is_none_or(|(w, h)| w * h < width * height) {
+                report.largest_texture = Some((width, height));
+            }
+            if width.max(height) >= IMPACT_4K_TEXTURE_SIZE {
+                if has_mips {
+                    huge_with_mips += 1;
+                } else {
+                    huge_without_mips += 1;
                 }
-            },
-            Err(e) => error!("Watch error: {:?}", e),
+            }
+        }
+        if huge_with_mips > 0 {
+            report.flags.push(format!("Contains {} 4K texture(s) with mipmaps", huge_with_mips));
+        }
+        if huge_without_mips > 0 {
+            report.flags.push(format!("Contains {} 4K texture(s)", huge_without_mips));
         }
-    });
 
-    match watcher_result {
-        Ok(mut watcher) => {
-            if let Err(e) = watcher.watch(&game_path, RecursiveMode::Recursive) {
-                error!("Failed to watch game path: {}", e);
-                return Err(e.to_string());
+        for (name, info) in meshes {
+            let Some(triangles) = info.triangle_count else {
+                continue;
+            };
+            report.score += triangles as f64 / 100_000.0;
+            report.max_triangles = Some(report.max_triangles.map_or(triangles, |max| max.max(triangles)));
+            if triangles > IMPACT_HIGH_POLY_TRIANGLES {
+                report.flags.push(format!("High-poly mesh: {} ({} triangles)", name, triangles));
             }
-            info!("Started watching game path: {:?}", game_path);
-            *watcher_guard = Some(watcher);
-            Ok(())
-        },
-        Err(e) => {
-            error!("Failed to create watcher: {}", e);
-            Err(e.to_string())
         }
+
+        // A flagged 4K texture or high-poly mesh is never "low", whatever the total score
+        report.level = if report.score > IMPACT_HIGH_SCORE {
+            "high"
+        } else if report.score > IMPACT_MEDIUM_SCORE || !report.flags.is_empty() {
+            "medium"
+        } else {
+            "low"
+        }.to_string();
+        report
     }
 }
 
+/// Estimate how much a mod's textures and meshes might cost in-game, so users get a
+/// "this might cause stutter" warning before installing it
 #[tauri::command]
-async fn get_pak_files(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec<ModEntry>, String> {
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
-    
-    info!("Loading mods from: {}", game_path.display());
-    
-    if !game_path.exists() {
-        info!("Game path does not exist: {}", game_path.display());
-        return Err(format!("Game path does not exist: {}", game_path.display()));
+async fn estimate_mod_impact(
+    mod_path: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<ImpactReport, String> {
+    info!("estimate_mod_impact called: {}", mod_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+    let usmap_filename = state.read().await.usmap_path.clone();
+    // UAssetTool needs the mappings to parse textures and meshes
+    if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+        std::env::set_var("USMAP_PATH", &usmap_full_path);
     }
 
-    // game_path IS the ~mods directory (matching egui behavior)
-    let mut mods = Vec::new();
-    
-    // Scan root ~mods directory and all subdirectories recursively (no depth limit)
-    for entry in WalkDir::new(&game_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        
-        // Skip directories themselves
-        if path.is_dir() {
-            continue;
+    tauri::async_runtime::spawn_blocking(move || {
+        let work_dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+        let utoc = iostore_companion_path(&path, "utoc");
+        if utoc.exists() {
+            let aes_key = install_mod::current_aes_key_hex();
+            uasset_toolkit::extract_iostore(&utoc.to_string_lossy(), &work_dir.path().to_string_lossy(), Some(&aes_key))
+                .map_err(|e| e.to_string())?;
+        } else {
+            let mut reader = std::io::BufReader::new(File::open(&path).map_err(|e| format!("Failed to open PAK: {}", e))?);
+            let pak_reader = repak::PakBuilder::new()
+                .key(install_mod::current_aes_key().0)
+                .reader(&mut reader)
+                .map_err(|e| format!("Failed to read PAK: {}", e))?;
+            extract_pak_entries(&pak_reader, &mut reader, work_dir.path())?;
         }
-        
-        let ext = path.extension().and_then(|s| s.to_str());
-        
-        // Check for .pak, .bak_repak, and .pak_disabled files
-        if ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled") {
-            let is_enabled = ext == Some("pak");
-            
-            // Determine which folder this mod is in
-            let root_folder_name = game_path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("~mods")
-                .to_string();
-            
-            // Determine folder_id based on relative path from game_path
-            let folder_id = if let Some(parent) = path.parent() {
-                if parent == game_path {
-                    // Mod is directly in root - use root folder name (e.g., "~mods")
-                    Some(root_folder_name)
-                } else {
-                    // Mod is in a subfolder - use relative path from game_path as ID
-                    parent.strip_prefix(game_path)
-                        .map(|p| p.to_string_lossy().replace('\\', "/"))
-                        .ok()
-                }
-            } else {
-                Some(root_folder_name)
-            };
-            
-            info!("Found PAK file: {} (enabled: {}, folder: {:?})", path.display(), is_enabled, folder_id);
-            
-            let metadata = state.mod_metadata.iter()
-                .find(|m| {
-                    m.path == path || 
-                    m.path.with_extension("pak") == path || 
-                    m.path.with_extension("bak_repak") == path ||
-                    m.path.with_extension("pak_disabled") == path
-                });
-            
-            let ucas_path = path.with_extension("ucas");
-            let file_size = if ucas_path.exists() {
-                std::fs::metadata(&ucas_path)
-                    .map(|m| m.len())
-                    .unwrap_or(0)
-            } else {
-                std::fs::metadata(path)
-                    .map(|m| m.len())
-                    .unwrap_or(0)
-            };
-            
-            // Calculate priority
-            // Priority 0 = "!" prefix (highest priority)
-            // Priority 1-N = 7-N+6 nines displayed as 1-based (7 nines → Priority 1, 8 nines → Priority 2, etc.)
-            let mut priority = 0;
-            let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-            
-            // Check for "!" prefix (highest priority)
-            if file_stem.starts_with("!") {
-                priority = 0; // Highest priority
-            } else if file_stem.ends_with("_P") {
-                let base_no_p = file_stem.strip_suffix("_P").unwrap();
-                // Check for _999... suffix
-                let re_nums = Regex::new(r"_(\d+)$").unwrap();
-                if let Some(caps) = re_nums.captures(base_no_p) {
-                    let nums = &caps[1];
-                    // Verify they are all 9s
-                    if nums.chars().all(|c| c == '9') {
-                        let actual_nines = nums.len();
-                        // Convert actual nines count to UI priority (1-based)
-                        // 7 nines → Priority 1, 8 nines → Priority 2, etc.
-                        if actual_nines >= 7 {
-                            priority = actual_nines - 6;
-                        }
-                    }
-                }
-            }
-            
-            mods.push(ModEntry {
-                path: path.to_path_buf(),
-                enabled: is_enabled,
-                custom_name: metadata.and_then(|m| m.custom_name.clone()),
-                folder_id,
-                custom_tags: metadata.map(|m| m.custom_tags.clone()).unwrap_or_default(),
-                file_size,
-                priority,
-                character_name: None,
-                skin_name: None,
-            });
-        }
-    }
 
-    info!("Found {} mod(s)", mods.len());
-    Ok(mods)
+        let assets: Vec<String> = WalkDir::new(work_dir.path())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "uasset"))
+            .map(|e| e.path().to_string_lossy().to_string())
+            .collect();
+        let asset_name = |asset: &str| Path::new(asset).file_stem().unwrap_or_default().to_string_lossy().to_string();
+
+        let textures: Vec<_> = uasset_toolkit::batch_get_texture_info(&assets)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .zip(&assets)
+            .filter_map(|(info, asset)| Some((asset_name(asset), info?)))
+            .filter(|(_, info)| info.width.is_some())
+            .collect();
+        let meshes: Vec<_> = uasset_toolkit::batch_get_mesh_info(&assets)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .zip(&assets)
+            .filter_map(|(info, asset)| Some((asset_name(asset), info?)))
+            .filter(|(_, info)| info.triangle_count.is_some())
+            .collect();
+
+        let report = ImpactReport::from_assets(&textures, &meshes);
+        info!(
+            "[Impact] {}: score {:.1} ({}), {} texture(s), {} mesh(es)",
+            path.display(), report.score, report.level, report.texture_count, report.mesh_count
+        );
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Impact estimate failed: {}", e))?
 }
 
+/// Set whether quick organize recompresses legacy paks that aren't Oodle-compressed
 #[tauri::command]
-async fn set_mod_priority(mod_path: String, priority: usize) -> Result<(), String> {
-    let path = PathBuf::from(&mod_path);
-    if !path.exists() {
-         return Err("Mod file does not exist".to_string());
-    }
-    
-    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-    let stem = path.file_stem().and_then(|s| s.to_str()).ok_or("Invalid filename")?;
-    
-    // Strip leading "!" if present (highest priority marker)
-    let stem_no_exclaim = stem.strip_prefix("!").unwrap_or(stem);
-    
-    // 1. Strip _P if present
-    let base_no_p = if stem_no_exclaim.ends_with("_P") {
-        stem_no_exclaim.strip_suffix("_P").unwrap()
-    } else {
-        stem_no_exclaim
-    };
-    
-    // 2. Strip _999... if present
-    let re = Regex::new(r"^(.*)_(\d+)$").unwrap();
-    let clean_base = if let Some(caps) = re.captures(base_no_p) {
-        let prefix = &caps[1];
-        let numbers = &caps[2];
-        if numbers.chars().all(|c| c == '9') {
-            prefix.to_string()
-        } else {
-            base_no_p.to_string()
-        }
-    } else {
-        base_no_p.to_string()
-    };
-    
-    // 3. Construct new name with new priority
-    // Priority 0 = "!" prefix (highest priority) with minimum 7 nines
-    // Priority 1-N = 7-N+6 nines (1→7 nines, 2→8 nines, etc.)
-    let new_stem = if priority == 0 {
-        // Highest priority: use "!" prefix with minimum 7 nines
-        let min_nines = "9".repeat(7);
-        format!("!{}_{}_P", clean_base, min_nines)
-    } else {
-        // Convert UI priority (1-based) to actual nines count (7-based)
-        // Remove "!" prefix if present (since priority > 0)
-        let actual_nines = priority + 6; // Priority 1 → 7 nines, Priority 2 → 8 nines, etc.
-        let new_nines = "9".repeat(actual_nines);
-        format!("{}_{}_P", clean_base, new_nines)
-    };
-    
-    let new_filename = format!("{}.{}", new_stem, extension);
-    let new_path = path.with_file_name(&new_filename);
-    
-    if new_path == path {
-        return Ok(()); // No change
-    }
+async fn set_recompress_on_organize(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_recompress_on_organize called: enabled={}", enabled);
+    let mut state = state.write().await;
+    state.recompress_on_organize = enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-    if new_path.exists() {
-        return Err("A mod with this priority already exists".to_string());
+/// Get current recompress-on-organize setting
+#[tauri::command]
+async fn get_recompress_on_organize(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
+    Ok(state.recompress_on_organize)
+}
+
+/// Set whether detection uses the header-sniffing fast path for texture classification
+#[tauri::command]
+async fn set_fast_detection(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_fast_detection called: enabled={}", enabled);
+    let mut state = state.write().await;
+    state.fast_detection = enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Get current fast detection setting
+#[tauri::command]
+async fn get_fast_detection(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
+    Ok(state.fast_detection)
+}
+
+/// Set whether texture mods always get their mipmaps stripped on install
+#[tauri::command]
+async fn set_auto_strip_mipmaps(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_auto_strip_mipmaps called: enabled={}", enabled);
+    let mut state = state.write().await;
+    state.auto_strip_mipmaps = enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Whether the auto strip mipmaps setting covers a mod: it ships uassets and either looks like a
+/// texture mod or had textures detected. Shared by detection and install so both agree.
+fn auto_strip_mipmaps_applies(mod_type: &str, contains_uassets: bool, detected_textures: bool) -> bool {
+    contains_uassets && (detected_textures || mod_type.contains("Texture"))
+}
+
+/// Get current auto strip mipmaps setting
+#[tauri::command]
+async fn get_auto_strip_mipmaps(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
+    Ok(state.auto_strip_mipmaps)
+}
+
+/// Set archive extraction limits (guards against decompression bombs). 0 = built-in default.
+#[tauri::command]
+async fn set_archive_limits(
+    max_extracted_bytes: u64,
+    max_entries: usize,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_archive_limits called: max_extracted_bytes={}, max_entries={}", max_extracted_bytes, max_entries);
+    let mut state = state.write().await;
+    state.max_extracted_bytes = max_extracted_bytes;
+    state.max_entries = max_entries;
+    install_mod::install_mod_logic::archives::set_extraction_limits(max_extracted_bytes, max_entries);
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Set the largest UAssetTool response accepted before the tool is restarted. 0 = built-in default.
+#[tauri::command]
+async fn set_max_tool_response_bytes(
+    max_bytes: usize,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_max_tool_response_bytes called: {}", max_bytes);
+    let mut state = state.write().await;
+    state.max_tool_response_bytes = max_bytes;
+    uasset_toolkit::set_max_response_bytes(effective_max_tool_response_bytes(max_bytes))
+        .map_err(|e| format!("Failed to apply UAssetTool response limit: {}", e))?;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Get the current UAssetTool response size limit in bytes
+#[tauri::command]
+async fn get_max_tool_response_bytes(state: State<'_, Arc<RwLock<AppState>>>) -> Result<usize, String> {
+    let state = state.read().await;
+    Ok(effective_max_tool_response_bytes(state.max_tool_response_bytes))
+}
+
+fn effective_max_tool_response_bytes(saved: usize) -> usize {
+    if saved == 0 { uasset_toolkit::DEFAULT_MAX_RESPONSE_BYTES } else { saved }
+}
+
+/// Set whether archives are extracted to a persistent folder for inspection
+#[tauri::command]
+async fn set_keep_extracted(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_keep_extracted called: enabled={}", enabled);
+    let mut state = state.write().await;
+    state.keep_extracted = enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Get current keep_extracted setting
+#[tauri::command]
+async fn get_keep_extracted(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
+    Ok(state.keep_extracted)
+}
+
+/// Open the most recent persistent archive extraction in the file explorer
+#[tauri::command]
+async fn reveal_last_extraction() -> Result<String, String> {
+    let dir = crate::install_mod::install_mod_logic::archives::last_kept_extraction()
+        .filter(|d| d.exists())
+        .ok_or_else(|| "No kept extraction found. Enable 'keep extracted' and drop an archive first.".to_string())?;
+    let dir_str = dir.to_string_lossy().to_string();
+    open_in_explorer(dir_str.clone()).await?;
+    Ok(dir_str)
+}
+
+/// Reveal the per-session install log directory in the file explorer.
+/// Returns the path of the current session's log file.
+#[tauri::command]
+async fn open_install_logs() -> Result<String, String> {
+    let dir = install_log_dir();
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create install log directory: {}", e))?;
+    open_in_explorer(dir.to_string_lossy().to_string()).await?;
+    Ok(install_mod::install_mod_logic::session_log::session_log_path()
+        .unwrap_or(dir)
+        .to_string_lossy()
+        .to_string())
+}
+
+/// Install log lines captured during this app session
+#[tauri::command]
+async fn get_last_install_log(log_buffer: State<'_, Arc<InstallLogBuffer>>) -> Result<Vec<String>, String> {
+    Ok(log_buffer.snapshot())
+}
+
+/// Write the install log lines captured during this app session to `dest`
+#[tauri::command]
+async fn save_install_log(dest: String, log_buffer: State<'_, Arc<InstallLogBuffer>>) -> Result<(), String> {
+    info!("save_install_log called: {}", dest);
+    let lines = log_buffer.snapshot();
+    if lines.is_empty() {
+        return Err("No install log was recorded this session".to_string());
     }
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    std::fs::write(&dest, contents).map_err(|e| format!("Failed to write install log: {}", e))
+}
+
+/// Get current archive extraction limits as (max_extracted_bytes, max_entries)
+#[tauri::command]
+async fn get_archive_limits(state: State<'_, Arc<RwLock<AppState>>>) -> Result<(u64, usize), String> {
+    use install_mod::install_mod_logic::archives::{DEFAULT_MAX_ENTRIES, DEFAULT_MAX_EXTRACTED_BYTES};
+    let state = state.read().await;
+    let bytes = if state.max_extracted_bytes == 0 { DEFAULT_MAX_EXTRACTED_BYTES } else { state.max_extracted_bytes };
+    let entries = if state.max_entries == 0 { DEFAULT_MAX_ENTRIES } else { state.max_entries };
+    Ok((bytes, entries))
+}
+
+#[tauri::command]
+async fn get_game_path(state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
+    let state = state.read().await;
+    Ok(state.game_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn set_game_path(path: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    let mods_path = PathBuf::from(&path);
     
-    // Rename main file
-    std::fs::rename(&path, &new_path).map_err(|e| format!("Failed to rename mod: {}", e))?;
-    
-    // Rename associated files (.utoc, .ucas)
-    let exts = ["utoc", "ucas"];
-    for ext in exts {
-        let old_f = path.with_extension(ext);
-        if old_f.exists() {
-             let new_f = new_path.with_extension(ext);
-             let _ = std::fs::rename(old_f, new_f);
+    // Auto-deploy bundled LOD Disabler mod if path exists
+    if mods_path.exists() {
+        match deploy_bundled_lod_mod(&mods_path) {
+            Ok(true) => info!("Auto-deployed bundled LOD Disabler mod"),
+            Ok(false) => info!("Bundled LOD Disabler mod already present or not bundled"),
+            Err(e) => warn!("Failed to auto-deploy LOD Disabler mod: {}", e),
         }
     }
     
+    let mut state = state.write().await;
+    state.game_root = find_game_root(&mods_path);
+    state.game_path = mods_path;
+    save_state(&state).map_err(|e| e.to_string())?;
     Ok(())
 }
 
-#[derive(Clone, serde::Serialize, serde::Deserialize)]
-struct InstallableModInfo {
-    mod_name: String,
-    mod_type: String,
-    is_dir: bool,
-    path: String,
-    auto_fix_texture: bool,
-    auto_fix_serialize_size: bool,
-    auto_to_repak: bool,
-    /// Whether the mod contains any .uasset/.uexp/.ubulk/.umap files
-    /// Used by frontend to lock/unlock certain toggles (e.g., fix texture only applies to uasset mods)
-    contains_uassets: bool,
+/// Summary of a migrate_mods_to_path run
+#[derive(Clone, Serialize)]
+struct MigrationReport {
+    old_path: String,
+    new_path: String,
+    /// Whether files were moved (true) or copied (false)
+    moved: bool,
+    mods_migrated: usize,
+    folders_migrated: usize,
+    /// Files that couldn't be copied/moved, with the reason
+    failed: Vec<String>,
+}
+
+/// Copy or move a single file, falling back to copy + delete when a rename crosses drives
+fn transfer_file(from: &Path, to: &Path, move_file: bool) -> std::io::Result<()> {
+    if move_file && std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    if move_file {
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
 }
 
+/// Relocate the whole mods library (folders, mods, IoStore companions) to a new ~mods directory,
+/// then point game_path and every mod's metadata at the new location and re-deploy the LOD mod
 #[tauri::command]
-async fn parse_dropped_files(
-    paths: Vec<String>,
-    state: State<'_, Arc<Mutex<AppState>>>,
-    window: Window
-) -> Result<Vec<InstallableModInfo>, String> {
-    use crate::utils::get_current_pak_characteristics;
-    use repak::PakBuilder;
-    use repak::utils::AesKey;
-    use std::str::FromStr;
-    use std::fs::File;
-    use std::io::BufReader;
-    
-    // Emit start detection log
-    let _ = window.emit("install_log", "[Detection] Starting UAssetAPI detection...");
-    
-    // Set USMAP_PATH for detection (from roaming folder)
-    {
-        let state_guard = state.lock().unwrap();
-        let usmap_filename = state_guard.usmap_path.clone();
-        
-        if !usmap_filename.is_empty() {
-            if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
-                std::env::set_var("USMAP_PATH", &usmap_full_path);
-                let msg = format!("[Detection] Set USMAP_PATH: {}", usmap_full_path.display());
-                info!("{}", msg);
-                let _ = window.emit("install_log", &msg);
-            } else {
-                let expected_path = usmap_dir().join(&usmap_filename);
-                let msg = format!("[Detection] WARNING: USMAP not found at: {}", expected_path.display());
-                info!("{}", msg);
-                let _ = window.emit("install_log", &msg);
+async fn migrate_mods_to_path(
+    new_game_path: String,
+    move_files: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<MigrationReport, String> {
+    info!("migrate_mods_to_path called: new_game_path={}, move_files={}", new_game_path, move_files);
+    let old_path = state.read().await.game_path.clone();
+    let new_path = PathBuf::from(new_game_path.trim());
+
+    if !old_path.is_dir() {
+        return Err(format!("Current mods folder does not exist: {}", old_path.display()));
+    }
+    let is_named = |p: Option<&Path>, name: &str| {
+        p.and_then(|p| p.file_name()).and_then(|n| n.to_str()).is_some_and(|n| n.eq_ignore_ascii_case(name))
+    };
+    if !is_named(Some(&new_path), "~mods") || !is_named(new_path.parent(), "Paks") {
+        return Err("The new path must be a ~mods folder inside the game's Content/Paks directory".to_string());
+    }
+    if !new_path.parent().is_some_and(|p| p.is_dir()) {
+        return Err(format!("Game Paks folder not found: {}", new_path.parent().unwrap_or(&new_path).display()));
+    }
+    if new_path == old_path || new_path.starts_with(&old_path) || old_path.starts_with(&new_path) {
+        return Err("The new mods folder must be outside the current one".to_string());
+    }
+    std::fs::create_dir_all(&new_path)
+        .map_err(|e| format!("Failed to create {}: {}", new_path.display(), e))?;
+
+    let mut report = MigrationReport {
+        old_path: old_path.to_string_lossy().to_string(),
+        new_path: new_path.to_string_lossy().to_string(),
+        moved: move_files,
+        mods_migrated: 0,
+        folders_migrated: 0,
+        failed: Vec::new(),
+    };
+
+    for entry in WalkDir::new(&old_path).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(&old_path) else {
+            continue;
+        };
+        let target = new_path.join(relative);
+        if entry.file_type().is_dir() {
+            match std::fs::create_dir_all(&target) {
+                Ok(()) => report.folders_migrated += 1,
+                Err(e) => report.failed.push(format!("{}: {}", relative.display(), e)),
+            }
+            continue;
+        }
+        match transfer_file(entry.path(), &target, move_files) {
+            Ok(()) => {
+                let ext = entry.path().extension().and_then(|s| s.to_str());
+                if matches!(ext, Some("pak") | Some("bak_repak") | Some("pak_disabled")) {
+                    report.mods_migrated += 1;
+                }
+            }
+            Err(e) => {
+                warn!("Failed to migrate {}: {}", entry.path().display(), e);
+                report.failed.push(format!("{}: {}", relative.display(), e));
             }
-        } else {
-            let _ = window.emit("install_log", "[Detection] WARNING: No USMAP configured in settings");
         }
     }
-    
-    let mut mods = Vec::new();
-    
-    // Filter out .utoc and .ucas files - they will be handled with their .pak file
-    let filtered_paths: Vec<String> = paths.into_iter()
-        .filter(|p| {
-            let path = PathBuf::from(p);
-            if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                ext != "utoc" && ext != "ucas"
-            } else {
-                true
+
+    // Clear out the now-empty folder tree, leaving the old ~mods itself in place
+    if move_files {
+        for entry in WalkDir::new(&old_path).min_depth(1).contents_first(true).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                let _ = std::fs::remove_dir(entry.path());
             }
-        })
-        .collect();
-    
-    for path_str in filtered_paths {
-        let path = PathBuf::from(&path_str);
-        
-        if !path.exists() {
-            continue;
         }
-        
-        let mod_name = path.file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("Unknown")
-            .to_string();
-        
-        // Determine mod type and auto-detection flags
-        // 4-tuple: (mod_type, auto_fix_texture, auto_fix_serialize_size, contains_uassets)
-        // Note: mesh patching is handled automatically by UAssetTool
-        let (mod_type, auto_fix_texture, auto_fix_serialize_size, contains_uassets) = if path.is_dir() {
-            // First check if directory contains multiple PAK files - if so, process each PAK separately
-            use walkdir::WalkDir;
-            let mut pak_files = Vec::new();
-            
-            for entry in WalkDir::new(&path).max_depth(1).into_iter().filter_map(|e| e.ok()) {
-                let entry_path = entry.path();
-                if let Some(ext) = entry_path.extension().and_then(|s| s.to_str()) {
-                    if ext == "pak" {
-                        pak_files.push(entry_path.to_path_buf());
-                    }
-                }
+    }
+
+    {
+        let mut state = state.write().await;
+        state.game_path = new_path.clone();
+        for metadata in state.mod_metadata.iter_mut() {
+            if let Ok(relative) = metadata.path.strip_prefix(&old_path) {
+                metadata.path = new_path.join(relative);
             }
+        }
+        save_state(&state).map_err(|e| e.to_string())?;
+    }
+
+    match deploy_bundled_lod_mod(&new_path) {
+        Ok(true) => info!("Re-deployed bundled LOD Disabler mod to {}", new_path.display()),
+        Ok(false) => {}
+        Err(e) => warn!("Failed to re-deploy LOD Disabler mod: {}", e),
+    }
+
+    info!(
+        "Migrated {} mod(s) and {} folder(s) from {} to {} ({} failed)",
+        report.mods_migrated, report.folders_migrated, report.old_path, report.new_path, report.failed.len()
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+async fn auto_detect_game_path(state: State<'_, Arc<RwLock<AppState>>>, window: Window) -> Result<String, String> {
+    match find_marvel_rivals() {
+        Some(game_root) => {
+            // game_path should be the ~mods directory (matching egui behavior)
+            let mods_path = game_root.join("~mods");
             
-            if pak_files.len() > 1 {
-                // Multiple PAK files - process each separately
-                let _ = window.emit("install_log", format!("[Detection] Found {} PAK files in directory, processing each separately", pak_files.len()));
-                
-                for pak_file in pak_files {
-                    let pak_mods = Box::pin(parse_dropped_files(vec![pak_file.to_string_lossy().to_string()], state.clone(), window.clone())).await?;
-                    for pak_mod in pak_mods {
-                        mods.push(pak_mod);
-                    }
-                }
-                
-                return Ok(mods);
-            } else if pak_files.len() == 1 {
-                // Single PAK file in directory - process it directly (handles IoStore if present)
-                let pak_file = &pak_files[0];
-                let _ = window.emit("install_log", format!("[Detection] Found single PAK in directory: {}", pak_file.display()));
-                
-                let pak_mods = Box::pin(parse_dropped_files(vec![pak_file.to_string_lossy().to_string()], state.clone(), window.clone())).await?;
-                for pak_mod in pak_mods {
-                    mods.push(pak_mod);
+            // Create ~mods directory if it doesn't exist
+            if !mods_path.exists() {
+                if let Err(e) = std::fs::create_dir_all(&mods_path) {
+                    let error_msg = format!("Failed to create ~mods directory: {}", e);
+                    toast_events::emit_game_path_failed(&window, &error_msg);
+                    return Err(error_msg);
                 }
-                
-                return Ok(mods);
             }
             
-            // No PAK files - analyze directory contents for loose assets
-            let _ = window.emit("install_log", "[Detection] No PAK files found, analyzing directory for loose assets...");
+            // Auto-deploy bundled LOD Disabler mod
+            match deploy_bundled_lod_mod(&mods_path) {
+                Ok(true) => info!("Auto-deployed bundled LOD Disabler mod"),
+                Ok(false) => info!("Bundled LOD Disabler mod already present or not bundled"),
+                Err(e) => warn!("Failed to auto-deploy LOD Disabler mod: {}", e),
+            }
             
-            use crate::utils::collect_files;
-            let mut all_files = Vec::new();
-            if collect_files(&mut all_files, &path).is_ok() {
-                let _ = window.emit("install_log", format!("[Detection] Collected {} files from directory", all_files.len()));
-                
-                // Convert absolute paths to relative paths for proper classification
-                // Strip the base directory path to get relative paths
-                let content_files_relative: Vec<String> = all_files.iter()
-                    .filter_map(|p| {
-                        p.strip_prefix(&path).ok()
-                            .map(|rel| rel.to_string_lossy().to_string().replace('\\', "/"))
-                    })
-                    .collect();
-                
-                if !content_files_relative.is_empty() {
-                    let _ = window.emit("install_log", format!("[Detection] Processing {} files for classification", content_files_relative.len()));
-                    
-                    // Use detailed characteristics for proper classification (needs relative paths)
-                    use crate::utils::get_pak_characteristics_detailed;
-                    let characteristics = get_pak_characteristics_detailed(content_files_relative.clone());
-                    let mod_type = characteristics.mod_type.clone();
-                    
-                    let _ = window.emit("install_log", format!("[Detection] Detected mod type: {}", mod_type));
-                    
-                    // Get uasset files for detection (needs absolute paths for UAssetAPI to read files)
-                    // Prioritize skeletal mesh files (SK_*), static mesh (SM_*), and textures (T_*) over materials
-                    // Limit to 100 total to prevent hangs on large directories
-                    let mut uasset_files_absolute: Vec<String> = Vec::new();
-                    let mut priority_files: Vec<String> = Vec::new();
-                    let mut other_files: Vec<String> = Vec::new();
-                    
-                    for file in all_files.iter() {
-                        if file.extension().and_then(|s| s.to_str()) == Some("uasset") {
-                            let filename = file.file_name().and_then(|s| s.to_str()).unwrap_or("");
-                            let filename_lower = filename.to_lowercase();
-                            
-                            // Prioritize SK_, SM_, T_ files (skeletal mesh, static mesh, textures)
-                            if filename_lower.starts_with("sk_") || filename_lower.starts_with("sm_") || filename_lower.starts_with("t_") {
-                                priority_files.push(file.to_string_lossy().to_string());
-                            } else {
-                                other_files.push(file.to_string_lossy().to_string());
-                            }
-                        }
-                    }
-                    
-                    // Add priority files first, then fill up to 100 with other files
-                    uasset_files_absolute.extend(priority_files);
-                    let remaining = 100usize.saturating_sub(uasset_files_absolute.len());
-                    uasset_files_absolute.extend(other_files.into_iter().take(remaining));
-                    
-                    // Only scan for textures - SkeletalMesh and StaticMesh are auto-fixed by ZenConverter
-                    // This significantly speeds up detection by skipping unnecessary UAssetAPI calls
-                    let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes are automatic)...");
-                    
-                    // For texture detection, we need ALL files (including .ubulk) to check for bulk data
-                    let all_files_absolute: Vec<String> = all_files.iter()
-                        .map(|p| p.to_string_lossy().to_string())
-                        .collect();
-                    let has_texture = detect_texture_files_async(&all_files_absolute).await;
-                    let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
-                    
-                    let summary = format!("[Detection] Directory results: texture={} (mesh fixes automatic)", has_texture);
-                    info!("{}", summary);
-                    let _ = window.emit("install_log", &summary);
-                    
-                    // Check if directory contains uasset files
-                    use crate::install_mod::contains_uasset_files;
-                    let has_uassets = contains_uasset_files(&all_files_absolute);
-                    let _ = window.emit("install_log", format!("[Detection] Contains UAssets: {}", has_uassets));
-                    
-                    (mod_type, has_texture, false, has_uassets)
-                } else {
-                    ("Directory".to_string(), false, false, true) // Default to true for safety
+            let mut state = state.write().await;
+            state.game_path = mods_path.clone();
+            save_state(&state).map_err(|e| e.to_string())?;
+            Ok(mods_path.to_string_lossy().to_string())
+        }
+        None => {
+            let error_msg = "Could not auto-detect Marvel Rivals installation".to_string();
+            toast_events::emit_game_path_failed(&window, &error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+#[tauri::command]
+async fn start_file_watcher(
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    watcher_state: State<'_, WatcherState>,
+) -> Result<(), String> {
+    let state_guard = state.read().await;
+    let game_path = state_guard.game_path.clone();
+    drop(state_guard);
+
+    if !game_path.exists() {
+        return Ok(()); // Can't watch non-existent path
+    }
+
+    let mut watcher_guard = watcher_state.watcher.lock().unwrap();
+    
+    // Create a new watcher with debouncing
+    let window_clone = window.clone();
+    let last_event_time = Arc::new(Mutex::new(std::time::Instant::now()));
+    
+    let paused = watcher_state.paused.clone();
+    let watched_path = game_path.clone();
+    let watcher_result = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+        match res {
+            Ok(event) => {
+                // Skip events while paused (e.g. during P2P transfer)
+                if paused.load(Ordering::Relaxed) {
+                    return;
                 }
-            } else {
-                ("Directory".to_string(), false, false, true) // Default to true for safety
+                // We only care about Create, Remove, Rename, and Modify events (files and directories)
+                match event.kind {
+                    EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_) => {
+                         // Debounce: only emit if 500ms have passed since last event
+                         let mut last_time = last_event_time.lock().unwrap();
+                         let now = std::time::Instant::now();
+                         let elapsed = now.duration_since(*last_time);
+                         
+                         if elapsed.as_millis() >= 500 {
+                             *last_time = now;
+                             window_clone.emit("mods_dir_changed", ()).unwrap_or_else(|e| {
+                                 error!("Failed to emit mods_dir_changed: {}", e);
+                             });
+                             // Keep the asset index current once it has been built
+                             if ASSET_INDEX.lock().unwrap().is_some() {
+                                 let game_path = watched_path.clone();
+                                 std::thread::spawn(move || {
+                                     if let Err(e) = refresh_asset_index(&game_path) {
+                                         warn!("[AssetIndex] Refresh failed: {}", e);
+                                     }
+                                 });
+                             }
+                         }
+                    },
+                    _ => {}
+                }
+            },
+            Err(e) => error!("Watch error: {:?}", e),
+        }
+    });
+
+    match watcher_result {
+        Ok(mut watcher) => {
+            if let Err(e) = watcher.watch(&game_path, RecursiveMode::Recursive) {
+                error!("Failed to watch game path: {}", e);
+                return Err(e.to_string());
             }
-        } else {
-            // Get file extension
-            let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-            
-            // Check if it's an archive file (zip, rar, 7z)
-            if ext == "zip" || ext == "rar" || ext == "7z" {
-                use crate::install_mod::install_mod_logic::archives::{extract_zip, extract_rar, extract_7z};
-                use walkdir::WalkDir;
-                
-                let _ = window.emit("install_log", format!("[Detection] Archive detected: {} ({})", mod_name, ext));
-                
-                // Extract archive to temp directory for analysis
-                let temp_dir = tempfile::tempdir().ok();
-                if let Some(ref temp) = temp_dir {
-                    let temp_path = temp.path().to_str().unwrap();
-                    
-                    // Extract based on type
-                    let extract_result = if ext == "zip" {
-                        extract_zip(path.to_str().unwrap(), temp_path)
-                    } else if ext == "rar" {
-                        extract_rar(path.to_str().unwrap(), temp_path).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
-                    } else {
-                        extract_7z(path.to_str().unwrap(), temp_path)
-                    };
-                    
-                    if extract_result.is_ok() {
-                        let _ = window.emit("install_log", "[Detection] Archive extracted successfully");
-                        
-                        // Look for PAK files in extracted content
-                        let mut pak_files_in_archive = Vec::new();
-                        for entry in WalkDir::new(temp_path).into_iter().filter_map(|e| e.ok()) {
-                            let entry_path = entry.path();
-                            if let Some(entry_ext) = entry_path.extension().and_then(|s| s.to_str()) {
-                                if entry_ext == "pak" {
-                                    pak_files_in_archive.push(entry_path.to_path_buf());
-                                }
-                            }
-                        }
-                        
-                        if pak_files_in_archive.len() > 1 {
-                            // Multiple PAK files found in archive
-                            let _ = window.emit("install_log", format!("[Detection] Found {} PAK files in archive, processing each separately", pak_files_in_archive.len()));
-                            
-                            for pak_file_path in pak_files_in_archive {
-                                let pak_mods = Box::pin(parse_dropped_files(vec![pak_file_path.to_string_lossy().to_string()], state.clone(), window.clone())).await?;
-                                for pak_mod in pak_mods {
-                                    mods.push(pak_mod);
-                                }
-                            }
-                            
-                            return Ok(mods);
-                        }
-                        
-                        // Single PAK file or no PAK files - continue with existing logic
-                        let found_pak = !pak_files_in_archive.is_empty();
-                        if found_pak {
-                            let entry_path = &pak_files_in_archive[0];
-                            
-                            // Check if this is an IoStore package (has .utoc and .ucas companions)
-                            let utoc_path = entry_path.with_extension("utoc");
-                            let ucas_path = entry_path.with_extension("ucas");
-                            let is_iostore = utoc_path.exists() && ucas_path.exists();
-                            
-                            if is_iostore {
-                                let _ = window.emit("install_log", format!("[Detection] IoStore package detected in archive: {}", mod_name));
-                            }
-                            
-                            // Get file list - for IoStore, read from utoc directly (works with obfuscated mods);
-                            // otherwise open PAK with AES key
-                            let files: Option<Vec<String>> = if is_iostore {
-                                use crate::utoc_utils::read_utoc;
-                                let _ = window.emit("install_log", "[Detection] Reading IoStore .utoc file for accurate file list");
-                                let utoc_files: Vec<String> = read_utoc(&utoc_path)
-                                    .iter()
-                                    .map(|entry| entry.file_path.clone())
-                                    .collect();
-                                if utoc_files.is_empty() { None } else { Some(utoc_files) }
-                            } else if let Ok(file) = File::open(entry_path) {
-                                if let Ok(aes_key) = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74") {
-                                    let mut reader = BufReader::new(file);
-                                    PakBuilder::new().key(aes_key.0).reader(&mut reader).ok().map(|pak| pak.files())
-                                } else { None }
-                            } else { None };
-                            
-                            if let Some(files) = files {
-                                        
-                                        // Use detailed characteristics (same as get_mod_details)
-                                        use crate::utils::get_pak_characteristics_detailed;
-                                        let characteristics = get_pak_characteristics_detailed(files.clone());
-                                        let mod_type = characteristics.mod_type.clone();
-                                        
-                                        let _ = window.emit("install_log", format!("[Detection] Detected mod type: {}", mod_type));
-                                        
-                                        // Get files to extract (both .uasset and .uexp needed by UAssetAPI)
-                                        // Prioritize SK_, SM_, T_ files for detection
-                                        let files_to_extract: Vec<&String> = files.iter()
-                                            .filter(|f| {
-                                                let lower = f.to_lowercase();
-                                                (lower.ends_with(".uasset") || lower.ends_with(".uexp")) &&
-                                                if let Some(filename) = std::path::Path::new(f).file_name().and_then(|n| n.to_str()) {
-                                                    let fname_lower = filename.to_lowercase();
-                                                    fname_lower.starts_with("sk_") || fname_lower.starts_with("sm_") || fname_lower.starts_with("t_")
-                                                } else {
-                                                    false
-                                                }
-                                            })
-                                            .take(40)  // Limit to 40 files (20 uasset + 20 uexp pairs)
-                                            .collect();
-                                        
-                                        let _ = window.emit("install_log", format!("[Detection] Extracting {} files from archive PAK for analysis...", files_to_extract.len()));
-                                        
-                                        // Extract to temp directory for UAssetAPI analysis
-                                        let mut extracted_paths: Vec<String> = Vec::new();
-                                        let uasset_temp_dir = tempfile::tempdir().ok();
-                                        let aes_key_for_extraction = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74").unwrap();
-                                        
-                                        if let Some(ref uasset_temp) = uasset_temp_dir {
-                                            use rayon::prelude::*;
-                                            use std::sync::Mutex;
-                                            
-                                            let extracted = Mutex::new(Vec::new());
-                                            let pak_path = entry_path.clone();
-                                            
-                                            // Parallel extraction
-                                            files_to_extract.par_iter().for_each(|internal_path| {
-                                                if let Ok(file) = File::open(&pak_path) {
-                                                    let mut reader = BufReader::new(file);
-                                                    if let Ok(pak) = PakBuilder::new().key(aes_key_for_extraction.0.clone()).reader(&mut reader) {
-                                                        // Use just the filename to preserve .uasset/.uexp pairing
-                                                        let filename = std::path::Path::new(internal_path.as_str())
-                                                            .file_name()
-                                                            .and_then(|n| n.to_str())
-                                                            .unwrap_or(internal_path);
-                                                        let dest_path = uasset_temp.path().join(filename);
-                                                        
-                                                        if let Ok(extract_file) = File::open(&pak_path) {
-                                                            let mut extract_reader = BufReader::new(extract_file);
-                                                            if let Ok(data) = pak.get(internal_path, &mut extract_reader) {
-                                                                if let Ok(_) = std::fs::write(&dest_path, data) {
-                                                                    if internal_path.to_lowercase().ends_with(".uasset") {
-                                                                        extracted.lock().unwrap().push(dest_path.to_string_lossy().to_string());
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            });
-                                            
-                                            extracted_paths = extracted.into_inner().unwrap();
-                                            let _ = window.emit("install_log", format!("[Detection] Extracted {} uasset files for UAssetAPI", extracted_paths.len()));
-                                        }
-                                        
-                                        // Only scan for textures - mesh fixes are automatic in ZenConverter
-                                        let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes automatic)...");
-                                        
-                                        // Texture detection - use extracted files but also check for .ubulk in original file list
-                                        let has_ubulk = files.iter().any(|f| f.to_lowercase().ends_with(".ubulk"));
-                                        let has_texture = if has_ubulk && !extracted_paths.is_empty() {
-                                            // Add .ubulk indicator to detection files so detect_texture_files_async knows there's bulk data
-                                            let mut texture_detection_files = extracted_paths.clone();
-                                            texture_detection_files.push("dummy.ubulk".to_string()); // Signal that .ubulk exists
-                                            detect_texture_files_async(&texture_detection_files).await
-                                        } else {
-                                            false
-                                        };
-                                        let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
-                                        
-                                        let summary = format!("[Detection] Archive PAK results: texture={} (mesh fixes automatic)", has_texture);
-                                        info!("{}", summary);
-                                        let _ = window.emit("install_log", &summary);
-                                        
-                                        // Clean up temp dir
-                                        drop(temp_dir);
-                                        
-                                        // Check if files contain uassets
-                                        use crate::install_mod::contains_uasset_files;
-                                        let has_uassets = contains_uasset_files(&files);
-                                        
-                                        return Ok(vec![InstallableModInfo {
-                                            mod_name,
-                                            mod_type,
-                                            is_dir: false,
-                                            path: path_str,
-                                            auto_fix_texture: has_texture,
-                                            auto_fix_serialize_size: false, // Mesh fixes are automatic
-                                            auto_to_repak: !is_iostore,  // Don't repak IoStore packages
-                                            contains_uassets: has_uassets,
-                                        }]);
-                            }
-                        }
-                        
-                        // If no .pak files found, look for content folders with loose assets
-                        if !found_pak {
-                            let _ = window.emit("install_log", "[Detection] No PAK files found in archive, looking for content folders...");
-                            
-                            use crate::utils::collect_files;
-                            
-                            // Collect all files from the extracted archive
-                            let mut all_files = Vec::new();
-                            let temp_path_buf = PathBuf::from(temp_path);
-                            if collect_files(&mut all_files, &temp_path_buf).is_ok() {
-                                // Check if there are content files (.uasset, .uexp, .ubulk, etc.)
-                                let content_files: Vec<String> = all_files.iter()
-                                    .filter(|f| {
-                                        if let Some(ext) = f.extension().and_then(|s| s.to_str()) {
-                                            matches!(ext, "uasset" | "uexp" | "ubulk" | "bnk" | "wem")
-                                        } else {
-                                            false
-                                        }
-                                    })
-                                    .map(|p| p.to_string_lossy().to_string())
-                                    .collect();
-                                
-                                if !content_files.is_empty() {
-                                    let _ = window.emit("install_log", format!("[Detection] Found {} content files in archive folder", content_files.len()));
-                                    
-                                    // Get mod type from content
-                                    let mod_type = get_current_pak_characteristics(content_files.clone());
-                                    
-                                    // Only scan for textures - mesh fixes are automatic in ZenConverter
-                                    let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes automatic)...");
-                                    let has_texture = detect_texture_files_async(&content_files).await;
-                                    let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
-                                    
-                                    let summary = format!("[Detection] Archive folder results: texture={} (mesh fixes automatic)", has_texture);
-                                    info!("{}", summary);
-                                    let _ = window.emit("install_log", &summary);
-                                    
-                                    // Clean up temp dir
-                                    drop(temp_dir);
-                                    
-                                    // Check if content files contain uassets
-                                    use crate::install_mod::contains_uasset_files;
-                                    let has_uassets = contains_uasset_files(&content_files);
-                                    
-                                    // Return as a directory mod (will be converted to IoStore)
-                                    return Ok(vec![InstallableModInfo {
-                                        mod_name,
-                                        mod_type,
-                                        is_dir: true,
-                                        path: path_str,
-                                        auto_fix_texture: has_texture,
-                                        auto_fix_serialize_size: false, // Mesh fixes are automatic
-                                        auto_to_repak: false,
-                                        contains_uassets: has_uassets,
-                                    }]);
-                                }
-                            }
-                        }
-                    }
+            info!("Started watching game path: {:?}", game_path);
+            *watcher_guard = Some(watcher);
+            Ok(())
+        },
+        Err(e) => {
+            error!("Failed to create watcher: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Quiet period after the last source change before dev mode reinstalls
+const DEV_WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(1500);
+
+/// Payload of the `dev_reinstall` event
+#[derive(Debug, Clone, Serialize)]
+struct DevReinstallEvent {
+    source: String,
+    mod_name: String,
+    success: bool,
+    error: Option<String>,
+    duration_ms: u64,
+}
+
+/// Install settings captured when dev mode starts
+#[derive(Clone)]
+struct DevInstallSettings {
+    game_path: PathBuf,
+    usmap_path: String,
+    parallel_processing: bool,
+    obfuscate: bool,
+    iostore_version: Option<u8>,
+}
+
+/// Re-run the install pipeline for `source`, writing over the installed copy of `target_mod_name`
+/// (same subfolder, patch suffix and enabled state). Returns the installed pak's stem.
+fn dev_reinstall(source: &Path, target_mod_name: &str, settings: &DevInstallSettings, window: &Window) -> Result<String, String> {
+    use crate::install_mod::install_mod_logic::{base_pak_name, install_mods_in_viewport, ModInstallOutcome};
+    use std::sync::atomic::AtomicI32;
+
+    let (mut mods, skipped) = crate::install_mod::map_paths_to_mods_with_skipped(&[source.to_path_buf()], None);
+    if let Some((_, reason)) = skipped.first() {
+        return Err(format!("Could not read source: {}", reason));
+    }
+    let installable = mods.first_mut().ok_or("Source contains no installable mod")?;
+
+    let target_base = base_pak_name(target_mod_name);
+    let existing = list_mod_entries(&settings.game_path, &[])?
+        .into_iter()
+        .find(|m| {
+            let stem = m.path.file_stem().unwrap_or_default().to_string_lossy();
+            base_pak_name(&stem).eq_ignore_ascii_case(&target_base)
+        });
+    installable.mod_name = target_mod_name.to_string();
+    if let Some(existing) = &existing {
+        let stem = existing.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        installable.mod_name = stem.clone();
+        installable.is_patch = stem.ends_with("_P");
+        installable.install_disabled = !existing.enabled;
+        installable.install_subfolder = existing.path.parent()
+            .and_then(|parent| parent.strip_prefix(&settings.game_path).ok())
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+    }
+    installable.usmap_path = settings.usmap_path.clone();
+    installable.parallel_processing = settings.parallel_processing;
+    installable.obfuscate = settings.obfuscate;
+    installable.iostore_version = settings.iostore_version;
+
+    let stage_window = window.clone();
+    let outcomes = install_mods_in_viewport(
+        &mut mods,
+        &settings.game_path,
+        &AtomicI32::new(0),
+        &AtomicBool::new(false),
+        &move |stage| {
+            stage_window.emit("install_stage", &stage).ok();
+        },
+    );
+
+    match outcomes.first() {
+        Some(ModInstallOutcome::Installed(_)) => Ok(mods[0].mod_name.clone()),
+        _ => Err(format!("Install of {} failed, check the install logs", mods[0].mod_name)),
+    }
+}
+
+/// Dev mode: watch a mod author's working folder (or pak) and reinstall it over the installed
+/// `target_mod_name` whenever it changes. Emits `dev_reinstall` after every attempt.
+#[tauri::command]
+async fn watch_source_for_dev(
+    source_path: String,
+    target_mod_name: String,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    dev_watch: State<'_, DevWatchState>,
+) -> Result<(), String> {
+    info!("watch_source_for_dev called: {} -> {}", source_path, target_mod_name);
+    let source = PathBuf::from(&source_path);
+    if !source.exists() {
+        return Err(format!("Source does not exist: {}", source.display()));
+    }
+    if target_mod_name.trim().is_empty() {
+        return Err("Target mod name is empty".to_string());
+    }
+    let settings = {
+        let state = state.read().await;
+        DevInstallSettings {
+            game_path: state.game_path.clone(),
+            usmap_path: state.usmap_path.clone(),
+            parallel_processing: state.parallel_processing,
+            obfuscate: state.obfuscate,
+            iostore_version: state.iostore_version,
+        }
+    };
+    if let Some(usmap_full_path) = get_usmap_full_path(&settings.usmap_path) {
+        std::env::set_var("USMAP_PATH", &usmap_full_path);
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+        match res {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)) {
+                    let _ = tx.send(());
                 }
-                
-                // Fallback if extraction/analysis failed
-                ("Archive".to_string(), false, false, true) // Default to true for safety
-            } else if ext == "pak" {
-                // Check if this is an IoStore package (has .utoc and .ucas companions)
-                let utoc_path = path.with_extension("utoc");
-                let ucas_path = path.with_extension("ucas");
-                let is_iostore = utoc_path.exists() && ucas_path.exists();
-                
-                if is_iostore {
-                    let _ = window.emit("install_log", format!("[Detection] IoStore package detected: {}", mod_name));
+            }
+            Err(e) => error!("Dev watch error: {:?}", e),
+        }
+    }).map_err(|e| format!("Failed to create watcher: {}", e))?;
+    let mode = if source.is_dir() { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    watcher.watch(&source, mode).map_err(|e| format!("Failed to watch {}: {}", source.display(), e))?;
+
+    // Replacing the watcher drops the old sender, which ends the previous worker
+    *dev_watch.watcher.lock().unwrap() = Some(watcher);
+
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            // Wait for the burst of writes from a save/export to settle
+            loop {
+                match rx.recv_timeout(DEV_WATCH_DEBOUNCE) {
+                    Ok(()) => continue,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => return,
                 }
-                
-                // Read file list for mod type detection
-                // For IoStore, read from utoc directly (works with obfuscated mods);
-                // otherwise open PAK with AES key
-                let mod_type = {
-                    let files_and_key: Option<(Vec<String>, Option<repak::utils::AesKey>)> = if is_iostore {
+            }
+            info!("[DevWatch] {} changed, reinstalling as {}", source.display(), target_mod_name);
+            let _ = window.emit("install_log", format!("[DevWatch] Change detected, reinstalling {}", target_mod_name));
+            let started = std::time::Instant::now();
+            let result = dev_reinstall(&source, &target_mod_name, &settings, &window);
+            if let Err(e) = &result {
+                warn!("[DevWatch] Reinstall failed: {}", e);
+            }
+            let _ = window.emit("dev_reinstall", DevReinstallEvent {
+                source: source.to_string_lossy().to_string(),
+                mod_name: result.as_ref().cloned().unwrap_or_else(|_| target_mod_name.clone()),
+                success: result.is_ok(),
+                error: result.err(),
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
+        }
+        info!("[DevWatch] Stopped watching {}", source.display());
+    });
+
+    info!("[DevWatch] Watching {}", source_path);
+    Ok(())
+}
+
+/// Stop dev mode's source watcher
+#[tauri::command]
+async fn stop_dev_watch(dev_watch: State<'_, DevWatchState>) -> Result<(), String> {
+    info!("stop_dev_watch called");
+    dev_watch.watcher.lock().unwrap().take();
+    Ok(())
+}
+
+/// Resolved (character, skin) names for a mod, tagged with the pak mtime they were read at
+type CachedModNames = (std::time::SystemTime, Option<(String, String)>);
+
+/// Cache of resolved character/skin names keyed by mod path, invalidated by pak mtime
+static MOD_NAME_CACHE: once_cell::sync::Lazy<Mutex<std::collections::HashMap<PathBuf, CachedModNames>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// Cached mod type (e.g. "Hulk - Skin"), invalidated by pak mtime like MOD_NAME_CACHE
+static MOD_TYPE_CACHE: once_cell::sync::Lazy<Mutex<std::collections::HashMap<PathBuf, (std::time::SystemTime, String)>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// Cached internal file count, invalidated by pak mtime like MOD_NAME_CACHE
+static MOD_FILE_COUNT_CACHE: once_cell::sync::Lazy<Mutex<std::collections::HashMap<PathBuf, (std::time::SystemTime, usize)>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// Cached classification (category, heroes), invalidated by pak mtime like MOD_NAME_CACHE
+static MOD_CHARACTERISTICS_CACHE: once_cell::sync::Lazy<Mutex<std::collections::HashMap<PathBuf, (std::time::SystemTime, utils::ModCharacteristics)>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// List the internal file paths of an installed mod.
+/// Reads the .utoc for IoStore mods, otherwise opens the pak index.
+fn list_mod_files(path: &Path) -> Option<Vec<String>> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    let utoc_path = path.with_extension("utoc");
+    if utoc_path.exists() {
+        Some(crate::utoc_utils::read_utoc(&utoc_path)
+            .iter()
+            .map(|entry| entry.file_path.clone())
+            .collect())
+    } else {
+        let file = File::open(path).ok()?;
+        let mut reader = BufReader::new(file);
+        Some(PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut reader)
+            .ok()?
+            .files())
+    }
+}
+
+/// Internal file list of one installed mod, tagged with the pak mtime it was read at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedMod {
+    mtime: std::time::SystemTime,
+    files: Vec<String>,
+}
+
+/// Reverse index of internal asset paths across the library, persisted to asset_index.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AssetIndex {
+    mods: std::collections::HashMap<PathBuf, IndexedMod>,
+    /// Internal path -> mods that contain it
+    paths: std::collections::BTreeMap<String, Vec<PathBuf>>,
+}
+
+impl AssetIndex {
+    fn load() -> Self {
+        std::fs::read_to_string(asset_index_path())
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(app_dir()).map_err(|e| e.to_string())?;
+        std::fs::write(asset_index_path(), json).map_err(|e| format!("Failed to write asset index: {}", e))
+    }
+
+    /// Re-read only the mods whose mtime changed since the last build and drop mods that are gone
+    fn update(&mut self, mod_paths: &[PathBuf]) {
+        let mut refreshed = 0;
+        self.mods.retain(|path, _| mod_paths.contains(path));
+        for path in mod_paths {
+            let Ok(mtime) = std::fs::metadata(path).and_then(|m| m.modified()) else {
+                continue;
+            };
+            if self.mods.get(path).is_some_and(|m| m.mtime == mtime) {
+                continue;
+            }
+            match list_mod_files(path) {
+                Some(files) => {
+                    self.mods.insert(path.clone(), IndexedMod { mtime, files });
+                    refreshed += 1;
+                }
+                None => {
+                    warn!("[AssetIndex] Could not read {}", path.display());
+                    self.mods.remove(path);
+                }
+            }
+        }
+        self.rebuild_paths();
+        info!("[AssetIndex] {} mod(s) indexed ({} re-read), {} path(s)", self.mods.len(), refreshed, self.paths.len());
+    }
+
+    fn rebuild_paths(&mut self) {
+        self.paths.clear();
+        for (mod_path, indexed) in &self.mods {
+            for file in &indexed.files {
+                self.paths.entry(file.clone()).or_default().push(mod_path.clone());
+            }
+        }
+    }
+
+    /// Mods owning any internal path that contains `needle` (case-insensitive)
+    fn mods_touching(&self, needle: &str) -> std::collections::HashSet<PathBuf> {
+        let needle = needle.to_lowercase().replace('\\', "/");
+        self.paths.iter()
+            .filter(|(path, _)| path.to_lowercase().contains(&needle))
+            .flat_map(|(_, owners)| owners.iter().cloned())
+            .collect()
+    }
+}
+
+/// In-memory copy of the asset index, None until it's first built or loaded from disk
+static ASSET_INDEX: once_cell::sync::Lazy<Mutex<Option<AssetIndex>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(None));
+
+/// Bring the asset index up to date with the mods in `game_path` and persist it
+fn refresh_asset_index(game_path: &Path) -> Result<usize, String> {
+    let mod_paths: Vec<PathBuf> = list_mod_entries(game_path, &[])?
+        .into_iter()
+        .map(|m| m.path)
+        .collect();
+    let mut guard = ASSET_INDEX.lock().unwrap();
+    let index = guard.get_or_insert_with(AssetIndex::load);
+    index.update(&mod_paths);
+    index.save()?;
+    Ok(index.paths.len())
+}
+
+/// Resolve character/skin names for an installed mod from its internal file paths.
+fn resolve_mod_character_names(path: &Path) -> Option<(String, String)> {
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(mtime) = mtime {
+        if let Some((cached_mtime, names)) = MOD_NAME_CACHE.lock().unwrap().get(path) {
+            if *cached_mtime == mtime {
+                return names.clone();
+            }
+        }
+    }
+
+    let files = list_mod_files(path)?;
+    let names = character_data::identify_mod_from_paths(&files);
+    if let Some(mtime) = mtime {
+        MOD_NAME_CACHE.lock().unwrap().insert(path.to_path_buf(), (mtime, names.clone()));
+    }
+    names
+}
+
+/// Resolve how many files an installed mod contains, cached by pak mtime
+fn resolve_mod_file_count(path: &Path) -> Option<usize> {
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(mtime) = mtime {
+        if let Some((cached_mtime, count)) = MOD_FILE_COUNT_CACHE.lock().unwrap().get(path) {
+            if *cached_mtime == mtime {
+                return Some(*count);
+            }
+        }
+    }
+
+    let count = list_mod_files(path)?.len();
+    if let Some(mtime) = mtime {
+        MOD_FILE_COUNT_CACHE.lock().unwrap().insert(path.to_path_buf(), (mtime, count));
+    }
+    Some(count)
+}
+
+/// Resolve the mod type string for an installed mod, cached by pak mtime
+fn resolve_mod_type(path: &Path) -> Option<String> {
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(mtime) = mtime {
+        if let Some((cached_mtime, mod_type)) = MOD_TYPE_CACHE.lock().unwrap().get(path) {
+            if *cached_mtime == mtime {
+                return Some(mod_type.clone());
+            }
+        }
+    }
+
+    let files = list_mod_files(path)?;
+    let mod_type = utils::get_current_pak_characteristics(files);
+    if let Some(mtime) = mtime {
+        MOD_TYPE_CACHE.lock().unwrap().insert(path.to_path_buf(), (mtime, mod_type.clone()));
+    }
+    Some(mod_type)
+}
+
+/// Classify an installed mod from its internal file paths, cached by pak mtime
+fn resolve_mod_characteristics(path: &Path) -> Option<utils::ModCharacteristics> {
+    let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(mtime) = mtime {
+        if let Some((cached_mtime, characteristics)) = MOD_CHARACTERISTICS_CACHE.lock().unwrap().get(path) {
+            if *cached_mtime == mtime {
+                return Some(characteristics.clone());
+            }
+        }
+    }
+
+    let files = list_mod_files(path)?;
+    let characteristics = utils::get_pak_characteristics_detailed(files);
+    if let Some(mtime) = mtime {
+        MOD_CHARACTERISTICS_CACHE.lock().unwrap().insert(path.to_path_buf(), (mtime, characteristics.clone()));
+    }
+    Some(characteristics)
+}
+
+/// Library overview for the dashboard: how many mods of each kind are installed
+#[derive(Debug, Default, Serialize)]
+struct LibraryStats {
+    total_mods: usize,
+    enabled_mods: usize,
+    /// Combined size of the installed paks in bytes
+    total_size: u64,
+    /// Mods per category ("Mesh", "UI", "Audio", ...), "Unreadable" for paks that couldn't be opened
+    by_type: std::collections::BTreeMap<String, usize>,
+    /// Mods per hero; a mod touching several heroes counts for each of them
+    by_character: std::collections::BTreeMap<String, usize>,
+    /// Mods per folder id (the root folder is "~mods")
+    by_folder: std::collections::BTreeMap<String, usize>,
+}
+
+impl LibraryStats {
+    fn add(&mut self, entry: &ModEntry, characteristics: Option<&utils::ModCharacteristics>) {
+        self.total_mods += 1;
+        if entry.enabled {
+            self.enabled_mods += 1;
+        }
+        self.total_size += entry.file_size;
+        let category = characteristics.map_or("Unreadable", |c| c.category.as_str());
+        *self.by_type.entry(category.to_string()).or_default() += 1;
+        for hero in characteristics.map(|c| c.heroes.as_slice()).unwrap_or_default() {
+            *self.by_character.entry(hero.clone()).or_default() += 1;
+        }
+        let folder = entry.folder_id.clone().unwrap_or_else(|| "~mods".to_string());
+        *self.by_folder.entry(folder).or_default() += 1;
+    }
+}
+
+/// Count installed mods per type, hero and folder, plus their total size
+#[tauri::command]
+async fn get_library_stats(state: State<'_, Arc<RwLock<AppState>>>) -> Result<LibraryStats, String> {
+    info!("get_library_stats called");
+    let (game_path, mod_metadata) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone())
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        use rayon::prelude::*;
+
+        let entries = list_mod_entries(&game_path, &mod_metadata)?;
+        let characteristics: Vec<_> = entries.par_iter()
+            .map(|m| resolve_mod_characteristics(&m.path))
+            .collect();
+        let mut stats = LibraryStats::default();
+        for (entry, characteristics) in entries.iter().zip(&characteristics) {
+            stats.add(entry, characteristics.as_ref());
+        }
+        info!("[Stats] {} mod(s), {} type(s), {} hero(es)", stats.total_mods, stats.by_type.len(), stats.by_character.len());
+        Ok(stats)
+    })
+    .await
+    .map_err(|e| format!("Library stats failed: {}", e))?
+}
+
+/// Index the internal paths of every installed mod for global search, returning how many
+/// distinct paths are indexed. Only mods whose pak changed since the last build are re-read.
+#[tauri::command]
+async fn build_asset_index(state: State<'_, Arc<RwLock<AppState>>>) -> Result<usize, String> {
+    info!("build_asset_index called");
+    let game_path = state.read().await.game_path.clone();
+    tauri::async_runtime::spawn_blocking(move || refresh_asset_index(&game_path))
+        .await
+        .map_err(|e| format!("Asset index build failed: {}", e))?
+}
+
+/// Find installed mods containing an internal path that matches `path_substring`
+#[tauri::command]
+async fn find_mods_touching_path(
+    path_substring: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<Vec<ModEntry>, String> {
+    info!("find_mods_touching_path called: {}", path_substring);
+    if path_substring.trim().is_empty() {
+        return Err("Search path is empty".to_string());
+    }
+    let (game_path, mod_metadata) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone())
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        if ASSET_INDEX.lock().unwrap().is_none() {
+            refresh_asset_index(&game_path)?;
+        }
+        let owners = ASSET_INDEX.lock().unwrap()
+            .as_ref()
+            .map(|index| index.mods_touching(path_substring.trim()))
+            .unwrap_or_default();
+        let mods: Vec<ModEntry> = list_mod_entries(&game_path, &mod_metadata)?
+            .into_iter()
+            .filter(|m| owners.contains(&m.path))
+            .collect();
+        info!("[AssetIndex] {} mod(s) touch '{}'", mods.len(), path_substring);
+        Ok(mods)
+    })
+    .await
+    .map_err(|e| format!("Asset search failed: {}", e))?
+}
+
+#[tauri::command]
+async fn get_pak_files(
+    resolve_names: Option<bool>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<Vec<ModEntry>, String> {
+    // Snapshot what the scan needs so the directory walk doesn't hold the state lock
+    let (game_path, mod_metadata) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone())
+    };
+    let mut mods = list_mod_entries(&game_path, &mod_metadata)?;
+
+    // Optionally enrich each entry with character/skin names and file counts (opens every pak/utoc, so opt-in)
+    if resolve_names.unwrap_or(false) {
+        use rayon::prelude::*;
+        mods.par_iter_mut().for_each(|m| {
+            if let Some((character, skin)) = resolve_mod_character_names(&m.path) {
+                m.character_name = Some(character);
+                m.skin_name = Some(skin);
+            }
+            m.file_count = resolve_mod_file_count(&m.path);
+        });
+    }
+
+    info!("Found {} mod(s)", mods.len());
+    Ok(mods)
+}
+
+/// Walk ~mods (including subfolders) and build a `ModEntry` for every enabled or disabled pak
+fn list_mod_entries(game_path: &Path, mod_metadata: &[ModMetadata]) -> Result<Vec<ModEntry>, String> {
+    info!("Loading mods from: {}", game_path.display());
+    
+    if !game_path.exists() {
+        info!("Game path does not exist: {}", game_path.display());
+        return Err(format!("Game path does not exist: {}", game_path.display()));
+    }
+
+    // game_path IS the ~mods directory (matching egui behavior)
+    let mut mods = Vec::new();
+    
+    // Scan root ~mods directory and all subdirectories recursively (no depth limit)
+    for entry in WalkDir::new(&game_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        
+        // Skip directories themselves
+        if path.is_dir() {
+            continue;
+        }
+        
+        let ext = path.extension().and_then(|s| s.to_str());
+        
+        // Check for .pak, .bak_repak, and .pak_disabled files
+        if ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled") {
+            let is_enabled = ext == Some("pak");
+            
+            // Determine which folder this mod is in
+            let root_folder_name = game_path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("~mods")
+                .to_string();
+            
+            // Determine folder_id based on relative path from game_path
+            let folder_id = if let Some(parent) = path.parent() {
+                if parent == game_path {
+                    // Mod is directly in root - use root folder name (e.g., "~mods")
+                    Some(root_folder_name)
+                } else {
+                    // Mod is in a subfolder - use relative path from game_path as ID
+                    parent.strip_prefix(game_path)
+                        .map(|p| p.to_string_lossy().replace('\\', "/"))
+                        .ok()
+                }
+            } else {
+                Some(root_folder_name)
+            };
+            
+            info!("Found PAK file: {} (enabled: {}, folder: {:?})", path.display(), is_enabled, folder_id);
+            
+            let metadata = mod_metadata.iter()
+                .find(|m| m.matches(path));
+            
+            let ucas_path = iostore_companion_path(path, "ucas");
+            let utoc_path = iostore_companion_path(path, "utoc");
+            let obfuscated = utoc_path.exists()
+                && uasset_toolkit::is_iostore_encrypted_header(&utoc_path.to_string_lossy()).unwrap_or(false);
+            let file_size = if ucas_path.exists() {
+                std::fs::metadata(&ucas_path)
+                    .map(|m| m.len())
+                    .unwrap_or(0)
+            } else {
+                std::fs::metadata(path)
+                    .map(|m| m.len())
+                    .unwrap_or(0)
+            };
+            
+            let priority = mod_priority(path);
+            
+            mods.push(ModEntry {
+                path: path.to_path_buf(),
+                enabled: is_enabled,
+                custom_name: metadata.and_then(|m| m.custom_name.clone()),
+                folder_id,
+                custom_tags: metadata.map(|m| m.custom_tags.clone()).unwrap_or_default(),
+                file_size,
+                priority,
+                character_name: None,
+                skin_name: None,
+                source_url: metadata.and_then(|m| m.source_url.clone()),
+                mod_type: None,
+                file_count: None,
+                obfuscated,
+                thumbnail_path: metadata
+                    .and_then(|m| m.thumbnail_path.clone())
+                    .filter(|p| Path::new(p).exists()),
+            });
+        }
+    }
+
+    Ok(mods)
+}
+
+/// Server-side filter for query_mods. Empty/None fields are ignored.
+#[derive(Debug, Default, Deserialize)]
+struct ModFilter {
+    /// Mod must have at least one of these tags
+    #[serde(default)]
+    any_tags: Vec<String>,
+    /// Mod must have every one of these tags
+    #[serde(default)]
+    all_tags: Vec<String>,
+    /// Character name, case-insensitive exact match
+    #[serde(default)]
+    character: Option<String>,
+    /// Mod type substring, case-insensitive (e.g. "Skin" or "Hulk - Skin")
+    #[serde(default)]
+    mod_type: Option<String>,
+    #[serde(default)]
+    enabled: Option<bool>,
+    /// Folder id as reported in ModEntry.folder_id
+    #[serde(default)]
+    folder: Option<String>,
+}
+
+/// Return only the installed mods matching `filter`.
+/// Cheap checks (tags, enabled, folder) run first; character and type are resolved
+/// lazily for the remaining mods and cached by pak mtime.
+#[tauri::command]
+async fn query_mods(
+    filter: ModFilter,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<Vec<ModEntry>, String> {
+    use rayon::prelude::*;
+
+    info!("query_mods called: {:?}", filter);
+    let mods = get_pak_files(Some(false), state).await?;
+
+    let mods: Vec<ModEntry> = mods.into_iter()
+        .filter(|m| filter.enabled.is_none_or(|e| m.enabled == e))
+        .filter(|m| filter.folder.as_ref().is_none_or(|f| m.folder_id.as_ref() == Some(f)))
+        .filter(|m| filter.any_tags.is_empty() || filter.any_tags.iter().any(|t| m.custom_tags.contains(t)))
+        .filter(|m| filter.all_tags.iter().all(|t| m.custom_tags.contains(t)))
+        .collect();
+
+    let character = filter.character.as_ref().map(|c| c.to_lowercase());
+    let mod_type = filter.mod_type.as_ref().map(|t| t.to_lowercase());
+
+    let mods: Vec<ModEntry> = mods.into_par_iter()
+        .filter_map(|mut m| {
+            if let Some(ref character) = character {
+                let (name, skin) = resolve_mod_character_names(&m.path)?;
+                if name.to_lowercase() != *character {
+                    return None;
+                }
+                m.character_name = Some(name);
+                m.skin_name = Some(skin);
+            }
+            if let Some(ref wanted) = mod_type {
+                let resolved = resolve_mod_type(&m.path)?;
+                if !resolved.to_lowercase().contains(wanted.as_str()) {
+                    return None;
+                }
+                m.mod_type = Some(resolved);
+            }
+            Some(m)
+        })
+        .collect();
+
+    info!("query_mods matched {} mod(s)", mods.len());
+    Ok(mods)
+}
+
+#[tauri::command]
+async fn set_mod_priority(mod_path: String, priority: usize) -> Result<(), String> {
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+         return Err("Mod file does not exist".to_string());
+    }
+    
+    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let stem = path.file_stem().and_then(|s| s.to_str()).ok_or("Invalid filename")?;
+    if !has_patch_suffix(stem) {
+        return Err("Base paks have no priority, they always load before patch paks".to_string());
+    }
+    
+    // Strip leading "!" if present (highest priority marker)
+    let stem_no_exclaim = stem.strip_prefix("!").unwrap_or(stem);
+    
+    // 1. Strip _P if present
+    let base_no_p = if stem_no_exclaim.ends_with("_P") {
+        stem_no_exclaim.strip_suffix("_P").unwrap()
+    } else {
+        stem_no_exclaim
+    };
+    
+    // 2. Strip _999... if present
+    let re = Regex::new(r"^(.*)_(\d+)$").unwrap();
+    let clean_base = if let Some(caps) = re.captures(base_no_p) {
+        let prefix = &caps[1];
+        let numbers = &caps[2];
+        if numbers.chars().all(|c| c == '9') {
+            prefix.to_string()
+        } else {
+            base_no_p.to_string()
+        }
+    } else {
+        base_no_p.to_string()
+    };
+    
+    // 3. Construct new name with new priority
+    // Priority 0 = "!" prefix (highest priority) with minimum 7 nines
+    // Priority 1-N = 7-N+6 nines (1→7 nines, 2→8 nines, etc.)
+    let new_stem = if priority == 0 {
+        // Highest priority: use "!" prefix with minimum 7 nines
+        let min_nines = "9".repeat(7);
+        format!("!{}_{}_P", clean_base, min_nines)
+    } else {
+        // Convert UI priority (1-based) to actual nines count (7-based)
+        // Remove "!" prefix if present (since priority > 0)
+        let actual_nines = priority + 6; // Priority 1 → 7 nines, Priority 2 → 8 nines, etc.
+        let new_nines = "9".repeat(actual_nines);
+        format!("{}_{}_P", clean_base, new_nines)
+    };
+    
+    let new_filename = format!("{}.{}", new_stem, extension);
+    let new_path = path.with_file_name(&new_filename);
+    
+    if new_path == path {
+        return Ok(()); // No change
+    }
+
+    if new_path.exists() {
+        return Err("A mod with this priority already exists".to_string());
+    }
+    
+    rename_with_companions(&path, &new_path)
+}
+
+/// Rename a mod file along with its IoStore companions (.utoc/.ucas, enabled or disabled)
+fn rename_with_companions(path: &Path, new_path: &Path) -> Result<(), String> {
+    // Rename main file
+    std::fs::rename(path, new_path).map_err(|e| format!("Failed to rename mod: {}", e))?;
+    
+    // Rename associated files (.utoc, .ucas)
+    let exts = ["utoc", "ucas", "utoc_disabled", "ucas_disabled"];
+    for ext in exts {
+        let old_f = path.with_extension(ext);
+        if old_f.exists() {
+             let new_f = new_path.with_extension(ext);
+             let _ = std::fs::rename(old_f, new_f);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Mod stem with every priority marker removed: the "!" prefix and any number of
+/// stacked "_999..._P" suffixes
+fn strip_priority_markers(stem: &str) -> String {
+    let mut clean = stem.to_string();
+    loop {
+        let stripped = mod_base_name_without_priority(&clean);
+        if stripped == clean {
+            return clean;
+        }
+        clean = stripped;
+    }
+}
+
+/// Sibling .utoc/.ucas files whose stem drifted from the pak's (e.g. `Mod_9999999_P.pak` next to
+/// `Mod.utoc` after renaming in Explorer), paired with the name they should have. Companions
+/// follow the pak's enabled state, so a same-stem `Mod_P.utoc_disabled` next to an enabled
+/// `Mod_P.pak` is picked up too. Files that already belong to another pak are left alone.
+fn find_drifted_companions(pak_path: &Path) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let dir = pak_path.parent().ok_or("Mod has no parent folder")?;
+    let stem = pak_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let enabled = pak_path.extension().is_some_and(|e| e == "pak");
+    let base = strip_priority_markers(&stem).to_lowercase();
+
+    let mut renames = Vec::new();
+    for ext in ["utoc", "ucas"] {
+        let target_ext = if enabled { ext.to_string() } else { format!("{}_disabled", ext) };
+        let target = pak_path.with_extension(&target_ext);
+        if target.exists() {
+            continue;
+        }
+        let candidates: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                let p_ext = p.extension().and_then(|e| e.to_str()).unwrap_or("");
+                p_ext == ext || p_ext == format!("{}_disabled", ext)
+            })
+            .filter(|p| {
+                let p_stem = p.file_stem().unwrap_or_default().to_string_lossy();
+                strip_priority_markers(&p_stem).to_lowercase() == base
+            })
+            .filter(|p| {
+                !["pak", "bak_repak", "pak_disabled"].iter().any(|e| {
+                    let owner = p.with_extension(e);
+                    owner != pak_path && owner.exists()
+                })
+            })
+            .collect();
+        match candidates.as_slice() {
+            [] => {}
+            [candidate] => renames.push((candidate.clone(), target)),
+            _ => return Err(format!(
+                "Several .{} files could belong to {}, rename them by hand: {}",
+                ext,
+                stem,
+                candidates.iter().map(|c| c.display().to_string()).collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+    Ok(renames)
+}
+
+/// Rename a mod's .utoc/.ucas to exactly match its pak's stem when they drifted apart through
+/// manual renaming (most often the pak gained a "_P" suffix the companions lack).
+/// Returns the corrected companion paths.
+#[tauri::command]
+async fn fix_mod_companion_names(mod_path: String) -> Result<Vec<String>, String> {
+    info!("fix_mod_companion_names called: {}", mod_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+    let renames = find_drifted_companions(&path)?;
+    let mut fixed = Vec::new();
+    for (from, to) in renames {
+        std::fs::rename(&from, &to)
+            .map_err(|e| format!("Failed to rename {}: {}", from.display(), e))?;
+        info!("Renamed companion {} -> {}", from.display(), to.display());
+        fixed.push(to.to_string_lossy().to_string());
+    }
+    if !fixed.is_empty() {
+        // A new IoStore pair changes what the mod is, drop anything cached for the pak
+        MOD_NAME_CACHE.lock().unwrap().remove(&path);
+        MOD_TYPE_CACHE.lock().unwrap().remove(&path);
+        MOD_FILE_COUNT_CACHE.lock().unwrap().remove(&path);
+        MOD_CHARACTERISTICS_CACHE.lock().unwrap().remove(&path);
+        if let Some(index) = ASSET_INDEX.lock().unwrap().as_mut() {
+            if index.mods.remove(&path).is_some() {
+                index.rebuild_paths();
+            }
+        }
+    }
+    Ok(fixed)
+}
+
+/// Undo priority changes: rename a mod to its plain author name with the default
+/// "_9999999_P" suffix, moving companions and metadata along. Base paks (no "_P") have no
+/// priority to reset and are left as they are. Returns the new path.
+#[tauri::command]
+async fn reset_mod_priority(mod_path: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
+    info!("reset_mod_priority called: {}", mod_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err("Mod file does not exist".to_string());
+    }
+    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let stem = path.file_stem().and_then(|s| s.to_str()).ok_or("Invalid filename")?;
+    if !has_patch_suffix(stem) {
+        return Ok(mod_path);
+    }
+
+    let clean_base = strip_priority_markers(stem);
+    if clean_base.is_empty() {
+        return Err("Mod name is empty without its priority markers".to_string());
+    }
+    let new_stem = crate::install_mod::install_mod_logic::normalize_mod_base_name(&clean_base, 7);
+    let new_path = path.with_file_name(format!("{}.{}", new_stem, extension));
+    if new_path == path {
+        return Ok(new_path.to_string_lossy().to_string());
+    }
+    if new_path.exists() {
+        return Err(format!("A mod named {} already exists", new_path.file_name().unwrap_or_default().to_string_lossy()));
+    }
+
+    rename_with_companions(&path, &new_path)?;
+
+    let mut state = state.write().await;
+    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
+        metadata.path = new_path.clone();
+        save_state(&state).map_err(|e| e.to_string())?;
+    }
+    info!("Reset priority: {} -> {}", path.display(), new_path.display());
+    Ok(new_path.to_string_lossy().to_string())
+}
+
+/// Enabled mod that replaces base-game assets but is missing the "_P" patch suffix,
+/// so the engine mounts it as a regular pak and the overrides never win
+#[derive(Clone, serde::Serialize)]
+struct SuffixAuditEntry {
+    path: PathBuf,
+    /// Number of assets in the mod that also exist in the base game
+    overriding_files: usize,
+    /// A few of those asset paths, for display
+    sample_files: Vec<String>,
+    /// File stem the mod would get from fix_pak_suffix
+    suggested_name: String,
+}
+
+/// Whether a mod stem carries the "_P" patch suffix (the engine compares case-insensitively)
+fn has_patch_suffix(stem: &str) -> bool {
+    stem.to_ascii_uppercase().ends_with("_P")
+}
+
+/// Content-relative, lowercase path, so pak entries ("Marvel/Content/X.uasset") and
+/// IoStore entries ("/Game/X.uasset") compare equal
+fn content_relative_path(path: &str) -> String {
+    let path = path.replace('\\', "/").to_lowercase();
+    let relative = if let Some(idx) = path.find("/content/") {
+        &path[idx + "/content/".len()..]
+    } else if let Some(rest) = path.strip_prefix("content/") {
+        rest
+    } else if let Some(rest) = path.strip_prefix("/game/") {
+        rest
+    } else {
+        path.trim_start_matches('/')
+    };
+    relative.to_string()
+}
+
+/// `content_relative_path` of a package without its extension. Only package files
+/// (.uasset/.umap) are keyed.
+fn asset_override_key(path: &str) -> Option<String> {
+    let relative = content_relative_path(path);
+    relative.strip_suffix(".uasset")
+        .or_else(|| relative.strip_suffix(".umap"))
+        .map(str::to_string)
+}
+
+/// Where a base-game file lives. Legacy pak entries can be read back for byte comparison;
+/// IoStore packages can only be listed.
+enum BaseGameFile {
+    Pak { pak: PathBuf, entry: String },
+    IoStore,
+}
+
+/// Every file shipped in the base game's containers, keyed by `content_relative_path`
+fn index_base_game_files(paks_dir: &Path) -> std::collections::HashMap<String, BaseGameFile> {
+    use std::io::BufReader;
+
+    let mut index = std::collections::HashMap::new();
+    for rel in list_base_game_paks(paks_dir) {
+        let path = paks_dir.join(&rel);
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref() {
+            Some("utoc") => {
+                for entry in crate::utoc_utils::read_utoc(&path) {
+                    index.insert(content_relative_path(&entry.file_path), BaseGameFile::IoStore);
+                }
+            }
+            // Also covers IoStore .pak files, which hold the container's loose (non-package) files
+            Some("pak") => {
+                let Ok(file) = File::open(&path) else { continue };
+                let Ok(reader) = repak::PakBuilder::new()
+                    .key(install_mod::current_aes_key().0)
+                    .reader(&mut BufReader::new(file))
+                else {
+                    warn!("Could not read base game pak {}", path.display());
+                    continue;
+                };
+                for entry in reader.files() {
+                    index.insert(
+                        content_relative_path(&entry),
+                        BaseGameFile::Pak { pak: path.clone(), entry },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    index
+}
+
+/// Override keys of every package shipped in the base game's containers
+fn list_base_game_assets(paks_dir: &Path) -> std::collections::HashSet<String> {
+    index_base_game_files(paks_dir)
+        .keys()
+        .filter_map(|key| asset_override_key(key))
+        .collect()
+}
+
+/// What a single mod changes relative to the vanilla game
+#[derive(Debug, Default, Serialize)]
+struct BaseDiff {
+    /// Files that replace a base-game file with different content
+    overrides: Vec<String>,
+    /// Files the base game doesn't have
+    additions: Vec<String>,
+    /// Files byte-identical to the base game copy (only checkable for legacy paks on both sides)
+    identical: Vec<String>,
+}
+
+/// Whether `key` is the .uexp/.ubulk/.uptnl part of a package the base game has. IoStore
+/// listings only name the package, so companions are matched through it.
+fn is_base_package_companion(key: &str, base: &std::collections::HashMap<String, BaseGameFile>) -> bool {
+    let Some((stem, ext)) = key.rsplit_once('.') else {
+        return false;
+    };
+    matches!(ext, "uexp" | "ubulk" | "uptnl")
+        && (base.contains_key(&format!("{}.uasset", stem)) || base.contains_key(&format!("{}.umap", stem)))
+}
+
+/// Compare a mod against the base game (Paks outside ~mods): which of its files override
+/// vanilla files, which are new, and which are identical to the vanilla copy
+#[tauri::command]
+async fn diff_mod_against_base(mod_path: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<BaseDiff, String> {
+    use std::io::BufReader;
+
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err("Mod file does not exist".to_string());
+    }
+    let game_path = state.read().await.game_path.clone();
+    // game_path is the ~mods folder; the base game containers live in its parent Paks folder
+    let paks_dir = game_path.parent()
+        .filter(|p| p.is_dir())
+        .ok_or("Game path is not set")?
+        .to_path_buf();
+
+    tokio::task::spawn_blocking(move || {
+        let files = list_mod_files(&path).ok_or("Failed to read mod contents")?;
+        let base = index_base_game_files(&paks_dir);
+        info!("diff_mod_against_base: {} mod files vs {} base-game files", files.len(), base.len());
+
+        let open_pak = |pak: &Path| -> Option<repak::PakReader> {
+            let file = File::open(pak).ok()?;
+            repak::PakBuilder::new()
+                .key(install_mod::current_aes_key().0)
+                .reader(&mut BufReader::new(file))
+                .ok()
+        };
+        // Legacy mods can be read back entry by entry for the identical check
+        let mod_pak = if path.with_extension("utoc").exists() { None } else { open_pak(&path) };
+        let mut base_readers: std::collections::HashMap<PathBuf, Option<repak::PakReader>> = std::collections::HashMap::new();
+
+        let mut diff = BaseDiff::default();
+        for file in files {
+            let key = content_relative_path(&file);
+            let identical = match (base.get(&key), &mod_pak) {
+                (None, _) => {
+                    if is_base_package_companion(&key, &base) {
+                        diff.overrides.push(file);
+                    } else {
+                        diff.additions.push(file);
+                    }
+                    continue;
+                }
+                (Some(BaseGameFile::Pak { pak, entry }), Some(mod_reader)) => {
+                    let base_reader = base_readers.entry(pak.clone()).or_insert_with(|| open_pak(pak));
+                    match base_reader {
+                        Some(base_reader) => {
+                            let same_size = matches!(
+                                (mod_reader.get_file_entry(&file), base_reader.get_file_entry(entry)),
+                                (Ok(a), Ok(b)) if a.uncompressed == b.uncompressed
+                            );
+                            same_size && {
+                                let mod_bytes = File::open(&path).ok()
+                                    .and_then(|f| mod_reader.get(&file, &mut BufReader::new(f)).ok());
+                                let base_bytes = File::open(pak).ok()
+                                    .and_then(|f| base_reader.get(entry, &mut BufReader::new(f)).ok());
+                                mod_bytes.is_some() && mod_bytes == base_bytes
+                            }
+                        }
+                        None => false,
+                    }
+                }
+                _ => false,
+            };
+            if identical {
+                diff.identical.push(file);
+            } else {
+                diff.overrides.push(file);
+            }
+        }
+        Ok(diff)
+    })
+    .await
+    .map_err(|e| format!("Base game diff task failed: {}", e))?
+}
+
+/// Flag enabled mods that override base-game assets but whose filename lacks "_P".
+/// Such paks are mounted below the base game, so their replacements are ignored.
+#[tauri::command]
+async fn audit_pak_suffixes(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<SuffixAuditEntry>, String> {
+    use crate::install_mod::install_mod_logic::normalize_mod_base_name;
+
+    let (game_path, metadata) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone())
+    };
+    // game_path is the ~mods folder; the base game containers live in its parent Paks folder
+    let paks_dir = game_path.parent()
+        .filter(|p| p.is_dir())
+        .ok_or("Game path is not set")?
+        .to_path_buf();
+
+    let candidates: Vec<PathBuf> = list_mod_entries(&game_path, &metadata)?
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.path)
+        .filter(|p| {
+            p.file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|stem| !has_patch_suffix(stem))
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let base_assets = list_base_game_assets(&paks_dir);
+        info!("audit_pak_suffixes: {} base-game assets, {} unsuffixed mods", base_assets.len(), candidates.len());
+
+        let mut report = Vec::new();
+        for path in candidates {
+            let Some(files) = list_mod_files(&path) else {
+                warn!("Could not list contents of {}", path.display());
+                continue;
+            };
+            let overrides: Vec<String> = files
+                .into_iter()
+                .filter(|f| asset_override_key(f).is_some_and(|key| base_assets.contains(&key)))
+                .collect();
+            if overrides.is_empty() {
+                continue;
+            }
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            report.push(SuffixAuditEntry {
+                suggested_name: normalize_mod_base_name(stem, 7),
+                overriding_files: overrides.len(),
+                sample_files: overrides.into_iter().take(5).collect(),
+                path,
+            });
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Suffix audit task failed: {}", e))?
+}
+
+/// Give a mod the "_P" patch suffix (with the default priority nines), renaming its
+/// IoStore companions and metadata along with it. Returns the new path.
+#[tauri::command]
+async fn fix_pak_suffix(mod_path: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
+    info!("fix_pak_suffix called: {}", mod_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err("Mod file does not exist".to_string());
+    }
+    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let stem = path.file_stem().and_then(|s| s.to_str()).ok_or("Invalid filename")?;
+    if has_patch_suffix(stem) {
+        return Ok(mod_path);
+    }
+
+    let new_stem = crate::install_mod::install_mod_logic::normalize_mod_base_name(stem, 7);
+    let new_path = path.with_file_name(format!("{}.{}", new_stem, extension));
+    if new_path.exists() {
+        return Err(format!("A mod named {} already exists", new_path.file_name().unwrap_or_default().to_string_lossy()));
+    }
+
+    rename_with_companions(&path, &new_path)?;
+
+    let mut state = state.write().await;
+    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
+        metadata.path = new_path.clone();
+        save_state(&state).map_err(|e| e.to_string())?;
+    }
+    info!("Added patch suffix: {} -> {}", path.display(), new_path.display());
+    Ok(new_path.to_string_lossy().to_string())
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct InstallableModInfo {
+    mod_name: String,
+    mod_type: String,
+    is_dir: bool,
+    path: String,
+    auto_fix_texture: bool,
+    auto_fix_serialize_size: bool,
+    auto_to_repak: bool,
+    /// Whether the mod contains any .uasset/.uexp/.ubulk/.umap files
+    /// Used by frontend to lock/unlock certain toggles (e.g., fix texture only applies to uasset mods)
+    contains_uassets: bool,
+    /// Final on-disk base name the mod will install as (e.g. "MyMod_9999999_P")
+    predicted_output_name: String,
+    /// "iostore" (.pak + .utoc/.ucas) or "legacy_pak" (single .pak), based on the detected defaults
+    predicted_format: String,
+    /// Rough output size in bytes, taken from the source content size
+    estimated_output_size: u64,
+}
+
+impl InstallableModInfo {
+    /// Fill in the install preview fields, mirroring the routing in install_mods_in_viewport
+    fn with_prediction(mut self) -> Self {
+        use crate::install_mod::install_mod_logic::normalize_mod_base_name;
+
+        let source = PathBuf::from(&self.path);
+        let ext = source.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        let is_iostore_source = ext == "pak"
+            && source.with_extension("utoc").exists()
+            && source.with_extension("ucas").exists();
+        let is_archive = matches!(ext.as_str(), "zip" | "rar" | "7z");
+        let is_audio_or_movies = self.mod_type.contains("Audio") || self.mod_type.contains("Movies");
+
+        let iostore = if self.is_dir || self.auto_to_repak {
+            !is_audio_or_movies
+        } else {
+            is_iostore_source || is_archive
+        };
+
+        self.predicted_output_name = normalize_mod_base_name(&self.mod_name, 7);
+        self.predicted_format = if iostore { "iostore" } else { "legacy_pak" }.to_string();
+        self.estimated_output_size = source_content_size(&source);
+        self
+    }
+}
+
+/// Size in bytes of an install source: every file of a folder, the uncompressed contents of an
+/// archive, or a pak plus its .utoc/.ucas
+fn source_content_size(source: &Path) -> u64 {
+    if source.is_dir() {
+        WalkDir::new(source)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.metadata().ok())
+            .map(|m| m.len())
+            .sum()
+    } else if let Some(size) = install_mod::install_mod_logic::archives::declared_uncompressed_size(source) {
+        // Archives are measured by what they unpack to, not their compressed size
+        size
+    } else {
+        let mut size = std::fs::metadata(source).map(|m| m.len()).unwrap_or(0);
+        if source.extension().is_some_and(|e| e == "pak") {
+            for companion in ["utoc", "ucas"] {
+                size += std::fs::metadata(source.with_extension(companion)).map(|m| m.len()).unwrap_or(0);
+            }
+        }
+        size
+    }
+}
+
+/// Installs need this many times the source size free on the mods drive (conversion output
+/// plus archives growing when extracted)
+const INSTALL_SPACE_FACTOR: f64 = 1.5;
+/// Extra free space kept on top of the estimate so the drive is never filled to the last byte
+const INSTALL_SPACE_HEADROOM: u64 = 256 * 1024 * 1024;
+
+/// Free and total space of the volume holding the mods folder
+#[derive(Debug, Clone, Serialize)]
+struct DiskSpace {
+    mount_point: String,
+    free_bytes: u64,
+    total_bytes: u64,
+}
+
+/// Find the disk `path` lives on (the mounted volume with the longest matching mount point)
+fn disk_space_for(path: &Path) -> Option<DiskSpace> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    // canonicalize gives a verbatim \\?\C:\... path on Windows, which never starts_with "C:\"
+    let path_str = path.to_string_lossy();
+    let path = match path_str.strip_prefix(r"\\?\") {
+        Some(stripped) => PathBuf::from(stripped),
+        None => path.clone(),
+    };
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks.list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| DiskSpace {
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            free_bytes: disk.available_space(),
+            total_bytes: disk.total_space(),
+        })
+}
+
+/// Bytes an install of `source_bytes` worth of mods needs free before it may start
+fn required_install_space(source_bytes: u64) -> u64 {
+    (source_bytes as f64 * INSTALL_SPACE_FACTOR) as u64 + INSTALL_SPACE_HEADROOM
+}
+
+/// Free/total space on the drive holding the mods folder
+#[tauri::command]
+async fn get_mods_drive_space(state: State<'_, Arc<RwLock<AppState>>>) -> Result<DiskSpace, String> {
+    let game_path = state.read().await.game_path.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        disk_space_for(&game_path)
+            .ok_or_else(|| format!("Could not find the drive for {}", game_path.display()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn parse_dropped_files(
+    paths: Vec<String>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    window: Window
+) -> Result<Vec<InstallableModInfo>, String> {
+    use crate::install_mod::install_mod_logic::archives::{begin_detection_scan, end_detection_scan};
+
+    let cancel = begin_detection_scan();
+    let result = parse_dropped_files_inner(paths, cancel.clone(), state, window.clone()).await;
+    end_detection_scan(&cancel);
+
+    if cancel.load(std::sync::atomic::Ordering::SeqCst) {
+        info!("[Detection] Cancelled by user");
+        let _ = window.emit("install_log", "[Detection] Cancelled by user");
+        let _ = window.emit("detection_cancelled", ());
+        return Err("Detection cancelled".to_string());
+    }
+
+    result
+}
+
+/// Abort an in-progress parse_dropped_files scan (archive extraction and detection)
+#[tauri::command]
+async fn cancel_detection() -> Result<(), String> {
+    if crate::install_mod::install_mod_logic::archives::request_cancel_extraction() {
+        info!("Detection cancellation requested");
+    } else {
+        info!("Detection cancellation requested, but no scan is running");
+    }
+    Ok(())
+}
+
+/// Open a pak once for a detection worker: the parsed index plus a reader positioned on the file,
+/// both reused for every entry that worker extracts
+fn open_pak_for_extraction(
+    pak_path: &Path,
+    aes_key: &repak::utils::AesKey,
+) -> Option<(repak::PakReader, std::io::BufReader<std::fs::File>)> {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(pak_path).ok()?);
+    let pak = repak::PakBuilder::new().key(aes_key.0.clone()).reader(&mut reader).ok()?;
+    Some((pak, reader))
+}
+
+async fn parse_dropped_files_inner(
+    paths: Vec<String>,
+    cancel: Arc<std::sync::atomic::AtomicBool>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    window: Window
+) -> Result<Vec<InstallableModInfo>, String> {
+    let is_extraction_cancelled = || cancel.load(std::sync::atomic::Ordering::SeqCst);
+    use crate::utils::get_current_pak_characteristics;
+    use repak::PakBuilder;
+    use repak::utils::AesKey;
+    use std::str::FromStr;
+    use std::fs::File;
+    use std::io::BufReader;
+    
+    // Emit start detection log
+    let _ = window.emit("install_log", "[Detection] Starting UAssetAPI detection...");
+    
+    // Set USMAP_PATH for detection (from roaming folder)
+    let (fast_detection, auto_strip_mipmaps) = {
+        let state_guard = state.read().await;
+        let usmap_filename = state_guard.usmap_path.clone();
+        
+        if !usmap_filename.is_empty() {
+            if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+                std::env::set_var("USMAP_PATH", &usmap_full_path);
+                let msg = format!("[Detection] Set USMAP_PATH: {}", usmap_full_path.display());
+                info!("{}", msg);
+                let _ = window.emit("install_log", &msg);
+            } else {
+                let expected_path = usmap_dir().join(&usmap_filename);
+                let msg = format!("[Detection] WARNING: USMAP not found at: {}", expected_path.display());
+                info!("{}", msg);
+                let _ = window.emit("install_log", &msg);
+            }
+        } else {
+            let _ = window.emit("install_log", "[Detection] WARNING: No USMAP configured in settings");
+        }
+        (state_guard.fast_detection, state_guard.auto_strip_mipmaps)
+    };
+    
+    let mut mods = Vec::new();
+    
+    // Filter out .utoc and .ucas files - they will be handled with their .pak file
+    let filtered_paths: Vec<String> = paths.into_iter()
+        .filter(|p| {
+            let path = PathBuf::from(p);
+            if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
+                ext != "utoc" && ext != "ucas"
+            } else {
+                true
+            }
+        })
+        .collect();
+    
+    for path_str in filtered_paths {
+        if is_extraction_cancelled() {
+            return Err("Detection cancelled".to_string());
+        }
+
+        let path = PathBuf::from(&path_str);
+        
+        if !path.exists() {
+            continue;
+        }
+        
+        let mod_name = path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Unknown")
+            .to_string();
+        
+        // Determine mod type and auto-detection flags
+        // 4-tuple: (mod_type, auto_fix_texture, auto_fix_serialize_size, contains_uassets)
+        // Note: mesh patching is handled automatically by UAssetTool
+        let (mod_type, auto_fix_texture, auto_fix_serialize_size, contains_uassets) = if path.is_dir() {
+            // First check if directory contains multiple PAK files - if so, process each PAK separately
+            use walkdir::WalkDir;
+            let mut pak_files = Vec::new();
+            
+            for entry in WalkDir::new(&path).max_depth(1).into_iter().filter_map(|e| e.ok()) {
+                let entry_path = entry.path();
+                if let Some(ext) = entry_path.extension().and_then(|s| s.to_str()) {
+                    if ext == "pak" {
+                        pak_files.push(entry_path.to_path_buf());
+                    }
+                }
+            }
+            
+            if pak_files.len() > 1 {
+                // Multiple PAK files - process each separately
+                let _ = window.emit("install_log", format!("[Detection] Found {} PAK files in directory, processing each separately", pak_files.len()));
+                
+                for pak_file in pak_files {
+                    let pak_mods = Box::pin(parse_dropped_files_inner(vec![pak_file.to_string_lossy().to_string()], cancel.clone(), state.clone(), window.clone())).await?;
+                    for pak_mod in pak_mods {
+                        mods.push(pak_mod);
+                    }
+                }
+                
+                return Ok(mods);
+            } else if pak_files.len() == 1 {
+                // Single PAK file in directory - process it directly (handles IoStore if present)
+                let pak_file = &pak_files[0];
+                let _ = window.emit("install_log", format!("[Detection] Found single PAK in directory: {}", pak_file.display()));
+                
+                let pak_mods = Box::pin(parse_dropped_files_inner(vec![pak_file.to_string_lossy().to_string()], cancel.clone(), state.clone(), window.clone())).await?;
+                for pak_mod in pak_mods {
+                    mods.push(pak_mod);
+                }
+                
+                return Ok(mods);
+            }
+            
+            // No PAK files - analyze directory contents for loose assets
+            let _ = window.emit("install_log", "[Detection] No PAK files found, analyzing directory for loose assets...");
+            
+            use crate::utils::collect_files;
+            let mut all_files = Vec::new();
+            if collect_files(&mut all_files, &path).is_ok() {
+                let _ = window.emit("install_log", format!("[Detection] Collected {} files from directory", all_files.len()));
+                
+                // Convert absolute paths to relative paths for proper classification
+                // Strip the base directory path to get relative paths
+                let content_files_relative: Vec<String> = all_files.iter()
+                    .filter_map(|p| {
+                        p.strip_prefix(&path).ok()
+                            .map(|rel| rel.to_string_lossy().to_string().replace('\\', "/"))
+                    })
+                    .collect();
+                
+                if !content_files_relative.is_empty() {
+                    let _ = window.emit("install_log", format!("[Detection] Processing {} files for classification", content_files_relative.len()));
+                    
+                    // Use detailed characteristics for proper classification (needs relative paths)
+                    use crate::utils::get_pak_characteristics_detailed;
+                    let characteristics = get_pak_characteristics_detailed(content_files_relative.clone());
+                    let mod_type = characteristics.mod_type.clone();
+                    
+                    let _ = window.emit("install_log", format!("[Detection] Detected mod type: {}", mod_type));
+                    
+                    // Get uasset files for detection (needs absolute paths for UAssetAPI to read files)
+                    // Prioritize skeletal mesh files (SK_*), static mesh (SM_*), and textures (T_*) over materials
+                    // Limit to 100 total to prevent hangs on large directories
+                    let mut uasset_files_absolute: Vec<String> = Vec::new();
+                    let mut priority_files: Vec<String> = Vec::new();
+                    let mut other_files: Vec<String> = Vec::new();
+                    
+                    for file in all_files.iter() {
+                        if file.extension().and_then(|s| s.to_str()) == Some("uasset") {
+                            let filename = file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+                            let filename_lower = filename.to_lowercase();
+                            
+                            // Prioritize SK_, SM_, T_ files (skeletal mesh, static mesh, textures)
+                            if filename_lower.starts_with("sk_") || filename_lower.starts_with("sm_") || filename_lower.starts_with("t_") {
+                                priority_files.push(file.to_string_lossy().to_string());
+                            } else {
+                                other_files.push(file.to_string_lossy().to_string());
+                            }
+                        }
+                    }
+                    
+                    // Add priority files first, then fill up to 100 with other files
+                    uasset_files_absolute.extend(priority_files);
+                    let remaining = 100usize.saturating_sub(uasset_files_absolute.len());
+                    uasset_files_absolute.extend(other_files.into_iter().take(remaining));
+                    
+                    // Only scan for textures - SkeletalMesh and StaticMesh are auto-fixed by ZenConverter
+                    // This significantly speeds up detection by skipping unnecessary UAssetAPI calls
+                    let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes are automatic)...");
+                    
+                    // For texture detection, we need ALL files (including .ubulk) to check for bulk data
+                    let all_files_absolute: Vec<String> = all_files.iter()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .collect();
+                    if is_extraction_cancelled() {
+                        return Err("Detection cancelled".to_string());
+                    }
+                    let has_texture = detect_texture_files_async(&all_files_absolute, fast_detection).await;
+                    let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
+                    
+                    let summary = format!("[Detection] Directory results: texture={} (mesh fixes automatic)", has_texture);
+                    info!("{}", summary);
+                    let _ = window.emit("install_log", &summary);
+                    
+                    // Check if directory contains uasset files
+                    use crate::install_mod::contains_uasset_files;
+                    let has_uassets = contains_uasset_files(&all_files_absolute);
+                    let _ = window.emit("install_log", format!("[Detection] Contains UAssets: {}", has_uassets));
+                    
+                    (mod_type, has_texture, false, has_uassets)
+                } else {
+                    ("Directory".to_string(), false, false, true) // Default to true for safety
+                }
+            } else {
+                ("Directory".to_string(), false, false, true) // Default to true for safety
+            }
+        } else {
+            // Get file extension
+            let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+            
+            // Check if it's an archive file (zip, rar, 7z)
+            if ext == "zip" || ext == "rar" || ext == "7z" {
+                use crate::install_mod::install_mod_logic::archives::{extract_zip_with_progress, extract_rar_with_progress, extract_7z_with_progress};
+                use walkdir::WalkDir;
+                
+                let _ = window.emit("install_log", format!("[Detection] Archive detected: {} ({})", mod_name, ext));
+                
+                // Extract archive to temp directory for analysis
+                // Extract archive to temp directory for analysis (or a persistent folder in keep_extracted mode)
+                let temp_dir = tempfile::tempdir().ok();
+                let keep_extracted = state.read().await.keep_extracted;
+                let kept_dir = if keep_extracted {
+                    use crate::install_mod::install_mod_logic::archives::prepare_kept_extraction_dir;
+                    match prepare_kept_extraction_dir(&extraction_dir(), &path) {
+                        Ok(dir) => {
+                            let _ = window.emit("install_log", format!("[Detection] Keeping extracted contents at: {}", dir.display()));
+                            Some(dir)
+                        }
+                        Err(e) => {
+                            warn!("Failed to prepare persistent extraction folder: {}", e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let extract_root = kept_dir.or_else(|| temp_dir.as_ref().map(|t| t.path().to_path_buf()));
+                if let Some(ref extract_root) = extract_root {
+                    let temp_path = extract_root.to_str().unwrap();
+                    
+                    // Extract based on type
+                    let progress_window = window.clone();
+                    let on_progress = move |progress: crate::install_mod::install_mod_logic::archives::ArchiveExtractProgress| {
+                        let _ = progress_window.emit("archive_extract_progress", &progress);
+                    };
+                    let extract_result = if ext == "zip" {
+                        extract_zip_with_progress(path.to_str().unwrap(), temp_path, &on_progress, Some(&cancel))
+                    } else if ext == "rar" {
+                        extract_rar_with_progress(path.to_str().unwrap(), temp_path, &on_progress, Some(&cancel))
+                    } else {
+                        extract_7z_with_progress(path.to_str().unwrap(), temp_path, &on_progress, Some(&cancel))
+                    };
+                    
+                    if is_extraction_cancelled() {
+                        // temp_dir is removed on drop
+                        return Err("Detection cancelled".to_string());
+                    }
+
+                    if extract_result.is_ok() {
+                        let _ = window.emit("install_log", "[Detection] Archive extracted successfully");
+                        
+                        // Look for PAK files in extracted content
+                        let mut pak_files_in_archive = Vec::new();
+                        for entry in WalkDir::new(temp_path).into_iter().filter_map(|e| e.ok()) {
+                            let entry_path = entry.path();
+                            if let Some(entry_ext) = entry_path.extension().and_then(|s| s.to_str()) {
+                                if entry_ext == "pak" {
+                                    pak_files_in_archive.push(entry_path.to_path_buf());
+                                }
+                            }
+                        }
+                        
+                        if pak_files_in_archive.len() > 1 {
+                            // Multiple PAK files found in archive
+                            let _ = window.emit("install_log", format!("[Detection] Found {} PAK files in archive, processing each separately", pak_files_in_archive.len()));
+                            
+                            for pak_file_path in pak_files_in_archive {
+                                let pak_mods = Box::pin(parse_dropped_files_inner(vec![pak_file_path.to_string_lossy().to_string()], cancel.clone(), state.clone(), window.clone())).await?;
+                                for pak_mod in pak_mods {
+                                    mods.push(pak_mod);
+                                }
+                            }
+                            
+                            return Ok(mods);
+                        }
+                        
+                        // Single PAK file or no PAK files - continue with existing logic
+                        let found_pak = !pak_files_in_archive.is_empty();
+                        if found_pak {
+                            let entry_path = &pak_files_in_archive[0];
+                            
+                            // Check if this is an IoStore package (has .utoc and .ucas companions)
+                            let utoc_path = entry_path.with_extension("utoc");
+                            let ucas_path = entry_path.with_extension("ucas");
+                            let is_iostore = utoc_path.exists() && ucas_path.exists();
+                            
+                            if is_iostore {
+                                let _ = window.emit("install_log", format!("[Detection] IoStore package detected in archive: {}", mod_name));
+                            }
+                            
+                            // Get file list - for IoStore, read from utoc directly (works with obfuscated mods);
+                            // otherwise open PAK with AES key
+                            let files: Option<Vec<String>> = if is_iostore {
+                                use crate::utoc_utils::read_utoc;
+                                let _ = window.emit("install_log", "[Detection] Reading IoStore .utoc file for accurate file list");
+                                let utoc_files: Vec<String> = read_utoc(&utoc_path)
+                                    .iter()
+                                    .map(|entry| entry.file_path.clone())
+                                    .collect();
+                                if utoc_files.is_empty() { None } else { Some(utoc_files) }
+                            } else if let Ok(file) = File::open(entry_path) {
+                                if let Ok(aes_key) = AesKey::from_str(&install_mod::current_aes_key_hex()) {
+                                    let mut reader = BufReader::new(file);
+                                    PakBuilder::new().key(aes_key.0).reader(&mut reader).ok().map(|pak| pak.files())
+                                } else { None }
+                            } else { None };
+                            
+                            if let Some(files) = files {
+                                        
+                                        // Use detailed characteristics (same as get_mod_details)
+                                        use crate::utils::get_pak_characteristics_detailed;
+                                        let characteristics = get_pak_characteristics_detailed(files.clone());
+                                        let mod_type = characteristics.mod_type.clone();
+                                        
+                                        let _ = window.emit("install_log", format!("[Detection] Detected mod type: {}", mod_type));
+                                        
+                                        // Get files to extract (both .uasset and .uexp needed by UAssetAPI)
+                                        // Prioritize SK_, SM_, T_ files for detection
+                                        let files_to_extract: Vec<&String> = files.iter()
+                                            .filter(|f| {
+                                                let lower = f.to_lowercase();
+                                                (lower.ends_with(".uasset") || lower.ends_with(".uexp")) &&
+                                                if let Some(filename) = std::path::Path::new(f).file_name().and_then(|n| n.to_str()) {
+                                                    let fname_lower = filename.to_lowercase();
+                                                    fname_lower.starts_with("sk_") || fname_lower.starts_with("sm_") || fname_lower.starts_with("t_")
+                                                } else {
+                                                    false
+                                                }
+                                            })
+                                            .take(40)  // Limit to 40 files (20 uasset + 20 uexp pairs)
+                                            .collect();
+                                        
+                                        let _ = window.emit("install_log", format!("[Detection] Extracting {} files from archive PAK for analysis...", files_to_extract.len()));
+                                        
+                                        // Extract to temp directory for UAssetAPI analysis
+                                        let mut extracted_paths: Vec<String> = Vec::new();
+                                        let uasset_temp_dir = tempfile::tempdir().ok();
+                                        let aes_key_for_extraction = AesKey::from_str(&install_mod::current_aes_key_hex()).unwrap();
+                                        
+                                        if let Some(ref uasset_temp) = uasset_temp_dir {
+                                            use rayon::prelude::*;
+                                            use std::sync::Mutex;
+                                            
+                                            let extracted = Mutex::new(Vec::new());
+                                            let pak_path = entry_path.clone();
+                                            
+                                            // Parallel extraction - each worker opens the pak and builds its reader once,
+                                            // then reuses both for every entry it is handed
+                                            files_to_extract.par_iter().for_each_init(
+                                                || open_pak_for_extraction(&pak_path, &aes_key_for_extraction),
+                                                |worker, internal_path| {
+                                                    let Some((pak, extract_reader)) = worker.as_mut() else {
+                                                        return;
+                                                    };
+                                                    // Use just the filename to preserve .uasset/.uexp pairing
+                                                    let filename = std::path::Path::new(internal_path.as_str())
+                                                        .file_name()
+                                                        .and_then(|n| n.to_str())
+                                                        .unwrap_or(internal_path);
+                                                    let dest_path = uasset_temp.path().join(filename);
+                                                    
+                                                    if let Ok(data) = pak.get(internal_path, extract_reader) {
+                                                        if std::fs::write(&dest_path, data).is_ok() && internal_path.to_lowercase().ends_with(".uasset") {
+                                                            extracted.lock().unwrap().push(dest_path.to_string_lossy().to_string());
+                                                        }
+                                                    }
+                                                },
+                                            );
+                                            
+                                            extracted_paths = extracted.into_inner().unwrap();
+                                            let _ = window.emit("install_log", format!("[Detection] Extracted {} uasset files for UAssetAPI", extracted_paths.len()));
+                                        }
+                                        
+                                        // Only scan for textures - mesh fixes are automatic in ZenConverter
+                                        let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes automatic)...");
+                                        
+                                        // Texture detection - use extracted files but also check for .ubulk in original file list
+                                        let has_ubulk = files.iter().any(|f| f.to_lowercase().ends_with(".ubulk"));
+                                        let has_texture = if has_ubulk && !extracted_paths.is_empty() {
+                                            // Add .ubulk indicator to detection files so detect_texture_files_async knows there's bulk data
+                                            let mut texture_detection_files = extracted_paths.clone();
+                                            texture_detection_files.push("dummy.ubulk".to_string()); // Signal that .ubulk exists
+                                            detect_texture_files_async(&texture_detection_files, fast_detection).await
+                                        } else {
+                                            false
+                                        };
+                                        let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
+                                        
+                                        let summary = format!("[Detection] Archive PAK results: texture={} (mesh fixes automatic)", has_texture);
+                                        info!("{}", summary);
+                                        let _ = window.emit("install_log", &summary);
+                                        
+                                        // Clean up temp dir
+                                        drop(temp_dir);
+                                        
+                                        // Check if files contain uassets
+                                        use crate::install_mod::contains_uasset_files;
+                                        let has_uassets = contains_uasset_files(&files);
+                                        
+                                        return Ok(vec![InstallableModInfo {
+                                            mod_name,
+                                            mod_type,
+                                            is_dir: false,
+                                            path: path_str,
+                                            auto_fix_texture: has_texture,
+                                            auto_fix_serialize_size: false, // Mesh fixes are automatic
+                                            auto_to_repak: !is_iostore,  // Don't repak IoStore packages
+                                            contains_uassets: has_uassets,
+                                            ..Default::default()
+                                        }.with_prediction()]);
+                            }
+                        }
+                        
+                        // If no .pak files found, look for content folders with loose assets
+                        if !found_pak {
+                            let _ = window.emit("install_log", "[Detection] No PAK files found in archive, looking for content folders...");
+                            
+                            use crate::utils::collect_files;
+                            
+                            // Collect all files from the extracted archive
+                            let mut all_files = Vec::new();
+                            let temp_path_buf = PathBuf::from(temp_path);
+                            if collect_files(&mut all_files, &temp_path_buf).is_ok() {
+                                // Check if there are content files (.uasset, .uexp, .ubulk, etc.)
+                                let content_files: Vec<String> = all_files.iter()
+                                    .filter(|f| {
+                                        f.extension()
+                                            .and_then(|s| s.to_str())
+                                            .is_some_and(crate::install_mod::is_content_file_ext)
+                                    })
+                                    .map(|p| p.to_string_lossy().to_string())
+                                    .collect();
+                                
+                                if !content_files.is_empty() {
+                                    let _ = window.emit("install_log", format!("[Detection] Found {} content files in archive folder", content_files.len()));
+                                    
+                                    // Get mod type from content
+                                    let mod_type = get_current_pak_characteristics(content_files.clone());
+                                    
+                                    // Only scan for textures - mesh fixes are automatic in ZenConverter
+                                    let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes automatic)...");
+                                    if is_extraction_cancelled() {
+                                        return Err("Detection cancelled".to_string());
+                                    }
+                                    let has_texture = detect_texture_files_async(&content_files, fast_detection).await;
+                                    let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
+                                    
+                                    let summary = format!("[Detection] Archive folder results: texture={} (mesh fixes automatic)", has_texture);
+                                    info!("{}", summary);
+                                    let _ = window.emit("install_log", &summary);
+                                    
+                                    // Clean up temp dir
+                                    drop(temp_dir);
+                                    
+                                    // Check if content files contain uassets
+                                    use crate::install_mod::contains_uasset_files;
+                                    let has_uassets = contains_uasset_files(&content_files);
+                                    
+                                    // Return as a directory mod (will be converted to IoStore)
+                                    return Ok(vec![InstallableModInfo {
+                                        mod_name,
+                                        mod_type,
+                                        is_dir: true,
+                                        path: path_str,
+                                        auto_fix_texture: has_texture,
+                                        auto_fix_serialize_size: false, // Mesh fixes are automatic
+                                        auto_to_repak: false,
+                                        contains_uassets: has_uassets,
+                                        ..Default::default()
+                                    }.with_prediction()]);
+                                }
+                            }
+                        }
+                    }
+                }
+                
+                // Fallback if extraction/analysis failed
+                ("Archive".to_string(), false, false, true) // Default to true for safety
+            } else if ext == "pak" {
+                // Check if this is an IoStore package (has .utoc and .ucas companions)
+                let utoc_path = path.with_extension("utoc");
+                let ucas_path = path.with_extension("ucas");
+                let is_iostore = utoc_path.exists() && ucas_path.exists();
+                
+                if is_iostore {
+                    let _ = window.emit("install_log", format!("[Detection] IoStore package detected: {}", mod_name));
+                }
+                
+                // Read file list for mod type detection
+                // For IoStore, read from utoc directly (works with obfuscated mods);
+                // otherwise open PAK with AES key
+                let mod_type = {
+                    let files_and_key: Option<(Vec<String>, Option<repak::utils::AesKey>)> = if is_iostore {
                         use crate::utoc_utils::read_utoc;
                         let _ = window.emit("install_log", "[Detection] Reading IoStore .utoc file for accurate file list");
                         let utoc_files: Vec<String> = read_utoc(&utoc_path)
@@ -1053,7 +3024,7 @@ async fn parse_dropped_files(
                             .collect();
                         if utoc_files.is_empty() { None } else { Some((utoc_files, None)) }
                     } else if let Ok(file) = File::open(&path) {
-                        if let Ok(aes_key) = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74") {
+                        if let Ok(aes_key) = AesKey::from_str(&install_mod::current_aes_key_hex()) {
                             let aes_key_for_extraction = aes_key.clone();
                             let mut reader = BufReader::new(file);
                             PakBuilder::new().key(aes_key.0).reader(&mut reader).ok().map(|pak| (pak.files(), Some(aes_key_for_extraction)))
@@ -1061,7 +3032,7 @@ async fn parse_dropped_files(
                     } else { None };
                     
                     if let Some((files, aes_key_opt)) = files_and_key {
-                        let aes_key_for_extraction = aes_key_opt.unwrap_or_else(|| AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74").unwrap());
+                        let aes_key_for_extraction = aes_key_opt.unwrap_or_else(|| AesKey::from_str(&install_mod::current_aes_key_hex()).unwrap());
                             
                             // Use detailed characteristics (same as get_mod_details)
                             use crate::utils::get_pak_characteristics_detailed;
@@ -1099,2236 +3070,5836 @@ async fn parse_dropped_files(
                                 let extracted = Mutex::new(Vec::new());
                                 let pak_path = path.clone();
                                 
-                                // Parallel extraction
-                                files_to_extract.par_iter().for_each(|internal_path| {
-                                    // Each thread opens its own file handle
-                                    if let Ok(file) = File::open(&pak_path) {
-                                        let mut reader = BufReader::new(file);
-                                        if let Ok(pak) = PakBuilder::new().key(aes_key_for_extraction.0.clone()).reader(&mut reader) {
-                                            // Sanitize filename for filesystem
-                                            let safe_name = internal_path.replace("/", "_").replace("\\", "_");
-                                            let dest_path = uasset_temp.path().join(&safe_name);
-                                            
-                                            // Re-open file for extraction (pak.get needs mutable reader)
-                                            if let Ok(extract_file) = File::open(&pak_path) {
-                                                let mut extract_reader = BufReader::new(extract_file);
-                                                // Extract file
-                                                if let Ok(data) = pak.get(internal_path, &mut extract_reader) {
-                                                    if let Ok(_) = std::fs::write(&dest_path, data) {
-                                                        if internal_path.to_lowercase().ends_with(".uasset") {
-                                                            extracted.lock().unwrap().push(dest_path.to_string_lossy().to_string());
-                                                        }
-                                                    }
-                                                }
+                                // Parallel extraction - each worker opens the pak and builds its reader once,
+                                // then reuses both for every entry it is handed
+                                files_to_extract.par_iter().for_each_init(
+                                    || open_pak_for_extraction(&pak_path, &aes_key_for_extraction),
+                                    |worker, internal_path| {
+                                        if is_extraction_cancelled() {
+                                            return;
+                                        }
+                                        let Some((pak, extract_reader)) = worker.as_mut() else {
+                                            return;
+                                        };
+                                        // Sanitize filename for filesystem
+                                        let safe_name = internal_path.replace("/", "_").replace("\\", "_");
+                                        let dest_path = uasset_temp.path().join(&safe_name);
+                                        
+                                        if let Ok(data) = pak.get(internal_path, extract_reader) {
+                                            if std::fs::write(&dest_path, data).is_ok() && internal_path.to_lowercase().ends_with(".uasset") {
+                                                extracted.lock().unwrap().push(dest_path.to_string_lossy().to_string());
                                             }
                                         }
-                                    }
-                                });
+                                    },
+                                );
                                 
                                 extracted_paths = extracted.into_inner().unwrap();
                                 let _ = window.emit("install_log", format!("[Detection] Extracted {} uasset files for UAssetAPI", extracted_paths.len()));
                             }
                             
-                            // Only scan for textures - mesh fixes are automatic in ZenConverter
-                            let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes automatic)...");
-                            
-                            // Texture detection - use extracted files but also check for .ubulk in original file list
-                            let has_ubulk = files.iter().any(|f| f.to_lowercase().ends_with(".ubulk"));
-                            let has_texture = if has_ubulk && !extracted_paths.is_empty() {
-                                // Add .ubulk indicator to detection files so detect_texture_files_async knows there's bulk data
-                                let mut texture_detection_files = extracted_paths.clone();
-                                texture_detection_files.push("dummy.ubulk".to_string()); // Signal that .ubulk exists
-                                detect_texture_files_async(&texture_detection_files).await
-                            } else {
-                                false
+                            if is_extraction_cancelled() {
+                                return Err("Detection cancelled".to_string());
+                            }
+
+                            // Only scan for textures - mesh fixes are automatic in ZenConverter
+                            let _ = window.emit("install_log", "[Detection] Checking for textures with .ubulk (mesh fixes automatic)...");
+                            
+                            // Texture detection - use extracted files but also check for .ubulk in original file list
+                            let has_ubulk = files.iter().any(|f| f.to_lowercase().ends_with(".ubulk"));
+                            let has_texture = if has_ubulk && !extracted_paths.is_empty() {
+                                // Add .ubulk indicator to detection files so detect_texture_files_async knows there's bulk data
+                                let mut texture_detection_files = extracted_paths.clone();
+                                texture_detection_files.push("dummy.ubulk".to_string()); // Signal that .ubulk exists
+                                detect_texture_files_async(&texture_detection_files, fast_detection).await
+                            } else {
+                                false
+                            };
+                            let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
+                            
+                            let summary = format!("[Detection] PAK file results: texture={} (mesh fixes automatic)", has_texture);
+                            info!("{}", summary);
+                            let _ = window.emit("install_log", &summary);
+                            
+                            // Check if files contain uassets
+                            use crate::install_mod::contains_uasset_files;
+                            let has_uassets = contains_uasset_files(&files);
+                            
+                            // Push this PAK mod and continue processing other files
+                            mods.push(InstallableModInfo {
+                                mod_name,
+                                mod_type,
+                                is_dir: false,
+                                path: path_str,
+                                auto_fix_texture: has_texture,
+                                auto_fix_serialize_size: false, // Mesh fixes are automatic
+                                auto_to_repak: !is_iostore,  // Don't repak IoStore packages
+                                contains_uassets: has_uassets,
+                                ..Default::default()
+                            }.with_prediction());
+                            continue; // Continue to next file instead of returning
+                    }
+                    
+                    "PAK".to_string()
+                };
+                
+                (mod_type, false, false, true) // Default to true for safety
+            } else {
+                ("Unknown".to_string(), false, false, true) // Default to true for safety
+            }
+        };
+
+        // For .pak files, auto-enable repak UNLESS it's an IoStore package
+        let is_pak = path.extension().and_then(|s| s.to_str()) == Some("pak");
+        let is_iostore_pkg = is_pak && path.with_extension("utoc").exists() && path.with_extension("ucas").exists();
+        let auto_to_repak = is_pak && !is_iostore_pkg;
+
+        // The global default wins over detection for anything that looks like it ships textures
+        let auto_fix_texture = auto_fix_texture
+            || (auto_strip_mipmaps && auto_strip_mipmaps_applies(&mod_type, contains_uassets, auto_fix_texture));
+
+        mods.push(InstallableModInfo {
+            mod_name,
+            mod_type,
+            is_dir: path.is_dir(),
+            path: path_str,
+            auto_fix_texture,
+            auto_fix_serialize_size,
+            auto_to_repak,
+            contains_uassets,
+            ..Default::default()
+        }.with_prediction());
+    }
+
+    Ok(mods)
+}
+
+#[derive(serde::Deserialize)]
+struct ModToInstall {
+    path: String,
+    #[serde(rename = "customName")]
+    custom_name: Option<String>,
+    #[serde(rename = "fixTexture")]
+    fix_texture: bool,
+    #[serde(rename = "fixSerializeSize")]
+    fix_serialize_size: bool,
+    #[serde(rename = "toRepak")]
+    to_repak: bool,
+    #[serde(rename = "forceLegacy")]
+    force_legacy: bool,
+    /// Subfolder within the mods directory to install into (empty = root)
+    #[serde(rename = "installSubfolder", default)]
+    install_subfolder: String,
+    /// Pak mount point override (None/empty = default "../../../")
+    #[serde(rename = "mountPoint", default)]
+    mount_point: Option<String>,
+    /// Install the mod in the disabled state (.bak_repak) instead of enabled
+    #[serde(rename = "installDisabled", default)]
+    install_disabled: bool,
+    /// Install as a patch pak (_9999999_P). false installs a base pak under the plain name
+    #[serde(rename = "isPatch", default = "crate::install_mod::default_is_patch")]
+    is_patch: bool,
+    /// Keep the source pak's compression when repacking instead of forcing Oodle
+    #[serde(rename = "preserveCompression", default)]
+    preserve_compression: bool,
+}
+
+/// Per-file outcome of a quick_organize run
+#[derive(Serialize, Default)]
+struct QuickOrganizeReport {
+    copied: Vec<String>,
+    /// IoStore bundles that were recompressed with Oodle after copying
+    recompressed: Vec<String>,
+    /// Mods whose textures had their mipmaps stripped and were repacked (strip_mipmaps only)
+    mipmaps_stripped: Vec<String>,
+    /// Inputs that were ignored (missing, unsupported type, or incomplete IoStore bundle)
+    skipped: Vec<String>,
+    /// (file, error) pairs for files that failed to copy or recompress
+    errors: Vec<(String, String)>,
+}
+
+/// Helper function to copy an IoStore bundle (.utoc/.ucas and .pak or .bak_repak) and recompress if needed
+fn copy_iostore_with_compression_check(
+    utoc_src: &Path,
+    output_dir: &Path,
+    iostore_version: Option<u8>,
+    window: &Window,
+    report: &mut QuickOrganizeReport,
+) -> Result<(), String> {
+    let utoc_name = utoc_src.file_name().unwrap();
+    let ucas_src = utoc_src.with_extension("ucas");
+    let utoc_dest = output_dir.join(utoc_name);
+    let ucas_dest = output_dir.join(ucas_src.file_name().unwrap());
+    
+    // Also check for .pak or .bak_repak file (part of IoStore bundle)
+    let pak_src = utoc_src.with_extension("pak");
+    let bak_repak_src = utoc_src.with_extension("bak_repak");
+    
+    // Copy .pak if it exists
+    if pak_src.exists() {
+        let pak_dest = output_dir.join(pak_src.file_name().unwrap());
+        if let Err(e) = std::fs::copy(&pak_src, &pak_dest) {
+            warn!("[QuickOrganize] Failed to copy {}: {}", pak_src.file_name().unwrap().to_string_lossy(), e);
+            report.errors.push((pak_src.file_name().unwrap().to_string_lossy().to_string(), e.to_string()));
+        } else {
+            info!("[QuickOrganize] Copied: {}", pak_src.file_name().unwrap().to_string_lossy());
+            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", pak_src.file_name().unwrap().to_string_lossy()));
+            report.copied.push(pak_src.file_name().unwrap().to_string_lossy().to_string());
+        }
+    }
+    
+    // Copy .bak_repak if it exists (disabled pak file)
+    if bak_repak_src.exists() {
+        let bak_repak_dest = output_dir.join(bak_repak_src.file_name().unwrap());
+        if let Err(e) = std::fs::copy(&bak_repak_src, &bak_repak_dest) {
+            warn!("[QuickOrganize] Failed to copy {}: {}", bak_repak_src.file_name().unwrap().to_string_lossy(), e);
+            report.errors.push((bak_repak_src.file_name().unwrap().to_string_lossy().to_string(), e.to_string()));
+        } else {
+            info!("[QuickOrganize] Copied: {}", bak_repak_src.file_name().unwrap().to_string_lossy());
+            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", bak_repak_src.file_name().unwrap().to_string_lossy()));
+            report.copied.push(bak_repak_src.file_name().unwrap().to_string_lossy().to_string());
+        }
+    }
+    
+    // Check if the IoStore is compressed
+    let is_compressed = match uasset_toolkit::is_iostore_compressed(&utoc_src.to_string_lossy()) {
+        Ok(compressed) => compressed,
+        Err(e) => {
+            warn!("[QuickOrganize] Failed to check IoStore compression for {}: {}", utoc_name.to_string_lossy(), e);
+            // Assume compressed if we can't check, just copy
+            true
+        }
+    };
+    
+    if is_compressed {
+        // Already compressed, just copy
+        info!("[QuickOrganize] IoStore {} is already compressed, copying directly", utoc_name.to_string_lossy());
+        let _ = window.emit("install_log", format!("[QuickOrganize] Copying compressed IoStore: {}", utoc_name.to_string_lossy()));
+        
+        std::fs::copy(utoc_src, &utoc_dest)
+            .map_err(|e| format!("Failed to copy {}: {}", utoc_name.to_string_lossy(), e))?;
+        std::fs::copy(&ucas_src, &ucas_dest)
+            .map_err(|e| format!("Failed to copy {}: {}", ucas_src.file_name().unwrap().to_string_lossy(), e))?;
+        
+        report.copied.push(utoc_name.to_string_lossy().to_string());
+        report.copied.push(ucas_src.file_name().unwrap().to_string_lossy().to_string());
+    } else {
+        // Not compressed, need to recompress with Oodle
+        info!("[QuickOrganize] IoStore {} is NOT compressed, recompressing with Oodle...", utoc_name.to_string_lossy());
+        let _ = window.emit("install_log", format!("[QuickOrganize] Recompressing uncompressed IoStore: {}", utoc_name.to_string_lossy()));
+        
+        // First copy to destination
+        std::fs::copy(utoc_src, &utoc_dest)
+            .map_err(|e| format!("Failed to copy {}: {}", utoc_name.to_string_lossy(), e))?;
+        std::fs::copy(&ucas_src, &ucas_dest)
+            .map_err(|e| format!("Failed to copy {}: {}", ucas_src.file_name().unwrap().to_string_lossy(), e))?;
+        
+        report.copied.push(utoc_name.to_string_lossy().to_string());
+        report.copied.push(ucas_src.file_name().unwrap().to_string_lossy().to_string());
+        
+        // Now recompress in place
+        match uasset_toolkit::recompress_iostore(&utoc_dest.to_string_lossy(), iostore_version) {
+            Ok(_) => {
+                info!("[QuickOrganize] Successfully recompressed IoStore: {}", utoc_name.to_string_lossy());
+                let _ = window.emit("install_log", format!("[QuickOrganize] ✓ Recompressed: {}", utoc_name.to_string_lossy()));
+                report.recompressed.push(utoc_name.to_string_lossy().to_string());
+            }
+            Err(e) => {
+                warn!("[QuickOrganize] Failed to recompress IoStore {}: {}", utoc_name.to_string_lossy(), e);
+                let _ = window.emit("install_log", format!("[QuickOrganize] Warning: Could not recompress {}: {}", utoc_name.to_string_lossy(), e));
+                // Files are still copied, just not recompressed
+                report.errors.push((utoc_name.to_string_lossy().to_string(), format!("Could not recompress: {}", e)));
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// After quick organize copied a legacy pak to `dest`, recompress it with Oodle in place
+/// if it is uncompressed or uses another codec. IoStore stub paks are left alone.
+fn recompress_organized_pak(dest: &Path, window: &Window, report: &mut QuickOrganizeReport) {
+    use repak::Compression;
+    use std::io::BufReader;
+
+    if dest.with_extension("utoc").exists() {
+        return;
+    }
+    let name = dest.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let pak_reader = match File::open(dest)
+        .map_err(|e| e.to_string())
+        .and_then(|f| repak::PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut BufReader::new(f))
+            .map_err(|e| e.to_string()))
+    {
+        Ok(reader) => reader,
+        Err(e) => {
+            warn!("[QuickOrganize] Could not read {} to check compression: {}", name, e);
+            return;
+        }
+    };
+    if install_mod::is_iostore_stub(&pak_reader) || pak_reader.compression().contains(&Compression::Oodle) {
+        return;
+    }
+
+    info!("[QuickOrganize] PAK {} is not Oodle-compressed, recompressing...", name);
+    let _ = window.emit("install_log", format!("[QuickOrganize] Recompressing PAK: {}", name));
+    match recompress_pak_file(dest, &pak_reader) {
+        Ok(_) => {
+            let _ = window.emit("install_log", format!("[QuickOrganize] ✓ Recompressed: {}", name));
+            report.recompressed.push(name);
+        }
+        Err(e) => {
+            warn!("[QuickOrganize] Failed to recompress PAK {}: {}", name, e);
+            let _ = window.emit("install_log", format!("[QuickOrganize] Warning: Could not recompress {}: {}", name, e));
+            // The plain copy is still in place
+            report.errors.push((name, format!("Could not recompress: {}", e)));
+        }
+    }
+}
+
+/// Strip mipmaps from the textures (.uasset with .ubulk) in an extracted mod and drop the
+/// .ubulk of every texture that was converted. Returns the number of textures stripped.
+fn strip_mipmaps_in_dir(dir: &Path) -> Result<usize, String> {
+    let texture_paths: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|e| e == "uasset") && p.with_extension("ubulk").exists())
+        .collect();
+    if texture_paths.is_empty() {
+        return Ok(0);
+    }
+    let (stripped, _, _, processed) = uasset_api_integration::batch_convert_textures_to_inline(&texture_paths)
+        .map_err(|e| e.to_string())?;
+    let processed: std::collections::HashSet<String> = processed.into_iter().collect();
+    for texture in &texture_paths {
+        let stem = texture.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        if processed.contains(&stem) {
+            let _ = std::fs::remove_file(texture.with_extension("ubulk"));
+        }
+    }
+    // Leftovers from the conversion must not end up in the repacked mod
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if ext == "bak" || ext == "temp" || entry.file_name() == "patched_files" {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+    Ok(stripped)
+}
+
+/// Write every entry of a legacy pak under `dir`, keeping the pak's relative paths
+fn extract_pak_entries<R: std::io::Read + std::io::Seek>(pak_reader: &repak::PakReader, reader: &mut R, dir: &Path) -> Result<(), String> {
+    for entry in pak_reader.files() {
+        if entry.split(['/', '\\']).any(|part| part == "..") {
+            return Err(format!("Refusing to extract {} outside the work directory", entry));
+        }
+        let out_path = dir.join(&entry);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = pak_reader.get(&entry, reader)
+            .map_err(|e| format!("Failed to read entry {}: {}", entry, e))?;
+        std::fs::write(&out_path, data).map_err(|e| format!("Failed to write {}: {}", entry, e))?;
+    }
+    Ok(())
+}
+
+/// Strip mipmaps from a legacy pak in place: extract, strip, and repack with Oodle.
+/// Returns the number of textures stripped (the pak is left untouched when there are none).
+fn strip_pak_mipmaps(pak_path: &Path) -> Result<usize, String> {
+    use repak::{Compression, Version};
+    use std::io::{BufReader, BufWriter};
+    use tempfile::NamedTempFile;
+
+    let file = File::open(pak_path).map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let pak_reader = repak::PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+    if install_mod::is_iostore_stub_at(&pak_reader, pak_path) {
+        return Ok(0);
+    }
+
+    let work_dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    extract_pak_entries(&pak_reader, &mut reader, work_dir.path())?;
+
+    let stripped = strip_mipmaps_in_dir(work_dir.path())?;
+    if stripped == 0 {
+        return Ok(0);
+    }
+
+    let temp_file = NamedTempFile::new_in(pak_path.parent().unwrap_or_else(|| Path::new(".")))
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    let output_file = File::create(temp_file.path())
+        .map_err(|e| format!("Failed to create output file: {}", e))?;
+    let mut pak_writer = repak::PakBuilder::new()
+        .compression(vec![Compression::Oodle])
+        .key(install_mod::current_aes_key().0)
+        .encrypt_index_only(install_mod::is_index_only_encrypted(&pak_reader))
+        .writer(
+            BufWriter::new(output_file),
+            Version::V11,
+            pak_reader.mount_point().to_string(),
+            pak_reader.path_hash_seed(),
+        );
+    let entry_builder = pak_writer.entry_builder();
+    for entry in WalkDir::new(work_dir.path()).into_iter().filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+        let rel = entry.path().strip_prefix(work_dir.path()).unwrap_or(entry.path())
+            .to_string_lossy().replace('\\', "/");
+        let data = std::fs::read(entry.path()).map_err(|e| format!("Failed to read {}: {}", rel, e))?;
+        let built = entry_builder.build_entry(true, data, &rel)
+            .map_err(|e| format!("Failed to build entry {}: {}", rel, e))?;
+        pak_writer.write_entry(rel.clone(), built)
+            .map_err(|e| format!("Failed to write entry {}: {}", rel, e))?;
+    }
+    let writer = pak_writer.write_index().map_err(|e| format!("Failed to write index: {}", e))?;
+    writer.into_inner().map_err(|e| format!("Failed to flush PAK: {}", e))?;
+    drop(reader);
+    temp_file.persist(pak_path).map_err(|e| format!("Failed to replace original PAK: {}", e))?;
+    Ok(stripped)
+}
+
+/// Strip mipmaps from an IoStore bundle in place: extract to legacy assets, strip, and rebuild
+/// the container (keeping its obfuscation). Returns the number of textures stripped.
+fn strip_iostore_mipmaps(utoc_path: &Path, iostore_version: Option<u8>) -> Result<usize, String> {
+    let work_dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let content_dir = work_dir.path().join("content");
+    let aes_key = install_mod::current_aes_key_hex();
+    uasset_toolkit::extract_iostore(&utoc_path.to_string_lossy(), &content_dir.to_string_lossy(), Some(&aes_key))
+        .map_err(|e| e.to_string())?;
+
+    let stripped = strip_mipmaps_in_dir(&content_dir)?;
+    if stripped == 0 {
+        return Ok(0);
+    }
+
+    // The stub pak may be disabled (.bak_repak), keep whatever name it had
+    let pak_dest = ["pak", "bak_repak"].iter()
+        .map(|ext| utoc_path.with_extension(ext))
+        .find(|p| p.exists())
+        .ok_or_else(|| format!("No pak found next to {}", utoc_path.display()))?;
+    let mount_point = read_pak_mount_point(&pak_dest)?;
+
+    let stem = utoc_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let output_base = work_dir.path().join(&stem);
+    let obfuscate = uasset_toolkit::is_iostore_encrypted_header(&utoc_path.to_string_lossy()).unwrap_or(false);
+    let usmap_path = std::env::var("USMAP_PATH").ok();
+    uasset_toolkit::create_mod_iostore(
+        &output_base.to_string_lossy(),
+        &content_dir.to_string_lossy(),
+        &uasset_toolkit::ModIoStoreOptions {
+            usmap_path: usmap_path.as_deref(),
+            mount_point: Some(&mount_point),
+            compress: Some(true),
+            aes_key: Some(&aes_key),
+            parallel: false,
+            obfuscate,
+            container_version: iostore_version,
+        },
+    ).map_err(|e| e.to_string())?;
+
+    // Stage all three next to the originals first, so a failed copy never leaves a
+    // mismatched container behind; the renames into place stay on the same drive
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (ext, dest) in [("utoc", utoc_path.to_path_buf()), ("ucas", utoc_path.with_extension("ucas")), ("pak", pak_dest)] {
+        let temp = dest.with_file_name(format!("{}.strip_tmp", dest.file_name().unwrap_or_default().to_string_lossy()));
+        if let Err(e) = std::fs::copy(output_base.with_extension(ext), &temp) {
+            let _ = std::fs::remove_file(&temp);
+            for (temp, _) in &staged {
+                let _ = std::fs::remove_file(temp);
+            }
+            return Err(format!("Failed to stage {}: {}", dest.display(), e));
+        }
+        staged.push((temp, dest));
+    }
+    for (temp, dest) in &staged {
+        std::fs::rename(temp, dest)
+            .map_err(|e| format!("Failed to replace {}: {}", dest.display(), e))?;
+    }
+    Ok(stripped)
+}
+
+/// Quick organize's optional "clean and place" step for a mod that was just copied to `dest`
+/// (a legacy pak or any file of an IoStore bundle)
+fn strip_organized_mipmaps(dest: &Path, iostore_version: Option<u8>, window: &Window, report: &mut QuickOrganizeReport) {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let utoc = dest.with_extension("utoc");
+    let _ = window.emit("install_log", format!("[QuickOrganize] Stripping mipmaps: {}", name));
+    let result = if utoc.exists() {
+        strip_iostore_mipmaps(&utoc, iostore_version)
+    } else {
+        strip_pak_mipmaps(dest)
+    };
+    match result {
+        Ok(0) => info!("[QuickOrganize] No textures with mipmaps to strip in {}", name),
+        Ok(count) => {
+            info!("[QuickOrganize] Stripped mipmaps from {} texture(s) in {}", count, name);
+            let _ = window.emit("install_log", format!("[QuickOrganize] ✓ Stripped mipmaps from {} texture(s): {}", count, name));
+            report.mipmaps_stripped.push(name);
+        }
+        Err(e) => {
+            warn!("[QuickOrganize] Failed to strip mipmaps from {}: {}", name, e);
+            let _ = window.emit("install_log", format!("[QuickOrganize] Warning: Could not strip mipmaps from {}: {}", name, e));
+            // The copied mod is still in place, unchanged
+            report.errors.push((name, format!("Could not strip mipmaps: {}", e)));
+        }
+    }
+}
+
+/// Quick Organize: Simply copy/move files to a target folder without any repak processing
+/// This is for organizing existing mod files into subfolders
+/// Now also detects uncompressed IoStore bundles and recompresses them with Oodle
+/// Preserves subfolder structure from archives and directories
+/// With `strip_mipmaps`, textures in the organized mods also get the mipmap fix (extract, strip, repack)
+#[tauri::command]
+async fn quick_organize(
+    paths: Vec<String>,
+    target_folder: String,
+    strip_mipmaps: Option<bool>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    window: Window,
+) -> Result<QuickOrganizeReport, String> {
+    use crate::install_mod::install_mod_logic::archives::{extract_zip_with_progress, extract_rar_with_progress, extract_7z_with_progress, ArchiveExtractProgress};
+    use walkdir::WalkDir;
+    
+    let state_guard = state.read().await;
+    let mod_directory = state_guard.game_path.clone();
+    let iostore_version = state_guard.iostore_version;
+    let recompress_on_organize = state_guard.recompress_on_organize;
+    let usmap_filename = state_guard.usmap_path.clone();
+    drop(state_guard);
+    let strip_mipmaps = strip_mipmaps.unwrap_or(false);
+    if strip_mipmaps {
+        // UAssetTool needs the mappings to parse textures
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
+        }
+    }
+    
+    // Determine the output directory
+    let output_dir = if target_folder.is_empty() || target_folder == "~mods" {
+        mod_directory.clone()
+    } else {
+        mod_directory.join(crate::install_mod::sanitize_subfolder(&target_folder)?)
+    };
+    
+    // Create output directory if it doesn't exist (for "New Folder" drops and subfolder preservation)
+    if !output_dir.exists() {
+        std::fs::create_dir_all(&output_dir)
+            .map_err(|e| format!("Failed to create target folder '{}': {}", target_folder, e))?;
+        info!("[QuickOrganize] Created target folder: {}", output_dir.display());
+    }
+    
+    info!("[QuickOrganize] Copying {} file(s) to '{}'", paths.len(), output_dir.display());
+    let _ = window.emit("install_log", format!("[QuickOrganize] Copying to folder: {}", if target_folder.is_empty() { "~mods (root)".to_string() } else { target_folder.clone() }));
+    
+    let mut report = QuickOrganizeReport::default();
+    
+    /// Helper to compute relative path from a base directory to preserve subfolder structure
+    fn get_relative_subpath(entry_path: &Path, base_path: &Path) -> Option<PathBuf> {
+        entry_path.parent()
+            .and_then(|parent| parent.strip_prefix(base_path).ok())
+            .map(|rel| rel.to_path_buf())
+            .filter(|rel| !rel.as_os_str().is_empty())
+    }
+    
+    /// Helper to ensure destination directory exists and return the full destination path
+    fn prepare_dest_with_subfolders(
+        entry_path: &Path,
+        base_path: &Path,
+        output_dir: &Path,
+        window: &Window,
+    ) -> Result<PathBuf, String> {
+        let file_name = entry_path.file_name().unwrap();
+        
+        if let Some(rel_subpath) = get_relative_subpath(entry_path, base_path) {
+            let dest_subdir = output_dir.join(&rel_subpath);
+            if !dest_subdir.exists() {
+                std::fs::create_dir_all(&dest_subdir)
+                    .map_err(|e| format!("Failed to create subfolder '{}': {}", rel_subpath.display(), e))?;
+                info!("[QuickOrganize] Created subfolder: {}", rel_subpath.display());
+                let _ = window.emit("install_log", format!("[QuickOrganize] Created subfolder: {}", rel_subpath.display()));
+            }
+            Ok(dest_subdir.join(file_name))
+        } else {
+            Ok(output_dir.join(file_name))
+        }
+    }
+    
+    /// Helper to get the destination directory for IoStore bundles with subfolder preservation
+    fn get_iostore_dest_dir(
+        entry_path: &Path,
+        base_path: &Path,
+        output_dir: &Path,
+        window: &Window,
+    ) -> Result<PathBuf, String> {
+        if let Some(rel_subpath) = get_relative_subpath(entry_path, base_path) {
+            let dest_subdir = output_dir.join(&rel_subpath);
+            if !dest_subdir.exists() {
+                std::fs::create_dir_all(&dest_subdir)
+                    .map_err(|e| format!("Failed to create subfolder '{}': {}", rel_subpath.display(), e))?;
+                info!("[QuickOrganize] Created subfolder: {}", rel_subpath.display());
+                let _ = window.emit("install_log", format!("[QuickOrganize] Created subfolder: {}", rel_subpath.display()));
+            }
+            Ok(dest_subdir)
+        } else {
+            Ok(output_dir.to_path_buf())
+        }
+    }
+    
+    for path_str in paths {
+        let path = PathBuf::from(&path_str);
+        
+        if !path.exists() {
+            warn!("[QuickOrganize] Path does not exist: {}", path_str);
+            report.skipped.push(path_str);
+            continue;
+        }
+        
+        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+        
+        // Handle archives - extract and copy contents preserving subfolder structure
+        if ext == "zip" || ext == "rar" || ext == "7z" {
+            let _ = window.emit("install_log", format!("[QuickOrganize] Extracting archive: {}", path.file_name().unwrap_or_default().to_string_lossy()));
+            
+            let temp_dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            let temp_path = temp_dir.path();
+            let temp_path_str = temp_path.to_str().unwrap();
+            
+            // Extract archive
+            let progress_window = window.clone();
+            let on_progress = move |progress: ArchiveExtractProgress| {
+                let _ = progress_window.emit("archive_extract_progress", &progress);
+            };
+            let extract_result = if ext == "zip" {
+                extract_zip_with_progress(path.to_str().unwrap(), temp_path_str, &on_progress, None)
+            } else if ext == "rar" {
+                extract_rar_with_progress(path.to_str().unwrap(), temp_path_str, &on_progress, None)
+            } else {
+                extract_7z_with_progress(path.to_str().unwrap(), temp_path_str, &on_progress, None)
+            };
+            
+            if let Err(e) = extract_result {
+                error!("[QuickOrganize] Failed to extract archive: {}", e);
+                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: Failed to extract archive: {}", e));
+                report.errors.push((path_str, format!("Failed to extract archive: {}", e)));
+                continue;
+            }
+            
+            // Find and copy all pak/utoc/ucas files from extracted content with subfolder preservation
+            let mut processed_utocs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+            
+            for entry in WalkDir::new(temp_path).into_iter().filter_map(|e| e.ok()) {
+                let entry_path = entry.path();
+                if let Some(entry_ext) = entry_path.extension().and_then(|s| s.to_str()) {
+                    if entry_ext == "pak" {
+                        // Prepare destination with subfolder structure
+                        let dest = match prepare_dest_with_subfolders(entry_path, temp_path, &output_dir, &window) {
+                            Ok(d) => d,
+                            Err(e) => {
+                                error!("[QuickOrganize] {}", e);
+                                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                                report.errors.push((entry_path.display().to_string(), e));
+                                continue;
+                            }
+                        };
+                        
+                        if let Err(e) = std::fs::copy(entry_path, &dest) {
+                            error!("[QuickOrganize] Failed to copy {}: {}", entry_path.file_name().unwrap().to_string_lossy(), e);
+                            report.errors.push((entry_path.file_name().unwrap().to_string_lossy().to_string(), e.to_string()));
+                        } else {
+                            let rel_dest = dest.strip_prefix(&output_dir).unwrap_or(&dest);
+                            info!("[QuickOrganize] Copied: {}", rel_dest.display());
+                            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", rel_dest.display()));
+                            report.copied.push(rel_dest.display().to_string());
+                            if recompress_on_organize {
+                                recompress_organized_pak(&dest, &window, &mut report);
+                            }
+                            if strip_mipmaps && !dest.with_extension("utoc").exists() {
+                                strip_organized_mipmaps(&dest, iostore_version, &window, &mut report);
+                            }
+                        }
+                    } else if entry_ext == "utoc" {
+                        // Process IoStore with compression check, preserving subfolder structure
+                        let ucas_path = entry_path.with_extension("ucas");
+                        if ucas_path.exists() && !processed_utocs.contains(entry_path) {
+                            processed_utocs.insert(entry_path.to_path_buf());
+                            
+                            // Determine destination directory with subfolder preservation
+                            let dest_dir = match get_iostore_dest_dir(entry_path, temp_path, &output_dir, &window) {
+                                Ok(d) => d,
+                                Err(e) => {
+                                    error!("[QuickOrganize] {}", e);
+                                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                                    report.errors.push((entry_path.display().to_string(), e));
+                                    continue;
+                                }
                             };
-                            let _ = window.emit("install_log", format!("[Detection] Texture result: {}", has_texture));
                             
-                            let summary = format!("[Detection] PAK file results: texture={} (mesh fixes automatic)", has_texture);
-                            info!("{}", summary);
-                            let _ = window.emit("install_log", &summary);
+                            if let Err(e) = copy_iostore_with_compression_check(entry_path, &dest_dir, iostore_version, &window, &mut report) {
+                                error!("[QuickOrganize] Failed to process IoStore: {}", e);
+                                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                                report.errors.push((entry_path.display().to_string(), e));
+                            } else if strip_mipmaps {
+                                strip_organized_mipmaps(&dest_dir.join(entry_path.file_name().unwrap()), iostore_version, &window, &mut report);
+                            }
+                        }
+                    }
+                    // Skip .ucas files - they're handled together with .utoc
+                }
+            }
+        }
+        // Handle pak files (and their iostore companions) - no subfolder structure for single files
+        else if ext == "pak" {
+            let file_name = path.file_name().unwrap();
+            let dest = output_dir.join(file_name);
+            
+            // Copy the pak file
+            if let Err(e) = std::fs::copy(&path, &dest) {
+                error!("[QuickOrganize] Failed to copy {}: {}", file_name.to_string_lossy(), e);
+                report.errors.push((file_name.to_string_lossy().to_string(), e.to_string()));
+                continue;
+            }
+            
+            info!("[QuickOrganize] Copied: {}", file_name.to_string_lossy());
+            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", file_name.to_string_lossy()));
+            report.copied.push(file_name.to_string_lossy().to_string());
+            if recompress_on_organize && !path.with_extension("utoc").exists() {
+                recompress_organized_pak(&dest, &window, &mut report);
+            }
+            if strip_mipmaps && !path.with_extension("utoc").exists() {
+                strip_organized_mipmaps(&dest, iostore_version, &window, &mut report);
+            }
+            
+            // Also handle utoc and ucas if they exist (IoStore package)
+            let utoc_path = path.with_extension("utoc");
+            let ucas_path = path.with_extension("ucas");
+            
+            if utoc_path.exists() && ucas_path.exists() {
+                if let Err(e) = copy_iostore_with_compression_check(&utoc_path, &output_dir, iostore_version, &window, &mut report) {
+                    error!("[QuickOrganize] Failed to process IoStore: {}", e);
+                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                    report.errors.push((utoc_path.display().to_string(), e));
+                } else if strip_mipmaps {
+                    strip_organized_mipmaps(&output_dir.join(utoc_path.file_name().unwrap()), iostore_version, &window, &mut report);
+                }
+            } else if utoc_path.exists() {
+                let utoc_name = utoc_path.file_name().unwrap();
+                if let Err(e) = std::fs::copy(&utoc_path, output_dir.join(utoc_name)) {
+                    error!("[QuickOrganize] Failed to copy {}: {}", utoc_name.to_string_lossy(), e);
+                    report.errors.push((utoc_name.to_string_lossy().to_string(), e.to_string()));
+                } else {
+                    report.copied.push(utoc_name.to_string_lossy().to_string());
+                }
+            }
+        }
+        // Handle utoc files directly (IoStore bundle without .pak or with .bak_repak)
+        else if ext == "utoc" {
+            let ucas_path = path.with_extension("ucas");
+            if ucas_path.exists() {
+                if let Err(e) = copy_iostore_with_compression_check(&path, &output_dir, iostore_version, &window, &mut report) {
+                    error!("[QuickOrganize] Failed to process IoStore: {}", e);
+                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                    report.errors.push((path_str, e));
+                } else if strip_mipmaps {
+                    strip_organized_mipmaps(&output_dir.join(path.file_name().unwrap()), iostore_version, &window, &mut report);
+                }
+            } else {
+                warn!("[QuickOrganize] Skipping {}: no matching .ucas", path_str);
+                report.skipped.push(path_str);
+            }
+        }
+        // Handle directories - copy all pak/utoc/ucas files preserving subfolder structure
+        else if path.is_dir() {
+            let mut processed_utocs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+            
+            for entry in WalkDir::new(&path).into_iter().filter_map(|e| e.ok()) {
+                let entry_path = entry.path();
+                if let Some(entry_ext) = entry_path.extension().and_then(|s| s.to_str()) {
+                    if entry_ext == "pak" {
+                        // Prepare destination with subfolder structure
+                        let dest = match prepare_dest_with_subfolders(entry_path, &path, &output_dir, &window) {
+                            Ok(d) => d,
+                            Err(e) => {
+                                error!("[QuickOrganize] {}", e);
+                                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                                report.errors.push((entry_path.display().to_string(), e));
+                                continue;
+                            }
+                        };
+                        
+                        if let Err(e) = std::fs::copy(entry_path, &dest) {
+                            error!("[QuickOrganize] Failed to copy {}: {}", entry_path.file_name().unwrap().to_string_lossy(), e);
+                            report.errors.push((entry_path.file_name().unwrap().to_string_lossy().to_string(), e.to_string()));
+                        } else {
+                            let rel_dest = dest.strip_prefix(&output_dir).unwrap_or(&dest);
+                            info!("[QuickOrganize] Copied: {}", rel_dest.display());
+                            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", rel_dest.display()));
+                            report.copied.push(rel_dest.display().to_string());
+                            if recompress_on_organize {
+                                recompress_organized_pak(&dest, &window, &mut report);
+                            }
+                            if strip_mipmaps && !dest.with_extension("utoc").exists() {
+                                strip_organized_mipmaps(&dest, iostore_version, &window, &mut report);
+                            }
+                        }
+                    } else if entry_ext == "utoc" {
+                        // Process IoStore with compression check, preserving subfolder structure
+                        let ucas_path = entry_path.with_extension("ucas");
+                        if ucas_path.exists() && !processed_utocs.contains(entry_path) {
+                            processed_utocs.insert(entry_path.to_path_buf());
                             
-                            // Check if files contain uassets
-                            use crate::install_mod::contains_uasset_files;
-                            let has_uassets = contains_uasset_files(&files);
+                            // Determine destination directory with subfolder preservation
+                            let dest_dir = match get_iostore_dest_dir(entry_path, &path, &output_dir, &window) {
+                                Ok(d) => d,
+                                Err(e) => {
+                                    error!("[QuickOrganize] {}", e);
+                                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                                    report.errors.push((entry_path.display().to_string(), e));
+                                    continue;
+                                }
+                            };
                             
-                            // Push this PAK mod and continue processing other files
-                            mods.push(InstallableModInfo {
-                                mod_name,
-                                mod_type,
-                                is_dir: false,
-                                path: path_str,
-                                auto_fix_texture: has_texture,
-                                auto_fix_serialize_size: false, // Mesh fixes are automatic
-                                auto_to_repak: !is_iostore,  // Don't repak IoStore packages
-                                contains_uassets: has_uassets,
-                            });
-                            continue; // Continue to next file instead of returning
+                            if let Err(e) = copy_iostore_with_compression_check(entry_path, &dest_dir, iostore_version, &window, &mut report) {
+                                error!("[QuickOrganize] Failed to process IoStore: {}", e);
+                                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
+                                report.errors.push((entry_path.display().to_string(), e));
+                            } else if strip_mipmaps {
+                                strip_organized_mipmaps(&dest_dir.join(entry_path.file_name().unwrap()), iostore_version, &window, &mut report);
+                            }
+                        }
+                    }
+                    // Skip .ucas files - they're handled together with .utoc
+                }
+            }
+        }
+        else {
+            warn!("[QuickOrganize] Skipping unsupported file: {}", path_str);
+            report.skipped.push(path_str);
+        }
+    }
+    
+    let _ = window.emit("install_log", format!(
+        "[QuickOrganize] Done! Copied {} file(s), recompressed {}, stripped mipmaps in {}, skipped {}, {} error(s)",
+        report.copied.len(), report.recompressed.len(), report.mipmaps_stripped.len(), report.skipped.len(), report.errors.len()
+    ));
+    info!("[QuickOrganize] Completed: {} files copied to {}", report.copied.len(), output_dir.display());
+    
+    Ok(report)
+}
+
+#[tauri::command]
+async fn install_mods(
+    mut mods: Vec<ModToInstall>,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    // Reject install folders that would write outside ~mods before doing any work
+    for mod_to_install in mods.iter_mut() {
+        mod_to_install.install_subfolder = crate::install_mod::sanitize_subfolder(&mod_to_install.install_subfolder)?;
+    }
+
+    let state_guard = state.read().await;
+    let mod_directory = state_guard.game_path.clone();
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    let keep_extracted = state_guard.keep_extracted;
+    let auto_strip_mipmaps = state_guard.auto_strip_mipmaps;
+    drop(state_guard);
+
+    // Refuse up front rather than run out of space halfway and leave a broken mod behind
+    let source_bytes: u64 = mods.iter().map(|m| source_content_size(Path::new(&m.path))).sum();
+    let required = required_install_space(source_bytes);
+    match disk_space_for(&mod_directory) {
+        Some(space) if space.free_bytes < required => {
+            let error_msg = format!(
+                "Not enough free space on {}: the install needs about {} MB but only {} MB are free",
+                space.mount_point,
+                required / (1024 * 1024),
+                space.free_bytes / (1024 * 1024)
+            );
+            error!("[Install] {}", error_msg);
+            let _ = window.emit("install_log", format!("ERROR: {}", error_msg));
+            toast_events::emit_installation_failed(&window, &error_msg);
+            return Err(error_msg);
+        }
+        Some(_) => {}
+        None => warn!("[Install] Could not read free space for {}, skipping the check", mod_directory.display()),
+    }
+
+    // Propagate USMAP path to UAssetTool via environment for UAssetAPI-based processing (from roaming folder)
+    if !usmap_filename.is_empty() {
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
+            info!(
+                "Set USMAP_PATH for UAssetTool: {}",
+                usmap_full_path.display()
+            );
+        } else {
+            let expected_path = usmap_dir().join(&usmap_filename);
+            error!(
+                "USMAP file not found at expected path for UAssetTool: {}",
+                expected_path.display()
+            );
+        }
+    }
+
+    if !mod_directory.exists() {
+        std::fs::create_dir_all(&mod_directory)
+            .map_err(|e| format!("Failed to create mods directory: {}", e))?;
+    }
+
+    // Convert paths to properly initialized InstallableMods
+    use crate::install_mod::map_paths_to_mods_with_skipped;
+
+    let paths: Vec<PathBuf> = mods.iter().map(|m| PathBuf::from(&m.path)).collect();
+
+    // Log the paths we're trying to install
+    for p in &paths {
+        info!("[Install] Processing path: {}", p.display());
+        let _ = window.emit("install_log", format!("[Install] Processing path: {}", p.display()));
+    }
+
+    let kept_root = extraction_dir();
+    let (mut installable_mods, skipped) = map_paths_to_mods_with_skipped(
+        &paths,
+        if keep_extracted { Some(kept_root.as_path()) } else { None },
+    );
+    if !skipped.is_empty() {
+        for (path, reason) in &skipped {
+            warn!("[Install] Skipping {}: {}", path.display(), reason);
+            let _ = window.emit("install_log", format!("WARNING: Skipping {}: {}", path.display(), reason));
+        }
+        let summary = format!("Couldn't read {} of {} files", skipped.len(), paths.len());
+        let _ = window.emit("install_log", format!("WARNING: {}", summary));
+        let skipped_paths: Vec<String> = skipped.iter().map(|(p, _)| p.to_string_lossy().to_string()).collect();
+        let _ = window.emit("install_skipped_files", &skipped_paths);
+        toast_events::emit_toast(&window, toast_events::ToastPayload::warning("Some Files Skipped", summary));
+        // Keep the per-mod settings below aligned with the mods that were actually read
+        mods.retain(|m| !skipped.iter().any(|(p, _)| p == Path::new(&m.path)));
+    }
+    if keep_extracted {
+        if let Some(dir) = crate::install_mod::install_mod_logic::archives::last_kept_extraction() {
+            let _ = window.emit("install_log", format!("[Install] Extracted archive contents kept at: {}", dir.display()));
+        }
+    }
+
+    // Check if we actually have mods to install
+    if installable_mods.is_empty() {
+        error!("[Install] No valid mods found from {} input path(s)", paths.len());
+        let _ = window.emit("install_log", "ERROR: No valid mods found to install!");
+        let _ = window.emit("install_log", "Possible causes:");
+        let _ = window.emit("install_log", "  - PAK file couldn't be read (wrong AES key or corrupted)");
+        let _ = window.emit("install_log", "  - Archive contains no .pak files or content folders");
+        let _ = window.emit("install_log", "  - Directory contains no valid content");
+        let error_msg = "No valid mods found to install. Check the install logs for details.";
+        toast_events::emit_installation_failed(&window, error_msg);
+        return Err(error_msg.to_string());
+    }
+
+    // Apply user settings to each mod
+    for (idx, mod_to_install) in mods.iter().enumerate() {
+        if let Some(installable) = installable_mods.get_mut(idx) {
+            // Apply custom name if provided
+            if let Some(ref custom) = mod_to_install.custom_name {
+                if !custom.is_empty() {
+                    installable.mod_name = custom.clone();
+                }
+            }
+
+            // Apply fix settings (mesh patching is handled automatically by UAssetTool).
+            // fix_textures still holds the auto-detection here, which the global default keeps on.
+            let detected_textures = installable.fix_textures;
+            installable.fix_textures = mod_to_install.fix_texture
+                || (auto_strip_mipmaps && auto_strip_mipmaps_applies(&installable.mod_type, installable.contains_uassets, detected_textures));
+            if installable.fix_textures && !mod_to_install.fix_texture {
+                let _ = window.emit("install_log", format!("[Install] Auto-stripping mipmaps for {} (global setting)", installable.mod_name));
+            }
+            installable.fix_serialsize_header = mod_to_install.fix_serialize_size;
+            installable.repak = mod_to_install.to_repak;
+            installable.force_legacy_pak = mod_to_install.force_legacy;
+            installable.install_subfolder = mod_to_install.install_subfolder.clone();
+            installable.install_disabled = mod_to_install.install_disabled;
+            installable.is_patch = mod_to_install.is_patch;
+            installable.preserve_source_compression = mod_to_install.preserve_compression;
+            if let Some(ref mount_point) = mod_to_install.mount_point {
+                if !mount_point.trim().is_empty() {
+                    if !installable.writes_legacy_pak() {
+                        let error_msg = format!(
+                            "A custom mount point only applies to legacy PAK output. Enable Force Legacy PAK for {} or clear the mount point.",
+                            installable.mod_name
+                        );
+                        toast_events::emit_installation_failed(&window, &error_msg);
+                        return Err(error_msg);
+                    }
+                    installable.mount_point = mount_point.trim().to_string();
+                }
+            }
+            installable.usmap_path = usmap_filename.clone();
+            // Apply parallel processing setting from app state
+            installable.parallel_processing = parallel_processing;
+            // Apply obfuscation setting from app state
+            installable.obfuscate = obfuscate;
+            installable.iostore_version = iostore_version;
+        }
+    }
+
+    spawn_install(installable_mods, mod_directory, window);
+    Ok(())
+}
+
+/// Install the files of a verified P2P transfer from their staging folder with default settings,
+/// naming a single received mod after the sender's pack and tagging it with the sender's tags
+fn install_received_mods(
+    staging: tempfile::TempDir,
+    pack: &p2p_sharing::ShareableModPack,
+    tags: &[String],
+    mod_directory: &Path,
+    window: &Window,
+) {
+    use crate::install_mod::map_paths_to_mods_with_skipped;
+
+    let state = window.state::<Arc<RwLock<AppState>>>();
+    let state_guard = state.blocking_read();
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    drop(state_guard);
+
+    if !usmap_filename.is_empty() {
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
+        }
+    }
+
+    let paths: Vec<PathBuf> = pack.mods.iter().map(|m| staging.path().join(&m.filename)).collect();
+    info!("[P2P] Auto-installing {} received mod(s) from '{}'", paths.len(), pack.name);
+    let _ = window.emit("install_log", format!("[P2P] Auto-installing {} received mod(s) from '{}'", paths.len(), pack.name));
+
+    let (mut installable_mods, skipped) = map_paths_to_mods_with_skipped(&paths, None);
+    for (path, reason) in &skipped {
+        warn!("[P2P] Skipping received file {}: {}", path.display(), reason);
+        let _ = window.emit("install_log", format!("WARNING: Skipping {}: {}", path.display(), reason));
+    }
+    if installable_mods.is_empty() {
+        let error_msg = "No valid mods found in the received files";
+        let _ = window.emit("install_log", format!("ERROR: {}", error_msg));
+        toast_events::emit_installation_failed(window, error_msg);
+        return;
+    }
+
+    // Keep the staged files around until the install thread is done with them
+    let staging_guard = Arc::new(staging);
+    let single = installable_mods.len() == 1;
+    for installable in installable_mods.iter_mut() {
+        if single && !pack.name.trim().is_empty() {
+            installable.mod_name = pack.name.trim().to_string();
+        }
+        installable.custom_tags = tags.to_vec();
+        installable.usmap_path = usmap_filename.clone();
+        installable.parallel_processing = parallel_processing;
+        installable.obfuscate = obfuscate;
+        installable.iostore_version = iostore_version;
+        if installable.temp_dir.is_none() {
+            installable.temp_dir = Some(staging_guard.clone());
+        }
+    }
+    drop(staging_guard);
+
+    spawn_install(installable_mods, mod_directory.to_path_buf(), window.clone());
+}
+
+/// Run the install pipeline on a background thread, streaming `install_log`/`install_stage`
+/// events and polling `install_progress` until `install_complete`
+fn spawn_install(
+    mut installable_mods: Vec<crate::install_mod::InstallableMod>,
+    mod_directory: PathBuf,
+    window: Window,
+) {
+    use std::sync::atomic::{AtomicI32, AtomicBool};
+    use std::sync::Arc as StdArc;
+
+    // Use existing installation logic
+    let installed_counter = StdArc::new(AtomicI32::new(0));
+    let stop_flag = StdArc::new(AtomicBool::new(false));
+
+    let total = installable_mods.len() as i32;
+    let counter_clone = installed_counter.clone();
+    let _stop_clone = stop_flag.clone();
+    let window_clone = window.clone();
+    
+    // Spawn installation thread
+    let window_for_logs = window.clone();
+    std::thread::spawn(move || {
+        use std::panic;
+        
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            window_for_logs.emit("install_log", "Starting installation...").ok();
+            window_for_logs.emit("install_log", format!("Installing {} mod(s)", installable_mods.len())).ok();
+            
+            for (idx, imod) in installable_mods.iter().enumerate() {
+                window_for_logs.emit("install_log", format!("[{}/{}] Mod: {}", idx + 1, installable_mods.len(), imod.mod_name)).ok();
+                window_for_logs.emit("install_log", format!("  - Fix Textures: {}", imod.fix_textures)).ok();
+                window_for_logs.emit("install_log", format!("  - Fix SerializeSize: {}", imod.fix_serialsize_header)).ok();
+                window_for_logs.emit("install_log", format!("  - Repak: {}", imod.repak)).ok();
+                window_for_logs.emit("install_log", format!("  - Force Legacy PAK: {}", imod.force_legacy_pak)).ok();
+                window_for_logs.emit("install_log", format!("  - Mount Point: {}", imod.mount_point)).ok();
+                window_for_logs.emit("install_log", format!("  - Install Disabled: {}", imod.install_disabled)).ok();
+                window_for_logs.emit("install_log", format!("  - Patch Pak: {}", imod.is_patch)).ok();
+                window_for_logs.emit("install_log", format!(
+                    "  - Compression: {}{}",
+                    describe_compression(&imod.output_compression()),
+                    if imod.preserve_source_compression { " (from source, legacy PAK output)" } else { "" }
+                )).ok();
+            }
+            
+            window_for_logs.emit("install_log", "Calling installation logic...").ok();
+            window_for_logs.emit("install_log", format!("Mod directory: {}", mod_directory.display())).ok();
+            
+            use crate::install_mod::install_mod_logic::install_mods_in_viewport;
+            
+            window_for_logs.emit("install_log", "Entering install_mods_in_viewport...").ok();
+            
+            // Log each mod's path before processing
+            for (idx, m) in installable_mods.iter().enumerate() {
+                window_for_logs.emit("install_log", format!("  Mod {} path exists: {}", idx, m.mod_path.exists())).ok();
+                window_for_logs.emit("install_log", format!("  Mod {} path: {}", idx, m.mod_path.display())).ok();
+            }
+            
+            let stage_window = window_for_logs.clone();
+            let outcomes = install_mods_in_viewport(
+                &mut installable_mods,
+                &mod_directory,
+                &installed_counter,
+                &stop_flag,
+                &move |stage| {
+                    stage_window.emit("install_stage", &stage).ok();
+                },
+            );
+            window_for_logs.emit("install_log", "Exited install_mods_in_viewport").ok();
+            outcomes.iter()
+                .filter(|o| matches!(o, crate::install_mod::install_mod_logic::ModInstallOutcome::Failed))
+                .count()
+        }));
+        
+        match result {
+            Ok(0) => {
+                window_for_logs.emit("install_log", "Installation completed successfully!").ok();
+            }
+            Ok(failed) => {
+                window_for_logs.emit("install_log", format!("Installation finished, {} mod(s) failed", failed)).ok();
+            }
+            Err(e) => {
+                let msg = if let Some(s) = e.downcast_ref::<&str>() {
+                    format!("PANIC: {}", s)
+                } else if let Some(s) = e.downcast_ref::<String>() {
+                    format!("PANIC: {}", s)
+                } else {
+                    "PANIC: Unknown error".to_string()
+                };
+                window_for_logs.emit("install_log", &msg).ok();
+                toast_events::emit_installation_failed(&window_for_logs, &msg);
+                error!("Installation thread panicked!");
+            }
+        }
+    });
+    
+    // Monitor progress
+    std::thread::spawn(move || {
+        loop {
+            let current = counter_clone.load(std::sync::atomic::Ordering::SeqCst);
+            if current == -255 {
+                window_clone.emit("install_complete", ()).ok();
+                break;
+            }
+            let progress = (current as f32 / total as f32) * 100.0;
+            window_clone.emit("install_progress", progress).ok();
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    });
+}
+
+/// Guess a downloaded file's type from its magic bytes, returning the extension
+/// `map_paths_to_mods` expects for it
+fn detect_download_extension(path: &Path) -> Option<&'static str> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = File::open(path).ok()?;
+    let mut head = [0u8; 8];
+    let read = file.read(&mut head).ok()?;
+    let head = &head[..read];
+
+    if head.starts_with(b"PK\x03\x04") {
+        return Some("zip");
+    }
+    if head.starts_with(b"Rar!\x1a\x07") {
+        return Some("rar");
+    }
+    if head.starts_with(&[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C]) {
+        return Some("7z");
+    }
+
+    // Paks have no header magic; the footer (within the last few hundred bytes) holds 0x5A6F12E1
+    let len = file.metadata().ok()?.len();
+    let tail_len = len.min(512);
+    file.seek(SeekFrom::Start(len - tail_len)).ok()?;
+    let mut tail = vec![0u8; tail_len as usize];
+    file.read_exact(&mut tail).ok()?;
+    if tail.windows(4).any(|w| w == 0x5A6F12E1u32.to_le_bytes()) {
+        return Some("pak");
+    }
+    None
+}
+
+/// Download a mod from a direct URL and install it through the normal pipeline.
+/// The file type is detected from its content, not the URL.
+#[tauri::command]
+async fn install_mod_from_url(
+    url: String,
+    install_subfolder: String,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    use crate::install_mod::map_paths_to_mods;
+    use tokio::io::AsyncWriteExt;
+
+    info!("install_mod_from_url called: {}", url);
+    let install_subfolder = crate::install_mod::sanitize_subfolder(&install_subfolder)?;
+    let parsed = url::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err("Only http(s) URLs are supported".to_string());
+    }
+
+    // Name the download after the last URL segment so the installed mod gets a sensible name
+    let file_stem = parsed.path_segments()
+        .and_then(|mut segments| segments.next_back().map(|s| s.to_string()))
+        .map(|s| urlencoding::decode(&s).map(|d| d.into_owned()).unwrap_or(s))
+        .and_then(|s| Path::new(&s).file_stem().map(|st| st.to_string_lossy().to_string()))
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "DownloadedMod".to_string());
+
+    let download_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let partial_path = download_dir.path().join(format!("{}.download", file_stem));
+
+    let _ = window.emit("install_log", format!("[URL] Downloading {}", url));
+    let client = reqwest::Client::builder()
+        .connect_timeout(UPDATE_CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let mut response = client.get(parsed)
+        .header("User-Agent", "RepakX")
+        .send()
+        .await
+        .map_err(|e| format!("Download request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed with status: {}", response.status()));
+    }
+
+    let total_size = response.content_length();
+    let mut downloaded: u64 = 0;
+    let mut last_emitted_pct: i32 = -1;
+    let mut file = tokio::fs::File::create(&partial_path)
+        .await
+        .map_err(|e| format!("Failed to create download file: {}", e))?;
+
+    while let Some(chunk) = tokio::time::timeout(UPDATE_CHUNK_TIMEOUT, response.chunk())
+        .await
+        .map_err(|_| format!("Download stalled: no data received for {} seconds", UPDATE_CHUNK_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Download stream error: {}", e))?
+    {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write chunk: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        if let Some(total) = total_size {
+            let pct = ((downloaded as f32 / total as f32) * 100.0) as i32;
+            if pct > last_emitted_pct {
+                last_emitted_pct = pct;
+                let _ = window.emit("install_progress", pct as f32);
+            }
+        }
+    }
+    file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
+    drop(file);
+    let _ = window.emit("install_log", format!("[URL] Downloaded {} bytes", downloaded));
+
+    let ext = detect_download_extension(&partial_path).ok_or_else(|| {
+        let msg = "Downloaded file is not a recognised mod (.pak, .zip, .rar or .7z)".to_string();
+        toast_events::emit_installation_failed(&window, &msg);
+        msg
+    })?;
+    let download_path = download_dir.path().join(format!("{}.{}", file_stem, ext));
+    std::fs::rename(&partial_path, &download_path)
+        .map_err(|e| format!("Failed to rename download: {}", e))?;
+    let _ = window.emit("install_log", format!("[URL] Detected file type: .{}", ext));
+
+    let state_guard = state.read().await;
+    let mod_directory = state_guard.game_path.clone();
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    let keep_extracted = state_guard.keep_extracted;
+    drop(state_guard);
+
+    if !usmap_filename.is_empty() {
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
+        }
+    }
+    if !mod_directory.exists() {
+        std::fs::create_dir_all(&mod_directory)
+            .map_err(|e| format!("Failed to create mods directory: {}", e))?;
+    }
+
+    let kept_root = extraction_dir();
+    let mut installable_mods = map_paths_to_mods(
+        &[download_path],
+        if keep_extracted { Some(kept_root.as_path()) } else { None },
+    );
+    if installable_mods.is_empty() {
+        let error_msg = "No valid mods found in the downloaded file";
+        let _ = window.emit("install_log", format!("ERROR: {}", error_msg));
+        toast_events::emit_installation_failed(&window, error_msg);
+        return Err(error_msg.to_string());
+    }
+
+    // Keep the downloaded file around until the install thread is done with it
+    let download_guard = Arc::new(download_dir);
+    for installable in installable_mods.iter_mut() {
+        installable.install_subfolder = install_subfolder.clone();
+        installable.usmap_path = usmap_filename.clone();
+        installable.parallel_processing = parallel_processing;
+        installable.obfuscate = obfuscate;
+        installable.iostore_version = iostore_version;
+        if installable.temp_dir.is_none() {
+            installable.temp_dir = Some(download_guard.clone());
+        }
+    }
+    drop(download_guard);
+
+    spawn_install(installable_mods, mod_directory, window);
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_mod(path: String, window: Window) -> Result<(), String> {
+    let path_buf = PathBuf::from(&path);
+    log::info!("delete_mod called with path: {}", path);
+    
+    // Determine the actual file to delete - check both .pak and .bak_repak variants
+    let (actual_path, is_disabled) = if path_buf.exists() {
+        (path_buf.clone(), path.ends_with(".bak_repak"))
+    } else if path.ends_with(".pak") {
+        // The .pak file doesn't exist, check if there's a disabled version (.bak_repak)
+        let disabled_path = PathBuf::from(format!("{}.bak_repak", path.trim_end_matches(".pak")));
+        if disabled_path.exists() {
+            log::info!("Found disabled mod at: {:?}", disabled_path);
+            (disabled_path, true)
+        } else {
+            (path_buf.clone(), false)
+        }
+    } else if path.ends_with(".bak_repak") {
+        // The .bak_repak doesn't exist, check if there's an enabled version (.pak)
+        let enabled_path = PathBuf::from(format!("{}.pak", path.trim_end_matches(".pak.bak_repak")));
+        if enabled_path.exists() {
+            log::info!("Found enabled mod at: {:?}", enabled_path);
+            (enabled_path, false)
+        } else {
+            (path_buf.clone(), true)
+        }
+    } else {
+        (path_buf.clone(), false)
+    };
+    
+    log::info!("Attempting to delete: {:?} (is_disabled: {})", actual_path, is_disabled);
+    
+    // Try to delete the main file
+    if actual_path.exists() {
+        if let Err(e) = std::fs::remove_file(&actual_path) {
+            let error_msg = format!("Failed to delete mod file: {}", e);
+            toast_events::emit_delete_failed(&window, &error_msg);
+            return Err(error_msg);
+        }
+        log::info!("Deleted main mod file: {:?}", actual_path);
+    } else {
+        log::warn!("Main mod file does not exist: {:?}", actual_path);
+    }
+
+    // Determine the base path for IoStore files (always based on .pak name, not .bak_repak)
+    let base_pak_path = if is_disabled || path.ends_with(".bak_repak") {
+        // For disabled mods, derive the .pak base path
+        let path_str = if actual_path.to_string_lossy().ends_with(".bak_repak") {
+            actual_path.to_string_lossy().trim_end_matches(".bak_repak").to_string()
+        } else {
+            path.trim_end_matches(".pak.bak_repak").to_string()
+        };
+        PathBuf::from(format!("{}.pak", path_str))
+    } else {
+        actual_path.clone()
+    };
+    
+    log::info!("Base path for IoStore files: {:?}", base_pak_path);
+    
+    // Delete associated IoStore files (.ucas and .utoc)
+    let ucas_path = iostore_companion_path(&base_pak_path, "ucas");
+    if ucas_path.exists() {
+        if let Err(e) = std::fs::remove_file(&ucas_path) {
+            log::warn!("Failed to delete .ucas file: {}", e);
+        } else {
+            log::info!("Deleted associated .ucas file: {:?}", ucas_path);
+        }
+    }
+    
+    let utoc_path = iostore_companion_path(&base_pak_path, "utoc");
+    if utoc_path.exists() {
+        if let Err(e) = std::fs::remove_file(&utoc_path) {
+            log::warn!("Failed to delete .utoc file: {}", e);
+        } else {
+            log::info!("Deleted associated .utoc file: {:?}", utoc_path);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Set of installed mods whose containers are byte-identical
+#[derive(Debug, Clone, Serialize)]
+struct DuplicateGroup {
+    /// SHA-256 over the .pak/.utoc/.ucas hashes of each member
+    content_hash: String,
+    total_size: u64,
+    paths: Vec<String>,
+}
+
+/// Hash a mod's pak and IoStore companions into a single content hash
+fn hash_mod_containers(path: &Path) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(p2p_sharing::hash_file(path).map_err(|e| e.to_string())?);
+    for ext in ["utoc", "ucas"] {
+        let companion = path.with_extension(ext);
+        if companion.exists() {
+            hasher.update(ext);
+            hasher.update(p2p_sharing::hash_file(&companion).map_err(|e| e.to_string())?);
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Group installed mods that are byte-identical (same pak/utoc/ucas content),
+/// e.g. the same mod installed twice at different priorities or folders
+#[tauri::command]
+async fn find_duplicate_mods(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<DuplicateGroup>, String> {
+    use rayon::prelude::*;
+    use std::collections::HashMap;
+
+    info!("find_duplicate_mods called");
+    let mods = get_pak_files(Some(false), state).await?;
+
+    // Only mods with identical total size can be identical, so bucket by size before hashing
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for m in mods {
+        let total_size: u64 = ["utoc", "ucas"].iter()
+            .filter_map(|ext| std::fs::metadata(m.path.with_extension(ext)).ok())
+            .map(|meta| meta.len())
+            .sum::<u64>()
+            + std::fs::metadata(&m.path).map(|meta| meta.len()).unwrap_or(0);
+        by_size.entry(total_size).or_default().push(m.path);
+    }
+
+    let candidates: Vec<(u64, PathBuf)> = by_size.into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .flat_map(|(size, paths)| paths.into_iter().map(move |p| (size, p)))
+        .collect();
+
+    let hashed: Vec<(u64, String, PathBuf)> = candidates.into_par_iter()
+        .filter_map(|(size, path)| match hash_mod_containers(&path) {
+            Ok(hash) => Some((size, hash, path)),
+            Err(e) => {
+                warn!("Failed to hash {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect();
+
+    let mut by_hash: HashMap<String, (u64, Vec<String>)> = HashMap::new();
+    for (size, hash, path) in hashed {
+        by_hash.entry(hash).or_insert((size, Vec::new())).1.push(path.to_string_lossy().to_string());
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_hash.into_iter()
+        .filter(|(_, (_, paths))| paths.len() > 1)
+        .map(|(content_hash, (total_size, mut paths))| {
+            paths.sort();
+            DuplicateGroup { content_hash, total_size, paths }
+        })
+        .collect();
+    groups.sort_by(|a, b| a.paths[0].cmp(&b.paths[0]));
+
+    info!("Found {} duplicate group(s)", groups.len());
+    Ok(groups)
+}
+
+/// Delete redundant copies of a duplicated mod (and their IoStore companions),
+/// keeping `keep_path` and its metadata untouched. Returns the number of mods removed.
+#[tauri::command]
+async fn merge_duplicates(
+    keep_path: String,
+    remove_paths: Vec<String>,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    info!("merge_duplicates called: keep {}, remove {} mod(s)", keep_path, remove_paths.len());
+
+    let keep = PathBuf::from(&keep_path);
+    if !keep.exists() {
+        return Err(format!("Mod to keep does not exist: {}", keep_path));
+    }
+    if remove_paths.iter().any(|p| Path::new(p) == keep) {
+        return Err("The mod to keep is also listed for removal".to_string());
+    }
+
+    // Refuse to delete anything that isn't actually identical to the kept mod
+    let keep_hash = hash_mod_containers(&keep)?;
+    for path in &remove_paths {
+        if hash_mod_containers(Path::new(path))? != keep_hash {
+            return Err(format!("{} is not identical to the mod being kept", path));
+        }
+    }
+
+    let mut removed = 0;
+    for path in &remove_paths {
+        delete_mod(path.clone(), window.clone()).await?;
+        removed += 1;
+    }
+
+    let mut state = state.write().await;
+    let removed_set: std::collections::HashSet<PathBuf> = remove_paths.iter().map(PathBuf::from).collect();
+    state.mod_metadata.retain(|m| !removed_set.contains(&m.path));
+    save_state(&state).map_err(|e| e.to_string())?;
+
+    info!("Removed {} duplicate(s) of {}", removed, keep_path);
+    Ok(removed)
+}
+
+/// Result of an update_mod operation
+#[derive(Clone, Serialize, Deserialize)]
+struct UpdateModResult {
+    /// Path to the newly installed mod
+    new_mod_path: String,
+    /// Whether the old mod was successfully deleted
+    old_mod_deleted: bool,
+    /// The preserved metadata that was applied
+    preserved_enabled_state: bool,
+    preserved_folder: Option<String>,
+}
+
+/// Update (replace) an existing mod with new mod files.
+/// This preserves the mod's metadata (folder location, enabled state, custom name, tags)
+/// and replaces the old mod files with the new ones.
+/// 
+/// # Arguments
+/// * `old_mod_path` - Path to the existing mod to be replaced
+/// * `new_mod_source` - Path to the new mod files (can be .pak, .zip, .rar, .7z, or directory)
+/// * `preserve_name` - If true, keeps the old mod's name; if false, uses the new mod's name
+/// * `window` - Tauri window for emitting events
+/// * `state` - Application state
+#[tauri::command]
+async fn update_mod(
+    old_mod_path: String,
+    new_mod_source: String,
+    preserve_name: bool,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<UpdateModResult, String> {
+    info!("update_mod called: old={}, new={}, preserve_name={}", old_mod_path, new_mod_source, preserve_name);
+    
+    let old_path = PathBuf::from(&old_mod_path);
+    let new_source = PathBuf::from(&new_mod_source);
+    
+    // Validate new source exists
+    if !new_source.exists() {
+        let err = format!("New mod source does not exist: {}", new_mod_source);
+        toast_events::emit_installation_failed(&window, &err);
+        return Err(err);
+    }
+    
+    // ========================================================================
+    // Step 1: Gather metadata from the old mod
+    // ========================================================================
+    
+    // Determine the actual old mod path (handle .pak vs .bak_repak)
+    let (actual_old_path, was_disabled) = if old_path.exists() {
+        (old_path.clone(), old_mod_path.ends_with(".bak_repak") || old_mod_path.ends_with(".pak_disabled"))
+    } else if old_mod_path.ends_with(".pak") {
+        // Check for disabled versions
+        let bak_repak_path = PathBuf::from(format!("{}.bak_repak", old_mod_path.trim_end_matches(".pak")));
+        let pak_disabled_path = PathBuf::from(format!("{}_disabled", old_mod_path.trim_end_matches(".pak")));
+        if bak_repak_path.exists() {
+            (bak_repak_path, true)
+        } else if pak_disabled_path.exists() {
+            (pak_disabled_path, true)
+        } else {
+            return Err(format!("Old mod not found: {}", old_mod_path));
+        }
+    } else {
+        return Err(format!("Old mod not found: {}", old_mod_path));
+    };
+    
+    info!("Actual old mod path: {:?}, was_disabled: {}", actual_old_path, was_disabled);
+    
+    // Get the old mod's folder (subfolder within mods directory)
+    let game_path = {
+        let state_guard = state.read().await;
+        state_guard.game_path.clone()
+    };
+    
+    let install_subfolder = if let Some(parent) = actual_old_path.parent() {
+        if parent == game_path {
+            String::new() // Root folder
+        } else {
+            parent.strip_prefix(&game_path)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default()
+        }
+    } else {
+        String::new()
+    };
+    let install_subfolder = crate::install_mod::sanitize_subfolder(&install_subfolder)?;
+    
+    info!("Preserved install subfolder: {}", install_subfolder);
+    
+    // Get the old mod's custom name and tags from metadata
+    let (old_custom_name, old_custom_tags, old_folder_id, old_source_url, old_thumbnail_path) = {
+        let state_guard = state.read().await;
+        let metadata = state_guard.mod_metadata.iter()
+            .find(|m| {
+                m.path == actual_old_path || 
+                m.path.with_extension("pak") == actual_old_path ||
+                m.path.with_extension("bak_repak") == actual_old_path ||
+                m.path.with_extension("pak_disabled") == actual_old_path
+            });
+        
+        match metadata {
+            Some(m) => (m.custom_name.clone(), m.custom_tags.clone(), m.folder_id.clone(), m.source_url.clone(), m.thumbnail_path.clone()),
+            None => (None, Vec::new(), None, None, None),
+        }
+    };
+    
+    info!("Preserved metadata - custom_name: {:?}, tags: {:?}, folder_id: {:?}", 
+          old_custom_name, old_custom_tags, old_folder_id);
+    
+    // Get the old mod's base name (for naming the new mod if preserve_name is true)
+    let old_mod_name = actual_old_path.file_stem()
+        .and_then(|s| s.to_str())
+        .map(|s| {
+            // Strip .bak_repak or _disabled suffix if present
+            s.trim_end_matches(".bak_repak")
+             .trim_end_matches("_disabled")
+             .to_string()
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+    
+    // ========================================================================
+    // Step 2: Move the old mod files to a backup, restored if the new install fails
+    // ========================================================================
+    
+    info!("Backing up old mod files...");
+    std::fs::create_dir_all(update_backup_dir()).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    let backup_dir = tempfile::tempdir_in(update_backup_dir())
+        .map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    let mut backups: Vec<(PathBuf, PathBuf)> = Vec::new();
+    
+    // Associated IoStore files (.ucas and .utoc)
+    // Base path is always the .pak version
+    let base_pak_path = if was_disabled {
+        let path_str = actual_old_path.to_string_lossy();
+        let clean = path_str
+            .trim_end_matches(".bak_repak")
+            .trim_end_matches("_disabled");
+        if clean.ends_with(".pak") {
+            PathBuf::from(clean)
+        } else {
+            PathBuf::from(format!("{}.pak", clean))
+        }
+    } else {
+        actual_old_path.clone()
+    };
+    
+    let mut old_files = vec![actual_old_path.clone()];
+    for ext in &["ucas", "utoc"] {
+        let companion_path = iostore_companion_path(&base_pak_path, ext);
+        if companion_path.exists() {
+            old_files.push(companion_path);
+        }
+    }
+    for old_file in old_files {
+        let backup = backup_dir.path().join(old_file.file_name().unwrap_or_default());
+        if let Err(e) = move_file(&old_file, &backup) {
+            // Nothing is lost yet, put back what was moved and stop
+            restore_update_backups(&backups);
+            let err = format!("Failed to back up {}: {}", old_file.display(), e);
+            toast_events::emit_installation_failed(&window, &err);
+            return Err(err);
+        }
+        info!("Backed up {:?} to {:?}", old_file, backup);
+        backups.push((old_file, backup));
+    }
+    
+    // ========================================================================
+    // Step 3: Install the new mod
+    // ========================================================================
+    
+    info!("Installing new mod from: {:?}", new_source);
+    
+    // Determine the mod name to use
+    let mod_name = if preserve_name {
+        old_custom_name.clone().unwrap_or(old_mod_name.clone())
+    } else {
+        new_source.file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "NewMod".to_string())
+    };
+    
+    // Use the existing install_mods logic
+    use std::sync::atomic::{AtomicI32, AtomicBool};
+    use std::sync::Arc as StdArc;
+    use crate::install_mod::map_paths_to_mods;
+    
+    let state_guard = state.read().await;
+    let mod_directory = state_guard.game_path.clone();
+    let usmap_filename = state_guard.usmap_path.clone();
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    let keep_extracted = state_guard.keep_extracted;
+    drop(state_guard);
+    
+    // Set USMAP path
+    if !usmap_filename.is_empty() {
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
+        }
+    }
+    
+    let paths = vec![new_source.clone()];
+    let kept_root = extraction_dir();
+    let mut installable_mods = map_paths_to_mods(
+        &paths,
+        if keep_extracted { Some(kept_root.as_path()) } else { None },
+    );
+    
+    if installable_mods.is_empty() {
+        let err = rollback_update(&backups, backup_dir, &[], &old_mod_name, "Failed to parse new mod source - no valid mods found");
+        toast_events::emit_installation_failed(&window, &err);
+        return Err(err);
+    }
+    
+    // Apply settings to the installable mod
+    if let Some(installable) = installable_mods.get_mut(0) {
+        installable.mod_name = mod_name.clone();
+        installable.install_subfolder = install_subfolder.clone();
+        installable.usmap_path = usmap_filename;
+        installable.obfuscate = obfuscate;
+        installable.iostore_version = iostore_version;
+    }
+    
+    // Install synchronously for update operation (we need to know the result)
+    let installed_counter = StdArc::new(AtomicI32::new(0));
+    let stop_flag = StdArc::new(AtomicBool::new(false));
+    
+    let window_clone = window.clone();
+    window_clone.emit("install_log", format!("[Update] Replacing mod: {}", old_mod_name)).ok();
+    window_clone.emit("install_log", format!("[Update] New source: {}", new_mod_source)).ok();
+    
+    use crate::install_mod::install_mod_logic::{install_mods_in_viewport, ModInstallOutcome};
+    
+    let stage_window = window.clone();
+    let install_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        install_mods_in_viewport(
+            &mut installable_mods,
+            &mod_directory,
+            &installed_counter,
+            &stop_flag,
+            &move |stage| {
+                stage_window.emit("install_stage", &stage).ok();
+            },
+        )
+    }));
+    
+    // ========================================================================
+    // Step 4: Apply preserved metadata to the new mod
+    // ========================================================================
+    
+    // The install settles the final "_999..._P" name and reports where the pak went
+    let outcome = match &install_result {
+        Ok(outcomes) => outcomes.first().cloned().unwrap_or(ModInstallOutcome::Skipped),
+        Err(_) => ModInstallOutcome::Failed,
+    };
+    let new_mod_path = match outcome {
+        ModInstallOutcome::Installed(path) => path,
+        _ => {
+            let reason = if install_result.is_err() {
+                "the installer crashed".to_string()
+            } else {
+                format!("{} was not installed, check the install logs", installable_mods[0].mod_name)
+            };
+            // The old files were moved to the backup folder, so anything under the new name is
+            // this install's (possibly half a container) and goes before the old mod returns
+            let install_dir = if install_subfolder.is_empty() {
+                mod_directory.clone()
+            } else {
+                mod_directory.join(&install_subfolder)
+            };
+            let new_outputs: Vec<PathBuf> = ["pak", "utoc", "ucas", "bak_repak", "utoc_disabled", "ucas_disabled"].iter()
+                .map(|ext| install_dir.join(format!("{}.{}", installable_mods[0].mod_name, ext)))
+                .filter(|p| p.exists())
+                .collect();
+            let err = rollback_update(&backups, backup_dir, &new_outputs, &old_mod_name, &reason);
+            window.emit("install_log", format!("[Update] {}", err)).ok();
+            toast_events::emit_installation_failed(&window, &err);
+            return Err(err);
+        }
+    };
+    
+    info!("New mod path: {:?}", new_mod_path);
+    
+    // If the old mod was disabled, disable the new one too
+    if was_disabled && new_mod_path.exists() {
+        if let Err(e) = toggle_mod_file(&new_mod_path) {
+            warn!("Failed to disable new mod to match old state: {}", e);
+        } else {
+            info!("Disabled new mod to match old mod's state");
+        }
+    }
+    
+    // Update metadata with preserved tags and folder assignment
+    if !old_custom_tags.is_empty() || old_folder_id.is_some() || old_custom_name.is_some() || old_source_url.is_some() || old_thumbnail_path.is_some() {
+        let mut state_guard = state.write().await;
+        
+        // Find or create metadata entry for the new mod
+        let new_path_for_metadata = if was_disabled {
+            PathBuf::from(format!("{}.bak_repak", 
+                new_mod_path.to_string_lossy().trim_end_matches(".pak")))
+        } else {
+            new_mod_path.clone()
+        };
+        
+        // Remove old metadata entry if it exists
+        state_guard.mod_metadata.retain(|m| {
+            m.path != actual_old_path && 
+            m.path != old_path &&
+            m.path.with_extension("pak") != actual_old_path
+        });
+        
+        // Add new metadata entry with preserved data
+        state_guard.mod_metadata.push(ModMetadata {
+            path: new_path_for_metadata.clone(),
+            custom_name: if preserve_name { old_custom_name } else { Some(mod_name.clone()) },
+            folder_id: old_folder_id,
+            custom_tags: old_custom_tags,
+            source_url: old_source_url,
+            thumbnail_path: old_thumbnail_path,
+        });
+        
+        // Save state
+        if let Err(e) = save_state(&state_guard) {
+            warn!("Failed to save state after update: {}", e);
+        }
+    }
+    
+    // Emit success event
+    toast_events::emit_success(&window, "Mod Updated", format!("Successfully replaced mod: {}", mod_name));
+    window.emit("install_complete", ()).ok();
+    
+    info!("update_mod completed successfully");
+    
+    Ok(UpdateModResult {
+        new_mod_path: new_mod_path.to_string_lossy().to_string(),
+        old_mod_deleted: true,
+        preserved_enabled_state: was_disabled,
+        preserved_folder: if install_subfolder.is_empty() { None } else { Some(install_subfolder) },
+    })
+}
+
+/// Move a file, falling back to copy + delete when the destination is on another drive
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+/// Put backed up files back where they were, returning the ones that couldn't be restored
+fn restore_update_backups(backups: &[(PathBuf, PathBuf)]) -> Vec<String> {
+    let mut failed = Vec::new();
+    for (original, backup) in backups {
+        if let Err(e) = move_file(backup, original) {
+            error!("Failed to restore {:?} from {:?}: {}", original, backup, e);
+            failed.push(format!("{}: {}", original.display(), e));
+        }
+    }
+    failed
+}
+
+/// Undo a failed update_mod: remove the files the failed install wrote, restore the old mod's
+/// files (same names, so the same enabled state and metadata) and describe what happened for
+/// the returned error
+fn rollback_update(
+    backups: &[(PathBuf, PathBuf)],
+    backup_dir: tempfile::TempDir,
+    new_outputs: &[PathBuf],
+    old_mod_name: &str,
+    reason: &str,
+) -> String {
+    warn!("[Update] Install failed ({}), rolling back {}", reason, old_mod_name);
+    for output in new_outputs {
+        match std::fs::remove_file(output) {
+            Ok(()) => info!("[Update] Removed partial output {:?}", output),
+            Err(e) => warn!("[Update] Failed to remove partial output {:?}: {}", output, e),
+        }
+    }
+    let failed = restore_update_backups(backups);
+    if failed.is_empty() {
+        format!("Update failed: {}. The previous version of {} was restored.", reason, old_mod_name)
+    } else {
+        // Keep the backup around so nothing is lost
+        let kept = backup_dir.into_path();
+        format!(
+            "Update failed: {}. Restoring {} also failed ({}), its files are kept in {}",
+            reason, old_mod_name, failed.join("; "), kept.display()
+        )
+    }
+}
+
+#[tauri::command]
+async fn open_in_explorer(path: String) -> Result<(), String> {
+    let path_buf = PathBuf::from(&path);
+    
+    info!("open_in_explorer called: path={}", path);
+    
+    if !path_buf.exists() {
+        return Err(format!("Path does not exist: {}", path_buf.display()));
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        
+        // On Windows, use explorer.exe with /select, to highlight the file
+        // The path must be quoted if it contains spaces, and use backslashes
+        let canonical_path = path_buf.canonicalize()
+            .unwrap_or_else(|_| path_buf.clone());
+        
+        // Remove the \\?\ prefix that canonicalize adds on Windows
+        let path_str = canonical_path.to_string_lossy();
+        let clean_path = if path_str.starts_with(r"\\?\") {
+            path_str[4..].to_string()
+        } else {
+            path_str.to_string()
+        };
+        
+        info!("open_in_explorer: using path={}", clean_path);
+        
+        // Use /select, with the path - explorer handles the quoting
+        let select_arg = format!("/select,\"{}\"", clean_path);
+        std::process::Command::new("explorer.exe")
+            .raw_arg(&select_arg)
+            .spawn()
+            .map_err(|e| format!("Failed to open explorer: {}", e))?;
+    }
+    
+    #[cfg(target_os = "macos")]
+    {
+        // On macOS, use open -R to reveal the file in Finder
+        std::process::Command::new("open")
+            .args(["-R", &path_buf.to_string_lossy()])
+            .spawn()
+            .map_err(|e| format!("Failed to open Finder: {}", e))?;
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        // On Linux, open the parent directory
+        let dir_to_open = if path_buf.is_file() {
+            path_buf.parent().map(|p| p.to_path_buf()).unwrap_or(path_buf.clone())
+        } else {
+            path_buf.clone()
+        };
+        std::process::Command::new("xdg-open")
+            .arg(&dir_to_open)
+            .spawn()
+            .map_err(|e| format!("Failed to open file manager: {}", e))?;
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+async fn copy_to_clipboard(text: String, window: Window) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::process::{Command, Stdio};
+        use std::os::windows::process::CommandExt;
+        
+        let mut child = Command::new("powershell")
+            .args(["-Command", "Set-Clipboard", "-Value", &format!("'{}'", text.replace("'", "''"))])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW - prevents PowerShell window from showing
+            .spawn()
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        
+        child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    }
+    
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::{Command, Stdio};
+        use std::io::Write;
+        
+        let mut child = Command::new("pbcopy")
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())
+                .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+        }
+        
+        child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::{Command, Stdio};
+        use std::io::Write;
+        
+        // Try xclip first, then xsel
+        let result = Command::new("xclip")
+            .args(["-selection", "clipboard"])
+            .stdin(Stdio::piped())
+            .spawn();
+        
+        match result {
+            Ok(mut child) => {
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(text.as_bytes())
+                        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+                }
+                child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+            }
+            Err(_) => {
+                let mut child = Command::new("xsel")
+                    .args(["--clipboard", "--input"])
+                    .stdin(Stdio::piped())
+                    .spawn()
+                    .map_err(|e| format!("Failed to copy to clipboard (neither xclip nor xsel available): {}", e))?;
+                
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(text.as_bytes())
+                        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+                }
+                child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+            }
+        }
+    }
+    
+    // Emit an event to notify the frontend that the copy was successful
+    let _ = window.emit("clipboard-copied", text);
+    
+    Ok(())
+}
+
+#[tauri::command]
+async fn rename_mod(mod_path: String, new_name: String, window: Window) -> Result<String, String> {
+    info!("rename_mod called: mod_path={}, new_name={}", mod_path, new_name);
+    match rename_mod_files(Path::new(&mod_path), &new_name) {
+        Ok(new_path) => {
+            info!("rename_mod: successfully renamed {} to {}", mod_path, new_path.display());
+            Ok(new_path.to_string_lossy().to_string())
+        }
+        Err(error_msg) => {
+            toast_events::emit_rename_failed(&window, &error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// File stem for a mod renamed to `new_name`: a suffix given in `new_name` wins, otherwise the
+/// old priority suffix is kept. A patch pak with a bare "_P" gets the default "_9999999_P";
+/// a base pak (no "_P") stays suffix-less so it isn't turned into a patch pak.
+fn renamed_mod_stem(old_stem: &str, new_name: &str) -> String {
+    let priority_suffix_regex = Regex::new(r"(_\d+_P)+$").unwrap();
+    if priority_suffix_regex.is_match(new_name) {
+        return new_name.to_string();
+    }
+    match priority_suffix_regex.find(old_stem) {
+        Some(suffix) => format!("{}{}", new_name, suffix.as_str()),
+        None if has_patch_suffix(old_stem) => format!("{}_9999999_P", new_name),
+        None => new_name.to_string(),
+    }
+}
+
+/// Rename a mod file to `new_name`, keeping its priority suffix and extension, and move its
+/// IoStore companions (enabled or disabled) along with it. Returns the new path.
+fn rename_mod_files(old_path_buf: &Path, new_name: &str) -> Result<PathBuf, String> {
+    if !old_path_buf.exists() {
+        return Err(format!("File does not exist: {}", old_path_buf.display()));
+    }
+    
+    // Get the parent directory
+    let parent = old_path_buf.parent()
+        .ok_or_else(|| "Cannot get parent directory".to_string())?;
+    
+    // Get the full filename to detect extension properly
+    let filename = old_path_buf.file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    
+    // Detect extension - handle .bak_repak as disabled .pak
+    let (extension, is_pak_type) = if filename.ends_with(".bak_repak") {
+        ("bak_repak".to_string(), true)
+    } else if filename.ends_with(".pak") {
+        ("pak".to_string(), true)
+    } else {
+        let ext = old_path_buf.extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_default();
+        (ext, false)
+    };
+    
+    // Get the old stem (filename without extension) BEFORE renaming
+    // For .bak_repak files, we need to strip that suffix manually
+    let old_stem = if filename.ends_with(".bak_repak") {
+        filename.trim_end_matches(".bak_repak").to_string()
+    } else {
+        old_path_buf.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    
+    let new_stem = renamed_mod_stem(&old_stem, new_name);
+    
+    info!("rename_mod: old_stem={}, extension={}, is_pak_type={}, new_stem={}", 
+          old_stem, extension, is_pak_type, new_stem);
+    
+    // Build the new path with the new stem and same extension
+    let new_file_name = if extension.is_empty() {
+        new_stem.clone()
+    } else {
+        format!("{}.{}", new_stem, extension)
+    };
+    let new_path = parent.join(&new_file_name);
+    
+    if new_path.exists() {
+        return Err(format!("A file with name '{}' already exists", new_file_name));
+    }
+    
+    // If it's a .pak or .bak_repak file, rename associated IoStore files FIRST, keeping
+    // their enabled/disabled extension (.utoc or .utoc_disabled, .ucas or .ucas_disabled)
+    if is_pak_type {
+        for ext in ["ucas", "utoc"] {
+            let old_companion = iostore_companion_path(old_path_buf, ext);
+            info!("rename_mod: checking {}: {} exists={}", ext, old_companion.display(), old_companion.exists());
+            if !old_companion.exists() {
+                continue;
+            }
+            let companion_ext = old_companion.extension().unwrap_or_default().to_string_lossy().to_string();
+            let new_companion = new_path.with_extension(&companion_ext);
+            match std::fs::rename(&old_companion, &new_companion) {
+                Ok(_) => info!("rename_mod: renamed {} to {}", ext, new_companion.display()),
+                Err(e) => warn!("rename_mod: failed to rename {}: {}", ext, e),
+            }
+        }
+    }
+    
+    // Rename the main file
+    std::fs::rename(old_path_buf, &new_path)
+        .map_err(|e| format!("Failed to rename file: {}", e))?;
+    
+    Ok(new_path)
+}
+
+/// Convert a simple glob pattern (`*` and `?` wildcards) into an equivalent regex
+fn glob_to_regex(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len() * 2);
+    for c in pattern.chars() {
+        match c {
+            '*' => out.push_str(".*"),
+            '?' => out.push('.'),
+            c if "\\.+()|[]{}^$".contains(c) => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Apply a find/replace to the base name of several mods at once (e.g. stripping an author prefix).
+/// The priority suffix and IoStore companions are handled by `rename_mod`.
+/// All new names are validated before anything is renamed; returns (old_path, new_path) pairs.
+#[tauri::command]
+async fn batch_rename_mods(
+    mod_paths: Vec<String>,
+    pattern: String,
+    replacement: String,
+    is_regex: bool,
+    window: Window,
+) -> Result<Vec<(String, String)>, String> {
+    info!("batch_rename_mods called: {} mod(s), pattern={}, is_regex={}", mod_paths.len(), pattern, is_regex);
+
+    if pattern.is_empty() {
+        return Err("Pattern cannot be empty".to_string());
+    }
+
+    let regex_source = if is_regex { pattern.clone() } else { glob_to_regex(&pattern) };
+    let find_regex = Regex::new(&regex_source)
+        .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+    let priority_suffix_regex = Regex::new(r"(_\d+_P)+$").unwrap();
+
+    // Plan every rename first so a collision aborts the whole batch before touching disk
+    let mut planned: Vec<(String, String)> = Vec::new();
+    let mut claimed_targets: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+
+    for mod_path in &mod_paths {
+        let path = PathBuf::from(mod_path);
+        if !path.exists() {
+            return Err(format!("File does not exist: {}", mod_path));
+        }
+
+        let filename = path.file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (stem, extension) = if let Some(stem) = filename.strip_suffix(".bak_repak") {
+            (stem.to_string(), "bak_repak".to_string())
+        } else {
+            (
+                path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+                path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default(),
+            )
+        };
+
+        let suffix = priority_suffix_regex.find(&stem)
+            .map(|m| m.as_str().to_string())
+            .unwrap_or_default();
+        let base = &stem[..stem.len() - suffix.len()];
+
+        // Drop any priority suffix the replacement introduced so the mod's own suffix
+        // is applied exactly once
+        let new_base = find_regex.replace_all(base, replacement.as_str()).to_string();
+        let new_base = priority_suffix_regex.replace(&new_base, "").to_string();
+        if new_base == base {
+            continue;
+        }
+        if new_base.trim().is_empty() {
+            return Err(format!("Replacement would leave '{}' with an empty name", base));
+        }
+
+        // Same suffix handling as rename_mod, to predict the final file name
+        let new_stem = renamed_mod_stem(&stem, &new_base);
+        let parent = path.parent().ok_or("Cannot get parent directory")?;
+        let new_path = if extension.is_empty() {
+            parent.join(&new_stem)
+        } else {
+            parent.join(format!("{}.{}", new_stem, extension))
+        };
+
+        if new_path.exists() || !claimed_targets.insert(new_path.clone()) {
+            return Err(format!(
+                "Renaming '{}' to '{}' would collide with an existing mod",
+                base, new_base
+            ));
+        }
+
+        planned.push((mod_path.clone(), new_base));
+    }
+
+    let mut results = Vec::with_capacity(planned.len());
+    for (old_path, new_base) in planned {
+        let new_path = rename_mod(old_path.clone(), new_base, window.clone()).await?;
+        results.push((old_path, new_path));
+    }
+
+    info!("batch_rename_mods: renamed {} mod(s)", results.len());
+    Ok(results)
+}
+
+#[tauri::command]
+async fn create_folder(name: String, state: State<'_, Arc<RwLock<AppState>>>, window: Window) -> Result<String, String> {
+    let game_path = state.read().await.game_path.clone();
+    
+    // Create physical directory in ~mods
+    let folder_path = game_path.join(&name);
+    
+    if folder_path.exists() {
+        let error_msg = "Folder already exists".to_string();
+        toast_events::emit_folder_create_failed(&window, &error_msg);
+        return Err(error_msg);
+    }
+    
+    // Use create_dir_all to support nested paths like "Category/Subcategory"
+    if let Err(e) = std::fs::create_dir_all(&folder_path) {
+        let error_msg = format!("Failed to create folder: {}", e);
+        toast_events::emit_folder_create_failed(&window, &error_msg);
+        return Err(error_msg);
+    }
+    
+    Ok(name)
+}
+
+#[tauri::command]
+async fn get_folders(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<ModFolder>, String> {
+    let game_path = state.read().await.game_path.clone();
+    
+    if !game_path.exists() {
+        return Ok(Vec::new());
+    }
+    
+    let mut folders = Vec::new();
+    
+    // Get root folder name (e.g., "~mods")
+    let root_name = game_path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Mods")
+        .to_string();
+    
+    // Count mods directly in root (not in subfolders)
+    let root_mod_count = std::fs::read_dir(&game_path)
+        .map(|entries| {
+            entries.filter_map(|e| e.ok())
+                .filter(|e| {
+                    let path = e.path();
+                    if path.is_file() {
+                        let ext = path.extension().and_then(|s| s.to_str());
+                        ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled")
+                    } else {
+                        false
                     }
-                    
-                    "PAK".to_string()
-                };
+                })
+                .count()
+        })
+        .unwrap_or(0);
+    
+    // Add root folder first (depth 0) - use actual folder name as ID
+    folders.push(ModFolder {
+        id: root_name.clone(),  // Use actual name like "~mods" as ID
+        name: root_name.clone(),
+        enabled: true,
+        expanded: true,
+        color: None,
+        depth: 0,
+        parent_id: None,
+        is_root: true,
+        mod_count: root_mod_count,
+    });
+    
+    // Recursively scan for subdirectories using WalkDir
+    for entry in WalkDir::new(&game_path)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok()) 
+    {
+        let path = entry.path();
+        
+        if path.is_dir() {
+            // Calculate relative path from game_path to get ID
+            let relative_path = path.strip_prefix(&game_path)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|_| "Unknown".to_string());
                 
-                (mod_type, false, false, true) // Default to true for safety
+            let name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Unknown")
+                .to_string();
+            
+            // Calculate depth (number of path segments)
+            let depth = relative_path.split('/').count();
+            
+            // Calculate parent ID
+            let parent_id = if depth > 1 {
+                // If depth > 1, parent is the directory containing this one
+                // e.g. "A/B" -> parent is "A"
+                let parent_rel = std::path::Path::new(&relative_path)
+                    .parent()
+                    .map(|p| p.to_string_lossy().replace('\\', "/"));
+                parent_rel
             } else {
-                ("Unknown".to_string(), false, false, true) // Default to true for safety
-            }
-        };
-
-        // For .pak files, auto-enable repak UNLESS it's an IoStore package
-        let is_pak = path.extension().and_then(|s| s.to_str()) == Some("pak");
-        let is_iostore_pkg = is_pak && path.with_extension("utoc").exists() && path.with_extension("ucas").exists();
-        let auto_to_repak = is_pak && !is_iostore_pkg;
+                // If depth is 1, parent is the root folder
+                Some(root_name.clone())
+            };
 
-        mods.push(InstallableModInfo {
-            mod_name,
-            mod_type,
-            is_dir: path.is_dir(),
-            path: path_str,
-            auto_fix_texture,
-            auto_fix_serialize_size,
-            auto_to_repak,
-            contains_uassets,
-        });
+            // Count mods in this folder (only direct children)
+            let mod_count = std::fs::read_dir(&path)
+                .map(|entries| {
+                    entries.filter_map(|e| e.ok())
+                        .filter(|e| {
+                            let p = e.path();
+                            if p.is_file() {
+                                let ext = p.extension().and_then(|s| s.to_str());
+                                ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled")
+                            } else {
+                                false
+                            }
+                        })
+                        .count()
+                })
+                .unwrap_or(0);
+            
+            folders.push(ModFolder {
+                id: relative_path, // ID is the relative path (e.g. "Category/Subcategory")
+                name,
+                enabled: true,
+                expanded: true,
+                color: None,
+                depth,
+                parent_id,
+                is_root: false,
+                mod_count,
+            });
+        }
     }
-
-    Ok(mods)
-}
-
-#[derive(serde::Deserialize)]
-struct ModToInstall {
-    path: String,
-    #[serde(rename = "customName")]
-    custom_name: Option<String>,
-    #[serde(rename = "fixTexture")]
-    fix_texture: bool,
-    #[serde(rename = "fixSerializeSize")]
-    fix_serialize_size: bool,
-    #[serde(rename = "toRepak")]
-    to_repak: bool,
-    #[serde(rename = "forceLegacy")]
-    force_legacy: bool,
-    /// Subfolder within the mods directory to install into (empty = root)
-    #[serde(rename = "installSubfolder", default)]
-    install_subfolder: String,
+    
+    Ok(folders)
 }
 
-/// Helper function to copy an IoStore bundle (.utoc/.ucas and .pak or .bak_repak) and recompress if needed
-fn copy_iostore_with_compression_check(
-    utoc_src: &Path,
-    output_dir: &Path,
-    window: &Window,
-) -> Result<u32, String> {
-    let utoc_name = utoc_src.file_name().unwrap();
-    let ucas_src = utoc_src.with_extension("ucas");
-    let utoc_dest = output_dir.join(utoc_name);
-    let ucas_dest = output_dir.join(ucas_src.file_name().unwrap());
+/// Get detailed info about the root mods folder
+#[tauri::command]
+async fn get_root_folder_info(state: State<'_, Arc<RwLock<AppState>>>) -> Result<RootFolderInfo, String> {
+    let game_path = state.read().await.game_path.clone();
+    
+    if !game_path.exists() {
+        return Err("Game path does not exist".to_string());
+    }
     
-    let mut file_count = 0u32;
+    let root_name = game_path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Mods")
+        .to_string();
     
-    // Also check for .pak or .bak_repak file (part of IoStore bundle)
-    let pak_src = utoc_src.with_extension("pak");
-    let bak_repak_src = utoc_src.with_extension("bak_repak");
+    let mut direct_mod_count = 0;
+    let mut subfolder_count = 0;
     
-    // Copy .pak if it exists
-    if pak_src.exists() {
-        let pak_dest = output_dir.join(pak_src.file_name().unwrap());
-        if let Err(e) = std::fs::copy(&pak_src, &pak_dest) {
-            warn!("[QuickOrganize] Failed to copy {}: {}", pak_src.file_name().unwrap().to_string_lossy(), e);
-        } else {
-            info!("[QuickOrganize] Copied: {}", pak_src.file_name().unwrap().to_string_lossy());
-            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", pak_src.file_name().unwrap().to_string_lossy()));
-            file_count += 1;
+    for entry in std::fs::read_dir(&game_path).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        
+        if path.is_dir() {
+            subfolder_count += 1;
+        } else if path.is_file() {
+            let ext = path.extension().and_then(|s| s.to_str());
+            if ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled") {
+                direct_mod_count += 1;
+            }
         }
     }
     
-    // Copy .bak_repak if it exists (disabled pak file)
-    if bak_repak_src.exists() {
-        let bak_repak_dest = output_dir.join(bak_repak_src.file_name().unwrap());
-        if let Err(e) = std::fs::copy(&bak_repak_src, &bak_repak_dest) {
-            warn!("[QuickOrganize] Failed to copy {}: {}", bak_repak_src.file_name().unwrap().to_string_lossy(), e);
-        } else {
-            info!("[QuickOrganize] Copied: {}", bak_repak_src.file_name().unwrap().to_string_lossy());
-            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", bak_repak_src.file_name().unwrap().to_string_lossy()));
-            file_count += 1;
-        }
+    Ok(RootFolderInfo {
+        name: root_name,
+        path: game_path.to_string_lossy().to_string(),
+        direct_mod_count,
+        subfolder_count,
+    })
+}
+
+/// What repair_config cleaned out of the saved state
+#[derive(Debug, Default, Serialize)]
+struct RepairSummary {
+    /// Metadata entries for mods that no longer exist in any enabled/disabled form
+    metadata_pruned: usize,
+    /// Saved folders whose directory is gone
+    folders_pruned: usize,
+    /// Duplicate or blank entries removed from the tag catalog
+    tags_deduped: usize,
+}
+
+/// Drop saved state that points at things no longer on disk. Nothing is pruned while the
+/// mods directory itself is missing (e.g. an unplugged drive), only the tag catalog is tidied.
+fn repair_app_state(state: &mut AppState) -> RepairSummary {
+    let mut summary = RepairSummary::default();
+
+    if state.game_path.is_dir() {
+        let before = state.mod_metadata.len();
+        state.mod_metadata.retain(|m| {
+            m.path.exists()
+                || ["pak", "bak_repak", "pak_disabled"].iter().any(|ext| m.path.with_extension(ext).exists())
+        });
+        summary.metadata_pruned = before - state.mod_metadata.len();
+
+        let game_path = state.game_path.clone();
+        let root_name = game_path.file_name().map(|n| n.to_string_lossy().to_string());
+        let before = state.folders.len();
+        state.folders.retain(|f| {
+            f.is_root || Some(&f.id) == root_name.as_ref() || game_path.join(&f.id).is_dir()
+        });
+        summary.folders_pruned = before - state.folders.len();
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let before = state.custom_tag_catalog.len();
+    state.custom_tag_catalog.retain(|tag| !tag.trim().is_empty() && seen.insert(tag.clone()));
+    summary.tags_deduped = before - state.custom_tag_catalog.len();
+    summary
+}
+
+/// Clean stale entries out of the saved state: metadata for deleted mods, folders that
+/// are gone and duplicate tags
+#[tauri::command]
+async fn repair_config(state: State<'_, Arc<RwLock<AppState>>>) -> Result<RepairSummary, String> {
+    info!("repair_config called");
+    let mut state = state.write().await;
+    let summary = repair_app_state(&mut state);
+    save_state(&state).map_err(|e| e.to_string())?;
+    info!(
+        "Config repaired: removed {} metadata entry(s), {} folder(s), {} tag(s)",
+        summary.metadata_pruned, summary.folders_pruned, summary.tags_deduped
+    );
+    Ok(summary)
+}
+
+/// Set whether repair_config runs at startup
+#[tauri::command]
+async fn set_repair_config_on_startup(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_repair_config_on_startup called: enabled={}", enabled);
+    let mut state = state.write().await;
+    state.repair_config_on_startup = enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Get current repair-config-on-startup setting
+#[tauri::command]
+async fn get_repair_config_on_startup(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    Ok(state.read().await.repair_config_on_startup)
+}
+
+#[tauri::command]
+async fn update_folder(
+    folder: ModFolder,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    let mut state = state.write().await;
+    if let Some(existing) = state.folders.iter_mut().find(|f| f.id == folder.id) {
+        *existing = folder;
+        save_state(&state).map_err(|e| e.to_string())?;
     }
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_folder(id: String, state: State<'_, Arc<RwLock<AppState>>>, window: Window) -> Result<(), String> {
+    let game_path = state.read().await.game_path.clone();
     
-    // Check if the IoStore is compressed
-    let is_compressed = match uasset_toolkit::is_iostore_compressed(&utoc_src.to_string_lossy()) {
-        Ok(compressed) => compressed,
-        Err(e) => {
-            warn!("[QuickOrganize] Failed to check IoStore compression for {}: {}", utoc_name.to_string_lossy(), e);
-            // Assume compressed if we can't check, just copy
-            true
-        }
-    };
+    let folder_path = game_path.join(&id);
     
-    if is_compressed {
-        // Already compressed, just copy
-        info!("[QuickOrganize] IoStore {} is already compressed, copying directly", utoc_name.to_string_lossy());
-        let _ = window.emit("install_log", format!("[QuickOrganize] Copying compressed IoStore: {}", utoc_name.to_string_lossy()));
-        
-        std::fs::copy(utoc_src, &utoc_dest)
-            .map_err(|e| format!("Failed to copy {}: {}", utoc_name.to_string_lossy(), e))?;
-        std::fs::copy(&ucas_src, &ucas_dest)
-            .map_err(|e| format!("Failed to copy {}: {}", ucas_src.file_name().unwrap().to_string_lossy(), e))?;
-        
-        file_count += 2; // Copied utoc + ucas
-    } else {
-        // Not compressed, need to recompress with Oodle
-        info!("[QuickOrganize] IoStore {} is NOT compressed, recompressing with Oodle...", utoc_name.to_string_lossy());
-        let _ = window.emit("install_log", format!("[QuickOrganize] Recompressing uncompressed IoStore: {}", utoc_name.to_string_lossy()));
-        
-        // First copy to destination
-        std::fs::copy(utoc_src, &utoc_dest)
-            .map_err(|e| format!("Failed to copy {}: {}", utoc_name.to_string_lossy(), e))?;
-        std::fs::copy(&ucas_src, &ucas_dest)
-            .map_err(|e| format!("Failed to copy {}: {}", ucas_src.file_name().unwrap().to_string_lossy(), e))?;
-        
-        file_count += 2; // Copied utoc + ucas
-        
-        // Now recompress in place
-        match uasset_toolkit::recompress_iostore(&utoc_dest.to_string_lossy()) {
-            Ok(_) => {
-                info!("[QuickOrganize] Successfully recompressed IoStore: {}", utoc_name.to_string_lossy());
-                let _ = window.emit("install_log", format!("[QuickOrganize] ✓ Recompressed: {}", utoc_name.to_string_lossy()));
-            }
-            Err(e) => {
-                warn!("[QuickOrganize] Failed to recompress IoStore {}: {}", utoc_name.to_string_lossy(), e);
-                let _ = window.emit("install_log", format!("[QuickOrganize] Warning: Could not recompress {}: {}", utoc_name.to_string_lossy(), e));
-                // Files are still copied, just not recompressed
-            }
-        }
+    if !folder_path.exists() {
+        let error_msg = "Folder does not exist".to_string();
+        toast_events::emit_folder_delete_failed(&window, &error_msg);
+        return Err(error_msg);
+    }
+    
+    // Delete physical directory (will fail if not empty, which is good for safety)
+    if let Err(e) = std::fs::remove_dir(&folder_path) {
+        let error_msg = format!("Failed to delete folder (may not be empty): {}", e);
+        toast_events::emit_folder_delete_failed(&window, &error_msg);
+        return Err(error_msg);
     }
     
-    Ok(file_count)
+    Ok(())
 }
 
-/// Quick Organize: Simply copy/move files to a target folder without any repak processing
-/// This is for organizing existing mod files into subfolders
-/// Now also detects uncompressed IoStore bundles and recompresses them with Oodle
-/// Preserves subfolder structure from archives and directories
 #[tauri::command]
-async fn quick_organize(
-    paths: Vec<String>,
-    target_folder: String,
-    state: State<'_, Arc<Mutex<AppState>>>,
+async fn rename_folder(
+    id: String,
+    new_name: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
     window: Window,
-) -> Result<i32, String> {
-    use crate::install_mod::install_mod_logic::archives::{extract_zip, extract_rar, extract_7z};
-    use walkdir::WalkDir;
-    
-    let state_guard = state.lock().unwrap();
-    let mod_directory = state_guard.game_path.clone();
-    drop(state_guard);
-    
-    // Determine the output directory
-    let output_dir = if target_folder.is_empty() || target_folder == "~mods" {
-        mod_directory.clone()
-    } else {
-        mod_directory.join(&target_folder)
-    };
-    
-    // Create output directory if it doesn't exist (for "New Folder" drops and subfolder preservation)
-    if !output_dir.exists() {
-        std::fs::create_dir_all(&output_dir)
-            .map_err(|e| format!("Failed to create target folder '{}': {}", target_folder, e))?;
-        info!("[QuickOrganize] Created target folder: {}", output_dir.display());
+) -> Result<String, String> {
+    let mut state = state.write().await;
+    let game_path = state.game_path.clone();
+
+    // Prevent renaming root folder
+    if id == game_path.file_name().and_then(|n| n.to_str()).unwrap_or("") {
+        let error_msg = "Cannot rename the root folder".to_string();
+        toast_events::emit_folder_rename_failed(&window, &error_msg);
+        return Err(error_msg);
     }
-    
-    info!("[QuickOrganize] Copying {} file(s) to '{}'", paths.len(), output_dir.display());
-    let _ = window.emit("install_log", format!("[QuickOrganize] Copying to folder: {}", if target_folder.is_empty() { "~mods (root)".to_string() } else { target_folder.clone() }));
-    
-    let mut copied_count = 0;
-    
-    /// Helper to compute relative path from a base directory to preserve subfolder structure
-    fn get_relative_subpath(entry_path: &Path, base_path: &Path) -> Option<PathBuf> {
-        entry_path.parent()
-            .and_then(|parent| parent.strip_prefix(base_path).ok())
-            .map(|rel| rel.to_path_buf())
-            .filter(|rel| !rel.as_os_str().is_empty())
+
+    // Validate new name (no path separators allowed in the leaf name)
+    if new_name.contains('/') || new_name.contains('\\') || new_name.is_empty() {
+        let error_msg = "Invalid folder name".to_string();
+        toast_events::emit_folder_rename_failed(&window, &error_msg);
+        return Err(error_msg);
     }
-    
-    /// Helper to ensure destination directory exists and return the full destination path
-    fn prepare_dest_with_subfolders(
-        entry_path: &Path,
-        base_path: &Path,
-        output_dir: &Path,
-        window: &Window,
-    ) -> Result<PathBuf, String> {
-        let file_name = entry_path.file_name().unwrap();
-        
-        if let Some(rel_subpath) = get_relative_subpath(entry_path, base_path) {
-            let dest_subdir = output_dir.join(&rel_subpath);
-            if !dest_subdir.exists() {
-                std::fs::create_dir_all(&dest_subdir)
-                    .map_err(|e| format!("Failed to create subfolder '{}': {}", rel_subpath.display(), e))?;
-                info!("[QuickOrganize] Created subfolder: {}", rel_subpath.display());
-                let _ = window.emit("install_log", format!("[QuickOrganize] Created subfolder: {}", rel_subpath.display()));
-            }
-            Ok(dest_subdir.join(file_name))
-        } else {
-            Ok(output_dir.join(file_name))
-        }
+
+    let old_path = game_path.join(&id);
+    if !old_path.exists() {
+        let error_msg = "Folder does not exist".to_string();
+        toast_events::emit_folder_rename_failed(&window, &error_msg);
+        return Err(error_msg);
     }
-    
-    /// Helper to get the destination directory for IoStore bundles with subfolder preservation
-    fn get_iostore_dest_dir(
-        entry_path: &Path,
-        base_path: &Path,
-        output_dir: &Path,
-        window: &Window,
-    ) -> Result<PathBuf, String> {
-        if let Some(rel_subpath) = get_relative_subpath(entry_path, base_path) {
-            let dest_subdir = output_dir.join(&rel_subpath);
-            if !dest_subdir.exists() {
-                std::fs::create_dir_all(&dest_subdir)
-                    .map_err(|e| format!("Failed to create subfolder '{}': {}", rel_subpath.display(), e))?;
-                info!("[QuickOrganize] Created subfolder: {}", rel_subpath.display());
-                let _ = window.emit("install_log", format!("[QuickOrganize] Created subfolder: {}", rel_subpath.display()));
-            }
-            Ok(dest_subdir)
+
+    // Build new path: replace only the last path segment with new_name
+    let new_id = if let Some(parent) = std::path::Path::new(&id).parent() {
+        let parent_str = parent.to_string_lossy().replace('\\', "/");
+        if parent_str.is_empty() {
+            new_name.clone()
         } else {
-            Ok(output_dir.to_path_buf())
+            format!("{}/{}", parent_str, new_name)
         }
+    } else {
+        new_name.clone()
+    };
+
+    let new_path = game_path.join(&new_id);
+
+    if new_path.exists() {
+        let error_msg = format!("A folder named \"{}\" already exists", new_name);
+        toast_events::emit_folder_rename_failed(&window, &error_msg);
+        return Err(error_msg);
     }
-    
-    for path_str in paths {
-        let path = PathBuf::from(&path_str);
-        
-        if !path.exists() {
-            warn!("[QuickOrganize] Path does not exist: {}", path_str);
-            continue;
-        }
-        
-        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-        
-        // Handle archives - extract and copy contents preserving subfolder structure
-        if ext == "zip" || ext == "rar" || ext == "7z" {
-            let _ = window.emit("install_log", format!("[QuickOrganize] Extracting archive: {}", path.file_name().unwrap_or_default().to_string_lossy()));
-            
-            let temp_dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
-            let temp_path = temp_dir.path();
-            let temp_path_str = temp_path.to_str().unwrap();
-            
-            // Extract archive
-            let extract_result = if ext == "zip" {
-                extract_zip(path.to_str().unwrap(), temp_path_str)
-            } else if ext == "rar" {
-                extract_rar(path.to_str().unwrap(), temp_path_str).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
-            } else {
-                extract_7z(path.to_str().unwrap(), temp_path_str)
-            };
-            
-            if let Err(e) = extract_result {
-                error!("[QuickOrganize] Failed to extract archive: {}", e);
-                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: Failed to extract archive: {}", e));
-                continue;
-            }
-            
-            // Find and copy all pak/utoc/ucas files from extracted content with subfolder preservation
-            let mut processed_utocs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
-            
-            for entry in WalkDir::new(temp_path).into_iter().filter_map(|e| e.ok()) {
-                let entry_path = entry.path();
-                if let Some(entry_ext) = entry_path.extension().and_then(|s| s.to_str()) {
-                    if entry_ext == "pak" {
-                        // Prepare destination with subfolder structure
-                        let dest = match prepare_dest_with_subfolders(entry_path, temp_path, &output_dir, &window) {
-                            Ok(d) => d,
-                            Err(e) => {
-                                error!("[QuickOrganize] {}", e);
-                                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                                continue;
-                            }
-                        };
-                        
-                        if let Err(e) = std::fs::copy(entry_path, &dest) {
-                            error!("[QuickOrganize] Failed to copy {}: {}", entry_path.file_name().unwrap().to_string_lossy(), e);
-                        } else {
-                            let rel_dest = dest.strip_prefix(&output_dir).unwrap_or(&dest);
-                            info!("[QuickOrganize] Copied: {}", rel_dest.display());
-                            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", rel_dest.display()));
-                            copied_count += 1;
-                        }
-                    } else if entry_ext == "utoc" {
-                        // Process IoStore with compression check, preserving subfolder structure
-                        let ucas_path = entry_path.with_extension("ucas");
-                        if ucas_path.exists() && !processed_utocs.contains(entry_path) {
-                            processed_utocs.insert(entry_path.to_path_buf());
-                            
-                            // Determine destination directory with subfolder preservation
-                            let dest_dir = match get_iostore_dest_dir(entry_path, temp_path, &output_dir, &window) {
-                                Ok(d) => d,
-                                Err(e) => {
-                                    error!("[QuickOrganize] {}", e);
-                                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                                    continue;
-                                }
-                            };
-                            
-                            match copy_iostore_with_compression_check(entry_path, &dest_dir, &window) {
-                                Ok(count) => copied_count += count as i32,
-                                Err(e) => {
-                                    error!("[QuickOrganize] Failed to process IoStore: {}", e);
-                                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                                }
-                            }
-                        }
-                    }
-                    // Skip .ucas files - they're handled together with .utoc
-                }
+
+    // Rename the physical directory
+    if let Err(e) = std::fs::rename(&old_path, &new_path) {
+        let error_msg = format!("Failed to rename folder: {}", e);
+        toast_events::emit_folder_rename_failed(&window, &error_msg);
+        return Err(error_msg);
+    }
+
+    // Update mod_metadata entries that reference the old folder ID (or children of it)
+    let old_prefix = format!("{}/", id);
+    for metadata in state.mod_metadata.iter_mut() {
+        if let Some(ref fid) = metadata.folder_id {
+            if fid == &id {
+                metadata.folder_id = Some(new_id.clone());
+            } else if fid.starts_with(&old_prefix) {
+                // Child folder: replace the old prefix with the new one
+                let suffix = &fid[old_prefix.len()..];
+                metadata.folder_id = Some(format!("{}/{}", new_id, suffix));
             }
         }
-        // Handle pak files (and their iostore companions) - no subfolder structure for single files
-        else if ext == "pak" {
-            let file_name = path.file_name().unwrap();
-            let dest = output_dir.join(file_name);
-            
-            // Copy the pak file
-            if let Err(e) = std::fs::copy(&path, &dest) {
-                error!("[QuickOrganize] Failed to copy {}: {}", file_name.to_string_lossy(), e);
-                continue;
-            }
-            
-            info!("[QuickOrganize] Copied: {}", file_name.to_string_lossy());
-            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", file_name.to_string_lossy()));
-            copied_count += 1;
-            
-            // Also handle utoc and ucas if they exist (IoStore package)
-            let utoc_path = path.with_extension("utoc");
-            let ucas_path = path.with_extension("ucas");
-            
-            if utoc_path.exists() && ucas_path.exists() {
-                match copy_iostore_with_compression_check(&utoc_path, &output_dir, &window) {
-                    Ok(count) => copied_count += count as i32,
-                    Err(e) => {
-                        error!("[QuickOrganize] Failed to process IoStore: {}", e);
-                        let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                    }
-                }
-            } else if utoc_path.exists() {
-                let utoc_name = utoc_path.file_name().unwrap();
-                if let Err(e) = std::fs::copy(&utoc_path, output_dir.join(utoc_name)) {
-                    error!("[QuickOrganize] Failed to copy {}: {}", utoc_name.to_string_lossy(), e);
-                } else {
-                    copied_count += 1;
-                }
-            }
+    }
+
+    // Update folder state entries that reference the old ID
+    for folder in state.folders.iter_mut() {
+        if folder.id == id {
+            folder.id = new_id.clone();
+            folder.name = new_name.clone();
+        } else if folder.id.starts_with(&old_prefix) {
+            let suffix = &folder.id[old_prefix.len()..];
+            folder.id = format!("{}/{}", new_id, suffix);
         }
-        // Handle utoc files directly (IoStore bundle without .pak or with .bak_repak)
-        else if ext == "utoc" {
-            let ucas_path = path.with_extension("ucas");
-            if ucas_path.exists() {
-                match copy_iostore_with_compression_check(&path, &output_dir, &window) {
-                    Ok(count) => copied_count += count as i32,
-                    Err(e) => {
-                        error!("[QuickOrganize] Failed to process IoStore: {}", e);
-                        let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                    }
-                }
+        // Update parent_id references
+        if let Some(ref pid) = folder.parent_id {
+            if pid == &id {
+                folder.parent_id = Some(new_id.clone());
+            } else if pid.starts_with(&old_prefix) {
+                let suffix = &pid[old_prefix.len()..];
+                folder.parent_id = Some(format!("{}/{}", new_id, suffix));
             }
         }
-        // Handle directories - copy all pak/utoc/ucas files preserving subfolder structure
-        else if path.is_dir() {
-            let mut processed_utocs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
-            
-            for entry in WalkDir::new(&path).into_iter().filter_map(|e| e.ok()) {
-                let entry_path = entry.path();
-                if let Some(entry_ext) = entry_path.extension().and_then(|s| s.to_str()) {
-                    if entry_ext == "pak" {
-                        // Prepare destination with subfolder structure
-                        let dest = match prepare_dest_with_subfolders(entry_path, &path, &output_dir, &window) {
-                            Ok(d) => d,
-                            Err(e) => {
-                                error!("[QuickOrganize] {}", e);
-                                let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                                continue;
-                            }
-                        };
-                        
-                        if let Err(e) = std::fs::copy(entry_path, &dest) {
-                            error!("[QuickOrganize] Failed to copy {}: {}", entry_path.file_name().unwrap().to_string_lossy(), e);
-                        } else {
-                            let rel_dest = dest.strip_prefix(&output_dir).unwrap_or(&dest);
-                            info!("[QuickOrganize] Copied: {}", rel_dest.display());
-                            let _ = window.emit("install_log", format!("[QuickOrganize] Copied: {}", rel_dest.display()));
-                            copied_count += 1;
-                        }
-                    } else if entry_ext == "utoc" {
-                        // Process IoStore with compression check, preserving subfolder structure
-                        let ucas_path = entry_path.with_extension("ucas");
-                        if ucas_path.exists() && !processed_utocs.contains(entry_path) {
-                            processed_utocs.insert(entry_path.to_path_buf());
-                            
-                            // Determine destination directory with subfolder preservation
-                            let dest_dir = match get_iostore_dest_dir(entry_path, &path, &output_dir, &window) {
-                                Ok(d) => d,
-                                Err(e) => {
-                                    error!("[QuickOrganize] {}", e);
-                                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                                    continue;
-                                }
-                            };
-                            
-                            match copy_iostore_with_compression_check(entry_path, &dest_dir, &window) {
-                                Ok(count) => copied_count += count as i32,
-                                Err(e) => {
-                                    error!("[QuickOrganize] Failed to process IoStore: {}", e);
-                                    let _ = window.emit("install_log", format!("[QuickOrganize] ERROR: {}", e));
-                                }
-                            }
-                        }
-                    }
-                    // Skip .ucas files - they're handled together with .utoc
-                }
-            }
+    }
+
+    save_state(&state).map_err(|e| e.to_string())?;
+
+    Ok(new_id)
+}
+
+#[tauri::command]
+async fn assign_mod_to_folder(
+    mod_path: String,
+    folder_id: Option<String>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    window: Window,
+) -> Result<(), String> {
+    let game_path = state.read().await.game_path.clone();
+    let source_path = PathBuf::from(&mod_path);
+    
+    if !source_path.exists() {
+        let error_msg = "Mod file does not exist".to_string();
+        toast_events::emit_move_failed(&window, &error_msg);
+        return Err(error_msg);
+    }
+    
+    let filename = source_path.file_name()
+        .ok_or("Invalid file name")?;
+    
+    let dest_path = if let Some(folder_name) = folder_id {
+        // Move to folder
+        let folder_path = game_path.join(&folder_name);
+        if !folder_path.exists() {
+            let error_msg = "Folder does not exist".to_string();
+            toast_events::emit_move_failed(&window, &error_msg);
+            return Err(error_msg);
         }
+        folder_path.join(filename)
+    } else {
+        // Move back to root ~mods directory
+        game_path.join(filename)
+    };
+    
+    // Move the main file
+    if let Err(e) = std::fs::rename(&source_path, &dest_path) {
+        let error_msg = format!("Failed to move mod: {}", e);
+        toast_events::emit_move_failed(&window, &error_msg);
+        return Err(error_msg);
     }
     
-    let _ = window.emit("install_log", format!("[QuickOrganize] Done! Copied {} file(s)", copied_count));
-    info!("[QuickOrganize] Completed: {} files copied to {}", copied_count, output_dir.display());
+    // Also move .utoc and .ucas files if they exist (IoStore files), in either enabled state
+    for ext in ["utoc", "ucas"] {
+        let companion = iostore_companion_path(&source_path, ext);
+        if let (true, Some(companion_ext)) = (companion.exists(), companion.extension()) {
+            let _ = std::fs::rename(&companion, dest_path.with_extension(companion_ext));
+        }
+    }
     
-    Ok(copied_count)
+    Ok(())
 }
 
 #[tauri::command]
-async fn install_mods(
-    mods: Vec<ModToInstall>,
-    window: Window,
-    state: State<'_, Arc<Mutex<AppState>>>,
+async fn add_custom_tag(
+    mod_path: String,
+    tag: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
 ) -> Result<(), String> {
-    use std::sync::atomic::{AtomicI32, AtomicBool};
-    use std::sync::Arc as StdArc;
+    let mut state = state.write().await;
+    let path = PathBuf::from(&mod_path);
+    
+    // Find or create mod metadata
+    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
+        if !metadata.custom_tags.contains(&tag) {
+            metadata.custom_tags.push(tag);
+        }
+    } else {
+        state.mod_metadata.push(ModMetadata {
+            path,
+            custom_name: None,
+            folder_id: None,
+            custom_tags: vec![tag],
+            source_url: None,
+            thumbnail_path: None,
+        });
+    }
+    
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-    let state_guard = state.lock().unwrap();
-    let mod_directory = state_guard.game_path.clone();
-    let usmap_filename = state_guard.usmap_path.clone();
-    let parallel_processing = state_guard.parallel_processing;
-    let obfuscate = state_guard.obfuscate;
-    drop(state_guard);
+#[tauri::command]
+async fn remove_custom_tag(
+    mod_path: String,
+    tag: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    let mut state = state.write().await;
+    let path = PathBuf::from(&mod_path);
 
-    // Propagate USMAP path to UAssetTool via environment for UAssetAPI-based processing (from roaming folder)
-    if !usmap_filename.is_empty() {
-        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
-            std::env::set_var("USMAP_PATH", &usmap_full_path);
-            info!(
-                "Set USMAP_PATH for UAssetTool: {}",
-                usmap_full_path.display()
-            );
+    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
+        metadata.custom_tags.retain(|t| t != &tag);
+    }
+
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Add a tag to several mods at once. Returns the number of mods that gained the tag.
+#[tauri::command]
+async fn add_tag_to_mods(
+    paths: Vec<String>,
+    tag: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    info!("add_tag_to_mods called: '{}' on {} mod(s)", tag, paths.len());
+    let mut state = state.write().await;
+    let mut affected = 0;
+
+    for mod_path in paths {
+        let path = PathBuf::from(&mod_path);
+        if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
+            if !metadata.custom_tags.contains(&tag) {
+                metadata.custom_tags.push(tag.clone());
+                affected += 1;
+            }
         } else {
-            let expected_path = usmap_dir().join(&usmap_filename);
-            error!(
-                "USMAP file not found at expected path for UAssetTool: {}",
-                expected_path.display()
-            );
+            state.mod_metadata.push(ModMetadata {
+                path,
+                custom_name: None,
+                folder_id: None,
+                custom_tags: vec![tag.clone()],
+                source_url: None,
+                thumbnail_path: None,
+            });
+            affected += 1;
         }
     }
 
-    if !mod_directory.exists() {
-        std::fs::create_dir_all(&mod_directory)
-            .map_err(|e| format!("Failed to create mods directory: {}", e))?;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(affected)
+}
+
+/// Remove a tag from several mods at once. Returns the number of mods that had the tag.
+#[tauri::command]
+async fn remove_tag_from_mods(
+    paths: Vec<String>,
+    tag: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    info!("remove_tag_from_mods called: '{}' on {} mod(s)", tag, paths.len());
+    let mut state = state.write().await;
+    let paths: std::collections::HashSet<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let mut affected = 0;
+
+    for metadata in state.mod_metadata.iter_mut().filter(|m| paths.contains(&m.path)) {
+        let before = metadata.custom_tags.len();
+        metadata.custom_tags.retain(|t| t != &tag);
+        if metadata.custom_tags.len() != before {
+            affected += 1;
+        }
     }
 
-    // Convert paths to properly initialized InstallableMods
-    use crate::install_mod::map_paths_to_mods;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(affected)
+}
 
-    let paths: Vec<PathBuf> = mods.iter().map(|m| PathBuf::from(&m.path)).collect();
+/// Rename a tag on every mod (and in the tag catalog). Returns the number of mods affected.
+#[tauri::command]
+async fn rename_tag(
+    old: String,
+    new: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    info!("rename_tag called: '{}' -> '{}'", old, new);
+    let new = new.trim().to_string();
+    if new.is_empty() {
+        return Err("Tag name cannot be empty".to_string());
+    }
+    if new == old {
+        return Ok(0);
+    }
 
-    // Log the paths we're trying to install
-    for p in &paths {
-        info!("[Install] Processing path: {}", p.display());
-        let _ = window.emit("install_log", format!("[Install] Processing path: {}", p.display()));
+    let mut state = state.write().await;
+    let mut affected = 0;
+
+    for metadata in state.mod_metadata.iter_mut() {
+        if metadata.custom_tags.contains(&old) {
+            metadata.custom_tags.retain(|t| t != &old);
+            // Mods that already had the new tag just lose the old one
+            if !metadata.custom_tags.contains(&new) {
+                metadata.custom_tags.push(new.clone());
+            }
+            affected += 1;
+        }
+    }
+
+    if state.custom_tag_catalog.contains(&old) {
+        state.custom_tag_catalog.retain(|t| t != &old);
+        if !state.custom_tag_catalog.contains(&new) {
+            state.custom_tag_catalog.push(new);
+        }
+    }
+
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(affected)
+}
+
+/// Remove a tag from every mod and from the tag catalog. Returns the number of mods affected.
+#[tauri::command]
+async fn delete_tag(
+    tag: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    info!("delete_tag called: '{}'", tag);
+    let mut state = state.write().await;
+    let mut affected = 0;
+
+    for metadata in state.mod_metadata.iter_mut() {
+        let before = metadata.custom_tags.len();
+        metadata.custom_tags.retain(|t| t != &tag);
+        if metadata.custom_tags.len() != before {
+            affected += 1;
+        }
+    }
+    state.custom_tag_catalog.retain(|t| t != &tag);
+
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(affected)
+}
+
+/// Copy a USMAP file to the roaming folder, replacing any existing USMAP files.
+/// 
+/// # Arguments
+/// * `source_path` - Full path to the source .usmap file
+/// 
+/// # Returns
+/// The filename of the copied USMAP file (just the name, not full path)
+/// 
+/// # Behavior
+/// - Copies the new file to `%APPDATA%/Repak-X/Usmap/`, alongside any existing ones
+/// - Overwrites an existing USMAP with the same filename
+/// - Use `set_active_usmap` to choose which one is used
+#[tauri::command]
+async fn copy_usmap_to_folder(source_path: String) -> Result<String, String> {
+    let source = PathBuf::from(&source_path);
+    
+    if !source.exists() {
+        return Err("Source file does not exist".to_string());
     }
+    
+    // Get the Usmap directory in roaming folder
+    let usmap_folder = usmap_dir();
+    std::fs::create_dir_all(&usmap_folder)
+        .map_err(|e| format!("Failed to create Usmap directory: {}", e))?;
+    
+    // Get filename from source
+    let filename = source.file_name()
+        .ok_or("Invalid source filename")?
+        .to_str()
+        .ok_or("Invalid UTF-8 in filename")?;
+    
+    // Copy file to Usmap/ folder in roaming
+    let dest_path = usmap_folder.join(filename);
+    std::fs::copy(&source, &dest_path)
+        .map_err(|e| format!("Failed to copy file: {}", e))?;
+    
+    info!("Copied USmap file {} to {}", filename, usmap_folder.display());
+    
+    // Return just the filename
+    Ok(filename.to_string())
+}
 
-    let mut installable_mods = map_paths_to_mods(&paths);
+#[tauri::command]
+async fn set_usmap_path(usmap_path: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    let mut state = state.write().await;
+    state.usmap_path = usmap_path.clone();
+    info!("Set USMAP path in AppState: {}", usmap_path);
+    Ok(())
+}
 
-    // Check if we actually have mods to install
-    if installable_mods.is_empty() {
-        error!("[Install] No valid mods found from {} input path(s)", paths.len());
-        let _ = window.emit("install_log", "ERROR: No valid mods found to install!");
-        let _ = window.emit("install_log", "Possible causes:");
-        let _ = window.emit("install_log", "  - PAK file couldn't be read (wrong AES key or corrupted)");
-        let _ = window.emit("install_log", "  - Archive contains no .pak files or content folders");
-        let _ = window.emit("install_log", "  - Directory contains no valid content");
-        let error_msg = "No valid mods found to install. Check the install logs for details.";
-        toast_events::emit_installation_failed(&window, error_msg);
-        return Err(error_msg.to_string());
-    }
+#[tauri::command]
+async fn get_usmap_path(state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
+    let state = state.read().await;
+    Ok(state.usmap_path.clone())
+}
 
-    // Apply user settings to each mod
-    for (idx, mod_to_install) in mods.iter().enumerate() {
-        if let Some(installable) = installable_mods.get_mut(idx) {
-            // Apply custom name if provided
-            if let Some(ref custom) = mod_to_install.custom_name {
-                if !custom.is_empty() {
-                    installable.mod_name = custom.clone();
-                }
-            }
+/// Get the USMAP directory path in the roaming folder.
+/// 
+/// # Returns
+/// Full path to `%APPDATA%/Repak-X/Usmap/`
+#[tauri::command]
+async fn get_usmap_dir_path() -> Result<String, String> {
+    Ok(usmap_dir().to_string_lossy().to_string())
+}
 
-            // Apply fix settings (mesh patching is handled automatically by UAssetTool)
-            installable.fix_textures = mod_to_install.fix_texture;
-            installable.fix_serialsize_header = mod_to_install.fix_serialize_size;
-            installable.repak = mod_to_install.to_repak;
-            installable.force_legacy_pak = mod_to_install.force_legacy;
-            installable.install_subfolder = mod_to_install.install_subfolder.clone();
-            installable.usmap_path = usmap_filename.clone();
-            // Apply parallel processing setting from app state
-            installable.parallel_processing = parallel_processing;
-            // Apply obfuscation setting from app state
-            installable.obfuscate = obfuscate;
-        }
+/// List all USMAP files currently in the roaming Usmap folder.
+/// Reads from filesystem at runtime, not from saved state.
+/// 
+/// # Returns
+/// Vector of filenames (not full paths) of .usmap files in the folder
+#[tauri::command]
+async fn list_usmap_files() -> Result<Vec<String>, String> {
+    let usmap_folder = usmap_dir();
+    
+    if !usmap_folder.exists() {
+        return Ok(Vec::new());
     }
-
-    // Use existing installation logic
-    let installed_counter = StdArc::new(AtomicI32::new(0));
-    let stop_flag = StdArc::new(AtomicBool::new(false));
-
-    let total = installable_mods.len() as i32;
-    let counter_clone = installed_counter.clone();
-    let _stop_clone = stop_flag.clone();
-    let window_clone = window.clone();
     
-    // Spawn installation thread
-    let window_for_logs = window.clone();
-    std::thread::spawn(move || {
-        use std::panic;
-        
-        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
-            window_for_logs.emit("install_log", "Starting installation...").ok();
-            window_for_logs.emit("install_log", format!("Installing {} mod(s)", installable_mods.len())).ok();
-            
-            for (idx, imod) in installable_mods.iter().enumerate() {
-                window_for_logs.emit("install_log", format!("[{}/{}] Mod: {}", idx + 1, installable_mods.len(), imod.mod_name)).ok();
-                window_for_logs.emit("install_log", format!("  - Fix Textures: {}", imod.fix_textures)).ok();
-                window_for_logs.emit("install_log", format!("  - Fix SerializeSize: {}", imod.fix_serialsize_header)).ok();
-                window_for_logs.emit("install_log", format!("  - Repak: {}", imod.repak)).ok();
-                window_for_logs.emit("install_log", format!("  - Force Legacy PAK: {}", imod.force_legacy_pak)).ok();
-            }
-            
-            window_for_logs.emit("install_log", "Calling installation logic...").ok();
-            window_for_logs.emit("install_log", format!("Mod directory: {}", mod_directory.display())).ok();
-            
-            use crate::install_mod::install_mod_logic::install_mods_in_viewport;
-            
-            window_for_logs.emit("install_log", "Entering install_mods_in_viewport...").ok();
-            
-            // Log each mod's path before processing
-            for (idx, m) in installable_mods.iter().enumerate() {
-                window_for_logs.emit("install_log", format!("  Mod {} path exists: {}", idx, m.mod_path.exists())).ok();
-                window_for_logs.emit("install_log", format!("  Mod {} path: {}", idx, m.mod_path.display())).ok();
-            }
-            
-            install_mods_in_viewport(
-                &mut installable_mods,
-                &mod_directory,
-                &installed_counter,
-                &stop_flag,
-            );
-            window_for_logs.emit("install_log", "Exited install_mods_in_viewport").ok();
-        }));
-        
-        match result {
-            Ok(_) => {
-                window_for_logs.emit("install_log", "Installation completed successfully!").ok();
-            }
-            Err(e) => {
-                let msg = if let Some(s) = e.downcast_ref::<&str>() {
-                    format!("PANIC: {}", s)
-                } else if let Some(s) = e.downcast_ref::<String>() {
-                    format!("PANIC: {}", s)
-                } else {
-                    "PANIC: Unknown error".to_string()
-                };
-                window_for_logs.emit("install_log", &msg).ok();
-                toast_events::emit_installation_failed(&window_for_logs, &msg);
-                error!("Installation thread panicked!");
-            }
-        }
-    });
+    let entries = std::fs::read_dir(&usmap_folder)
+        .map_err(|e| format!("Failed to read Usmap directory: {}", e))?;
     
-    // Monitor progress
-    std::thread::spawn(move || {
-        loop {
-            let current = counter_clone.load(std::sync::atomic::Ordering::SeqCst);
-            if current == -255 {
-                window_clone.emit("install_complete", ()).ok();
-                break;
+    let mut files = Vec::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("usmap") {
+            if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
+                files.push(filename.to_string());
             }
-            let progress = (current as f32 / total as f32) * 100.0;
-            window_clone.emit("install_progress", progress).ok();
-            std::thread::sleep(std::time::Duration::from_millis(100));
         }
-    });
+    }
     
+    Ok(files)
+}
+
+/// Make one of the stored USMAP files the active one, keeping the others on disk.
+/// 
+/// # Arguments
+/// * `filename` - Name of a .usmap file in the roaming Usmap folder
+#[tauri::command]
+async fn set_active_usmap(filename: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("set_active_usmap called: {}", filename);
+    if get_usmap_full_path(&filename).is_none() {
+        return Err(format!("USMAP file not found in Usmap folder: {}", filename));
+    }
+    let mut state = state.write().await;
+    state.usmap_path = filename;
+    save_state(&state).map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Get the currently active USMAP file.
+/// 
+/// # Returns
+/// - Filename of the active .usmap file, if it still exists on disk
+/// - Empty string if none is active
 #[tauri::command]
-async fn delete_mod(path: String, window: Window) -> Result<(), String> {
-    let path_buf = PathBuf::from(&path);
-    log::info!("delete_mod called with path: {}", path);
-    
-    // Determine the actual file to delete - check both .pak and .bak_repak variants
-    let (actual_path, is_disabled) = if path_buf.exists() {
-        (path_buf.clone(), path.ends_with(".bak_repak"))
-    } else if path.ends_with(".pak") {
-        // The .pak file doesn't exist, check if there's a disabled version (.bak_repak)
-        let disabled_path = PathBuf::from(format!("{}.bak_repak", path.trim_end_matches(".pak")));
-        if disabled_path.exists() {
-            log::info!("Found disabled mod at: {:?}", disabled_path);
-            (disabled_path, true)
-        } else {
-            (path_buf.clone(), false)
-        }
-    } else if path.ends_with(".bak_repak") {
-        // The .bak_repak doesn't exist, check if there's an enabled version (.pak)
-        let enabled_path = PathBuf::from(format!("{}.pak", path.trim_end_matches(".pak.bak_repak")));
-        if enabled_path.exists() {
-            log::info!("Found enabled mod at: {:?}", enabled_path);
-            (enabled_path, false)
-        } else {
-            (path_buf.clone(), true)
-        }
-    } else {
-        (path_buf.clone(), false)
+async fn get_current_usmap_file(state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
+    let active = state.read().await.usmap_path.clone();
+    Ok(if get_usmap_full_path(&active).is_some() { active } else { String::new() })
+}
+
+/// Get the full path to the currently active USMAP file.
+/// 
+/// # Returns
+/// - Full path to the active .usmap file if it exists
+/// - Empty string if none is active
+#[tauri::command]
+async fn get_current_usmap_full_path(state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
+    let active = state.read().await.usmap_path.clone();
+    Ok(get_usmap_full_path(&active)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default())
+}
+
+/// Delete the currently active USMAP file from the roaming folder and clear the selection.
+/// 
+/// # Returns
+/// - `true` if a file was deleted
+/// - `false` if no active file existed to delete
+#[tauri::command]
+async fn delete_current_usmap(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let mut state = state.write().await;
+    let Some(path) = get_usmap_full_path(&state.usmap_path) else {
+        return Ok(false);
     };
+    std::fs::remove_file(&path)
+        .map_err(|e| format!("Failed to delete USMAP file: {}", e))?;
+    info!("Deleted USMAP file: {:?}", path);
+    state.usmap_path.clear();
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Delete every USMAP file from the roaming folder and clear the active selection.
+/// 
+/// # Returns
+/// - `true` if at least one file was deleted
+/// - `false` if no files existed to delete
+#[tauri::command]
+async fn delete_all_usmaps(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let usmap_folder = usmap_dir();
     
-    log::info!("Attempting to delete: {:?} (is_disabled: {})", actual_path, is_disabled);
+    if !usmap_folder.exists() {
+        return Ok(false);
+    }
     
-    // Try to delete the main file
-    if actual_path.exists() {
-        if let Err(e) = std::fs::remove_file(&actual_path) {
-            let error_msg = format!("Failed to delete mod file: {}", e);
-            toast_events::emit_delete_failed(&window, &error_msg);
-            return Err(error_msg);
+    let entries = std::fs::read_dir(&usmap_folder)
+        .map_err(|e| format!("Failed to read Usmap directory: {}", e))?;
+    
+    let mut deleted = false;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("usmap") {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to delete USMAP file: {}", e))?;
+            info!("Deleted USMAP file: {:?}", path);
+            deleted = true;
         }
-        log::info!("Deleted main mod file: {:?}", actual_path);
-    } else {
-        log::warn!("Main mod file does not exist: {:?}", actual_path);
     }
-
-    // Determine the base path for IoStore files (always based on .pak name, not .bak_repak)
-    let base_pak_path = if is_disabled || path.ends_with(".bak_repak") {
-        // For disabled mods, derive the .pak base path
-        let path_str = if actual_path.to_string_lossy().ends_with(".bak_repak") {
-            actual_path.to_string_lossy().trim_end_matches(".bak_repak").to_string()
-        } else {
-            path.trim_end_matches(".pak.bak_repak").to_string()
-        };
-        PathBuf::from(format!("{}.pak", path_str))
-    } else {
-        actual_path.clone()
-    };
     
-    log::info!("Base path for IoStore files: {:?}", base_pak_path);
+    let mut state = state.write().await;
+    state.usmap_path.clear();
+    save_state(&state).map_err(|e| e.to_string())?;
     
-    // Delete associated IoStore files (.ucas and .utoc)
-    let ucas_path = base_pak_path.with_extension("ucas");
-    if ucas_path.exists() {
-        if let Err(e) = std::fs::remove_file(&ucas_path) {
-            log::warn!("Failed to delete .ucas file: {}", e);
-        } else {
-            log::info!("Deleted associated .ucas file: {:?}", ucas_path);
+    Ok(deleted)
+}
+
+#[tauri::command]
+async fn get_all_tags(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<String>, String> {
+    let state = state.read().await;
+    let mut tags = std::collections::HashSet::new();
+    
+    for metadata in &state.mod_metadata {
+        for tag in &metadata.custom_tags {
+            tags.insert(tag.clone());
         }
     }
     
-    let utoc_path = base_pak_path.with_extension("utoc");
-    if utoc_path.exists() {
-        if let Err(e) = std::fs::remove_file(&utoc_path) {
-            log::warn!("Failed to delete .utoc file: {}", e);
-        } else {
-            log::info!("Deleted associated .utoc file: {:?}", utoc_path);
+    let mut tags_vec: Vec<String> = tags.into_iter().collect();
+    tags_vec.sort();
+    Ok(tags_vec)
+}
+
+/// Store (or clear, with `None`/empty) the source URL a mod was downloaded from
+#[tauri::command]
+async fn set_mod_source_url(
+    mod_path: String,
+    url: Option<String>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_mod_source_url called: mod_path={}, url={:?}", mod_path, url);
+
+    let url = match url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()) {
+        Some(u) => {
+            let parsed = url::Url::parse(&u).map_err(|e| format!("Invalid URL: {}", e))?;
+            if parsed.scheme() != "http" && parsed.scheme() != "https" {
+                return Err("Only http(s) URLs are supported".to_string());
+            }
+            Some(parsed.to_string())
         }
+        None => None,
+    };
+
+    let mut state = state.write().await;
+    let path = PathBuf::from(&mod_path);
+
+    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.matches(&path)) {
+        metadata.source_url = url;
+    } else {
+        state.mod_metadata.push(ModMetadata {
+            path,
+            custom_name: None,
+            folder_id: None,
+            custom_tags: Vec::new(),
+            source_url: url,
+            thumbnail_path: None,
+        });
     }
-    
+
+    save_state(&state).map_err(|e| e.to_string())?;
     Ok(())
 }
 
-/// Result of an update_mod operation
-#[derive(Clone, Serialize, Deserialize)]
-struct UpdateModResult {
-    /// Path to the newly installed mod
-    new_mod_path: String,
-    /// Whether the old mod was successfully deleted
-    old_mod_deleted: bool,
-    /// The preserved metadata that was applied
-    preserved_enabled_state: bool,
-    preserved_folder: Option<String>,
+/// Open a mod's stored source URL in the default browser
+#[tauri::command]
+async fn open_mod_source(
+    mod_path: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    let path = PathBuf::from(&mod_path);
+    let url = {
+        let state = state.read().await;
+        state.mod_metadata.iter()
+            .find(|m| m.matches(&path))
+            .and_then(|m| m.source_url.clone())
+    }.ok_or_else(|| "No source URL stored for this mod".to_string())?;
+
+    // Re-validate in case state.json was edited by hand
+    let parsed = url::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err("Only http(s) URLs are supported".to_string());
+    }
+
+    info!("open_mod_source: opening {}", url);
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer.exe")
+            .arg(parsed.as_str())
+            .spawn()
+            .map_err(|e| format!("Failed to open browser: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(parsed.as_str())
+            .spawn()
+            .map_err(|e| format!("Failed to open browser: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(parsed.as_str())
+            .spawn()
+            .map_err(|e| format!("Failed to open browser: {}", e))?;
+    }
+
+    Ok(())
 }
 
-/// Update (replace) an existing mod with new mod files.
-/// This preserves the mod's metadata (folder location, enabled state, custom name, tags)
-/// and replaces the old mod files with the new ones.
-/// 
-/// # Arguments
-/// * `old_mod_path` - Path to the existing mod to be replaced
-/// * `new_mod_source` - Path to the new mod files (can be .pak, .zip, .rar, .7z, or directory)
-/// * `preserve_name` - If true, keeps the old mod's name; if false, uses the new mod's name
-/// * `window` - Tauri window for emitting events
-/// * `state` - Application state
+/// Pick the texture most likely to show what a skin mod looks like: a base color/diffuse map
+/// if there is one, otherwise the first texture-looking asset
+fn pick_thumbnail_texture(files: &[String]) -> Option<&String> {
+    let stem = |f: &String| Path::new(f).file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let textures: Vec<&String> = files.iter()
+        .filter(|f| f.to_lowercase().ends_with(".uasset"))
+        .filter(|f| stem(f).starts_with("t_") || f.to_lowercase().contains("/texture"))
+        .collect();
+    textures.iter()
+        .find(|f| {
+            let stem = stem(f);
+            stem.ends_with("_d") || stem.ends_with("_basecolor") || stem.ends_with("_diffuse")
+        })
+        .or_else(|| textures.first())
+        .copied()
+}
+
+/// Set a mod's cover image. The image is copied into the app's thumbnails folder so it survives
+/// the original being moved. Without `image_path` a preview of the mod's main texture is used.
 #[tauri::command]
-async fn update_mod(
-    old_mod_path: String,
-    new_mod_source: String,
-    preserve_name: bool,
-    window: Window,
-    state: State<'_, Arc<Mutex<AppState>>>,
-) -> Result<UpdateModResult, String> {
-    info!("update_mod called: old={}, new={}, preserve_name={}", old_mod_path, new_mod_source, preserve_name);
-    
-    let old_path = PathBuf::from(&old_mod_path);
-    let new_source = PathBuf::from(&new_mod_source);
-    
-    // Validate new source exists
-    if !new_source.exists() {
-        let err = format!("New mod source does not exist: {}", new_mod_source);
-        toast_events::emit_installation_failed(&window, &err);
-        return Err(err);
+async fn set_mod_thumbnail(
+    mod_path: String,
+    image_path: Option<String>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+
+    info!("set_mod_thumbnail called: mod_path={}, image_path={:?}", mod_path, image_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
     }
-    
-    // ========================================================================
-    // Step 1: Gather metadata from the old mod
-    // ========================================================================
-    
-    // Determine the actual old mod path (handle .pak vs .bak_repak)
-    let (actual_old_path, was_disabled) = if old_path.exists() {
-        (old_path.clone(), old_mod_path.ends_with(".bak_repak") || old_mod_path.ends_with(".pak_disabled"))
-    } else if old_mod_path.ends_with(".pak") {
-        // Check for disabled versions
-        let bak_repak_path = PathBuf::from(format!("{}.bak_repak", old_mod_path.trim_end_matches(".pak")));
-        let pak_disabled_path = PathBuf::from(format!("{}_disabled", old_mod_path.trim_end_matches(".pak")));
-        if bak_repak_path.exists() {
-            (bak_repak_path, true)
-        } else if pak_disabled_path.exists() {
-            (pak_disabled_path, true)
-        } else {
-            return Err(format!("Old mod not found: {}", old_mod_path));
+
+    let thumbnails_dir = app_dir().join("thumbnails");
+    std::fs::create_dir_all(&thumbnails_dir)
+        .map_err(|e| format!("Failed to create thumbnails folder: {}", e))?;
+    // Keyed by the enabled path so toggling the mod doesn't orphan its thumbnail
+    let key = hex::encode(Sha256::digest(path.with_extension("pak").to_string_lossy().as_bytes()));
+    let key = &key[..16];
+
+    let thumbnail = match image_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+        Some(image_path) => {
+            let image = PathBuf::from(&image_path);
+            let ext = image.extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .filter(|e| matches!(e.as_str(), "png" | "jpg" | "jpeg" | "webp" | "gif" | "bmp"))
+                .ok_or_else(|| "Unsupported image format".to_string())?;
+            let thumbnail = thumbnails_dir.join(format!("{}.{}", key, ext));
+            std::fs::copy(&image, &thumbnail)
+                .map_err(|e| format!("Failed to copy thumbnail: {}", e))?;
+            thumbnail
+        }
+        None => {
+            let details = get_mod_details(mod_path.clone(), None).await?;
+            let texture = pick_thumbnail_texture(&details.files)
+                .ok_or_else(|| "No texture found in this mod to use as a thumbnail".to_string())?
+                .clone();
+            info!("Generating thumbnail from {}", texture);
+            let png = preview_mod_texture(mod_path.clone(), texture).await?;
+            let thumbnail = thumbnails_dir.join(format!("{}.png", key));
+            std::fs::write(&thumbnail, png)
+                .map_err(|e| format!("Failed to write thumbnail: {}", e))?;
+            thumbnail
         }
-    } else {
-        return Err(format!("Old mod not found: {}", old_mod_path));
-    };
-    
-    info!("Actual old mod path: {:?}, was_disabled: {}", actual_old_path, was_disabled);
-    
-    // Get the old mod's folder (subfolder within mods directory)
-    let game_path = {
-        let state_guard = state.lock().unwrap();
-        state_guard.game_path.clone()
     };
-    
-    let install_subfolder = if let Some(parent) = actual_old_path.parent() {
-        if parent == game_path {
-            String::new() // Root folder
-        } else {
-            parent.strip_prefix(&game_path)
-                .map(|p| p.to_string_lossy().replace('\\', "/"))
-                .unwrap_or_default()
+    let thumbnail = thumbnail.to_string_lossy().to_string();
+
+    let mut state = state.write().await;
+    let existing = state.mod_metadata.iter_mut().find(|m| m.matches(&path));
+    if let Some(metadata) = existing {
+        // A different image format leaves the previous file behind
+        if let Some(old) = metadata.thumbnail_path.replace(thumbnail.clone()).filter(|old| *old != thumbnail) {
+            let _ = std::fs::remove_file(old);
         }
     } else {
-        String::new()
+        state.mod_metadata.push(ModMetadata {
+            path,
+            custom_name: None,
+            folder_id: None,
+            custom_tags: Vec::new(),
+            source_url: None,
+            thumbnail_path: Some(thumbnail.clone()),
+        });
+    }
+
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(thumbnail)
+}
+
+/// Manifest stored as `repakx_mod.json` inside an exported mod bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModBundleManifest {
+    /// Bumped if the bundle layout ever changes incompatibly
+    format_version: u32,
+    name: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    source_url: Option<String>,
+    #[serde(default)]
+    character: Option<String>,
+    #[serde(default)]
+    skin: Option<String>,
+    #[serde(default)]
+    author: Option<String>,
+    /// Mod files in the bundle (.pak plus .utoc/.ucas for IoStore mods)
+    files: Vec<String>,
+}
+
+const MOD_BUNDLE_MANIFEST: &str = "repakx_mod.json";
+const MOD_BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Export a mod as a .zip holding its pak/IoStore files and a `repakx_mod.json` manifest with its
+/// custom name, tags, source URL and character/skin, so another Repak-X user can import it as curated.
+/// `dest` is either a folder (the bundle is named after the mod) or the .zip path. Returns the written path.
+#[tauri::command]
+async fn export_mod_bundle(
+    mod_path: String,
+    dest: String,
+    author: Option<String>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<String, String> {
+    use std::io::Write;
+
+    info!("export_mod_bundle called: mod_path={}, dest={}", mod_path, dest);
+    let path = PathBuf::from(&mod_path);
+    if !path.is_file() {
+        return Err(format!("Mod file not found: {}", mod_path));
+    }
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Mod").to_string();
+    let base_name = mod_base_name_without_priority(&stem);
+
+    let (custom_name, tags, source_url) = {
+        let state = state.read().await;
+        state.mod_metadata.iter()
+            .find(|m| m.matches(&path))
+            .map(|m| (m.custom_name.clone(), m.custom_tags.clone(), m.source_url.clone()))
+            .unwrap_or_default()
     };
-    
-    info!("Preserved install subfolder: {}", install_subfolder);
-    
-    // Get the old mod's custom name and tags from metadata
-    let (old_custom_name, old_custom_tags, old_folder_id) = {
-        let state_guard = state.lock().unwrap();
-        let metadata = state_guard.mod_metadata.iter()
-            .find(|m| {
-                m.path == actual_old_path || 
-                m.path.with_extension("pak") == actual_old_path ||
-                m.path.with_extension("bak_repak") == actual_old_path ||
-                m.path.with_extension("pak_disabled") == actual_old_path
-            });
-        
-        match metadata {
-            Some(m) => (m.custom_name.clone(), m.custom_tags.clone(), m.folder_id.clone()),
-            None => (None, Vec::new(), None),
-        }
+    let (character, skin) = match resolve_mod_character_names(&path) {
+        Some((character, skin)) => (Some(character), Some(skin)),
+        None => (None, None),
     };
-    
-    info!("Preserved metadata - custom_name: {:?}, tags: {:?}, folder_id: {:?}", 
-          old_custom_name, old_custom_tags, old_folder_id);
-    
-    // Get the old mod's base name (for naming the new mod if preserve_name is true)
-    let old_mod_name = actual_old_path.file_stem()
-        .and_then(|s| s.to_str())
-        .map(|s| {
-            // Strip .bak_repak or _disabled suffix if present
-            s.trim_end_matches(".bak_repak")
-             .trim_end_matches("_disabled")
-             .to_string()
-        })
-        .unwrap_or_else(|| "Unknown".to_string());
-    
-    // ========================================================================
-    // Step 2: Delete the old mod files
-    // ========================================================================
-    
-    info!("Deleting old mod files...");
-    
-    // Delete main file
-    let mut old_deleted = false;
-    if actual_old_path.exists() {
-        if let Err(e) = std::fs::remove_file(&actual_old_path) {
-            warn!("Failed to delete old mod file: {}", e);
-        } else {
-            old_deleted = true;
-            info!("Deleted old mod file: {:?}", actual_old_path);
+
+    // Disabled mods are bundled under their enabled name so the bundle installs like any other pak
+    let mut files: Vec<(PathBuf, String)> = vec![(path.clone(), format!("{}.pak", stem))];
+    for ext in ["utoc", "ucas"] {
+        let companion = iostore_companion_path(&path, ext);
+        if companion.exists() {
+            files.push((companion, format!("{}.{}", stem, ext)));
         }
     }
-    
-    // Delete associated IoStore files (.ucas and .utoc)
-    // Base path is always the .pak version
-    let base_pak_path = if was_disabled {
-        let path_str = actual_old_path.to_string_lossy();
-        let clean = path_str
-            .trim_end_matches(".bak_repak")
-            .trim_end_matches("_disabled");
-        if clean.ends_with(".pak") {
-            PathBuf::from(clean)
-        } else {
-            PathBuf::from(format!("{}.pak", clean))
-        }
-    } else {
-        actual_old_path.clone()
+
+    let manifest = ModBundleManifest {
+        format_version: MOD_BUNDLE_FORMAT_VERSION,
+        name: custom_name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| base_name.clone()),
+        tags,
+        source_url,
+        character,
+        skin,
+        author: author.map(|a| a.trim().to_string()).filter(|a| !a.is_empty()),
+        files: files.iter().map(|(_, name)| name.clone()).collect(),
     };
-    
-    for ext in &["ucas", "utoc"] {
-        let companion_path = base_pak_path.with_extension(ext);
-        if companion_path.exists() {
-            if let Err(e) = std::fs::remove_file(&companion_path) {
-                warn!("Failed to delete .{} file: {}", ext, e);
-            } else {
-                info!("Deleted associated .{} file: {:?}", ext, companion_path);
-            }
-        }
-    }
-    
-    // ========================================================================
-    // Step 3: Install the new mod
-    // ========================================================================
-    
-    info!("Installing new mod from: {:?}", new_source);
-    
-    // Determine the mod name to use
-    let mod_name = if preserve_name {
-        old_custom_name.clone().unwrap_or(old_mod_name.clone())
+
+    let dest_path = PathBuf::from(&dest);
+    let bundle_path = if dest_path.is_dir() {
+        dest_path.join(format!("{}.zip", base_name))
     } else {
-        new_source.file_stem()
-            .and_then(|s| s.to_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "NewMod".to_string())
+        dest_path
     };
-    
-    // Use the existing install_mods logic
-    use std::sync::atomic::{AtomicI32, AtomicBool};
-    use std::sync::Arc as StdArc;
+
+    let bundle_file = std::fs::File::create(&bundle_path)
+        .map_err(|e| format!("Failed to create {}: {}", bundle_path.display(), e))?;
+    let mut zip = zip::ZipWriter::new(bundle_file);
+    // Paks are already compressed, so store them as-is
+    let stored = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    zip.start_file(MOD_BUNDLE_MANIFEST, zip::write::SimpleFileOptions::default())
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    zip.write_all(&manifest_json)
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+
+    for (source, name) in &files {
+        zip.start_file(name.as_str(), stored)
+            .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+        let mut input = std::fs::File::open(source)
+            .map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+        std::io::copy(&mut input, &mut zip)
+            .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to finish bundle: {}", e))?;
+
+    info!("Exported {} ({} file(s)) to {}", manifest.name, files.len(), bundle_path.display());
+    Ok(bundle_path.to_string_lossy().to_string())
+}
+
+/// Install a bundle written by `export_mod_bundle`, pre-applying the custom name, tags and
+/// source URL from its manifest to the installed mod
+#[tauri::command]
+async fn import_mod_bundle(
+    bundle_path: String,
+    install_subfolder: Option<String>,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    use crate::install_mod::install_mod_logic::normalize_mod_base_name;
     use crate::install_mod::map_paths_to_mods;
-    
-    let state_guard = state.lock().unwrap();
+
+    info!("import_mod_bundle called: {}", bundle_path);
+    let install_subfolder = crate::install_mod::sanitize_subfolder(&install_subfolder.unwrap_or_default())?;
+
+    let bundle_file = std::fs::File::open(&bundle_path)
+        .map_err(|e| format!("Failed to open bundle: {}", e))?;
+    let mut archive = zip::ZipArchive::new(bundle_file)
+        .map_err(|e| format!("Not a valid mod bundle: {}", e))?;
+
+    let manifest: ModBundleManifest = {
+        let entry = archive.by_name(MOD_BUNDLE_MANIFEST)
+            .map_err(|_| format!("Bundle has no {}", MOD_BUNDLE_MANIFEST))?;
+        serde_json::from_reader(entry)
+            .map_err(|e| format!("Invalid bundle manifest: {}", e))?
+    };
+    if manifest.format_version > MOD_BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Bundle format version {} is newer than this version of Repak-X supports",
+            manifest.format_version
+        ));
+    }
+
+    // Only unpack the mod files the manifest lists, flattened to their file names
+    let staging = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let mut pak_path: Option<PathBuf> = None;
+    for name in &manifest.files {
+        let file_name = Path::new(name).file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let ext = Path::new(file_name).extension().and_then(|e| e.to_str()).unwrap_or_default();
+        if !matches!(ext, "pak" | "utoc" | "ucas") {
+            warn!("[Bundle] Ignoring unexpected file in manifest: {}", name);
+            continue;
+        }
+        let mut entry = archive.by_name(name)
+            .map_err(|e| format!("Bundle is missing {}: {}", name, e))?;
+        let out_path = staging.path().join(file_name);
+        let mut out = std::fs::File::create(&out_path)
+            .map_err(|e| format!("Failed to extract {}: {}", name, e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to extract {}: {}", name, e))?;
+        if ext == "pak" {
+            pak_path = Some(out_path);
+        }
+    }
+    let pak_path = pak_path.ok_or_else(|| "Bundle contains no .pak file".to_string())?;
+
+    let state_guard = state.read().await;
     let mod_directory = state_guard.game_path.clone();
     let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
     let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
     drop(state_guard);
-    
-    // Set USMAP path
+
     if !usmap_filename.is_empty() {
         if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
             std::env::set_var("USMAP_PATH", &usmap_full_path);
         }
     }
-    
-    let paths = vec![new_source.clone()];
-    let mut installable_mods = map_paths_to_mods(&paths);
-    
+    if !mod_directory.exists() {
+        std::fs::create_dir_all(&mod_directory)
+            .map_err(|e| format!("Failed to create mods directory: {}", e))?;
+    }
+
+    let mut installable_mods = map_paths_to_mods(&[pak_path], None);
     if installable_mods.is_empty() {
-        let err = "Failed to parse new mod source - no valid mods found";
-        toast_events::emit_installation_failed(&window, err);
-        return Err(err.to_string());
+        let error_msg = "No valid mods found in the bundle";
+        let _ = window.emit("install_log", format!("ERROR: {}", error_msg));
+        toast_events::emit_installation_failed(&window, error_msg);
+        return Err(error_msg.to_string());
     }
-    
-    // Apply settings to the installable mod
-    if let Some(installable) = installable_mods.get_mut(0) {
-        installable.mod_name = mod_name.clone();
+
+    let source_url = manifest.source_url.clone().filter(|u| {
+        url::Url::parse(u).map(|p| p.scheme() == "http" || p.scheme() == "https").unwrap_or(false)
+    });
+    let staging_guard = Arc::new(staging);
+    let mut state_guard = state.write().await;
+    for installable in installable_mods.iter_mut() {
         installable.install_subfolder = install_subfolder.clone();
-        installable.usmap_path = usmap_filename;
+        installable.usmap_path = usmap_filename.clone();
+        installable.parallel_processing = parallel_processing;
         installable.obfuscate = obfuscate;
+        installable.iostore_version = iostore_version;
+        installable.custom_tags = manifest.tags.clone();
+        if installable.temp_dir.is_none() {
+            installable.temp_dir = Some(staging_guard.clone());
+        }
+
+        // Pre-apply the curated metadata to the path the install will produce
+        let installed_name = format!("{}.pak", normalize_mod_base_name(&installable.mod_name, 7));
+        let installed_path = if install_subfolder.is_empty() {
+            mod_directory.join(&installed_name)
+        } else {
+            mod_directory.join(&install_subfolder).join(&installed_name)
+        };
+        state_guard.mod_metadata.retain(|m| m.path != installed_path);
+        state_guard.mod_metadata.push(ModMetadata {
+            path: installed_path,
+            custom_name: Some(manifest.name.clone()),
+            folder_id: None,
+            custom_tags: manifest.tags.clone(),
+            source_url: source_url.clone(),
+            thumbnail_path: None,
+        });
+    }
+    save_state(&state_guard).map_err(|e| e.to_string())?;
+    drop(state_guard);
+    drop(staging_guard);
+
+    let _ = window.emit("install_log", format!("[Bundle] Importing {} from {}", manifest.name, bundle_path));
+    spawn_install(installable_mods, mod_directory, window);
+    Ok(())
+}
+
+#[tauri::command]
+async fn toggle_mod(mod_path: String, window: Window) -> Result<bool, String> {
+    toggle_mod_file(Path::new(&mod_path)).inspect_err(|error_msg| {
+        toast_events::emit_toggle_failed(&window, error_msg);
+    })
+}
+
+/// Enable/disable a mod by renaming it between `.pak` and `.bak_repak`, returning the new enabled state
+fn toggle_mod_file(path: &Path) -> Result<bool, String> {
+    if !path.exists() {
+        return Err("Mod file does not exist".to_string());
     }
     
-    // Install synchronously for update operation (we need to know the result)
-    let installed_counter = StdArc::new(AtomicI32::new(0));
-    let stop_flag = StdArc::new(AtomicBool::new(false));
-    
-    let window_clone = window.clone();
-    window_clone.emit("install_log", format!("[Update] Replacing mod: {}", old_mod_name)).ok();
-    window_clone.emit("install_log", format!("[Update] New source: {}", new_mod_source)).ok();
-    
-    use crate::install_mod::install_mod_logic::install_mods_in_viewport;
-    
-    install_mods_in_viewport(
-        &mut installable_mods,
-        &mod_directory,
-        &installed_counter,
-        &stop_flag,
-    );
-    
-    // ========================================================================
-    // Step 4: Apply preserved metadata to the new mod
-    // ========================================================================
+    // Check current state
+    let is_enabled = path.extension().and_then(|s| s.to_str()) == Some("pak");
     
-    // Determine the new mod's path
-    let new_mod_filename = format!("{}_9999999_P.pak", mod_name);
-    let new_mod_path = if install_subfolder.is_empty() {
-        mod_directory.join(&new_mod_filename)
+    // Toggle by renaming
+    let new_path = if is_enabled {
+        path.with_extension("bak_repak")
     } else {
-        mod_directory.join(&install_subfolder).join(&new_mod_filename)
+        path.with_extension("pak")
     };
     
-    info!("Expected new mod path: {:?}", new_mod_path);
-    
-    // If the old mod was disabled, disable the new one too
-    if was_disabled && new_mod_path.exists() {
-        let disabled_path = PathBuf::from(format!("{}.bak_repak", 
-            new_mod_path.to_string_lossy().trim_end_matches(".pak")));
-        if let Err(e) = std::fs::rename(&new_mod_path, &disabled_path) {
-            warn!("Failed to disable new mod to match old state: {}", e);
+    std::fs::rename(path, &new_path).map_err(|e| format!("Failed to toggle mod: {}", e))?;
+
+    // The game can mount a mod purely from its IoStore container (the pak being a stub),
+    // so the .utoc/.ucas have to follow the pak's state or the mod keeps loading
+    let mut renamed: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for ext in ["utoc", "ucas"] {
+        let disabled_ext = format!("{}_disabled", ext);
+        let (from, to) = if is_enabled {
+            (path.with_extension(ext), path.with_extension(&disabled_ext))
         } else {
-            info!("Disabled new mod to match old mod's state");
+            (path.with_extension(&disabled_ext), path.with_extension(ext))
+        };
+        if from.exists() {
+            if let Err(e) = std::fs::rename(&from, &to) {
+                // Put everything back so the mod isn't left half toggled
+                for (from, to) in renamed.iter().rev() {
+                    let _ = std::fs::rename(to, from);
+                }
+                let _ = std::fs::rename(&new_path, path);
+                return Err(format!("Failed to toggle .{} file: {}", ext, e));
+            }
+            renamed.push((from, to));
         }
     }
     
-    // Update metadata with preserved tags and folder assignment
-    if !old_custom_tags.is_empty() || old_folder_id.is_some() || old_custom_name.is_some() {
-        let mut state_guard = state.lock().unwrap();
-        
-        // Find or create metadata entry for the new mod
-        let new_path_for_metadata = if was_disabled {
-            PathBuf::from(format!("{}.bak_repak", 
-                new_mod_path.to_string_lossy().trim_end_matches(".pak")))
-        } else {
-            new_mod_path.clone()
-        };
-        
-        // Remove old metadata entry if it exists
-        state_guard.mod_metadata.retain(|m| {
-            m.path != actual_old_path && 
-            m.path != old_path &&
-            m.path.with_extension("pak") != actual_old_path
-        });
-        
-        // Add new metadata entry with preserved data
-        state_guard.mod_metadata.push(ModMetadata {
-            path: new_path_for_metadata.clone(),
-            custom_name: if preserve_name { old_custom_name } else { Some(mod_name.clone()) },
-            folder_id: old_folder_id,
-            custom_tags: old_custom_tags,
-        });
-        
-        // Save state
-        if let Err(e) = save_state(&state_guard) {
-            warn!("Failed to save state after update: {}", e);
+    Ok(!is_enabled)
+}
+
+/// What isolate_mod changed, so restore_from_isolation can put the library back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IsolateToken {
+    id: String,
+    /// The mod left enabled, by its .pak path
+    mod_path: PathBuf,
+    /// Whether the target was already enabled before isolating
+    target_was_enabled: bool,
+    /// Mods (by .pak path) that were enabled and got disabled
+    disabled_mods: Vec<PathBuf>,
+}
+
+/// Disable every other enabled mod and enable `mod_path` on its own, to test-load it in isolation
+#[tauri::command]
+async fn isolate_mod(
+    mod_path: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<IsolateToken, String> {
+    info!("isolate_mod called: {}", mod_path);
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+    let mut state = state.write().await;
+    if let Some(active) = &state.isolation {
+        return Err(format!(
+            "{} is already isolated, restore the other mods first",
+            active.mod_path.file_stem().unwrap_or_default().to_string_lossy()
+        ));
+    }
+    if state.bisection.is_some() {
+        return Err("A crash bisection is in progress, finish or cancel it first".to_string());
+    }
+
+    let target = path.with_extension("pak");
+    let target_was_enabled = path.extension().is_some_and(|e| e == "pak");
+    let disabled_mods: Vec<PathBuf> = list_mod_entries(&state.game_path, &state.mod_metadata)?
+        .into_iter()
+        .filter(|m| m.enabled && m.path != target)
+        .map(|m| m.path)
+        .collect();
+    let token = IsolateToken {
+        id: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            .to_string(),
+        mod_path: target,
+        target_was_enabled,
+        disabled_mods,
+    };
+    // Saved before touching any file, restoring skips mods that were never toggled
+    state.isolation = Some(token.clone());
+    save_state(&state).map_err(|e| e.to_string())?;
+
+    let mut result = Ok(());
+    if !target_was_enabled {
+        result = toggle_mod_file(&path).map(|_| ());
+    }
+    let mut toggled = Vec::new();
+    for mod_path in &token.disabled_mods {
+        if result.is_err() {
+            break;
         }
+        result = toggle_mod_file(mod_path).map(|_| toggled.push(mod_path.with_extension("bak_repak")));
     }
-    
-    // Emit success event
-    toast_events::emit_success(&window, "Mod Updated", format!("Successfully replaced mod: {}", mod_name));
-    window.emit("install_complete", ()).ok();
-    
-    info!("update_mod completed successfully");
-    
-    Ok(UpdateModResult {
-        new_mod_path: new_mod_path.to_string_lossy().to_string(),
-        old_mod_deleted: old_deleted,
-        preserved_enabled_state: was_disabled,
-        preserved_folder: if install_subfolder.is_empty() { None } else { Some(install_subfolder) },
-    })
+    if let Err(e) = result {
+        // Leave the library the way it was
+        for disabled in toggled {
+            let _ = toggle_mod_file(&disabled);
+        }
+        if !target_was_enabled && token.mod_path.exists() {
+            let _ = toggle_mod_file(&token.mod_path);
+        }
+        state.isolation = None;
+        save_state(&state).map_err(|e| e.to_string())?;
+        return Err(format!("Failed to isolate mod: {}", e));
+    }
+
+    info!("Isolated {} ({} other mod(s) disabled)", token.mod_path.display(), token.disabled_mods.len());
+    Ok(token)
 }
 
+/// Re-enable the mods isolate_mod disabled and put the isolated mod back in its previous state
 #[tauri::command]
-async fn open_in_explorer(path: String) -> Result<(), String> {
-    let path_buf = PathBuf::from(&path);
-    
-    info!("open_in_explorer called: path={}", path);
-    
-    if !path_buf.exists() {
-        return Err(format!("Path does not exist: {}", path_buf.display()));
+async fn restore_from_isolation(
+    token_id: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    info!("restore_from_isolation called: {}", token_id);
+    let mut state = state.write().await;
+    let token = match &state.isolation {
+        Some(token) if token.id == token_id => token.clone(),
+        Some(token) => return Err(format!("Isolation {} is not the active one ({})", token_id, token.id)),
+        None => return Err("No mod is currently isolated".to_string()),
+    };
+
+    let mut restored = 0;
+    let mut errors = Vec::new();
+    for mod_path in &token.disabled_mods {
+        let disabled = mod_path.with_extension("bak_repak");
+        // Re-enabled by hand (or removed) since, nothing to put back
+        if !disabled.exists() {
+            continue;
+        }
+        match toggle_mod_file(&disabled) {
+            Ok(_) => restored += 1,
+            Err(e) => errors.push(format!("{}: {}", mod_path.display(), e)),
+        }
     }
-    
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        
-        // On Windows, use explorer.exe with /select, to highlight the file
-        // The path must be quoted if it contains spaces, and use backslashes
-        let canonical_path = path_buf.canonicalize()
-            .unwrap_or_else(|_| path_buf.clone());
-        
-        // Remove the \\?\ prefix that canonicalize adds on Windows
-        let path_str = canonical_path.to_string_lossy();
-        let clean_path = if path_str.starts_with(r"\\?\") {
-            path_str[4..].to_string()
-        } else {
-            path_str.to_string()
+    if !token.target_was_enabled && token.mod_path.exists() {
+        if let Err(e) = toggle_mod_file(&token.mod_path) {
+            errors.push(format!("{}: {}", token.mod_path.display(), e));
+        }
+    }
+    if !errors.is_empty() {
+        // Keep the token so the restore can be retried
+        return Err(format!("Failed to restore some mods: {}", errors.join("; ")));
+    }
+
+    state.isolation = None;
+    save_state(&state).map_err(|e| e.to_string())?;
+    info!("Restored {} mod(s) after isolating {}", restored, token.mod_path.display());
+    Ok(restored)
+}
+
+/// The isolation left active by isolate_mod, if any (e.g. after a crash mid-test)
+#[tauri::command]
+async fn get_active_isolation(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Option<IsolateToken>, String> {
+    Ok(state.read().await.isolation.clone())
+}
+
+/// A guided binary search for the mod that crashes the game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BisectionSession {
+    id: String,
+    /// Every mod (by .pak path) that was enabled when the bisection started
+    all_mods: Vec<PathBuf>,
+    /// Mods that may still be the culprit
+    candidates: Vec<PathBuf>,
+    /// The half of the candidates enabled for the current launch
+    testing: Vec<PathBuf>,
+    /// Launches reported so far
+    step: usize,
+    /// Set once the candidates are narrowed down to one mod
+    culprit: Option<PathBuf>,
+}
+
+impl BisectionSession {
+    fn new(id: String, all_mods: Vec<PathBuf>) -> Self {
+        let mut session = BisectionSession {
+            id,
+            candidates: all_mods.clone(),
+            all_mods,
+            testing: Vec::new(),
+            step: 0,
+            culprit: None,
         };
-        
-        info!("open_in_explorer: using path={}", clean_path);
-        
-        // Use /select, with the path - explorer handles the quoting
-        let select_arg = format!("/select,\"{}\"", clean_path);
-        std::process::Command::new("explorer.exe")
-            .raw_arg(&select_arg)
-            .spawn()
-            .map_err(|e| format!("Failed to open explorer: {}", e))?;
+        session.split();
+        session
     }
-    
-    #[cfg(target_os = "macos")]
-    {
-        // On macOS, use open -R to reveal the file in Finder
-        std::process::Command::new("open")
-            .args(["-R", &path_buf.to_string_lossy()])
-            .spawn()
-            .map_err(|e| format!("Failed to open Finder: {}", e))?;
+
+    /// Narrow the candidates down from the last launch
+    fn record(&mut self, crashed: bool) {
+        self.step += 1;
+        if crashed {
+            self.candidates = std::mem::take(&mut self.testing);
+        } else {
+            self.candidates.retain(|m| !self.testing.contains(m));
+        }
+        self.split();
     }
-    
-    #[cfg(target_os = "linux")]
-    {
-        // On Linux, open the parent directory
-        let dir_to_open = if path_buf.is_file() {
-            path_buf.parent().map(|p| p.to_path_buf()).unwrap_or(path_buf.clone())
+
+    fn split(&mut self) {
+        if self.candidates.len() == 1 {
+            self.culprit = self.candidates.first().cloned();
+            self.testing.clear();
         } else {
-            path_buf.clone()
+            self.testing = self.candidates[..self.candidates.len().div_ceil(2)].to_vec();
+        }
+    }
+
+    /// Launches still needed in the worst case
+    fn remaining_steps(&self) -> u32 {
+        if self.culprit.is_some() {
+            0
+        } else {
+            self.candidates.len().next_power_of_two().trailing_zeros()
+        }
+    }
+}
+
+/// Enable exactly `enabled` out of the session's mods, toggling only the ones in the wrong state
+fn apply_bisection_mods(all_mods: &[PathBuf], enabled: &[PathBuf]) -> Result<(), String> {
+    for mod_path in all_mods {
+        let want_enabled = enabled.contains(mod_path);
+        let current = if mod_path.exists() {
+            mod_path.clone()
+        } else {
+            mod_path.with_extension("bak_repak")
         };
-        std::process::Command::new("xdg-open")
-            .arg(&dir_to_open)
-            .spawn()
-            .map_err(|e| format!("Failed to open file manager: {}", e))?;
+        // Removed since the bisection started
+        if !current.exists() {
+            continue;
+        }
+        if want_enabled != (current == *mod_path) {
+            toggle_mod_file(&current).map_err(|e| format!("{}: {}", mod_path.display(), e))?;
+        }
     }
-    
     Ok(())
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct BisectionStatus {
+    session: BisectionSession,
+    remaining_steps: u32,
+}
+
+impl From<BisectionSession> for BisectionStatus {
+    fn from(session: BisectionSession) -> Self {
+        BisectionStatus { remaining_steps: session.remaining_steps(), session }
+    }
+}
+
+/// Start a binary search over the enabled mods for the one crashing the game: half of them
+/// stay enabled, the user launches and reports the outcome with bisection_result
+#[tauri::command]
+async fn start_crash_bisection(state: State<'_, Arc<RwLock<AppState>>>) -> Result<BisectionStatus, String> {
+    info!("start_crash_bisection called");
+    let mut state = state.write().await;
+    if state.bisection.is_some() {
+        return Err("A crash bisection is already in progress".to_string());
+    }
+    if state.isolation.is_some() {
+        return Err("A mod is isolated, restore the other mods first".to_string());
+    }
+    let enabled: Vec<PathBuf> = list_mod_entries(&state.game_path, &state.mod_metadata)?
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.path)
+        .collect();
+    if enabled.len() < 2 {
+        return Err("Bisection needs at least two enabled mods".to_string());
+    }
+
+    let id = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .to_string();
+    let session = BisectionSession::new(id, enabled);
+    state.bisection = Some(session.clone());
+    save_state(&state).map_err(|e| e.to_string())?;
+    apply_bisection_mods(&session.all_mods, &session.testing)?;
+
+    info!("[Bisect] Started over {} mod(s), testing {}", session.all_mods.len(), session.testing.len());
+    Ok(session.into())
+}
+
+/// Report whether the game crashed with the current half enabled. Once the culprit is
+/// found every mod from the start is enabled again, except the culprit.
+#[tauri::command]
+async fn bisection_result(
+    session_id: String,
+    crashed: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<BisectionStatus, String> {
+    info!("bisection_result called: {} crashed={}", session_id, crashed);
+    let mut state = state.write().await;
+    let mut session = match &state.bisection {
+        Some(session) if session.id == session_id => session.clone(),
+        Some(session) => return Err(format!("Bisection {} is not the active one ({})", session_id, session.id)),
+        None => return Err("No crash bisection is in progress".to_string()),
+    };
+    if session.culprit.is_some() {
+        return Err("The bisection already found the culprit".to_string());
+    }
+
+    session.record(crashed);
+    let enabled = match &session.culprit {
+        Some(culprit) => {
+            info!("[Bisect] Culprit after {} launch(es): {}", session.step, culprit.display());
+            session.all_mods.iter().filter(|m| *m != culprit).cloned().collect()
+        }
+        None => session.testing.clone(),
+    };
+    apply_bisection_mods(&session.all_mods, &enabled)?;
+    state.bisection = if session.culprit.is_some() { None } else { Some(session.clone()) };
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(session.into())
+}
+
+/// Stop the bisection and re-enable every mod that was enabled when it started
+#[tauri::command]
+async fn cancel_crash_bisection(state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("cancel_crash_bisection called");
+    let mut state = state.write().await;
+    let session = state.bisection.clone().ok_or("No crash bisection is in progress")?;
+    apply_bisection_mods(&session.all_mods, &session.all_mods)?;
+    state.bisection = None;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// The bisection in progress, if any (it survives app restarts)
 #[tauri::command]
-async fn copy_to_clipboard(text: String, window: Window) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::{Command, Stdio};
-        use std::os::windows::process::CommandExt;
-        
-        let mut child = Command::new("powershell")
-            .args(["-Command", "Set-Clipboard", "-Value", &format!("'{}'", text.replace("'", "''"))])
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .creation_flags(0x08000000) // CREATE_NO_WINDOW - prevents PowerShell window from showing
-            .spawn()
-            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-        
-        child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+async fn get_crash_bisection(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Option<BisectionStatus>, String> {
+    Ok(state.read().await.bisection.clone().map(Into::into))
+}
+
+/// Path of a mod's .utoc/.ucas companion, which is renamed to `.<ext>_disabled`
+/// while the mod is disabled
+fn iostore_companion_path(pak_path: &Path, ext: &str) -> PathBuf {
+    let disabled = pak_path.with_extension(format!("{}_disabled", ext));
+    if disabled.exists() {
+        disabled
+    } else {
+        pak_path.with_extension(ext)
     }
+}
+
+#[tauri::command]
+async fn extract_pak_to_destination(mod_path: String, dest_path: String) -> Result<(), String> {
+    use crate::install_mod::install_mod_logic::pak_files::extract_pak_to_dir;
+    use crate::install_mod::InstallableMod;
+    use repak::PakBuilder;
+    use repak::utils::AesKey;
+    use std::str::FromStr;
+    use std::io::BufReader;
     
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::{Command, Stdio};
-        use std::io::Write;
-        
-        let mut child = Command::new("pbcopy")
-            .stdin(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-        
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin.write_all(text.as_bytes())
-                .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
-        }
-        
-        child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err("Pak file not found".to_string());
     }
+
+    let dest_dir = PathBuf::from(&dest_path);
+    let mod_name = pak_path.file_stem().unwrap().to_string_lossy().to_string();
+    let to_create = dest_dir.join(&mod_name);
     
-    #[cfg(target_os = "linux")]
-    {
-        use std::process::{Command, Stdio};
-        use std::io::Write;
+    std::fs::create_dir_all(&to_create).map_err(|e| e.to_string())?;
+    
+    // Open PAK
+    let file = File::open(&pak_path).map_err(|e| e.to_string())?;
+    let aes_key = AesKey::from_str(&install_mod::current_aes_key_hex())
+        .map_err(|e| e.to_string())?;
         
-        // Try xclip first, then xsel
-        let result = Command::new("xclip")
-            .args(["-selection", "clipboard"])
-            .stdin(Stdio::piped())
-            .spawn();
+    let mut reader = BufReader::new(file);
+    let pak_reader = PakBuilder::new()
+        .key(aes_key.0)
+        .reader(&mut reader)
+        .map_err(|e| e.to_string())?;
         
-        match result {
-            Ok(mut child) => {
-                if let Some(mut stdin) = child.stdin.take() {
-                    stdin.write_all(text.as_bytes())
-                        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
-                }
-                child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-            }
-            Err(_) => {
-                let mut child = Command::new("xsel")
-                    .args(["--clipboard", "--input"])
-                    .stdin(Stdio::piped())
-                    .spawn()
-                    .map_err(|e| format!("Failed to copy to clipboard (neither xclip nor xsel available): {}", e))?;
-                
-                if let Some(mut stdin) = child.stdin.take() {
-                    stdin.write_all(text.as_bytes())
-                        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
-                }
-                child.wait().map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-            }
-        }
-    }
+    let installable_mod = InstallableMod {
+        mod_name: mod_name.clone(),
+        mod_type: "".to_string(),
+        reader: Option::from(pak_reader),
+        mod_path: pak_path.clone(),
+        ..Default::default()
+    };
     
-    // Emit an event to notify the frontend that the copy was successful
-    let _ = window.emit("clipboard-copied", text);
+    extract_pak_to_dir(&installable_mod, to_create).map_err(|e| e.to_string())?;
     
     Ok(())
 }
 
-#[tauri::command]
-async fn rename_mod(mod_path: String, new_name: String, window: Window) -> Result<String, String> {
-    let old_path_buf = PathBuf::from(&mod_path);
+/// Cleanup .ubulk files for textures that have inline data.
+/// This is called after extraction to remove unnecessary .ubulk files
+/// that were pulled from the base game but aren't needed because the
+/// mod's textures have been patched to use inline data.
+async fn cleanup_ubulk_for_inline_textures(output_dir: &PathBuf) {
+    use walkdir::WalkDir;
+    use uasset_toolkit::get_global_toolkit;
     
-    info!("rename_mod called: mod_path={}, new_name={}", mod_path, new_name);
+    // Find all .uasset files - UAssetTool will detect which are textures
+    let uasset_files: Vec<String> = WalkDir::new(output_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let path = e.path();
+            if let Some(ext) = path.extension() {
+                return ext.to_string_lossy().to_lowercase() == "uasset";
+            }
+            false
+        })
+        .map(|e| e.path().to_string_lossy().to_string())
+        .collect();
     
-    if !old_path_buf.exists() {
-        let error_msg = format!("File does not exist: {}", mod_path);
-        toast_events::emit_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+    if uasset_files.is_empty() {
+        return;
     }
     
-    // Get the parent directory
-    let parent = old_path_buf.parent()
-        .ok_or_else(|| "Cannot get parent directory".to_string())?;
-    
-    // Get the full filename to detect extension properly
-    let filename = old_path_buf.file_name()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_default();
-    
-    // Detect extension - handle .bak_repak as disabled .pak
-    let (extension, is_pak_type) = if filename.ends_with(".bak_repak") {
-        ("bak_repak".to_string(), true)
-    } else if filename.ends_with(".pak") {
-        ("pak".to_string(), true)
-    } else {
-        let ext = old_path_buf.extension()
-            .map(|e| e.to_string_lossy().to_string())
-            .unwrap_or_default();
-        (ext, false)
-    };
-    
-    // Get the old stem (filename without extension) BEFORE renaming
-    // For .bak_repak files, we need to strip that suffix manually
-    let old_stem = if filename.ends_with(".bak_repak") {
-        filename.trim_end_matches(".bak_repak").to_string()
-    } else {
-        old_path_buf.file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_default()
-    };
-    
-    // Extract the priority suffix (e.g., _9999999_P) from the old stem
-    let priority_suffix_regex = regex_lite::Regex::new(r"(_\d+_P)+$").unwrap();
-    let old_priority_suffix = priority_suffix_regex.find(&old_stem)
-        .map(|m| m.as_str().to_string())
-        .unwrap_or_default();
-    
-    // Check if new_name already has a priority suffix
-    let new_name_has_suffix = priority_suffix_regex.is_match(&new_name);
-    
-    // Build the final new stem: new_name + priority suffix (if not already present)
-    let new_stem = if new_name_has_suffix {
-        new_name.clone()
-    } else if !old_priority_suffix.is_empty() {
-        // Preserve the old priority suffix
-        format!("{}{}", new_name, old_priority_suffix)
-    } else {
-        // Add default priority suffix if none existed
-        format!("{}_9999999_P", new_name)
-    };
-    
-    info!("rename_mod: old_stem={}, extension={}, is_pak_type={}, new_stem={}", 
-          old_stem, extension, is_pak_type, new_stem);
-    
-    // Build the new path with the new stem and same extension
-    let new_file_name = if extension.is_empty() {
-        new_stem.clone()
-    } else {
-        format!("{}.{}", new_stem, extension)
-    };
-    let new_path = parent.join(&new_file_name);
+    log::info!("[Extraction] Checking {} uasset files for textures with inline data...", uasset_files.len());
     
-    if new_path.exists() {
-        let error_msg = format!("A file with name '{}' already exists", new_file_name);
-        toast_events::emit_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+    // Use UAssetToolkit to check which are textures with inline data
+    // The batch_has_inline_texture_data function internally checks asset type
+    match get_global_toolkit() {
+        Ok(toolkit) => {
+            // Get USMAP path from environment
+            let usmap_path = std::env::var("USMAP_PATH").ok();
+            
+            match toolkit.batch_has_inline_texture_data(&uasset_files, usmap_path.as_deref()) {
+                Ok(inline_files) => {
+                    log::info!("[Extraction] Found {} textures with inline data", inline_files.len());
+                    
+                    // Delete .ubulk files for textures with inline data
+                    let mut deleted_count = 0;
+                    for uasset_path in inline_files {
+                        let ubulk_path = uasset_path.replace(".uasset", ".ubulk");
+                        if std::path::Path::new(&ubulk_path).exists() {
+                            if let Ok(_) = std::fs::remove_file(&ubulk_path) {
+                                deleted_count += 1;
+                                log::debug!("[Extraction] Deleted unnecessary .ubulk: {}", ubulk_path);
+                            }
+                        }
+                    }
+                    
+                    if deleted_count > 0 {
+                        log::info!("[Extraction] Cleaned up {} unnecessary .ubulk files", deleted_count);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("[Extraction] Failed to check inline texture data: {}", e);
+                }
+            }
+        }
+        Err(e) => {
+            log::warn!("[Extraction] UAssetToolkit unavailable for cleanup: {}", e);
+        }
     }
-    
-    // If it's a .pak or .bak_repak file, rename associated IoStore files (.ucas, .utoc) FIRST
-    // Do this before renaming the main file to ensure we have the correct old stem
-    if is_pak_type {
-        // Rename .ucas file if it exists
-        let old_ucas = parent.join(format!("{}.ucas", old_stem));
-        let new_ucas = parent.join(format!("{}.ucas", new_stem));
-        info!("rename_mod: checking ucas: {} exists={}", old_ucas.display(), old_ucas.exists());
-        if old_ucas.exists() {
-            match std::fs::rename(&old_ucas, &new_ucas) {
-                Ok(_) => info!("rename_mod: renamed ucas to {}", new_ucas.display()),
-                Err(e) => warn!("rename_mod: failed to rename ucas: {}", e),
+}
+
+/// Delete redundant .ubulk files across a whole tree of extracted/working folders, i.e. the ones
+/// next to textures that carry their data inline. All candidates go to UAssetTool in one batch.
+/// Returns the number of .ubulk files removed.
+#[tauri::command]
+async fn cleanup_redundant_ubulk(root: String, window: Window) -> Result<usize, String> {
+    info!("cleanup_redundant_ubulk called: {}", root);
+    let root = PathBuf::from(&root);
+    if !root.is_dir() {
+        return Err(format!("Directory not found: {}", root.display()));
+    }
+
+    let (deleted, freed_bytes) = tauri::async_runtime::spawn_blocking(move || {
+        // Only textures with a .ubulk next to them can have a redundant one
+        let candidates: Vec<String> = WalkDir::new(&root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.path().extension().is_some_and(|ext| ext.eq_ignore_ascii_case("uasset"))
+                    && e.path().with_extension("ubulk").exists()
+            })
+            .map(|e| e.path().to_string_lossy().to_string())
+            .collect();
+        if candidates.is_empty() {
+            return Ok((0, 0));
+        }
+        info!("[Cleanup] Checking {} textures with .ubulk for inline data", candidates.len());
+
+        let toolkit = uasset_toolkit::get_global_toolkit().map_err(|e| format!("UAssetToolkit unavailable: {}", e))?;
+        let usmap_path = std::env::var("USMAP_PATH").ok();
+        let inline_files = toolkit.batch_has_inline_texture_data(&candidates, usmap_path.as_deref())
+            .map_err(|e| format!("Failed to check inline texture data: {}", e))?;
+
+        let mut deleted = 0usize;
+        let mut freed_bytes = 0u64;
+        for uasset_path in inline_files {
+            let ubulk_path = Path::new(&uasset_path).with_extension("ubulk");
+            let size = std::fs::metadata(&ubulk_path).map(|m| m.len()).unwrap_or(0);
+            match std::fs::remove_file(&ubulk_path) {
+                Ok(()) => {
+                    deleted += 1;
+                    freed_bytes += size;
+                    log::debug!("[Cleanup] Deleted redundant .ubulk: {}", ubulk_path.display());
+                }
+                Err(e) => warn!("[Cleanup] Failed to delete {}: {}", ubulk_path.display(), e),
             }
         }
-        
-        // Rename .utoc file if it exists
-        let old_utoc = parent.join(format!("{}.utoc", old_stem));
-        let new_utoc = parent.join(format!("{}.utoc", new_stem));
-        info!("rename_mod: checking utoc: {} exists={}", old_utoc.display(), old_utoc.exists());
-        if old_utoc.exists() {
-            match std::fs::rename(&old_utoc, &new_utoc) {
-                Ok(_) => info!("rename_mod: renamed utoc to {}", new_utoc.display()),
-                Err(e) => warn!("rename_mod: failed to rename utoc: {}", e),
+        Ok::<_, String>((deleted, freed_bytes))
+    })
+    .await
+    .map_err(|e| format!("Cleanup task failed: {}", e))??;
+
+    let freed_mb = freed_bytes as f64 / (1024.0 * 1024.0);
+    info!("[Cleanup] Removed {} redundant .ubulk file(s), freed {:.1} MB", deleted, freed_mb);
+    toast_events::emit_success(
+        &window,
+        "Cleanup Complete",
+        format!("Removed {} redundant .ubulk file(s), freed {:.1} MB", deleted, freed_mb),
+    );
+    Ok(deleted)
+}
+
+/// Extract assets from a mod file (PAK or IoStore) to a destination directory.
+/// Automatically detects the mod type and uses the appropriate extraction method.
+/// Handles disabled mods (.bak_repak extension) by treating them as PAK files.
+/// 
+/// # Arguments
+/// * `mod_path` - Path to the mod file (.pak, .utoc, .bak_repak)
+/// * `dest_path` - Destination directory for extracted files
+/// 
+/// # Returns
+/// Number of files extracted
+#[tauri::command]
+async fn extract_mod_assets(mod_path: String, dest_path: String, window: Window, state: State<'_, Arc<RwLock<AppState>>>) -> Result<usize, String> {
+    // Set USMAP_PATH from AppState so UAssetTool can load mappings
+    {
+        let state_guard = state.read().await;
+        let usmap_filename = state_guard.usmap_path.clone();
+        drop(state_guard);
+        if !usmap_filename.is_empty() {
+            if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+                std::env::set_var("USMAP_PATH", &usmap_full_path);
             }
         }
     }
-    
-    // Rename the main file
-    if let Err(e) = std::fs::rename(&old_path_buf, &new_path) {
-        let error_msg = format!("Failed to rename file: {}", e);
-        toast_events::emit_rename_failed(&window, &error_msg);
-        return Err(error_msg);
-    }
-    
-    info!("rename_mod: successfully renamed {} to {}", mod_path, new_path.display());
-    
-    Ok(new_path.to_string_lossy().to_string())
+    extract_mod_assets_inner(mod_path, dest_path, window).await
 }
 
-#[tauri::command]
-async fn create_folder(name: String, state: State<'_, Arc<Mutex<AppState>>>, window: Window) -> Result<String, String> {
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
-    
-    // Create physical directory in ~mods
-    let folder_path = game_path.join(&name);
-    
-    if folder_path.exists() {
-        let error_msg = "Folder already exists".to_string();
-        toast_events::emit_folder_create_failed(&window, &error_msg);
-        return Err(error_msg);
+async fn extract_mod_assets_inner(mod_path: String, dest_path: String, window: Window) -> Result<usize, String> {
+    let mut path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("File not found: {}", mod_path));
     }
     
-    // Use create_dir_all to support nested paths like "Category/Subcategory"
-    if let Err(e) = std::fs::create_dir_all(&folder_path) {
-        let error_msg = format!("Failed to create folder: {}", e);
-        toast_events::emit_folder_create_failed(&window, &error_msg);
-        return Err(error_msg);
+    // Check if this is a PAK file with a corresponding .utoc file (IoStore mod)
+    // IoStore mods have both .pak (with just chunknames) and .utoc/.ucas (actual data)
+    let extension = path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    
+    if extension == "pak" || extension == "bak_repak" {
+        // Check if there's a .utoc file with the same name - if so, use IoStore extraction
+        // Handle .bak_repak (disabled mod) by stripping .bak_repak then .pak to get base name
+        let base_path = if extension == "bak_repak" {
+            let name = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let base_name = name.trim_end_matches(".bak_repak").trim_end_matches(".pak");
+            path.parent().map(|p| p.join(base_name)).unwrap_or_else(|| PathBuf::from(base_name))
+        } else {
+            path.with_extension("")
+        };
+        let utoc_path = base_path.with_extension("utoc");
+        if utoc_path.exists() {
+            log::info!("Detected IoStore mod (has .utoc alongside .pak/.bak_repak), using IoStore extraction");
+            path = utoc_path;
+        }
     }
     
-    Ok(name)
-}
-
-#[tauri::command]
-async fn get_folders(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec<ModFolder>, String> {
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
+    let dest_dir = PathBuf::from(&dest_path);
     
-    if !game_path.exists() {
-        return Ok(Vec::new());
-    }
+    // Get mod name - handle .bak_repak extension specially
+    let file_name = path.file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "extracted".to_string());
     
-    let mut folders = Vec::new();
+    // Strip .bak_repak or other extensions to get clean mod name
+    let mod_name = if file_name.ends_with(".bak_repak") {
+        // Remove .bak_repak and then .pak to get the base name
+        file_name.trim_end_matches(".bak_repak")
+            .trim_end_matches(".pak")
+            .to_string()
+    } else {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "extracted".to_string())
+    };
     
-    // Get root folder name (e.g., "~mods")
-    let root_name = game_path.file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("Mods")
-        .to_string();
+    let output_dir = dest_dir.join(&mod_name);
     
-    // Count mods directly in root (not in subfolders)
-    let root_mod_count = std::fs::read_dir(game_path)
-        .map(|entries| {
-            entries.filter_map(|e| e.ok())
-                .filter(|e| {
-                    let path = e.path();
-                    if path.is_file() {
-                        let ext = path.extension().and_then(|s| s.to_str());
-                        ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled")
-                    } else {
-                        false
-                    }
-                })
-                .count()
-        })
-        .unwrap_or(0);
+    // Create output directory
+    std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
     
-    // Add root folder first (depth 0) - use actual folder name as ID
-    folders.push(ModFolder {
-        id: root_name.clone(),  // Use actual name like "~mods" as ID
-        name: root_name.clone(),
-        enabled: true,
-        expanded: true,
-        color: None,
-        depth: 0,
-        parent_id: None,
-        is_root: true,
-        mod_count: root_mod_count,
-    });
+    // Re-get extension after potential path change
+    let extension = path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
     
-    // Recursively scan for subdirectories using WalkDir
-    for entry in WalkDir::new(game_path)
-        .min_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok()) 
-    {
-        let path = entry.path();
-        
-        if path.is_dir() {
-            // Calculate relative path from game_path to get ID
-            let relative_path = path.strip_prefix(game_path)
-                .map(|p| p.to_string_lossy().replace('\\', "/"))
-                .unwrap_or_else(|_| "Unknown".to_string());
-                
-            let name = path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("Unknown")
-                .to_string();
+    match extension.as_str() {
+        "utoc" => {
+            // IoStore extraction using UAssetTool
+            // Obfuscated containers can only be extracted if the game key decrypts them
+            if uasset_toolkit::is_iostore_encrypted_header(&path.to_string_lossy()).unwrap_or(false) {
+                let probe = uasset_toolkit::probe_iostore_encryption(
+                    &path.to_string_lossy(),
+                    &install_mod::current_aes_key_hex(),
+                ).map_err(|e| format!("Failed to check IoStore encryption: {}", e))?;
+                if !probe.readable_with_key {
+                    let msg = format!("{} is obfuscated with a key other than the game's AES key and can't be extracted", mod_name);
+                    log::warn!("{}", msg);
+                    let _ = window.emit("install_log", format!("WARNING: {}", msg));
+                    return Err(msg);
+                }
+                log::info!("{} is obfuscated; extracting with the game AES key", mod_name);
+            }
+            log::info!("Starting IoStore extraction from {:?} to {:?}", path, output_dir);
+            let file_count = uasset_toolkit::extract_iostore_with_progress(
+                &path.to_string_lossy(),
+                &output_dir.to_string_lossy(),
+                Some(&install_mod::current_aes_key_hex()),
+                &|progress| {
+                    let _ = window.emit("extract_progress", &progress);
+                },
+            ).map_err(|e| {
+                log::error!("IoStore extraction failed: {}", e);
+                format!("Failed to extract IoStore: {}", e)
+            })?;
             
-            // Calculate depth (number of path segments)
-            let depth = relative_path.split('/').count();
+            log::info!("Extracted {} files from IoStore to {:?}", file_count, output_dir);
             
-            // Calculate parent ID
-            let parent_id = if depth > 1 {
-                // If depth > 1, parent is the directory containing this one
-                // e.g. "A/B" -> parent is "A"
-                let parent_rel = std::path::Path::new(&relative_path)
-                    .parent()
-                    .map(|p| p.to_string_lossy().replace('\\', "/"));
-                parent_rel
-            } else {
-                // If depth is 1, parent is the root folder
-                Some(root_name.clone())
+            // Post-extraction cleanup: Remove .ubulk files for textures with inline data
+            cleanup_ubulk_for_inline_textures(&output_dir).await;
+            
+            Ok(file_count)
+        }
+        "pak" => {
+            // PAK extraction
+            use crate::install_mod::install_mod_logic::pak_files::extract_pak_to_dir;
+            use crate::install_mod::InstallableMod;
+            use repak::PakBuilder;
+            use repak::utils::AesKey;
+            use std::str::FromStr;
+            use std::io::BufReader;
+            
+            let file = File::open(&path).map_err(|e| e.to_string())?;
+            let aes_key = AesKey::from_str(&install_mod::current_aes_key_hex())
+                .map_err(|e| e.to_string())?;
+            
+            let mut reader = BufReader::new(file);
+            let pak_reader = PakBuilder::new()
+                .key(aes_key.0)
+                .reader(&mut reader)
+                .map_err(|e| e.to_string())?;
+            if install_mod::is_iostore_stub(&pak_reader) {
+                return Err("This is an IoStore mod but its .utoc/.ucas files are missing; only the stub .pak was found".to_string());
+            }
+            
+            let file_count = pak_reader.files().len();
+            
+            let installable_mod = InstallableMod {
+                mod_name: mod_name.clone(),
+                mod_type: "".to_string(),
+                reader: Some(pak_reader),
+                mod_path: path.clone(),
+                ..Default::default()
             };
-
-            // Count mods in this folder (only direct children)
-            let mod_count = std::fs::read_dir(&path)
-                .map(|entries| {
-                    entries.filter_map(|e| e.ok())
-                        .filter(|e| {
-                            let p = e.path();
-                            if p.is_file() {
-                                let ext = p.extension().and_then(|s| s.to_str());
-                                ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled")
-                            } else {
-                                false
-                            }
-                        })
-                        .count()
-                })
-                .unwrap_or(0);
             
-            folders.push(ModFolder {
-                id: relative_path, // ID is the relative path (e.g. "Category/Subcategory")
-                name,
-                enabled: true,
-                expanded: true,
-                color: None,
-                depth,
-                parent_id,
-                is_root: false,
-                mod_count,
-            });
+            extract_pak_to_dir(&installable_mod, output_dir.clone()).map_err(|e| e.to_string())?;
+            
+            log::info!("Extracted {} files from PAK to {:?}", file_count, output_dir);
+            Ok(file_count)
+        }
+        "ucas" => {
+            // User selected .ucas, find the corresponding .utoc
+            let utoc_path = path.with_extension("utoc");
+            if !utoc_path.exists() {
+                return Err(format!("Cannot find .utoc file for: {}", mod_path));
+            }
+            
+            // Recursively call with the .utoc path
+            let utoc_str = utoc_path.to_string_lossy().to_string();
+            Box::pin(extract_mod_assets_inner(utoc_str, dest_path, window)).await
+        }
+        "bak_repak" => {
+            // Disabled PAK file - extract it as a regular PAK
+            use crate::install_mod::install_mod_logic::pak_files::extract_pak_to_dir;
+            use crate::install_mod::InstallableMod;
+            use repak::PakBuilder;
+            use repak::utils::AesKey;
+            use std::str::FromStr;
+            use std::io::BufReader;
+            
+            let file = File::open(&path).map_err(|e| e.to_string())?;
+            let aes_key = AesKey::from_str(&install_mod::current_aes_key_hex())
+                .map_err(|e| e.to_string())?;
+            
+            let mut reader = BufReader::new(file);
+            let pak_reader = PakBuilder::new()
+                .key(aes_key.0)
+                .reader(&mut reader)
+                .map_err(|e| e.to_string())?;
+            
+            let file_count = pak_reader.files().len();
+            
+            let installable_mod = InstallableMod {
+                mod_name: mod_name.clone(),
+                mod_type: "".to_string(),
+                reader: Some(pak_reader),
+                mod_path: path.clone(),
+                ..Default::default()
+            };
+            
+            extract_pak_to_dir(&installable_mod, output_dir.clone()).map_err(|e| e.to_string())?;
+            
+            log::info!("Extracted {} files from disabled PAK to {:?}", file_count, output_dir);
+            Ok(file_count)
+        }
+        _ => {
+            Err(format!("Unsupported file type: .{}. Supported: .pak, .utoc, .ucas, .bak_repak", extension))
         }
     }
-    
-    Ok(folders)
 }
 
-/// Get detailed info about the root mods folder
+/// Convert an installed IoStore mod (.pak + .utoc + .ucas) back into a single legacy .pak.
+/// Extracts the IoStore content, repacks it with the legacy writer, then removes the .utoc/.ucas.
+/// Returns the path of the resulting .pak.
 #[tauri::command]
-async fn get_root_folder_info(state: State<'_, Arc<Mutex<AppState>>>) -> Result<RootFolderInfo, String> {
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
-    
-    if !game_path.exists() {
-        return Err("Game path does not exist".to_string());
+async fn convert_to_legacy_pak(mod_path: String, window: Window) -> Result<String, String> {
+    use crate::install_mod::install_mod_logic::pak_files::repak_dir;
+    use crate::install_mod::InstallableMod;
+
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
     }
-    
-    let root_name = game_path.file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("Mods")
+    if pak_path.extension().and_then(|e| e.to_str()) != Some("pak") {
+        return Err("Only enabled .pak mods can be converted to legacy format".to_string());
+    }
+
+    let utoc_path = pak_path.with_extension("utoc");
+    let ucas_path = pak_path.with_extension("ucas");
+    if !utoc_path.exists() || !ucas_path.exists() {
+        return Err("Mod is not an IoStore package (missing .utoc/.ucas)".to_string());
+    }
+
+    let mod_dir = pak_path.parent()
+        .ok_or("Invalid mod path")?
+        .to_path_buf();
+    let mod_name = pak_path.file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid mod file name")?
         .to_string();
-    
-    let mut direct_mod_count = 0;
-    let mut subfolder_count = 0;
-    
-    for entry in std::fs::read_dir(game_path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        
-        if path.is_dir() {
-            subfolder_count += 1;
-        } else if path.is_file() {
-            let ext = path.extension().and_then(|s| s.to_str());
-            if ext == Some("pak") || ext == Some("bak_repak") || ext == Some("pak_disabled") {
-                direct_mod_count += 1;
+
+    info!("Converting IoStore mod to legacy PAK: {}", mod_name);
+    let _ = window.emit("install_log", format!("[Legacy] Extracting IoStore content: {}", mod_name));
+
+    let extract_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let file_count = uasset_toolkit::extract_iostore(
+        &utoc_path.to_string_lossy(),
+        &extract_dir.path().to_string_lossy(),
+        Some(&install_mod::current_aes_key_hex()),
+    ).map_err(|e| format!("Failed to extract IoStore: {}", e))?;
+
+    if file_count == 0 {
+        return Err("IoStore extraction produced no files".to_string());
+    }
+
+    // Keep the source's compression: Oodle when the IoStore container is compressed, stored
+    // otherwise (taken from the uncompressed stub pak through output_compression)
+    let source_compressed = uasset_toolkit::is_iostore_compressed(&utoc_path.to_string_lossy())
+        .unwrap_or_else(|e| {
+            warn!("Could not check IoStore compression of {}, assuming Oodle: {}", mod_name, e);
+            true
+        });
+    let stub_reader = if source_compressed {
+        None
+    } else {
+        let mut reader = std::io::BufReader::new(
+            File::open(&pak_path).map_err(|e| format!("Failed to open PAK: {}", e))?,
+        );
+        Some(repak::PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut reader)
+            .map_err(|e| format!("Failed to read PAK: {}", e))?)
+    };
+
+    // Write the new pak into a staging dir next to the mod, so it can be renamed into place
+    // and a failure leaves the original mod untouched
+    let staging_dir = tempfile::Builder::new()
+        .prefix(".repakx_legacy")
+        .tempdir_in(&mod_dir)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+    let installable_mod = InstallableMod {
+        mod_name: mod_name.clone(),
+        mount_point: "../../../".to_string(),
+        path_hash_seed: "00000000".to_string(),
+        compression: repak::Compression::Oodle,
+        preserve_source_compression: stub_reader.is_some(),
+        reader: stub_reader,
+        force_legacy_pak: true,
+        ..Default::default()
+    };
+
+    let _ = window.emit("install_log", format!("[Legacy] Repacking {} files as legacy PAK", file_count));
+    let packed_counter = std::sync::atomic::AtomicI32::new(0);
+    repak_dir(
+        &installable_mod,
+        extract_dir.path().to_path_buf(),
+        staging_dir.path().to_path_buf(),
+        &packed_counter,
+    ).map_err(|e| format!("Failed to repack as legacy PAK: {}", e))?;
+
+    let staged_pak = staging_dir.path().join(format!("{}.pak", mod_name));
+
+    // Move the stub and its companions aside, then rename the legacy pak into place. Any
+    // failure moves the originals back; they are deleted with the staging dir on success.
+    let originals_dir = staging_dir.path().join("original");
+    std::fs::create_dir(&originals_dir)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let restore = |moved: &[(PathBuf, PathBuf)]| {
+        for (original, aside) in moved.iter().rev() {
+            if let Err(e) = std::fs::rename(aside, original) {
+                error!("Failed to restore {}: {}", original.display(), e);
             }
         }
+    };
+    for original in [&utoc_path, &ucas_path, &pak_path] {
+        let aside = originals_dir.join(original.file_name().unwrap_or_default());
+        if let Err(e) = std::fs::rename(original, &aside) {
+            restore(&moved);
+            return Err(format!("Failed to move {} aside: {}", original.display(), e));
+        }
+        moved.push((original.clone(), aside));
     }
-    
-    Ok(RootFolderInfo {
-        name: root_name,
-        path: game_path.to_string_lossy().to_string(),
-        direct_mod_count,
-        subfolder_count,
+    if let Err(e) = std::fs::rename(&staged_pak, &pak_path) {
+        restore(&moved);
+        return Err(format!("Failed to write legacy PAK: {}", e));
+    }
+
+    info!("Converted {} to legacy PAK ({} files)", mod_name, file_count);
+    let _ = window.emit("install_log", format!("[Legacy] Converted {} to legacy PAK", mod_name));
+
+    Ok(mod_dir.join(format!("{}.pak", mod_name)).to_string_lossy().to_string())
+}
+
+/// Get the mount point of a legacy .pak mod
+#[tauri::command]
+async fn get_pak_mount_point(mod_path: String) -> Result<String, String> {
+    read_pak_mount_point(Path::new(&mod_path))
+}
+
+/// Mount point stored in a pak's index (for IoStore mods, read it from the stub pak)
+fn read_pak_mount_point(pak_path: &Path) -> Result<String, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    let file = File::open(pak_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let pak = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+    Ok(pak.mount_point().to_string())
+}
+
+/// Check whether a .pak is the stub half of an IoStore mod (chunk names only, data in .ucas).
+/// Works from the pak's own entries, so it detects IoStore mods whose companions are missing.
+#[tauri::command]
+async fn is_iostore_stub_pak(mod_path: String) -> Result<bool, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    let file = File::open(&mod_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let pak = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut BufReader::new(file))
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+    Ok(install_mod::is_iostore_stub_at(&pak, Path::new(&mod_path)))
+}
+
+/// Header-level details of a legacy .pak
+#[derive(Serialize)]
+struct PakMetadata {
+    version: String,
+    mount_point: String,
+    path_hash_seed: Option<u64>,
+    compression: Vec<String>,
+    /// "none", "index" (only the index is encrypted) or "full"
+    encryption: String,
+    file_count: usize,
+}
+
+/// Report version, mount point, compression and encryption mode of a legacy .pak
+#[tauri::command]
+async fn get_pak_metadata(mod_path: String) -> Result<PakMetadata, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    let file = File::open(&mod_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let pak = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+    Ok(PakMetadata {
+        version: format!("{:?}", pak.version()),
+        mount_point: pak.mount_point().to_string(),
+        path_hash_seed: pak.path_hash_seed(),
+        compression: pak.compression().iter().map(|c| format!("{:?}", c)).collect(),
+        encryption: install_mod::pak_encryption_mode(&pak).to_string(),
+        file_count: pak.files().len(),
     })
 }
 
+/// Encryption requirements of a pak, read without needing the key
+#[derive(Serialize)]
+struct EncryptionInfo {
+    /// Pak index (or, for IoStore mods, the .utoc container) is encrypted
+    encrypted: bool,
+    /// GUID of the key the pak says it was encrypted with, when one is recorded
+    key_guid: Option<String>,
+    can_decrypt_with_current_key: bool,
+}
+
+/// Check whether a pak needs an AES key and whether the current key opens it, reading only
+/// the footer first so packs encrypted with an unknown key get a clear answer.
 #[tauri::command]
-async fn update_folder(
-    folder: ModFolder,
-    state: State<'_, Arc<Mutex<AppState>>>,
-) -> Result<(), String> {
-    let mut state = state.lock().unwrap();
-    if let Some(existing) = state.folders.iter_mut().find(|f| f.id == folder.id) {
-        *existing = folder;
-        save_state(&state).map_err(|e| e.to_string())?;
-    }
-    Ok(())
+async fn inspect_pak_encryption(path: String) -> Result<EncryptionInfo, String> {
+    use repak::{PakBuilder, PakFooterInfo};
+    use std::io::BufReader;
+
+    let pak_path = PathBuf::from(&path);
+    let file = File::open(&pak_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let footer = PakFooterInfo::read(&mut reader)
+        .map_err(|_| "Not a valid PAK file (no recognizable footer)".to_string())?;
+
+    let pak_ok = !footer.encrypted_index || PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .is_ok();
+
+    let utoc_path = pak_path.with_extension("utoc");
+    let utoc_str = utoc_path.to_string_lossy();
+    let utoc_encrypted = utoc_path.exists()
+        && uasset_toolkit::is_iostore_encrypted_header(&utoc_str).unwrap_or(false);
+    let utoc_ok = !utoc_encrypted
+        || uasset_toolkit::list_iostore_files(&utoc_str, Some(install_mod::current_aes_key_hex().as_str())).is_ok();
+
+    Ok(EncryptionInfo {
+        encrypted: footer.encrypted_index || utoc_encrypted,
+        key_guid: footer.encryption_guid.filter(|g| *g != 0).map(|g| format!("{:032X}", g)),
+        can_decrypt_with_current_key: pak_ok && utoc_ok,
+    })
 }
 
+/// Pak format versions Repak-X can read and write
 #[tauri::command]
-async fn delete_folder(id: String, state: State<'_, Arc<Mutex<AppState>>>, window: Window) -> Result<(), String> {
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
-    
-    let folder_path = game_path.join(&id);
-    
-    if !folder_path.exists() {
-        let error_msg = "Folder does not exist".to_string();
-        toast_events::emit_folder_delete_failed(&window, &error_msg);
-        return Err(error_msg);
+async fn list_supported_versions() -> Result<Vec<String>, String> {
+    Ok(repak::Version::iter().rev().map(|v| v.to_string()).collect())
+}
+
+/// Pak format version of a file (e.g. "V11"). Falls back to the footer alone when the
+/// pak is encrypted with a key we don't have.
+#[tauri::command]
+async fn detect_pak_version(path: String) -> Result<String, String> {
+    use repak::{PakBuilder, PakFooterInfo};
+    use std::io::BufReader;
+
+    let file = File::open(&path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let version = match PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+    {
+        Ok(pak) => pak.version(),
+        Err(_) => PakFooterInfo::read(&mut reader)
+            .map_err(|_| "Not a valid PAK file (no recognizable footer)".to_string())?
+            .version,
+    };
+    Ok(version.to_string())
+}
+
+/// Largest entry `read_pak_entry` will return, to keep IPC payloads reasonable
+const MAX_PREVIEW_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Read the decompressed bytes of a single file inside a mod for preview. Legacy paks are read
+/// in place; IoStore mods are extracted to a temp dir first. `internal_path` is a path as
+/// returned by `get_mod_details`/`list_mod_files`.
+#[tauri::command]
+async fn read_pak_entry(mod_path: String, internal_path: String) -> Result<Vec<u8>, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
     }
-    
-    // Delete physical directory (will fail if not empty, which is good for safety)
-    if let Err(e) = std::fs::remove_dir(&folder_path) {
-        let error_msg = format!("Failed to delete folder (may not be empty): {}", e);
-        toast_events::emit_folder_delete_failed(&window, &error_msg);
-        return Err(error_msg);
+    let too_large = |size: u64| format!(
+        "{} is too large to preview ({} bytes, limit {} bytes)",
+        internal_path, size, MAX_PREVIEW_ENTRY_BYTES
+    );
+
+    let utoc_path = pak_path.with_extension("utoc");
+    if utoc_path.exists() {
+        // IoStore content lives in the .ucas. UAssetTool can only extract whole containers,
+        // so extract to a temp dir and pick the entry from there
+        let temp_dir = tempfile::tempdir()
+            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+        uasset_toolkit::extract_iostore(
+            &utoc_path.to_string_lossy(),
+            &temp_dir.path().to_string_lossy(),
+            Some(&install_mod::current_aes_key_hex()),
+        ).map_err(|e| e.to_string())?;
+
+        let extracted = find_extracted_entry(temp_dir.path(), &internal_path)
+            .ok_or_else(|| format!("{} was not found in the IoStore container", internal_path))?;
+        let size = std::fs::metadata(&extracted).map(|m| m.len()).unwrap_or(0);
+        if size > MAX_PREVIEW_ENTRY_BYTES {
+            return Err(too_large(size));
+        }
+        return std::fs::read(&extracted)
+            .map_err(|e| format!("Failed to read extracted entry: {}", e));
     }
-    
-    Ok(())
+
+    let file = File::open(&pak_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let pak = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+    let entry = pak.get_file_entry(&internal_path)
+        .map_err(|e| format!("Failed to find {}: {}", internal_path, e))?;
+    if entry.uncompressed > MAX_PREVIEW_ENTRY_BYTES {
+        return Err(too_large(entry.uncompressed));
+    }
+    pak.get(&internal_path, &mut reader)
+        .map_err(|e| format!("Failed to read {}: {}", internal_path, e))
+}
+
+/// Locate `internal_path` in a container extracted by UAssetTool. The extracted layout doesn't
+/// keep the mount prefix, so pick the file with that name sharing the most trailing folders
+fn find_extracted_entry(root: &Path, internal_path: &str) -> Option<PathBuf> {
+    let wanted: Vec<&str> = internal_path.split(['/', '\\']).filter(|c| !c.is_empty()).collect();
+    let file_name = *wanted.last()?;
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name().to_str() == Some(file_name))
+        .max_by_key(|e| {
+            let rel = e.path().strip_prefix(root).unwrap_or(e.path());
+            rel.components()
+                .rev()
+                .zip(wanted.iter().rev())
+                .take_while(|(component, wanted)| component.as_os_str() == **wanted)
+                .count()
+        })
+        .map(|e| e.into_path())
+}
+
+/// Largest config file `extract_mod_configs` will return; bigger files are skipped
+const MAX_CONFIG_ENTRY_BYTES: u64 = 1024 * 1024;
+
+/// Whether an internal path is a text config file (ini overrides and the like)
+fn is_config_entry(internal_path: &str) -> bool {
+    matches!(
+        Path::new(internal_path).extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref(),
+        Some("ini" | "cfg" | "txt" | "json")
+    )
 }
 
+/// Read just the config/ini files out of a mod as (internal path, contents) pairs, for
+/// inspecting what a config mod changes without a full extraction. Loose files like these
+/// always live in the .pak, also for IoStore mods.
 #[tauri::command]
-async fn rename_folder(
-    id: String,
-    new_name: String,
-    state: State<'_, Arc<Mutex<AppState>>>,
-    window: Window,
-) -> Result<String, String> {
-    let mut state = state.lock().unwrap();
-    let game_path = state.game_path.clone();
+async fn extract_mod_configs(mod_path: String) -> Result<Vec<(String, String)>, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
 
-    // Prevent renaming root folder
-    if id == game_path.file_name().and_then(|n| n.to_str()).unwrap_or("") {
-        let error_msg = "Cannot rename the root folder".to_string();
-        toast_events::emit_folder_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
     }
 
-    // Validate new name (no path separators allowed in the leaf name)
-    if new_name.contains('/') || new_name.contains('\\') || new_name.is_empty() {
-        let error_msg = "Invalid folder name".to_string();
-        toast_events::emit_folder_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+    let file = File::open(&pak_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let pak = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+
+    let mut configs = Vec::new();
+    for internal_path in pak.files().into_iter().filter(|f| is_config_entry(f)) {
+        let entry = pak.get_file_entry(&internal_path)
+            .map_err(|e| format!("Failed to find {}: {}", internal_path, e))?;
+        if entry.uncompressed > MAX_CONFIG_ENTRY_BYTES {
+            warn!("Skipping {} in {}: {} bytes exceeds the config size limit", internal_path, mod_path, entry.uncompressed);
+            continue;
+        }
+        let data = pak.get(&internal_path, &mut reader)
+            .map_err(|e| format!("Failed to read {}: {}", internal_path, e))?;
+        configs.push((internal_path, String::from_utf8_lossy(&data).into_owned()));
     }
+    info!("extract_mod_configs: {} config file(s) in {}", configs.len(), mod_path);
+    Ok(configs)
+}
 
-    let old_path = game_path.join(&id);
-    if !old_path.exists() {
-        let error_msg = "Folder does not exist".to_string();
-        toast_events::emit_folder_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+/// Longest side of a texture preview, larger textures are downscaled by UAssetTool
+const MAX_TEXTURE_PREVIEW_SIZE: u32 = 1024;
+
+/// Decode a texture inside a mod to PNG bytes for preview before installing.
+/// For legacy paks only the texture's package (.uasset/.uexp/.ubulk) is extracted, to a temp dir.
+#[tauri::command]
+async fn preview_mod_texture(mod_path: String, internal_path: String) -> Result<Vec<u8>, String> {
+    use repak::PakBuilder;
+    use std::io::BufReader;
+
+    info!("preview_mod_texture called: {} -> {}", mod_path, internal_path);
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
     }
+    let internal_uasset = Path::new(&internal_path).with_extension("uasset")
+        .to_string_lossy().replace('\\', "/");
+    let file_name = Path::new(&internal_uasset).file_name()
+        .ok_or("Invalid internal path")?
+        .to_os_string();
 
-    // Build new path: replace only the last path segment with new_name
-    let new_id = if let Some(parent) = std::path::Path::new(&id).parent() {
-        let parent_str = parent.to_string_lossy().replace('\\', "/");
-        if parent_str.is_empty() {
-            new_name.clone()
-        } else {
-            format!("{}/{}", parent_str, new_name)
-        }
+    let temp_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let utoc_path = iostore_companion_path(&pak_path, "utoc");
+    if utoc_path.exists() {
+        // Extraction converts packages to legacy .uasset/.uexp, bulk data included. UAssetTool
+        // only extracts whole containers, the texture is picked out below
+        uasset_toolkit::extract_iostore(
+            &utoc_path.to_string_lossy(),
+            &temp_dir.path().to_string_lossy(),
+            Some(&install_mod::current_aes_key_hex()),
+        ).map_err(|e| e.to_string())?;
     } else {
-        new_name.clone()
-    };
+        let file = File::open(&pak_path)
+            .map_err(|e| format!("Failed to open PAK: {}", e))?;
+        let mut reader = BufReader::new(file);
+        let pak = PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut reader)
+            .map_err(|e| format!("Failed to read PAK: {}", e))?;
+        for ext in ["uasset", "uexp", "ubulk"] {
+            let entry_path = Path::new(&internal_uasset).with_extension(ext).to_string_lossy().to_string();
+            let data = match pak.get(&entry_path, &mut reader) {
+                Ok(data) => data,
+                // Only the .uasset is required, the rest depends on how the texture was cooked
+                Err(_) if ext != "uasset" => continue,
+                Err(e) => return Err(format!("Failed to read {}: {}", entry_path, e)),
+            };
+            std::fs::write(temp_dir.path().join(&file_name).with_extension(ext), data)
+                .map_err(|e| format!("Failed to write {}: {}", entry_path, e))?;
+        }
+    }
 
-    let new_path = game_path.join(&new_id);
+    let uasset = find_extracted_entry(temp_dir.path(), &internal_uasset)
+        .ok_or_else(|| format!("{} was not found in the mod", internal_uasset))?;
+    let png_path = temp_dir.path().join("preview.png");
+    let usmap_path = std::env::var("USMAP_PATH").ok();
+    uasset_toolkit::get_global_toolkit()
+        .and_then(|toolkit| toolkit.export_texture_png(
+            &uasset.to_string_lossy(),
+            &png_path.to_string_lossy(),
+            Some(MAX_TEXTURE_PREVIEW_SIZE),
+            usmap_path.as_deref(),
+        ))
+        .map_err(|e| e.to_string())?;
 
-    if new_path.exists() {
-        let error_msg = format!("A folder named \"{}\" already exists", new_name);
-        toast_events::emit_folder_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+    std::fs::read(&png_path).map_err(|e| format!("Failed to read texture preview: {}", e))
+}
+
+/// Advanced: rebuild a legacy .pak mod with a different mount point.
+/// Version, compression and path hash seed of the original pak are preserved.
+#[tauri::command]
+async fn set_pak_mount_point(mod_path: String, mount_point: String) -> Result<(), String> {
+    use repak::PakBuilder;
+    use std::io::{BufReader, BufWriter};
+    use tempfile::NamedTempFile;
+
+    let mount_point = mount_point.trim().to_string();
+    if mount_point.is_empty() {
+        return Err("Mount point cannot be empty".to_string());
     }
 
-    // Rename the physical directory
-    if let Err(e) = std::fs::rename(&old_path, &new_path) {
-        let error_msg = format!("Failed to rename folder: {}", e);
-        toast_events::emit_folder_rename_failed(&window, &error_msg);
-        return Err(error_msg);
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
+    }
+    // IoStore stubs only carry chunk metadata; the real content lives in the .utoc/.ucas
+    if pak_path.with_extension("utoc").exists() {
+        return Err("Changing the mount point of IoStore mods is not supported".to_string());
     }
 
-    // Update mod_metadata entries that reference the old folder ID (or children of it)
-    let old_prefix = format!("{}/", id);
-    for metadata in state.mod_metadata.iter_mut() {
-        if let Some(ref fid) = metadata.folder_id {
-            if fid == &id {
-                metadata.folder_id = Some(new_id.clone());
-            } else if fid.starts_with(&old_prefix) {
-                // Child folder: replace the old prefix with the new one
-                let suffix = &fid[old_prefix.len()..];
-                metadata.folder_id = Some(format!("{}/{}", new_id, suffix));
-            }
-        }
+    info!("set_pak_mount_point called: {} -> {}", mod_path, mount_point);
+
+    let source_file = File::open(&pak_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut source_reader = BufReader::new(source_file);
+    let pak_reader = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut source_reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+
+    // Keep the pak's encryption as it was: an unencrypted pak stays unencrypted
+    let mut builder = PakBuilder::new().compression(pak_reader.compression());
+    if install_mod::pak_encryption_mode(&pak_reader) != "none" {
+        builder = builder
+            .key(install_mod::current_aes_key().0)
+            .encrypt_index_only(install_mod::is_index_only_encrypted(&pak_reader));
+    }
+
+    // Build next to the original and rename it into place, so a failure or crash never
+    // leaves a half-written pak
+    let pak_dir = pak_path.parent().ok_or("PAK has no parent folder")?;
+    let temp_file = NamedTempFile::new_in(pak_dir)
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    let output_file = temp_file.reopen()
+        .map_err(|e| format!("Failed to open output file: {}", e))?;
+
+    let mut pak_writer = builder
+        .writer(
+            BufWriter::new(output_file),
+            pak_reader.version(),
+            mount_point.clone(),
+            pak_reader.path_hash_seed(),
+        );
+    let entry_builder = pak_writer.entry_builder();
+
+    for file_path in pak_reader.files() {
+        let data = pak_reader.get(&file_path, &mut source_reader)
+            .map_err(|e| format!("Failed to read entry {}: {}", file_path, e))?;
+        // Entries keep their own compression, stored ones stay stored
+        let compression = pak_reader.get_file_entry(&file_path)
+            .map(|entry| pak_reader.entry_compression(&entry))
+            .map_err(|e| format!("Failed to read entry {}: {}", file_path, e))?;
+        let entry = entry_builder
+            .build_entry_with_compression(compression, data, &file_path)
+            .map_err(|e| format!("Failed to build entry {}: {}", file_path, e))?;
+        pak_writer.write_entry(file_path.clone(), entry)
+            .map_err(|e| format!("Failed to write entry {}: {}", file_path, e))?;
     }
 
-    // Update folder state entries that reference the old ID
-    for folder in state.folders.iter_mut() {
-        if folder.id == id {
-            folder.id = new_id.clone();
-            folder.name = new_name.clone();
-        } else if folder.id.starts_with(&old_prefix) {
-            let suffix = &folder.id[old_prefix.len()..];
-            folder.id = format!("{}/{}", new_id, suffix);
-        }
-        // Update parent_id references
-        if let Some(ref pid) = folder.parent_id {
-            if pid == &id {
-                folder.parent_id = Some(new_id.clone());
-            } else if pid.starts_with(&old_prefix) {
-                let suffix = &pid[old_prefix.len()..];
-                folder.parent_id = Some(format!("{}/{}", new_id, suffix));
-            }
-        }
-    }
+    pak_writer.write_index()
+        .map_err(|e| format!("Failed to write index: {}", e))?;
+    drop(source_reader);
 
-    save_state(&state).map_err(|e| e.to_string())?;
+    temp_file.persist(&pak_path)
+        .map_err(|e| format!("Failed to replace original PAK: {}", e.error))?;
 
-    Ok(new_id)
+    info!("Mount point of {} set to {}", mod_path, mount_point);
+    Ok(())
 }
 
-#[tauri::command]
-async fn assign_mod_to_folder(
-    mod_path: String,
-    folder_id: Option<String>,
-    state: State<'_, Arc<Mutex<AppState>>>,
-    window: Window,
-) -> Result<(), String> {
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
-    let source_path = PathBuf::from(&mod_path);
-    
-    if !source_path.exists() {
-        let error_msg = "Mod file does not exist".to_string();
-        toast_events::emit_move_failed(&window, &error_msg);
-        return Err(error_msg);
+/// Group pak entries for `split_pak_by_folder`. Files under a character folder are grouped by
+/// skin (`Characters/<id>/<skin>`), so a skin's meshes, textures and materials stay together;
+/// a pak with a single skin is refused since its parts don't work on their own. Paks without
+/// character content fall back to the first folder below the path prefix every entry shares
+fn split_groups(files: &[String]) -> Result<std::collections::BTreeMap<String, Vec<String>>, String> {
+    use std::collections::BTreeMap;
+
+    if files.iter().any(|f| utils::skin_group_key(f).is_some()) {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut shared = Vec::new();
+        for file in files {
+            match utils::skin_group_key(file) {
+                Some(key) => groups.entry(key).or_default().push(file.clone()),
+                None => shared.push(file.clone()),
+            }
+        }
+        if groups.len() < 2 {
+            return Err("All files belong to a single skin; nothing to split".to_string());
+        }
+        if !shared.is_empty() {
+            groups.insert("Shared".to_string(), shared);
+        }
+        return Ok(groups);
     }
-    
-    let filename = source_path.file_name()
-        .ok_or("Invalid file name")?;
-    
-    let dest_path = if let Some(folder_name) = folder_id {
-        // Move to folder
-        let folder_path = game_path.join(&folder_name);
-        if !folder_path.exists() {
-            let error_msg = "Folder does not exist".to_string();
-            toast_events::emit_move_failed(&window, &error_msg);
-            return Err(error_msg);
+
+    // Longest folder prefix shared by every entry
+    let dirs: Vec<Vec<&str>> = files.iter()
+        .map(|f| {
+            let mut parts: Vec<&str> = f.split('/').collect();
+            parts.pop();
+            parts
+        })
+        .collect();
+    let mut common_len = dirs[0].len();
+    for parts in &dirs[1..] {
+        common_len = common_len.min(parts.len());
+        while common_len > 0 && parts[..common_len] != dirs[0][..common_len] {
+            common_len -= 1;
         }
-        folder_path.join(filename)
-    } else {
-        // Move back to root ~mods directory
-        game_path.join(filename)
-    };
-    
-    // Move the main file
-    if let Err(e) = std::fs::rename(&source_path, &dest_path) {
-        let error_msg = format!("Failed to move mod: {}", e);
-        toast_events::emit_move_failed(&window, &error_msg);
-        return Err(error_msg);
     }
-    
-    // Also move .utoc and .ucas files if they exist (IoStore files)
-    let utoc_source = source_path.with_extension("utoc");
-    let ucas_source = source_path.with_extension("ucas");
-    
-    if utoc_source.exists() {
-        let utoc_dest = dest_path.with_extension("utoc");
-        let _ = std::fs::rename(&utoc_source, &utoc_dest);
+
+    // Files sitting directly in the shared folder go into their own "Root" group
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (file, parts) in files.iter().zip(&dirs) {
+        let key = parts.get(common_len).map(|s| s.to_string()).unwrap_or_else(|| "Root".to_string());
+        groups.entry(key).or_default().push(file.clone());
     }
-    
-    if ucas_source.exists() {
-        let ucas_dest = dest_path.with_extension("ucas");
-        let _ = std::fs::rename(&ucas_source, &ucas_dest);
+    if groups.len() < 2 {
+        return Err("All files are in a single content folder; nothing to split".to_string());
     }
-    
-    Ok(())
+    Ok(groups)
 }
 
+/// Split a pak that bundles several unrelated mods into one pak per character skin (see
+/// `split_groups`). The new paks keep the source's mount point, path hash seed, version,
+/// compression and encryption, and are written to a staging folder for the user to install or
+/// organize; the source pak is left untouched.
 #[tauri::command]
-async fn add_custom_tag(
-    mod_path: String,
-    tag: String,
-    state: State<'_, Arc<Mutex<AppState>>>,
-) -> Result<(), String> {
-    let mut state = state.lock().unwrap();
-    let path = PathBuf::from(&mod_path);
-    
-    // Find or create mod metadata
-    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
-        if !metadata.custom_tags.contains(&tag) {
-            metadata.custom_tags.push(tag);
+async fn split_pak_by_folder(mod_path: String) -> Result<Vec<String>, String> {
+    use repak::PakBuilder;
+    use std::io::{BufReader, BufWriter};
+
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
+    }
+    if pak_path.with_extension("utoc").exists() {
+        return Err("Splitting IoStore mods is not supported".to_string());
+    }
+
+    info!("split_pak_by_folder called: {}", mod_path);
+
+    let source_file = File::open(&pak_path)
+        .map_err(|e| format!("Failed to open PAK: {}", e))?;
+    let mut source_reader = BufReader::new(source_file);
+    let pak_reader = PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut source_reader)
+        .map_err(|e| format!("Failed to read PAK: {}", e))?;
+
+    let files = pak_reader.files();
+    if files.is_empty() {
+        return Err("PAK contains no files".to_string());
+    }
+
+    let groups = split_groups(&files)?;
+
+    let base_name = mod_base_name_without_priority(
+        pak_path.file_stem().and_then(|s| s.to_str()).unwrap_or("Mod"),
+    );
+    let out_dir = split_dir().join(&base_name);
+    if out_dir.exists() {
+        std::fs::remove_dir_all(&out_dir)
+            .map_err(|e| format!("Failed to clear staging folder: {}", e))?;
+    }
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create staging folder: {}", e))?;
+
+    let compression = pak_reader.compression();
+    let compress = !compression.is_empty();
+    let mut outputs = Vec::new();
+
+    for (group, group_files) in &groups {
+        let out_path = out_dir.join(format!("{}_{}_9999999_P.pak", base_name, group.replace('/', "_")));
+        let output_file = File::create(&out_path)
+            .map_err(|e| format!("Failed to create {}: {}", out_path.display(), e))?;
+        let mut pak_writer = PakBuilder::new()
+            .compression(compression.clone())
+            .key(install_mod::current_aes_key().0)
+            .encrypt_index_only(install_mod::is_index_only_encrypted(&pak_reader))
+            .writer(
+                BufWriter::new(output_file),
+                pak_reader.version(),
+                pak_reader.mount_point().to_string(),
+                pak_reader.path_hash_seed(),
+            );
+        let entry_builder = pak_writer.entry_builder();
+
+        for file_path in group_files {
+            let data = pak_reader.get(file_path, &mut source_reader)
+                .map_err(|e| format!("Failed to read entry {}: {}", file_path, e))?;
+            let entry = entry_builder
+                .build_entry(compress, data, file_path)
+                .map_err(|e| format!("Failed to build entry {}: {}", file_path, e))?;
+            pak_writer.write_entry(file_path.clone(), entry)
+                .map_err(|e| format!("Failed to write entry {}: {}", file_path, e))?;
         }
-    } else {
-        state.mod_metadata.push(ModMetadata {
-            path,
-            custom_name: None,
-            folder_id: None,
-            custom_tags: vec![tag],
-        });
+
+        pak_writer.write_index()
+            .map_err(|e| format!("Failed to write index: {}", e))?;
+        info!("Split group '{}' ({} files) -> {}", group, group_files.len(), out_path.display());
+        outputs.push(out_path.to_string_lossy().to_string());
     }
-    
-    save_state(&state).map_err(|e| e.to_string())?;
-    Ok(())
+
+    info!("Split {} into {} paks in {}", mod_path, outputs.len(), out_dir.display());
+    Ok(outputs)
 }
 
+/// Combine several installed mods into one. Every source is extracted, overlapping internal
+/// paths are reported and fail the merge unless `overwrite_strategy` says which copy wins
+/// ("first" keeps the earliest source in `mod_paths`, "last" the latest). The result is built as
+/// IoStore if any source was IoStore, otherwise as a legacy pak, named after the highest
+/// priority among the sources and written to a staging folder, keeping the sources' shared
+/// mount point (sources with different mount points are refused). Sources are left untouched.
 #[tauri::command]
-async fn remove_custom_tag(
-    mod_path: String,
-    tag: String,
-    state: State<'_, Arc<Mutex<AppState>>>,
-) -> Result<(), String> {
-    let mut state = state.lock().unwrap();
-    let path = PathBuf::from(&mod_path);
+async fn merge_paks(
+    mod_paths: Vec<String>,
+    output_name: String,
+    overwrite_strategy: Option<String>,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<String, String> {
+    use crate::install_mod::install_mod_logic::iotoc::convert_to_iostore_directory;
+    use crate::install_mod::install_mod_logic::pak_files::repak_dir;
+    use crate::install_mod::InstallableMod;
+    use std::collections::HashMap;
+    use std::io::BufReader;
 
-    if let Some(metadata) = state.mod_metadata.iter_mut().find(|m| m.path == path) {
-        metadata.custom_tags.retain(|t| t != &tag);
+    info!("merge_paks called: {} mod(s) -> {} (strategy: {:?})", mod_paths.len(), output_name, overwrite_strategy);
+
+    let output_name = output_name.trim();
+    if output_name.is_empty() {
+        return Err("Output name cannot be empty".to_string());
     }
+    if mod_paths.len() < 2 {
+        return Err("Select at least two mods to merge".to_string());
+    }
+    let keep_last = match overwrite_strategy.as_deref() {
+        None => None,
+        Some("first") => Some(false),
+        Some("last") => Some(true),
+        Some(other) => return Err(format!("Unknown overwrite strategy: {} (expected \"first\" or \"last\")", other)),
+    };
 
-    save_state(&state).map_err(|e| e.to_string())?;
-    Ok(())
-}
+    let state_guard = state.read().await;
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    drop(state_guard);
+    if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+        std::env::set_var("USMAP_PATH", &usmap_full_path);
+    }
 
-/// Copy a USMAP file to the roaming folder, replacing any existing USMAP files.
-/// 
-/// # Arguments
-/// * `source_path` - Full path to the source .usmap file
-/// 
-/// # Returns
-/// The filename of the copied USMAP file (just the name, not full path)
-/// 
-/// # Behavior
-/// - Deletes ALL existing .usmap files in the roaming Usmap folder before copying
-/// - Copies the new file to `%APPDATA%/Repak-X/Usmap/`
-/// - Only one USMAP file should exist at a time
-#[tauri::command]
-async fn copy_usmap_to_folder(source_path: String) -> Result<String, String> {
-    let source = PathBuf::from(&source_path);
-    
-    if !source.exists() {
-        return Err("Source file does not exist".to_string());
+    // Entries are merged relative to the mount point, so every input has to share it
+    let mut mount_point: Option<String> = None;
+    for mod_path in &mod_paths {
+        let pak_path = Path::new(mod_path);
+        if !pak_path.exists() {
+            return Err(format!("File not found: {}", mod_path));
+        }
+        let mount = read_pak_mount_point(pak_path)?;
+        match &mount_point {
+            Some(first) if *first != mount => {
+                return Err(format!(
+                    "Can't merge mods with different mount points: {} uses \"{}\", {} uses \"{}\"",
+                    mod_paths[0], first, mod_path, mount
+                ));
+            }
+            Some(_) => {}
+            None => mount_point = Some(mount),
+        }
     }
-    
-    // Get the Usmap directory in roaming folder
-    let usmap_folder = usmap_dir();
-    std::fs::create_dir_all(&usmap_folder)
-        .map_err(|e| format!("Failed to create Usmap directory: {}", e))?;
-    
-    // Delete all existing .usmap files in the folder
-    if let Ok(entries) = std::fs::read_dir(&usmap_folder) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("usmap") {
-                if let Err(e) = std::fs::remove_file(&path) {
-                    warn!("Failed to delete old USMAP file {:?}: {}", path, e);
-                } else {
-                    info!("Deleted old USMAP file: {:?}", path);
+    let mount_point = mount_point.unwrap_or_else(|| "../../../".to_string());
+
+    let work_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let merged_dir = work_dir.path().join("merged");
+
+    // relative path -> index of the source it currently comes from
+    let mut owners: HashMap<PathBuf, usize> = HashMap::new();
+    let mut collisions: Vec<String> = Vec::new();
+    let mut any_iostore = false;
+    let mut bang_prefix = false;
+    let mut max_nines = 7;
+
+    for (idx, mod_path) in mod_paths.iter().enumerate() {
+        let pak_path = PathBuf::from(mod_path);
+        let stem = pak_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        let (bang, nines) = mod_priority_markers(stem);
+        bang_prefix |= bang;
+        max_nines = max_nines.max(nines);
+
+        let _ = window.emit("install_log", format!("[Merge] Extracting {}", stem));
+        let source_dir = work_dir.path().join(format!("source_{}", idx));
+        let utoc_path = pak_path.with_extension("utoc");
+        if utoc_path.exists() {
+            any_iostore = true;
+            uasset_toolkit::extract_iostore(
+                &utoc_path.to_string_lossy(),
+                &source_dir.to_string_lossy(),
+                Some(&install_mod::current_aes_key_hex()),
+            ).map_err(|e| format!("Failed to extract IoStore {}: {}", stem, e))?;
+        } else {
+            let mut reader = BufReader::new(
+                File::open(&pak_path).map_err(|e| format!("Failed to open {}: {}", stem, e))?,
+            );
+            let pak_reader = repak::PakBuilder::new()
+                .key(install_mod::current_aes_key().0)
+                .reader(&mut reader)
+                .map_err(|e| format!("Failed to read {}: {}", stem, e))?;
+            extract_pak_entries(&pak_reader, &mut reader, &source_dir)
+                .map_err(|e| format!("Failed to extract {}: {}", stem, e))?;
+        }
+
+        for entry in WalkDir::new(&source_dir).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(&source_dir)
+                .map_err(|e| e.to_string())?
+                .to_path_buf();
+            if let Some(&owner) = owners.get(&relative) {
+                collisions.push(format!(
+                    "{} ({} / {})",
+                    relative.to_string_lossy().replace('\\', "/"),
+                    mod_paths[owner],
+                    mod_path
+                ));
+                if keep_last != Some(true) {
+                    continue;
                 }
             }
+            let dest = merged_dir.join(&relative);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+            std::fs::copy(entry.path(), &dest)
+                .map_err(|e| format!("Failed to copy {}: {}", relative.display(), e))?;
+            owners.insert(relative, idx);
         }
     }
-    
-    // Get filename from source
-    let filename = source.file_name()
-        .ok_or("Invalid source filename")?
-        .to_str()
-        .ok_or("Invalid UTF-8 in filename")?;
-    
-    // Copy file to Usmap/ folder in roaming
-    let dest_path = usmap_folder.join(filename);
-    std::fs::copy(&source, &dest_path)
-        .map_err(|e| format!("Failed to copy file: {}", e))?;
-    
-    info!("Copied USmap file {} to {}", filename, usmap_folder.display());
-    
-    // Return just the filename
-    Ok(filename.to_string())
-}
 
-#[tauri::command]
-async fn set_usmap_path(usmap_path: String, state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), String> {
-    let mut state = state.lock().unwrap();
-    state.usmap_path = usmap_path.clone();
-    info!("Set USMAP path in AppState: {}", usmap_path);
-    Ok(())
-}
+    if !collisions.is_empty() {
+        for collision in &collisions {
+            let _ = window.emit("install_log", format!("[Merge] Collision: {}", collision));
+        }
+        if keep_last.is_none() {
+            return Err(format!(
+                "{} file(s) exist in more than one mod; choose an overwrite strategy to merge anyway",
+                collisions.len()
+            ));
+        }
+    }
 
-#[tauri::command]
-async fn get_usmap_path(state: State<'_, Arc<Mutex<AppState>>>) -> Result<String, String> {
-    let state = state.lock().unwrap();
-    Ok(state.usmap_path.clone())
-}
+    let merged_name = format!(
+        "{}{}_{}_P",
+        if bang_prefix { "!" } else { "" },
+        mod_base_name_without_priority(output_name),
+        "9".repeat(max_nines)
+    );
+    let out_dir = merged_output_dir();
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create output folder: {}", e))?;
+    for ext in ["pak", "utoc", "ucas"] {
+        let _ = std::fs::remove_file(out_dir.join(format!("{}.{}", merged_name, ext)));
+    }
+
+    let _ = window.emit("install_log", format!("[Merge] Packing {} file(s) as {}", owners.len(), merged_name));
+    let packed_counter = std::sync::atomic::AtomicI32::new(0);
+    let mut installable = InstallableMod {
+        mod_name: merged_name.clone(),
+        usmap_path: usmap_filename,
+        is_dir: true,
+        mod_path: merged_dir.clone(),
+        mount_point,
+        path_hash_seed: "00000000".to_string(),
+        compression: repak::Compression::Oodle,
+        parallel_processing,
+        obfuscate,
+        iostore_version,
+        ..Default::default()
+    };
+    if any_iostore {
+        let stage_window = window.clone();
+        convert_to_iostore_directory(
+            &installable,
+            out_dir.clone(),
+            merged_dir.clone(),
+            &packed_counter,
+            &move |stage| {
+                stage_window.emit("install_stage", &stage).ok();
+            },
+        ).map_err(|e| format!("Failed to build merged IoStore: {}", e))?;
+    } else {
+        installable.force_legacy_pak = true;
+        repak_dir(&installable, merged_dir.clone(), out_dir.clone(), &packed_counter)
+            .map_err(|e| format!("Failed to build merged PAK: {}", e))?;
+    }
 
-/// Get the USMAP directory path in the roaming folder.
-/// 
-/// # Returns
-/// Full path to `%APPDATA%/Repak-X/Usmap/`
-#[tauri::command]
-async fn get_usmap_dir_path() -> Result<String, String> {
-    Ok(usmap_dir().to_string_lossy().to_string())
+    let output = out_dir.join(format!("{}.pak", merged_name));
+    info!("Merged {} mod(s) into {}", mod_paths.len(), output.display());
+    let _ = window.emit("install_log", format!("[Merge] Done: {}", output.display()));
+    Ok(output.to_string_lossy().to_string())
 }
 
-/// List all USMAP files currently in the roaming Usmap folder.
-/// Reads from filesystem at runtime, not from saved state.
-/// 
-/// # Returns
-/// Vector of filenames (not full paths) of .usmap files in the folder
+/// Re-apply install-time fixes (texture mipmap strip, SerializeSize header) to an already
+/// installed mod. The mod is extracted to a temp dir, run through the normal install pipeline
+/// and written back over the original files, keeping its name, priority suffix, folder and
+/// enabled/disabled state. Legacy paks stay legacy; IoStore mods are rebuilt as IoStore.
 #[tauri::command]
-async fn list_usmap_files() -> Result<Vec<String>, String> {
-    let usmap_folder = usmap_dir();
-    
-    if !usmap_folder.exists() {
-        return Ok(Vec::new());
+async fn refix_installed_mod(
+    mod_path: String,
+    fix_textures: bool,
+    fix_serialsize: bool,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    use crate::install_mod::install_mod_logic::iotoc::convert_to_iostore_directory;
+    use crate::install_mod::install_mod_logic::pak_files::create_repak_from_pak;
+    use crate::install_mod::InstallableMod;
+    use std::io::BufReader;
+
+    let pak_path = PathBuf::from(&mod_path);
+    if !pak_path.exists() {
+        return Err(format!("File not found: {}", mod_path));
     }
-    
-    let entries = std::fs::read_dir(&usmap_folder)
-        .map_err(|e| format!("Failed to read Usmap directory: {}", e))?;
-    
-    let mut files = Vec::new();
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("usmap") {
-            if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-                files.push(filename.to_string());
-            }
+    let ext = pak_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if !matches!(ext, "pak" | "bak_repak" | "pak_disabled") {
+        return Err(format!("Unsupported file type: .{}", ext));
+    }
+    let mod_name = pak_path.file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid mod file name")?
+        .to_string();
+    let utoc_path = pak_path.with_extension("utoc");
+    let is_iostore = utoc_path.exists();
+
+    let state_guard = state.read().await;
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    drop(state_guard);
+
+    if !usmap_filename.is_empty() {
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
         }
     }
-    
-    Ok(files)
-}
 
-/// Get the currently active USMAP file by reading from filesystem.
-/// This reads the actual files in the Usmap folder, not the saved state.
-/// 
-/// # Returns
-/// - Filename of the first .usmap file found (there should only be one)
-/// - Empty string if no .usmap files exist
-#[tauri::command]
-async fn get_current_usmap_file() -> Result<String, String> {
-    let files = list_usmap_files().await?;
-    Ok(files.into_iter().next().unwrap_or_default())
-}
+    info!("refix_installed_mod called: {} (textures={}, serialsize={})", mod_path, fix_textures, fix_serialsize);
+    let _ = window.emit("install_log", format!("[Refix] Re-applying fixes to {}", mod_name));
 
-/// Get the full path to the currently active USMAP file.
-/// 
-/// # Returns
-/// - Full path to the .usmap file if one exists
-/// - Empty string if no .usmap file exists
-#[tauri::command]
-async fn get_current_usmap_full_path() -> Result<String, String> {
-    let files = list_usmap_files().await?;
-    if let Some(filename) = files.into_iter().next() {
-        let full_path = usmap_dir().join(&filename);
-        Ok(full_path.to_string_lossy().to_string())
+    let work_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    // Output goes to a staging dir first so a failure leaves the installed mod untouched
+    let staging_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let stage_window = window.clone();
+    let on_stage = move |stage: install_mod::install_mod_logic::InstallStage| {
+        stage_window.emit("install_stage", &stage).ok();
+    };
+    let packed_counter = std::sync::atomic::AtomicI32::new(0);
+
+    if is_iostore {
+        let _ = window.emit("install_log", "[Refix] Extracting IoStore content...");
+        let file_count = uasset_toolkit::extract_iostore(
+            &utoc_path.to_string_lossy(),
+            &work_dir.path().to_string_lossy(),
+            Some(&install_mod::current_aes_key_hex()),
+        ).map_err(|e| format!("Failed to extract IoStore: {}", e))?;
+        if file_count == 0 {
+            return Err("IoStore extraction produced no files".to_string());
+        }
+
+        let files: Vec<String> = crate::utoc_utils::read_utoc(&utoc_path)
+            .into_iter()
+            .map(|e| e.file_path)
+            .collect();
+        let installable = InstallableMod {
+            mod_name: mod_name.clone(),
+            mod_type: utils::get_current_pak_characteristics(files),
+            fix_textures,
+            fix_serialsize_header: fix_serialsize,
+            usmap_path: usmap_filename,
+            is_dir: true,
+            mod_path: work_dir.path().to_path_buf(),
+            mount_point: "../../../".to_string(),
+            path_hash_seed: "00000000".to_string(),
+            compression: repak::Compression::Oodle,
+            parallel_processing,
+            obfuscate,
+            iostore_version,
+            ..Default::default()
+        };
+
+        let _ = window.emit("install_log", format!("[Refix] Rebuilding IoStore from {} files...", file_count));
+        convert_to_iostore_directory(
+            &installable,
+            staging_dir.path().to_path_buf(),
+            work_dir.path().to_path_buf(),
+            &packed_counter,
+            &on_stage,
+        ).map_err(|e| format!("Failed to rebuild mod: {}", e))?;
     } else {
-        Ok(String::new())
+        let reader = repak::PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut BufReader::new(
+                File::open(&pak_path).map_err(|e| format!("Failed to open PAK: {}", e))?,
+            ))
+            .map_err(|e| format!("Failed to read PAK: {}", e))?;
+        let installable = InstallableMod {
+            mod_name: mod_name.clone(),
+            mod_type: utils::get_current_pak_characteristics(reader.files()),
+            repak: true,
+            fix_textures,
+            fix_serialsize_header: fix_serialsize,
+            usmap_path: usmap_filename,
+            mount_point: reader.mount_point().to_string(),
+            path_hash_seed: reader.path_hash_seed().unwrap_or(0).to_string(),
+            compression: repak::Compression::Oodle,
+            reader: Some(reader),
+            mod_path: pak_path.clone(),
+            // Keep the mod in its original legacy format
+            force_legacy_pak: true,
+            parallel_processing,
+            obfuscate,
+            iostore_version,
+            ..Default::default()
+        };
+
+        let _ = window.emit("install_log", "[Refix] Rebuilding legacy PAK...");
+        create_repak_from_pak(
+            &installable,
+            staging_dir.path().to_path_buf(),
+            &packed_counter,
+            &on_stage,
+        ).map_err(|e| format!("Failed to rebuild mod: {}", e))?;
     }
+
+    replace_installed_mod_files(staging_dir.path(), &mod_name, &pak_path)?;
+
+    info!("Re-applied fixes to {}", mod_name);
+    let _ = window.emit("install_log", format!("[Refix] Done: {}", mod_name));
+    Ok(())
 }
 
-/// Delete the currently active USMAP file from the roaming folder.
-/// 
-/// # Returns
-/// - `true` if a file was deleted
-/// - `false` if no file existed to delete
+/// Build an IoStore bundle (.pak/.utoc/.ucas) from a loose content folder into `output_dir`
+/// without installing it, for authors testing a build. Runs the same pipeline as a folder
+/// install on a temp copy, so the source folder is never modified. Returns the produced files.
 #[tauri::command]
-async fn delete_current_usmap() -> Result<bool, String> {
-    let usmap_folder = usmap_dir();
-    
-    if !usmap_folder.exists() {
-        return Ok(false);
-    }
-    
-    let entries = std::fs::read_dir(&usmap_folder)
-        .map_err(|e| format!("Failed to read Usmap directory: {}", e))?;
-    
-    let mut deleted = false;
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("usmap") {
-            std::fs::remove_file(&path)
-                .map_err(|e| format!("Failed to delete USMAP file: {}", e))?;
-            info!("Deleted USMAP file: {:?}", path);
-            deleted = true;
-        }
+async fn convert_directory_to_iostore(
+    source_dir: String,
+    output_dir: String,
+    fix_textures: bool,
+    obfuscate: bool,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<Vec<String>, String> {
+    use crate::install_mod::install_mod_logic::copy_dir_recursive;
+    use crate::install_mod::install_mod_logic::iotoc::convert_to_iostore_directory;
+    use crate::install_mod::InstallableMod;
+
+    let source = PathBuf::from(&source_dir);
+    if !source.is_dir() {
+        return Err(format!("Source folder not found: {}", source_dir));
     }
-    
-    Ok(deleted)
-}
+    let out_dir = PathBuf::from(&output_dir);
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create output folder: {}", e))?;
+    let mod_name = source.file_name()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid source folder name")?
+        .to_string();
 
-#[tauri::command]
-async fn get_all_tags(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec<String>, String> {
-    let state = state.lock().unwrap();
-    let mut tags = std::collections::HashSet::new();
-    
-    for metadata in &state.mod_metadata {
-        for tag in &metadata.custom_tags {
-            tags.insert(tag.clone());
+    let state_guard = state.read().await;
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let iostore_version = state_guard.iostore_version;
+    drop(state_guard);
+
+    if !usmap_filename.is_empty() {
+        if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
+            std::env::set_var("USMAP_PATH", &usmap_full_path);
         }
     }
-    
-    let mut tags_vec: Vec<String> = tags.into_iter().collect();
-    tags_vec.sort();
-    Ok(tags_vec)
-}
 
-#[tauri::command]
-async fn toggle_mod(mod_path: String, window: Window) -> Result<bool, String> {
-    let path = PathBuf::from(&mod_path);
-    
-    if !path.exists() {
-        let error_msg = "Mod file does not exist".to_string();
-        toast_events::emit_toggle_failed(&window, &error_msg);
-        return Err(error_msg);
+    info!("convert_directory_to_iostore called: {} -> {} (textures={}, obfuscate={})", source_dir, output_dir, fix_textures, obfuscate);
+    let _ = window.emit("install_log", format!("[Convert] Copying {} to a work folder...", mod_name));
+
+    // Texture fixes rewrite assets in place, so work on a copy
+    let work_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    copy_dir_recursive(&source, work_dir.path())
+        .map_err(|e| format!("Failed to copy source folder: {}", e))?;
+
+    let mut files = Vec::new();
+    crate::utils::collect_files(&mut files, work_dir.path())
+        .map_err(|e| format!("Failed to list source files: {}", e))?;
+    let relative_files: Vec<String> = files.iter()
+        .filter_map(|p| p.strip_prefix(work_dir.path()).ok())
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .collect();
+    if relative_files.is_empty() {
+        return Err("Source folder contains no files".to_string());
     }
-    
-    // Check current state
-    let is_enabled = path.extension().and_then(|s| s.to_str()) == Some("pak");
-    
-    // Toggle by renaming
-    let new_path = if is_enabled {
-        path.with_extension("bak_repak")
-    } else {
-        path.with_extension("pak")
+
+    let installable = InstallableMod {
+        mod_name: mod_name.clone(),
+        mod_type: utils::get_current_pak_characteristics(relative_files.clone()),
+        fix_textures,
+        usmap_path: usmap_filename,
+        is_dir: true,
+        mod_path: source.clone(),
+        mount_point: "../../../".to_string(),
+        path_hash_seed: "00000000".to_string(),
+        compression: repak::Compression::Oodle,
+        parallel_processing,
+        obfuscate,
+        iostore_version,
+        ..Default::default()
     };
-    
-    if let Err(e) = std::fs::rename(&path, &new_path) {
-        let error_msg = format!("Failed to toggle mod: {}", e);
-        toast_events::emit_toggle_failed(&window, &error_msg);
-        return Err(error_msg);
+
+    let _ = window.emit("install_log", format!("[Convert] Building IoStore from {} files...", relative_files.len()));
+    let stage_window = window.clone();
+    let packed_counter = std::sync::atomic::AtomicI32::new(0);
+    convert_to_iostore_directory(
+        &installable,
+        out_dir.clone(),
+        work_dir.path().to_path_buf(),
+        &packed_counter,
+        &move |stage| {
+            stage_window.emit("install_stage", &stage).ok();
+        },
+    ).map_err(|e| format!("Failed to build IoStore: {}", e))?;
+
+    // Audio/Movies content is written as a plain pak, so only report what was produced
+    let produced: Vec<String> = ["pak", "utoc", "ucas"]
+        .iter()
+        .map(|ext| out_dir.join(format!("{}.{}", mod_name, ext)))
+        .filter(|p| p.exists())
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    if produced.is_empty() {
+        return Err("Conversion did not produce any files".to_string());
+    }
+
+    info!("Converted {} to IoStore in {}", mod_name, out_dir.display());
+    let _ = window.emit("install_log", format!("[Convert] Done: {} file(s) in {}", produced.len(), out_dir.display()));
+    Ok(produced)
+}
+
+/// Copy a rebuilt mod from `staging_dir` over the installed files, keeping the installed
+/// names (and so .bak_repak/.pak_disabled for disabled mods)
+fn replace_installed_mod_files(staging_dir: &Path, mod_name: &str, pak_path: &Path) -> Result<(), String> {
+    let staged_pak = staging_dir.join(format!("{}.pak", mod_name));
+    if !staged_pak.exists() {
+        return Err("Rebuild did not produce a PAK file".to_string());
+    }
+
+    let utoc_path = pak_path.with_extension("utoc");
+    let ucas_path = pak_path.with_extension("ucas");
+    std::fs::copy(&staged_pak, pak_path)
+        .map_err(|e| format!("Failed to write PAK: {}", e))?;
+    let staged_utoc = staged_pak.with_extension("utoc");
+    let staged_ucas = staged_pak.with_extension("ucas");
+    if staged_utoc.exists() && staged_ucas.exists() {
+        std::fs::copy(&staged_utoc, &utoc_path)
+            .map_err(|e| format!("Failed to write .utoc: {}", e))?;
+        std::fs::copy(&staged_ucas, &ucas_path)
+            .map_err(|e| format!("Failed to write .ucas: {}", e))?;
+    } else {
+        // Pipeline produced a legacy pak (e.g. Audio/Movies), so old companions are stale
+        let _ = std::fs::remove_file(&utoc_path);
+        let _ = std::fs::remove_file(&ucas_path);
     }
-    
-    Ok(!is_enabled)
+    Ok(())
 }
 
+/// Run only the static-mesh SerializeSize fix on an installed mod and repack it in place.
+/// Returns the number of assets fixed; the mod is left untouched when nothing needed fixing.
 #[tauri::command]
-async fn extract_pak_to_destination(mod_path: String, dest_path: String) -> Result<(), String> {
-    use crate::install_mod::install_mod_logic::pak_files::extract_pak_to_dir;
+async fn fix_serialsize_for_mod(
+    mod_path: String,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<usize, String> {
+    use crate::install_mod::install_mod_logic::iotoc::{convert_to_iostore_directory, process_static_mesh_serializesize};
+    use crate::install_mod::install_mod_logic::pak_files::{extract_pak_to_dir, repak_dir};
     use crate::install_mod::InstallableMod;
-    use repak::PakBuilder;
-    use repak::utils::AesKey;
-    use std::str::FromStr;
     use std::io::BufReader;
-    
+
     let pak_path = PathBuf::from(&mod_path);
     if !pak_path.exists() {
-        return Err("Pak file not found".to_string());
+        return Err(format!("File not found: {}", mod_path));
     }
+    let mod_name = pak_path.file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid mod file name")?
+        .to_string();
+    let utoc_path = pak_path.with_extension("utoc");
+    let is_iostore = utoc_path.exists();
 
-    let dest_dir = PathBuf::from(&dest_path);
-    let mod_name = pak_path.file_stem().unwrap().to_string_lossy().to_string();
-    let to_create = dest_dir.join(&mod_name);
-    
-    std::fs::create_dir_all(&to_create).map_err(|e| e.to_string())?;
-    
-    // Open PAK
-    let file = File::open(&pak_path).map_err(|e| e.to_string())?;
-    let aes_key = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
-        .map_err(|e| e.to_string())?;
-        
-    let mut reader = BufReader::new(file);
-    let pak_reader = PakBuilder::new()
-        .key(aes_key.0)
-        .reader(&mut reader)
-        .map_err(|e| e.to_string())?;
-        
-    let installable_mod = InstallableMod {
+    let state_guard = state.read().await;
+    let usmap_filename = state_guard.usmap_path.clone();
+    let parallel_processing = state_guard.parallel_processing;
+    let obfuscate = state_guard.obfuscate;
+    let iostore_version = state_guard.iostore_version;
+    drop(state_guard);
+
+    // The fix re-serializes unversioned assets, so it can't run without mappings
+    let usmap_full_path = get_usmap_full_path(&usmap_filename)
+        .ok_or("A USMAP file is required to fix SerializeSize headers")?;
+    std::env::set_var("USMAP_PATH", &usmap_full_path);
+
+    info!("fix_serialsize_for_mod called: {}", mod_path);
+    let _ = window.emit("install_log", format!("[SerializeSize] Extracting {}", mod_name));
+
+    let work_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let staging_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    if is_iostore {
+        uasset_toolkit::extract_iostore(
+            &utoc_path.to_string_lossy(),
+            &work_dir.path().to_string_lossy(),
+            Some(&install_mod::current_aes_key_hex()),
+        ).map_err(|e| format!("Failed to extract IoStore: {}", e))?;
+    } else {
+        let reader = repak::PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut BufReader::new(
+                File::open(&pak_path).map_err(|e| format!("Failed to open PAK: {}", e))?,
+            ))
+            .map_err(|e| format!("Failed to read PAK: {}", e))?;
+        let source = InstallableMod {
+            mod_path: pak_path.clone(),
+            reader: Some(reader),
+            ..Default::default()
+        };
+        extract_pak_to_dir(&source, work_dir.path().to_path_buf())
+            .map_err(|e| format!("Failed to extract PAK: {}", e))?;
+    }
+
+    let _ = window.emit("install_log", "[SerializeSize] Scanning static meshes...");
+    let fixed = process_static_mesh_serializesize(work_dir.path(), Some(&usmap_full_path.to_string_lossy()))
+        .map_err(|e| format!("SerializeSize fix failed: {}", e))?;
+    if fixed == 0 {
+        let _ = window.emit("install_log", format!("[SerializeSize] No static meshes needed fixing in {}", mod_name));
+        return Ok(0);
+    }
+
+    let _ = window.emit("install_log", format!("[SerializeSize] Fixed {} asset(s), repacking...", fixed));
+    let packed_counter = std::sync::atomic::AtomicI32::new(0);
+    let mut installable = InstallableMod {
         mod_name: mod_name.clone(),
-        mod_type: "".to_string(),
-        reader: Option::from(pak_reader),
-        mod_path: pak_path.clone(),
+        usmap_path: usmap_filename,
+        is_dir: true,
+        mod_path: work_dir.path().to_path_buf(),
+        mount_point: "../../../".to_string(),
+        path_hash_seed: "00000000".to_string(),
+        compression: repak::Compression::Oodle,
+        parallel_processing,
+        obfuscate,
+        iostore_version,
         ..Default::default()
     };
-    
-    extract_pak_to_dir(&installable_mod, to_create).map_err(|e| e.to_string())?;
-    
-    Ok(())
-}
-
-/// Cleanup .ubulk files for textures that have inline data.
-/// This is called after extraction to remove unnecessary .ubulk files
-/// that were pulled from the base game but aren't needed because the
-/// mod's textures have been patched to use inline data.
-async fn cleanup_ubulk_for_inline_textures(output_dir: &PathBuf) {
-    use walkdir::WalkDir;
-    use uasset_toolkit::get_global_toolkit;
-    
-    // Find all .uasset files - UAssetTool will detect which are textures
-    let uasset_files: Vec<String> = WalkDir::new(output_dir)
+    if is_iostore {
+        let stage_window = window.clone();
+        convert_to_iostore_directory(
+            &installable,
+            staging_dir.path().to_path_buf(),
+            work_dir.path().to_path_buf(),
+            &packed_counter,
+            &move |stage| {
+                stage_window.emit("install_stage", &stage).ok();
+            },
+        ).map_err(|e| format!("Failed to rebuild IoStore: {}", e))?;
+    } else {
+        // extract_pak_to_dir lays files out relative to "../../../", so repack with that mount
+        installable.force_legacy_pak = true;
+        repak_dir(
+            &installable,
+            work_dir.path().to_path_buf(),
+            staging_dir.path().to_path_buf(),
+            &packed_counter,
+        ).map_err(|e| format!("Failed to rebuild PAK: {}", e))?;
+    }
+
+    replace_installed_mod_files(staging_dir.path(), &mod_name, &pak_path)?;
+
+    info!("Fixed SerializeSize for {} asset(s) in {}", fixed, mod_name);
+    let _ = window.emit("install_log", format!("[SerializeSize] Done: {} asset(s) fixed in {}", fixed, mod_name));
+    Ok(fixed)
+}
+
+#[derive(Serialize)]
+struct BackupReport {
+    dest: String,
+    copied: usize,
+    skipped: usize,
+    bytes_copied: u64,
+    /// Files that could not be copied, with the reason
+    failed: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PaksVerifyReport {
+    matching: usize,
+    /// Files whose contents differ from the backup
+    differing: Vec<String>,
+    /// Live files with no backed-up copy
+    missing_in_backup: Vec<String>,
+    /// Backed-up files no longer present in the game folder
+    missing_live: Vec<String>,
+}
+
+/// Base-game .pak/.utoc/.ucas files under `paks_dir`, relative to it. The `~mods` folder is skipped.
+fn list_base_game_paks(paks_dir: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(paks_dir)
         .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().eq_ignore_ascii_case("~mods"))
         .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
         .filter(|e| {
-            let path = e.path();
-            if let Some(ext) = path.extension() {
-                return ext.to_string_lossy().to_lowercase() == "uasset";
-            }
-            false
+            matches!(
+                e.path().extension().and_then(|x| x.to_str()).map(|x| x.to_lowercase()).as_deref(),
+                Some("pak" | "utoc" | "ucas")
+            )
         })
-        .map(|e| e.path().to_string_lossy().to_string())
-        .collect();
-    
-    if uasset_files.is_empty() {
-        return;
-    }
-    
-    log::info!("[Extraction] Checking {} uasset files for textures with inline data...", uasset_files.len());
-    
-    // Use UAssetToolkit to check which are textures with inline data
-    // The batch_has_inline_texture_data function internally checks asset type
-    match get_global_toolkit() {
-        Ok(toolkit) => {
-            // Get USMAP path from environment
-            let usmap_path = std::env::var("USMAP_PATH").ok();
-            
-            match toolkit.batch_has_inline_texture_data(&uasset_files, usmap_path.as_deref()) {
-                Ok(inline_files) => {
-                    log::info!("[Extraction] Found {} textures with inline data", inline_files.len());
-                    
-                    // Delete .ubulk files for textures with inline data
-                    let mut deleted_count = 0;
-                    for uasset_path in inline_files {
-                        let ubulk_path = uasset_path.replace(".uasset", ".ubulk");
-                        if std::path::Path::new(&ubulk_path).exists() {
-                            if let Ok(_) = std::fs::remove_file(&ubulk_path) {
-                                deleted_count += 1;
-                                log::debug!("[Extraction] Deleted unnecessary .ubulk: {}", ubulk_path);
-                            }
-                        }
-                    }
-                    
-                    if deleted_count > 0 {
-                        log::info!("[Extraction] Cleaned up {} unnecessary .ubulk files", deleted_count);
-                    }
-                }
-                Err(e) => {
-                    log::warn!("[Extraction] Failed to check inline texture data: {}", e);
-                }
-            }
-        }
-        Err(e) => {
-            log::warn!("[Extraction] UAssetToolkit unavailable for cleanup: {}", e);
-        }
-    }
+        .filter_map(|e| e.path().strip_prefix(paks_dir).ok().map(|p| p.to_path_buf()))
+        .collect()
 }
 
-/// Extract assets from a mod file (PAK or IoStore) to a destination directory.
-/// Automatically detects the mod type and uses the appropriate extraction method.
-/// Handles disabled mods (.bak_repak extension) by treating them as PAK files.
-/// 
-/// # Arguments
-/// * `mod_path` - Path to the mod file (.pak, .utoc, .bak_repak)
-/// * `dest_path` - Destination directory for extracted files
-/// 
-/// # Returns
-/// Number of files extracted
+/// Copy the base game's Paks (everything except ~mods) to `dest`.
+/// Files already backed up with the same size and modification time are skipped.
 #[tauri::command]
-async fn extract_mod_assets(mod_path: String, dest_path: String, state: State<'_, Arc<Mutex<AppState>>>) -> Result<usize, String> {
-    // Set USMAP_PATH from AppState so UAssetTool can load mappings
-    {
-        let state_guard = state.lock().unwrap();
-        let usmap_filename = state_guard.usmap_path.clone();
-        drop(state_guard);
-        if !usmap_filename.is_empty() {
-            if let Some(usmap_full_path) = get_usmap_full_path(&usmap_filename) {
-                std::env::set_var("USMAP_PATH", &usmap_full_path);
+async fn backup_game_paks(
+    dest: String,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<BackupReport, String> {
+    let game_path = state.read().await.game_path.clone();
+    // game_path is the ~mods folder; the base game containers live in its parent Paks folder
+    let paks_dir = game_path.parent()
+        .filter(|p| p.is_dir())
+        .ok_or("Game path is not set")?
+        .to_path_buf();
+    let dest_dir = PathBuf::from(&dest);
+    if dest_dir.starts_with(&paks_dir) {
+        return Err("Backup destination cannot be inside the game's Paks folder".to_string());
+    }
+
+    info!("backup_game_paks called: {} -> {}", paks_dir.display(), dest_dir.display());
+    let files = list_base_game_paks(&paks_dir);
+    let mut report = BackupReport {
+        dest: dest.clone(),
+        copied: 0,
+        skipped: 0,
+        bytes_copied: 0,
+        failed: Vec::new(),
+    };
+
+    for (idx, rel) in files.iter().enumerate() {
+        let src = paks_dir.join(rel);
+        let dst = dest_dir.join(rel);
+        let Ok(src_meta) = std::fs::metadata(&src) else {
+            report.failed.push(format!("{}: unreadable", rel.display()));
+            continue;
+        };
+        let unchanged = std::fs::metadata(&dst).is_ok_and(|dst_meta| {
+            dst_meta.len() == src_meta.len() && dst_meta.modified().ok() == src_meta.modified().ok()
+        });
+        if unchanged {
+            report.skipped += 1;
+            continue;
+        }
+
+        let _ = window.emit("install_log", format!(
+            "[Backup] ({}/{}) Copying {}", idx + 1, files.len(), rel.display()
+        ));
+        let result = dst.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::copy(&src, &dst))
+            .and_then(|bytes| {
+                // Carry the mtime over so the next backup can tell the file is unchanged
+                if let Ok(modified) = src_meta.modified() {
+                    File::options().write(true).open(&dst)?.set_modified(modified)?;
+                }
+                Ok(bytes)
+            });
+        match result {
+            Ok(bytes) => {
+                report.copied += 1;
+                report.bytes_copied += bytes;
+            }
+            Err(e) => {
+                warn!("Failed to back up {}: {}", src.display(), e);
+                report.failed.push(format!("{}: {}", rel.display(), e));
             }
         }
     }
-    extract_mod_assets_inner(mod_path, dest_path).await
+
+    info!("Game Paks backup: {} copied, {} skipped, {} failed", report.copied, report.skipped, report.failed.len());
+    let _ = window.emit("install_log", format!(
+        "[Backup] Done: {} copied, {} unchanged, {} failed", report.copied, report.skipped, report.failed.len()
+    ));
+
+    let mut state = state.write().await;
+    state.game_paks_backup_dir = Some(dest_dir);
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(report)
 }
 
-async fn extract_mod_assets_inner(mod_path: String, dest_path: String) -> Result<usize, String> {
-    let mut path = PathBuf::from(&mod_path);
-    if !path.exists() {
-        return Err(format!("File not found: {}", mod_path));
+/// AES keys Marvel Rivals is known to have shipped with, newest first
+const KNOWN_AES_KEYS: &[&str] = &[install_mod::DEFAULT_AES_KEY_HEX];
+
+/// Find which candidate key decrypts the base game's containers. Paks with an encrypted index
+/// are tried first since they're cheap to open; encrypted .utoc containers are the fallback.
+fn find_game_aes_key(paks_dir: &Path, candidates: &[String]) -> Option<String> {
+    use repak::utils::AesKey;
+    use std::str::FromStr;
+
+    let mut paks: Vec<PathBuf> = Vec::new();
+    let mut utocs: Vec<PathBuf> = Vec::new();
+    // Base-game containers sit directly in Paks; ~mods and other subfolders are skipped
+    for entry in std::fs::read_dir(paks_dir).ok()?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("pak") => paks.push(path),
+            Some("utoc") => utocs.push(path),
+            _ => {}
+        }
     }
-    
-    // Check if this is a PAK file with a corresponding .utoc file (IoStore mod)
-    // IoStore mods have both .pak (with just chunknames) and .utoc/.ucas (actual data)
-    let extension = path.extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
-    
-    if extension == "pak" || extension == "bak_repak" {
-        // Check if there's a .utoc file with the same name - if so, use IoStore extraction
-        // Handle .bak_repak (disabled mod) by stripping .bak_repak then .pak to get base name
-        let base_path = if extension == "bak_repak" {
-            let name = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
-            let base_name = name.trim_end_matches(".bak_repak").trim_end_matches(".pak");
-            path.parent().map(|p| p.join(base_name)).unwrap_or_else(|| PathBuf::from(base_name))
-        } else {
-            path.with_extension("")
+    paks.sort();
+    utocs.sort();
+
+    let open = |path: &Path, key: Option<&AesKey>| -> bool {
+        let Ok(file) = File::open(path) else {
+            return false;
         };
-        let utoc_path = base_path.with_extension("utoc");
-        if utoc_path.exists() {
-            log::info!("Detected IoStore mod (has .utoc alongside .pak/.bak_repak), using IoStore extraction");
-            path = utoc_path;
+        let mut reader = std::io::BufReader::new(file);
+        let mut builder = repak::PakBuilder::new();
+        if let Some(key) = key {
+            builder = builder.key(key.0.clone());
         }
-    }
-    
-    let dest_dir = PathBuf::from(&dest_path);
-    
-    // Get mod name - handle .bak_repak extension specially
-    let file_name = path.file_name()
-        .map(|s| s.to_string_lossy().to_string())
-        .unwrap_or_else(|| "extracted".to_string());
-    
-    // Strip .bak_repak or other extensions to get clean mod name
-    let mod_name = if file_name.ends_with(".bak_repak") {
-        // Remove .bak_repak and then .pak to get the base name
-        file_name.trim_end_matches(".bak_repak")
-            .trim_end_matches(".pak")
-            .to_string()
-    } else {
-        path.file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| "extracted".to_string())
+        builder.reader(&mut reader).is_ok()
     };
-    
-    let output_dir = dest_dir.join(&mod_name);
-    
-    // Create output directory
-    std::fs::create_dir_all(&output_dir).map_err(|e| e.to_string())?;
-    
-    // Re-get extension after potential path change
-    let extension = path.extension()
-        .map(|e| e.to_string_lossy().to_lowercase())
-        .unwrap_or_default();
-    
-    match extension.as_str() {
-        "utoc" => {
-            // IoStore extraction using UAssetTool
-            log::info!("Starting IoStore extraction from {:?} to {:?}", path, output_dir);
-            let file_count = uasset_toolkit::extract_iostore(
-                &path.to_string_lossy(),
-                &output_dir.to_string_lossy(),
-                None, // Use default AES key
-            ).map_err(|e| {
-                log::error!("IoStore extraction failed: {}", e);
-                format!("Failed to extract IoStore: {}", e)
-            })?;
-            
-            log::info!("Extracted {} files from IoStore to {:?}", file_count, output_dir);
-            
-            // Post-extraction cleanup: Remove .ubulk files for textures with inline data
-            cleanup_ubulk_for_inline_textures(&output_dir).await;
-            
-            Ok(file_count)
+
+    for pak in &paks {
+        // A pak that opens without a key tells us nothing about the key
+        if open(pak, None) {
+            continue;
         }
-        "pak" => {
-            // PAK extraction
-            use crate::install_mod::install_mod_logic::pak_files::extract_pak_to_dir;
-            use crate::install_mod::InstallableMod;
-            use repak::PakBuilder;
-            use repak::utils::AesKey;
-            use std::str::FromStr;
-            use std::io::BufReader;
-            
-            let file = File::open(&path).map_err(|e| e.to_string())?;
-            let aes_key = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
-                .map_err(|e| e.to_string())?;
-            
-            let mut reader = BufReader::new(file);
-            let pak_reader = PakBuilder::new()
-                .key(aes_key.0)
-                .reader(&mut reader)
-                .map_err(|e| e.to_string())?;
-            
-            let file_count = pak_reader.files().len();
-            
-            let installable_mod = InstallableMod {
-                mod_name: mod_name.clone(),
-                mod_type: "".to_string(),
-                reader: Some(pak_reader),
-                mod_path: path.clone(),
-                ..Default::default()
+        for candidate in candidates {
+            let Ok(key) = AesKey::from_str(candidate) else {
+                continue;
             };
-            
-            extract_pak_to_dir(&installable_mod, output_dir.clone()).map_err(|e| e.to_string())?;
-            
-            log::info!("Extracted {} files from PAK to {:?}", file_count, output_dir);
-            Ok(file_count)
+            if open(pak, Some(&key)) {
+                info!("AES key {}... decrypts {}", &candidate[..8.min(candidate.len())], pak.display());
+                return Some(candidate.clone());
+            }
         }
-        "ucas" => {
-            // User selected .ucas, find the corresponding .utoc
-            let utoc_path = path.with_extension("utoc");
-            if !utoc_path.exists() {
-                return Err(format!("Cannot find .utoc file for: {}", mod_path));
+        warn!("No candidate AES key decrypts {}", pak.display());
+        return None;
+    }
+
+    for utoc in &utocs {
+        let utoc_str = utoc.to_string_lossy();
+        if !uasset_toolkit::is_iostore_encrypted_header(&utoc_str).unwrap_or(false) {
+            continue;
+        }
+        for candidate in candidates {
+            if uasset_toolkit::list_iostore_files(&utoc_str, Some(candidate)).is_ok() {
+                info!("AES key {}... decrypts {}", &candidate[..8.min(candidate.len())], utoc.display());
+                return Some(candidate.clone());
             }
-            
-            // Recursively call with the .utoc path
-            let utoc_str = utoc_path.to_string_lossy().to_string();
-            Box::pin(extract_mod_assets_inner(utoc_str, dest_path)).await
         }
-        "bak_repak" => {
-            // Disabled PAK file - extract it as a regular PAK
-            use crate::install_mod::install_mod_logic::pak_files::extract_pak_to_dir;
-            use crate::install_mod::InstallableMod;
-            use repak::PakBuilder;
-            use repak::utils::AesKey;
-            use std::str::FromStr;
-            use std::io::BufReader;
-            
-            let file = File::open(&path).map_err(|e| e.to_string())?;
-            let aes_key = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
-                .map_err(|e| e.to_string())?;
-            
-            let mut reader = BufReader::new(file);
-            let pak_reader = PakBuilder::new()
-                .key(aes_key.0)
-                .reader(&mut reader)
-                .map_err(|e| e.to_string())?;
-            
-            let file_count = pak_reader.files().len();
-            
-            let installable_mod = InstallableMod {
-                mod_name: mod_name.clone(),
-                mod_type: "".to_string(),
-                reader: Some(pak_reader),
-                mod_path: path.clone(),
-                ..Default::default()
-            };
-            
-            extract_pak_to_dir(&installable_mod, output_dir.clone()).map_err(|e| e.to_string())?;
-            
-            log::info!("Extracted {} files from disabled PAK to {:?}", file_count, output_dir);
-            Ok(file_count)
+        warn!("No candidate AES key decrypts {}", utoc.display());
+        return None;
+    }
+    None
+}
+
+/// Detect the AES key the installed game uses by trying the current and known keys against the
+/// base game's containers, then use and persist the one that works for all pak operations
+#[tauri::command]
+async fn detect_game_aes_key(
+    game_paks_dir: String,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<String, String> {
+    info!("detect_game_aes_key called: {}", game_paks_dir);
+    let paks_dir = PathBuf::from(&game_paks_dir);
+    if !paks_dir.is_dir() {
+        return Err(format!("Game Paks folder not found: {}", paks_dir.display()));
+    }
+
+    let mut candidates: Vec<String> = vec![install_mod::current_aes_key_hex()];
+    for known in KNOWN_AES_KEYS {
+        if !candidates.iter().any(|c| c.eq_ignore_ascii_case(known)) {
+            candidates.push(known.to_string());
         }
-        _ => {
-            Err(format!("Unsupported file type: .{}. Supported: .pak, .utoc, .ucas, .bak_repak", extension))
+    }
+
+    let key = find_game_aes_key(&paks_dir, &candidates).ok_or_else(|| {
+        "None of the known AES keys decrypt the game's paks. The game has probably changed its key; set the new key manually.".to_string()
+    })?;
+
+    install_mod::set_aes_key(&key)?;
+    let mut state = state.write().await;
+    state.aes_key = if key.eq_ignore_ascii_case(install_mod::DEFAULT_AES_KEY_HEX) { None } else { Some(key.clone()) };
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// Use a specific AES key (hex) for all pak/IoStore operations, e.g. after the game rotated its key
+#[tauri::command]
+async fn set_aes_key(key: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("set_aes_key called");
+    install_mod::set_aes_key(&key)?;
+    let mut state = state.write().await;
+    state.aes_key = Some(install_mod::current_aes_key_hex());
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Point the app at a specific UAssetTool executable, for install layouts where it can't be found
+/// next to the app. The toolkit restarts with the new path right away.
+#[tauri::command]
+async fn set_uasset_tool_path(path: String, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("set_uasset_tool_path called: {}", path);
+    if !Path::new(&path).is_file() {
+        return Err(format!("UAssetTool executable not found at: {}", path));
+    }
+    uasset_toolkit::reinit_global_toolkit(path.clone()).map_err(|e| e.to_string())?;
+    let mut state = state.write().await;
+    state.uasset_tool_path = Some(path);
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// UAssetTool executable the toolkit currently launches, whether guessed or set by the user
+#[tauri::command]
+async fn get_uasset_tool_path() -> Result<String, String> {
+    let toolkit = uasset_toolkit::get_global_toolkit().map_err(|e| e.to_string())?;
+    Ok(toolkit.tool_path())
+}
+
+/// Recent stderr output of the UAssetTool process, for bug reports when the tool crashes
+#[tauri::command]
+async fn get_uasset_tool_stderr() -> Result<String, String> {
+    let toolkit = uasset_toolkit::get_global_toolkit().map_err(|e| e.to_string())?;
+    Ok(toolkit.stderr_tail())
+}
+
+/// Compare the live base-game Paks against the last backup by content hash
+/// to detect files corrupted by a bad patch or disk error
+#[tauri::command]
+async fn verify_game_paks(
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<PaksVerifyReport, String> {
+    let (game_path, backup_dir) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.game_paks_backup_dir.clone())
+    };
+    let backup_dir = backup_dir
+        .filter(|p| p.is_dir())
+        .ok_or("No game Paks backup found. Run a backup first.")?;
+    let paks_dir = game_path.parent()
+        .filter(|p| p.is_dir())
+        .ok_or("Game path is not set")?
+        .to_path_buf();
+
+    info!("verify_game_paks called: {} vs {}", paks_dir.display(), backup_dir.display());
+    let live: std::collections::BTreeSet<PathBuf> = list_base_game_paks(&paks_dir).into_iter().collect();
+    let backed_up: std::collections::BTreeSet<PathBuf> = list_base_game_paks(&backup_dir).into_iter().collect();
+
+    let mut report = PaksVerifyReport {
+        matching: 0,
+        differing: Vec::new(),
+        missing_in_backup: live.difference(&backed_up).map(|p| p.display().to_string()).collect(),
+        missing_live: backed_up.difference(&live).map(|p| p.display().to_string()).collect(),
+    };
+
+    let common: Vec<&PathBuf> = live.intersection(&backed_up).collect();
+    for (idx, rel) in common.iter().enumerate() {
+        let live_path = paks_dir.join(rel);
+        let backup_path = backup_dir.join(rel);
+        let _ = window.emit("install_log", format!(
+            "[Verify] ({}/{}) Checking {}", idx + 1, common.len(), rel.display()
+        ));
+        let same_size = std::fs::metadata(&live_path).map(|m| m.len()).ok()
+            == std::fs::metadata(&backup_path).map(|m| m.len()).ok();
+        let same = same_size && match (p2p_sharing::hash_file(&live_path), p2p_sharing::hash_file(&backup_path)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        };
+        if same {
+            report.matching += 1;
+        } else {
+            report.differing.push(rel.display().to_string());
         }
     }
+
+    info!("Game Paks verify: {} matching, {} differing, {} missing from backup, {} missing live",
+        report.matching, report.differing.len(), report.missing_in_backup.len(), report.missing_live.len());
+    Ok(report)
 }
 
 #[tauri::command]
@@ -3359,79 +8930,145 @@ fn is_game_process_running() -> bool {
             }
         }
         
-        // Fallback: Check process name() directly
-        let process_name = process.name().to_string_lossy().to_lowercase();
-        if process_name == game_exe_name {
-            return true;
+        // Fallback: Check process name() directly
+        let process_name = process.name().to_string_lossy().to_lowercase();
+        if process_name == game_exe_name {
+            return true;
+        }
+    }
+    
+    false
+}
+
+/// Whether `dir` is the game install folder, i.e. holds the launcher or its launch_record
+fn is_game_root(dir: &Path) -> bool {
+    dir.join("MarvelRivals_Launcher.exe").is_file() || dir.join("launch_record").is_file()
+}
+
+/// Walk up from the ~mods folder to the game install folder. On Steam this is 5 levels up
+/// (~mods -> Paks -> Content -> Marvel -> MarvelGame -> MarvelRivals), but other stores differ.
+fn find_game_root(mods_path: &Path) -> Option<PathBuf> {
+    mods_path.ancestors().skip(1).find(|dir| is_game_root(dir)).map(Path::to_path_buf)
+}
+
+/// The cached game root when it still matches the game path, without searching for a new one
+fn cached_game_root(state: &AppState) -> Option<PathBuf> {
+    state.game_root.as_ref()
+        .filter(|root| state.game_path.starts_with(root) && is_game_root(root))
+        .cloned()
+}
+
+/// The game root for the current game path, reusing the cached one while it's still valid
+fn detect_game_root(state: &mut AppState) -> Result<PathBuf, String> {
+    if let Some(root) = cached_game_root(state) {
+        return Ok(root);
+    }
+    let root = find_game_root(&state.game_path).ok_or_else(|| format!(
+        "Could not find the game folder (containing MarvelRivals_Launcher.exe) above {}",
+        state.game_path.display()
+    ))?;
+    info!("Detected game root: {}", root.display());
+    state.game_root = Some(root.clone());
+    if let Err(e) = save_state(state) {
+        warn!("Failed to save detected game root: {}", e);
+    }
+    Ok(root)
+}
+
+/// Replace `launch_record` with `value`. The file is deleted and recreated rather than
+/// overwritten, which the launcher otherwise ignores.
+fn write_launch_record(launch_record_path: &Path, value: &str) -> Result<(), String> {
+    if launch_record_path.exists() {
+        std::fs::remove_file(launch_record_path)
+            .map_err(|e| format!("Failed to delete launch_record: {}", e))?;
+    }
+    std::fs::write(launch_record_path, value)
+        .map_err(|e| format!("Failed to write launch_record: {}", e))
+}
+
+/// Apply the skip-launcher patch for a launch according to `mode`. Returns the original
+/// value to restore once the game is up (per-launch mode only).
+fn prepare_launch_record(launch_record_path: &Path, mode: LauncherSkipMode) -> Result<Option<String>, String> {
+    match mode {
+        LauncherSkipMode::Off => {
+            info!("Launcher skip is off, leaving launch_record untouched");
+            Ok(None)
+        }
+        LauncherSkipMode::Always => {
+            write_launch_record(launch_record_path, "0")?;
+            info!("launch_record set to 0 (skip launcher, persistent)");
+            Ok(None)
+        }
+        LauncherSkipMode::PerLaunch => {
+            // Backup original value
+            let original_value = match std::fs::read_to_string(launch_record_path) {
+                Ok(content) => {
+                    info!("Backed up launch_record value: {}", content.trim());
+                    Some(content)
+                }
+                Err(e) => {
+                    warn!("Could not read launch_record (file may not exist): {}", e);
+                    None
+                }
+            };
+            write_launch_record(launch_record_path, "0")?;
+            info!("Recreated launch_record with value 0 (skip launcher)");
+            Ok(original_value)
+        }
+    }
+}
+
+/// Restore `launch_record` to `original` once the game process is up (or after 30s)
+fn spawn_launch_record_restore(launch_record_path: PathBuf, original: String) {
+    std::thread::spawn(move || {
+        use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+        
+        // Wait for the game process to start (up to 30 seconds)
+        let mut waited = 0;
+        let mut game_started = false;
+        
+        while waited < 30000 {
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+            waited += 1000;
+            
+            // Check if game process is running
+            let s = System::new_with_specifics(
+                RefreshKind::new().with_processes(ProcessRefreshKind::new())
+            );
+            
+            let mut found = false;
+            for (_pid, process) in s.processes() {
+                let process_name = process.name().to_string_lossy().to_lowercase();
+                if process_name == "marvel-win64-shipping.exe" {
+                    info!("Game process detected, waiting 2 more seconds before restoring launch_record");
+                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    found = true;
+                    game_started = true;
+                    break;
+                }
+            }
+            
+            if found {
+                break;
+            }
+        }
+        
+        if !game_started {
+            warn!("Timeout waiting for game to start, restoring launch_record anyway");
+        }
+        
+        // DELETE and RECREATE with original value
+        match write_launch_record(&launch_record_path, original.trim()) {
+            Ok(()) => info!("Restored launch_record to original value (game_started: {})", game_started),
+            Err(e) => warn!("Failed to restore launch_record: {}", e),
         }
-    }
-    
-    false
+    });
 }
 
-/// Launch Marvel Rivals via Steam, temporarily skipping the launcher
-/// 
-/// This function:
-/// 1. Backs up the current launch_record value
-/// 2. DELETES the launch_record file
-/// 3. RECREATES it with "0" to skip the launcher
-/// 4. Launches the game via Steam protocol
-/// 5. Restores the original launch_record after game starts
-/// 
-/// This ensures the game launches without the launcher when using our app,
-/// but preserves the user's Steam launch settings for manual launches
-#[tauri::command]
-async fn launch_game(state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), String> {
+/// Start the game through Steam with RUNASINVOKER to skip the UAC prompt
+fn spawn_steam_launch() -> std::io::Result<std::process::Child> {
     use std::process::Command;
-    
-    // Get game path (this is the ~mods folder inside Paks)
-    let mods_path = {
-        let state = state.lock().unwrap();
-        state.game_path.clone()
-    };
-    
-    // Go up 5 levels to get the actual game root
-    // ~mods -> Paks -> Content -> Marvel -> MarvelGame -> MarvelRivals (game root)
-    let game_root = mods_path
-        .parent() // Paks
-        .and_then(|p| p.parent()) // Content
-        .and_then(|p| p.parent()) // Marvel
-        .and_then(|p| p.parent()) // MarvelGame
-        .and_then(|p| p.parent()) // MarvelRivals (game root)
-        .ok_or_else(|| "Could not determine game root directory".to_string())?;
-    
-    // Path to launch_record file (in the game root, next to MarvelRivals_Launcher.exe)
-    let launch_record_path = game_root.join("launch_record");
-    
-    // Backup original value
-    let original_value = match std::fs::read_to_string(&launch_record_path) {
-        Ok(content) => {
-            info!("Backed up launch_record value: {}", content.trim());
-            Some(content)
-        }
-        Err(e) => {
-            warn!("Could not read launch_record (file may not exist): {}", e);
-            None
-        }
-    };
-    
-    // DELETE the launch_record file
-    if launch_record_path.exists() {
-        if let Err(e) = std::fs::remove_file(&launch_record_path) {
-            error!("Failed to delete launch_record: {}", e);
-            return Err(format!("Failed to delete launch_record: {}", e));
-        }
-        info!("Deleted launch_record file");
-    }
-    
-    // RECREATE it with "0" to skip launcher
-    if let Err(e) = std::fs::write(&launch_record_path, "0") {
-        error!("Failed to recreate launch_record: {}", e);
-        return Err(format!("Failed to recreate launch_record: {}", e));
-    }
-    info!("Recreated launch_record with value 0 (skip launcher)");
-    
-    // Launch the game via Steam with RUNASINVOKER to skip UAC prompt
+
     #[cfg(target_os = "windows")]
     let launch_result = {
         use std::os::windows::process::CommandExt;
@@ -3458,67 +9095,68 @@ async fn launch_game(state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), Strin
     let launch_result = Command::new("xdg-open")
         .arg("steam://run/2767030")
         .spawn();
+
+    launch_result
+}
+
+/// The game mounts mods once at startup, remember which ones so later toggles can be flagged
+fn snapshot_mounted_mods(state: &mut AppState) {
+    match list_mod_entries(&state.game_path, &state.mod_metadata) {
+        Ok(entries) => {
+            state.mounted_mods = Some(entries.into_iter()
+                .filter(|e| e.enabled)
+                .map(|e| e.path.with_extension("pak"))
+                .collect());
+            if let Err(e) = save_state(state) {
+                warn!("Failed to save mounted mod snapshot: {}", e);
+            }
+        }
+        Err(e) => warn!("Failed to snapshot mounted mods: {}", e),
+    }
+}
+
+/// Locate Marvel-Win64-Shipping.exe below the game root (MarvelGame/Marvel/Binaries/Win64 on Steam)
+fn find_game_exe(game_root: &Path) -> Option<PathBuf> {
+    walkdir::WalkDir::new(game_root)
+        .max_depth(5)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().eq_ignore_ascii_case("Content"))
+        .filter_map(|e| e.ok())
+        .find(|e| e.file_type().is_file() && e.file_name().eq_ignore_ascii_case("Marvel-Win64-Shipping.exe"))
+        .map(|e| e.into_path())
+}
+
+/// Launch Marvel Rivals via Steam, skipping the launcher according to `launcher_skip_mode`
+/// 
+/// In per-launch mode (the default) this function:
+/// 1. Backs up the current launch_record value
+/// 2. DELETES the launch_record file
+/// 3. RECREATES it with "0" to skip the launcher
+/// 4. Launches the game via Steam protocol
+/// 5. Restores the original launch_record after game starts
+/// 
+/// This ensures the game launches without the launcher when using our app,
+/// but preserves the user's Steam launch settings for manual launches.
+/// "always" leaves the patch in place and "off" doesn't touch launch_record.
+#[tauri::command]
+async fn launch_game(state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    let (game_root, skip_mode) = {
+        let mut state = state.write().await;
+        (detect_game_root(&mut state)?, state.launcher_skip_mode)
+    };
+    
+    let launch_record_path = game_root.join("launch_record");
+    let original_value = prepare_launch_record(&launch_record_path, skip_mode)?;
     
     // Check launch result
-    match launch_result {
+    match spawn_steam_launch() {
         Ok(_) => {
             info!("Successfully launched Marvel Rivals via Steam");
+            snapshot_mounted_mods(&mut *state.write().await);
             
-            // Spawn a background task to restore the launch_record after the game starts
-            let launch_record_path_clone = launch_record_path.clone();
-            std::thread::spawn(move || {
-                use sysinfo::{ProcessRefreshKind, RefreshKind, System};
-                
-                // Wait for the game process to start (up to 30 seconds)
-                let mut waited = 0;
-                let mut game_started = false;
-                
-                while waited < 30000 {
-                    std::thread::sleep(std::time::Duration::from_millis(1000));
-                    waited += 1000;
-                    
-                    // Check if game process is running
-                    let s = System::new_with_specifics(
-                        RefreshKind::new().with_processes(ProcessRefreshKind::new())
-                    );
-                    
-                    let mut found = false;
-                    for (_pid, process) in s.processes() {
-                        let process_name = process.name().to_string_lossy().to_lowercase();
-                        if process_name == "marvel-win64-shipping.exe" {
-                            info!("Game process detected, waiting 2 more seconds before restoring launch_record");
-                            std::thread::sleep(std::time::Duration::from_secs(2));
-                            found = true;
-                            game_started = true;
-                            break;
-                        }
-                    }
-                    
-                    if found {
-                        break;
-                    }
-                }
-                
-                if !game_started {
-                    warn!("Timeout waiting for game to start, restoring launch_record anyway");
-                }
-                
-                // DELETE and RECREATE with original value
-                if let Some(original) = original_value {
-                    if launch_record_path_clone.exists() {
-                        if let Err(e) = std::fs::remove_file(&launch_record_path_clone) {
-                            warn!("Failed to delete launch_record for restoration: {}", e);
-                            return;
-                        }
-                    }
-                    
-                    if let Err(e) = std::fs::write(&launch_record_path_clone, original.trim()) {
-                        warn!("Failed to recreate launch_record with original value: {}", e);
-                    } else {
-                        info!("Restored launch_record to original value (game_started: {})", game_started);
-                    }
-                }
-            });
+            if let Some(original) = original_value {
+                spawn_launch_record_restore(launch_record_path, original);
+            }
             
             Ok(())
         }
@@ -3529,30 +9167,129 @@ async fn launch_game(state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), Strin
     }
 }
 
-/// Toggle the skip launcher patch (manual control)
-/// Returns true if skip launcher is now enabled (0), false if disabled (6)
+/// Launch the game executable directly instead of through Steam, e.g. for non-Steam installs
+/// or to attach a debugger. `extra_args` are passed through to the game. launch_record is
+/// handled like in launch_game. Falls back to Steam when the exe can't be found.
 #[tauri::command]
-async fn skip_launcher_patch(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
-    // Get game path (this is the ~mods folder inside Paks)
-    let mods_path = {
-        let state = state.lock().unwrap();
-        state.game_path.clone()
+async fn launch_game_direct(extra_args: Vec<String>, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("launch_game_direct called: {:?}", extra_args);
+    let (game_root, skip_mode) = {
+        let mut state = state.write().await;
+        (detect_game_root(&mut state)?, state.launcher_skip_mode)
     };
-    
-    // Go up 5 levels to get the actual game root
-    // ~mods -> Paks -> Content -> Marvel -> MarvelGame -> MarvelRivals (game root)
-    let game_root = mods_path
-        .parent() // Paks
-        .and_then(|p| p.parent()) // Content
-        .and_then(|p| p.parent()) // Marvel
-        .and_then(|p| p.parent()) // MarvelGame
-        .and_then(|p| p.parent()) // MarvelRivals (game root)
-        .ok_or_else(|| "Could not determine game root directory".to_string())?;
-    
-    // Path to launch_record file
+
+    let launch_record_path = game_root.join("launch_record");
+    let original_value = prepare_launch_record(&launch_record_path, skip_mode)?;
+
+    let launch_result = match find_game_exe(&game_root) {
+        Some(exe) => {
+            info!("Launching {} directly", exe.display());
+            let mut cmd = std::process::Command::new(&exe);
+            cmd.args(&extra_args);
+            if let Some(dir) = exe.parent() {
+                cmd.current_dir(dir);
+            }
+            #[cfg(target_os = "windows")]
+            cmd.env("__COMPAT_LAYER", "RUNASINVOKER");
+            cmd.spawn()
+        }
+        None => {
+            warn!("Marvel-Win64-Shipping.exe not found under {}, falling back to Steam", game_root.display());
+            if !extra_args.is_empty() {
+                warn!("Launch arguments are ignored when launching through Steam: {:?}", extra_args);
+            }
+            spawn_steam_launch()
+        }
+    };
+
+    match launch_result {
+        Ok(_) => {
+            info!("Successfully launched Marvel Rivals");
+            snapshot_mounted_mods(&mut *state.write().await);
+            if let Some(original) = original_value {
+                spawn_launch_record_restore(launch_record_path, original);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            error!("Failed to launch game: {}", e);
+            if let Some(original) = original_value {
+                if let Err(e) = write_launch_record(&launch_record_path, original.trim()) {
+                    warn!("Failed to restore launch_record: {}", e);
+                }
+            }
+            Err(format!("Failed to launch game: {}", e))
+        }
+    }
+}
+
+/// Whether a mod is mounted by the running game, as opposed to only enabled on disk
+#[derive(Debug, Clone, Serialize)]
+struct MountStatus {
+    path: PathBuf,
+    /// Enabled on disk right now
+    enabled: bool,
+    /// Mounted by the running game (same as `enabled` when the game isn't running)
+    mounted: bool,
+    /// Toggled, installed or removed since the game launched; needs a game restart to apply
+    pending_restart: bool,
+}
+
+/// Compare the mods enabled on disk against the ones mounted at launch
+fn compute_mount_status(entries: &[ModEntry], mounted_mods: Option<&[PathBuf]>) -> Vec<MountStatus> {
+    use std::collections::HashSet;
+
+    let Some(mounted_mods) = mounted_mods else {
+        return entries.iter()
+            .map(|e| MountStatus { path: e.path.clone(), enabled: e.enabled, mounted: e.enabled, pending_restart: false })
+            .collect();
+    };
+    let mounted: HashSet<&PathBuf> = mounted_mods.iter().collect();
+    let mut seen = HashSet::new();
+    let mut statuses: Vec<MountStatus> = entries.iter()
+        .map(|e| {
+            let pak_path = e.path.with_extension("pak");
+            let is_mounted = mounted.contains(&pak_path);
+            seen.insert(pak_path);
+            MountStatus {
+                path: e.path.clone(),
+                enabled: e.enabled,
+                mounted: is_mounted,
+                pending_restart: is_mounted != e.enabled,
+            }
+        })
+        .collect();
+    // Mods deleted since launch stay mounted until the game restarts
+    statuses.extend(mounted_mods.iter()
+        .filter(|p| !seen.contains(*p))
+        .map(|p| MountStatus { path: p.clone(), enabled: false, mounted: true, pending_restart: true }));
+    statuses
+}
+
+/// Report which mods the running game actually has mounted vs what is enabled on disk,
+/// so the UI can show a "restart game to apply" badge on mods toggled since launch
+#[tauri::command]
+async fn get_mount_status(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<MountStatus>, String> {
+    let (game_path, mod_metadata, mounted_mods) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone(), state.mounted_mods.clone())
+    };
+    let entries = list_mod_entries(&game_path, &mod_metadata)?;
+
+    // The distinction only matters while the game is running, and only for launches we recorded
+    let mounted_mods = mounted_mods.filter(|_| is_game_process_running());
+    Ok(compute_mount_status(&entries, mounted_mods.as_deref()))
+}
+
+/// Toggle the skip launcher patch (manual control)
+/// Returns true if skip launcher is now enabled (0), false if disabled (6).
+/// Enabling switches `launcher_skip_mode` to "always" so launch_game leaves the patch alone;
+/// disabling drops back to per-launch.
+#[tauri::command]
+async fn skip_launcher_patch(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let game_root = detect_game_root(&mut *state.write().await)?;
     let launch_record_path = game_root.join("launch_record");
     
-    info!("Mods path: {:?}", mods_path);
     info!("Game root: {:?}", game_root);
     info!("Launch record path: {:?}", launch_record_path);
     
@@ -3572,41 +9309,29 @@ async fn skip_launcher_patch(state: State<'_, Arc<Mutex<AppState>>>) -> Result<b
         "0" // Enable skip launcher
     };
     
-    // Delete and recreate the file with new value
-    if launch_record_path.exists() {
-        std::fs::remove_file(&launch_record_path)
-            .map_err(|e| format!("Failed to delete launch_record: {}", e))?;
-    }
-    
-    std::fs::write(&launch_record_path, &new_value)
-        .map_err(|e| format!("Failed to write launch_record: {}", e))?;
+    write_launch_record(&launch_record_path, new_value)?;
     
     let skip_enabled = new_value == "0";
     info!("Skip launcher patch toggled: {} (value: {})", skip_enabled, new_value);
+
+    let mut state = state.write().await;
+    state.launcher_skip_mode = if skip_enabled { LauncherSkipMode::Always } else { LauncherSkipMode::PerLaunch };
+    save_state(&state).map_err(|e| e.to_string())?;
     
     Ok(skip_enabled)
 }
 
-/// Check if skip launcher patch is currently enabled
+/// Check if skip launcher patch is currently enabled. Read-only: reports false when the
+/// game folder can't be found instead of erroring.
 #[tauri::command]
-async fn get_skip_launcher_status(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
-    // Get game path (this is the ~mods folder inside Paks)
-    let mods_path = {
-        let state = state.lock().unwrap();
-        state.game_path.clone()
+async fn get_skip_launcher_status(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let game_root = {
+        let state = state.read().await;
+        cached_game_root(&state).or_else(|| find_game_root(&state.game_path))
+    };
+    let Some(game_root) = game_root else {
+        return Ok(false);
     };
-    
-    // Go up 5 levels to get the actual game root
-    // ~mods -> Paks -> Content -> Marvel -> MarvelGame -> MarvelRivals (game root)
-    let game_root = mods_path
-        .parent() // Paks
-        .and_then(|p| p.parent()) // Content
-        .and_then(|p| p.parent()) // Marvel
-        .and_then(|p| p.parent()) // MarvelGame
-        .and_then(|p| p.parent()) // MarvelRivals (game root)
-        .ok_or_else(|| "Could not determine game root directory".to_string())?;
-    
-    // Path to launch_record file
     let launch_record_path = game_root.join("launch_record");
     
     // Read current value
@@ -3618,6 +9343,27 @@ async fn get_skip_launcher_status(state: State<'_, Arc<Mutex<AppState>>>) -> Res
     Ok(current_value == "0")
 }
 
+#[tauri::command]
+async fn get_launcher_skip_mode(state: State<'_, Arc<RwLock<AppState>>>) -> Result<LauncherSkipMode, String> {
+    Ok(state.read().await.launcher_skip_mode)
+}
+
+/// Choose how launches skip the launcher. "always" applies the patch right away; leaving
+/// "always" puts the game's default launch_record back.
+#[tauri::command]
+async fn set_launcher_skip_mode(mode: LauncherSkipMode, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("set_launcher_skip_mode called: {:?}", mode);
+    let mut state = state.write().await;
+    let previous = state.launcher_skip_mode;
+    if previous != mode && (mode == LauncherSkipMode::Always || previous == LauncherSkipMode::Always) {
+        let launch_record_path = detect_game_root(&mut state)?.join("launch_record");
+        write_launch_record(&launch_record_path, if mode == LauncherSkipMode::Always { "0" } else { "6" })?;
+    }
+    state.launcher_skip_mode = mode;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 // ============================================================================
 // BUNDLED LOD DISABLER MOD
 // ============================================================================
@@ -3662,12 +9408,26 @@ fn deploy_bundled_lod_mod(mods_path: &Path) -> Result<bool, String> {
     let lod_folder = mods_path.join(LOD_DISABLER_FOLDER);
     let pak_path = lod_folder.join(LOD_DISABLER_FILENAME);
     
-    // Check if already deployed
-    if pak_path.exists() {
+    // Check if already deployed (a disabled copy counts, so toggling it off sticks)
+    if pak_path.exists() || pak_path.with_extension("bak_repak").exists() {
         info!("Bundled LOD Disabler mod already deployed at: {}", pak_path.display());
         return Ok(false);
     }
     
+    // Make sure the embedded bytes are a readable pak before deploying anything
+    let file_count = repak::PakBuilder::new()
+        .key(install_mod::current_aes_key().0)
+        .reader(&mut std::io::Cursor::new(pak_bytes))
+        .map(|pak| pak.files().len())
+        .map_err(|e| {
+            error!("Bundled LOD Disabler pak failed validation: {}", e);
+            format!("Bundled LOD Disabler pak is corrupt or invalid: {}", e)
+        })?;
+    if file_count == 0 {
+        error!("Bundled LOD Disabler pak contains no files");
+        return Err("Bundled LOD Disabler pak contains no files".to_string());
+    }
+    
     // Create the folder
     std::fs::create_dir_all(&lod_folder)
         .map_err(|e| format!("Failed to create LOD Disabler folder: {}", e))?;
@@ -3676,31 +9436,84 @@ fn deploy_bundled_lod_mod(mods_path: &Path) -> Result<bool, String> {
     std::fs::write(&pak_path, pak_bytes)
         .map_err(|e| format!("Failed to write LOD Disabler pak: {}", e))?;
     
+    // Confirm the full pak landed on disk; a truncated pak silently breaks the game
+    let written = std::fs::metadata(&pak_path).map(|m| m.len()).unwrap_or(0);
+    if written != pak_bytes.len() as u64 {
+        error!("LOD Disabler pak size mismatch: wrote {} of {} bytes", written, pak_bytes.len());
+        let _ = std::fs::remove_file(&pak_path);
+        return Err(format!(
+            "LOD Disabler pak was not written completely ({} of {} bytes)",
+            written,
+            pak_bytes.len()
+        ));
+    }
+    
     info!("Deployed bundled LOD Disabler mod to: {}", pak_path.display());
     Ok(true)
 }
 
-/// Check if the bundled LOD Disabler mod is deployed
+/// Deployment state of the bundled LOD Disabler mod
+#[derive(Clone, Serialize)]
+struct LodDisablerStatus {
+    deployed: bool,
+    /// False when the pak has been toggled to .bak_repak
+    enabled: bool,
+}
+
+/// Check if the bundled LOD Disabler mod is deployed, and whether it is enabled
 #[tauri::command]
-async fn check_lod_disabler_deployed(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
+async fn check_lod_disabler_deployed(state: State<'_, Arc<RwLock<AppState>>>) -> Result<LodDisablerStatus, String> {
     let mods_path = {
-        let state = state.lock().unwrap();
+        let state = state.read().await;
         state.game_path.clone()
     };
     
     if !mods_path.exists() {
-        return Ok(false);
+        return Ok(LodDisablerStatus { deployed: false, enabled: false });
     }
     
     let pak_path = mods_path.join(LOD_DISABLER_FOLDER).join(LOD_DISABLER_FILENAME);
-    Ok(pak_path.exists())
+    let enabled = pak_path.exists();
+    let deployed = enabled || pak_path.with_extension("bak_repak").exists();
+    Ok(LodDisablerStatus { deployed, enabled })
+}
+
+/// Enable or disable the deployed LOD Disabler mod by renaming it between .pak and .bak_repak
+#[tauri::command]
+async fn set_lod_disabler_enabled(enabled: bool, state: State<'_, Arc<RwLock<AppState>>>) -> Result<(), String> {
+    info!("set_lod_disabler_enabled called: {}", enabled);
+    let mods_path = {
+        let state = state.read().await;
+        state.game_path.clone()
+    };
+    
+    let pak_path = mods_path.join(LOD_DISABLER_FOLDER).join(LOD_DISABLER_FILENAME);
+    let disabled_path = pak_path.with_extension("bak_repak");
+    let (from, to) = if enabled {
+        (disabled_path, pak_path)
+    } else {
+        (pak_path, disabled_path)
+    };
+    
+    if to.exists() {
+        // Already in the requested state
+        return Ok(());
+    }
+    if !from.exists() {
+        return Err("LOD Disabler is not deployed".to_string());
+    }
+    
+    std::fs::rename(&from, &to)
+        .map_err(|e| format!("Failed to toggle LOD Disabler: {}", e))?;
+    info!("LOD Disabler {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
 }
 
 /// Get the path to the bundled LOD Disabler mod
 #[tauri::command]
-async fn get_lod_disabler_path(state: State<'_, Arc<Mutex<AppState>>>) -> Result<String, String> {
+async fn get_lod_disabler_path(state: State<'_, Arc<RwLock<AppState>>>) -> Result<String, String> {
     let mods_path = {
-        let state = state.lock().unwrap();
+        let state = state.read().await;
         state.game_path.clone()
     };
     
@@ -3710,9 +9523,9 @@ async fn get_lod_disabler_path(state: State<'_, Arc<Mutex<AppState>>>) -> Result
 
 /// Manually deploy the bundled LOD Disabler mod
 #[tauri::command]
-async fn deploy_lod_disabler(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
+async fn deploy_lod_disabler(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
     let mods_path = {
-        let state = state.lock().unwrap();
+        let state = state.read().await;
         state.game_path.clone()
     };
     
@@ -3732,6 +9545,19 @@ struct RecompressResult {
     failed: usize,
     skipped_iostore: usize,
     details: Vec<RecompressDetail>,
+    /// Set when the scan was stopped via cancel_recompress; details cover only the paks handled so far
+    cancelled: bool,
+}
+
+/// Set by cancel_recompress, checked between paks in recompress_mods
+static CANCEL_RECOMPRESS: AtomicBool = AtomicBool::new(false);
+
+/// Stop an in-progress recompress_mods scan after the pak currently being processed
+#[tauri::command]
+async fn cancel_recompress() -> Result<(), String> {
+    CANCEL_RECOMPRESS.store(true, Ordering::SeqCst);
+    info!("Recompression cancellation requested");
+    Ok(())
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -3741,20 +9567,33 @@ struct RecompressDetail {
     original_size: u64,
     new_size: Option<u64>,
     error: Option<String>,
+    /// Compression the mod used when scanned, e.g. "Zlib", "LZ4", "None"
+    original_compression: Option<String>,
+    /// Compression after recompressing, only set when the mod was rewritten
+    new_compression: Option<String>,
+}
+
+/// Human-readable summary of a pak's compression methods ("None" when uncompressed)
+fn describe_compression(compressions: &[repak::Compression]) -> String {
+    if compressions.is_empty() {
+        "None".to_string()
+    } else {
+        compressions.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+    }
 }
 
 /// Scan all mods and recompress any that aren't using Oodle compression
 #[tauri::command]
 async fn recompress_mods(
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
     window: Window,
 ) -> Result<RecompressResult, String> {
     use repak::Compression;
     use std::io::BufReader;
     
-    let game_path = {
-        let state = state.lock().unwrap();
-        state.game_path.clone()
+    let (game_path, iostore_version) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.iostore_version)
     };
     
     if !game_path.exists() {
@@ -3770,7 +9609,9 @@ async fn recompress_mods(
         failed: 0,
         skipped_iostore: 0,
         details: Vec::new(),
+        cancelled: false,
     };
+    CANCEL_RECOMPRESS.store(false, Ordering::SeqCst);
     
     // Collect all .pak files
     let mut pak_files: Vec<PathBuf> = Vec::new();
@@ -3795,6 +9636,13 @@ async fn recompress_mods(
     }));
     
     for (idx, pak_path) in pak_files.iter().enumerate() {
+        // Checked at the top of each iteration, i.e. after the previous pak finished recompressing
+        if CANCEL_RECOMPRESS.swap(false, Ordering::SeqCst) {
+            info!("Recompression cancelled after {} of {} paks", idx, pak_files.len());
+            result.cancelled = true;
+            break;
+        }
+
         let mod_name = pak_path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("Unknown")
@@ -3827,6 +9675,8 @@ async fn recompress_mods(
                         original_size: ucas_size,
                         new_size: None,
                         error: Some(format!("Failed to check compression: {}", e)),
+                        original_compression: None,
+                        new_compression: None,
                     });
                     continue;
                 }
@@ -3842,6 +9692,8 @@ async fn recompress_mods(
                     original_size: ucas_size,
                     new_size: None,
                     error: None,
+                    original_compression: Some(Compression::Oodle.to_string()),
+                    new_compression: None,
                 });
             } else {
                 // Need to recompress IoStore
@@ -3853,7 +9705,7 @@ async fn recompress_mods(
                     "status": format!("Recompressing IoStore: {}", mod_name)
                 }));
                 
-                match uasset_toolkit::recompress_iostore(&utoc_path.to_string_lossy()) {
+                match uasset_toolkit::recompress_iostore(&utoc_path.to_string_lossy(), iostore_version) {
                     Ok(_) => {
                         let new_ucas_size = std::fs::metadata(&ucas_path).map(|m| m.len()).unwrap_or(0);
                         info!("Successfully recompressed IoStore: {} ({} -> {} bytes)", mod_name, ucas_size, new_ucas_size);
@@ -3864,6 +9716,8 @@ async fn recompress_mods(
                             original_size: ucas_size,
                             new_size: Some(new_ucas_size),
                             error: None,
+                            original_compression: Some("None".to_string()),
+                            new_compression: Some(Compression::Oodle.to_string()),
                         });
                     }
                     Err(e) => {
@@ -3875,6 +9729,8 @@ async fn recompress_mods(
                             original_size: ucas_size,
                             new_size: None,
                             error: Some(format!("Recompression failed: {}", e)),
+                            original_compression: Some("None".to_string()),
+                            new_compression: None,
                         });
                     }
                 }
@@ -3894,6 +9750,8 @@ async fn recompress_mods(
                     original_size: 0,
                     new_size: None,
                     error: Some(format!("Failed to open: {}", e)),
+                    original_compression: None,
+                    new_compression: None,
                 });
                 continue;
             }
@@ -3902,7 +9760,7 @@ async fn recompress_mods(
         let original_size = std::fs::metadata(pak_path).map(|m| m.len()).unwrap_or(0);
         
         let pak_reader = match repak::PakBuilder::new()
-            .key(install_mod::AES_KEY.clone().0)
+            .key(install_mod::current_aes_key().0)
             .reader(&mut BufReader::new(&file))
         {
             Ok(reader) => reader,
@@ -3915,6 +9773,8 @@ async fn recompress_mods(
                     original_size,
                     new_size: None,
                     error: Some(format!("Failed to parse PAK: {}", e)),
+                    original_compression: None,
+                    new_compression: None,
                 });
                 continue;
             }
@@ -3924,6 +9784,7 @@ async fn recompress_mods(
         let compressions = pak_reader.compression();
         let has_oodle = compressions.iter().any(|c| matches!(c, Compression::Oodle));
         let is_uncompressed = compressions.is_empty();
+        let original_compression = Some(describe_compression(&compressions));
         
         if has_oodle && !is_uncompressed {
             // Already using Oodle compression
@@ -3935,12 +9796,14 @@ async fn recompress_mods(
                 original_size,
                 new_size: None,
                 error: None,
+                original_compression,
+                new_compression: None,
             });
             continue;
         }
         
         // Need to recompress this PAK
-        info!("Recompressing: {} (compression: {:?})", mod_name, compressions);
+        info!("Recompressing: {} (compression: {})", mod_name, describe_compression(&compressions));
         
         // Emit progress for recompression
         let _ = window.emit("recompress_progress", serde_json::json!({
@@ -3960,6 +9823,8 @@ async fn recompress_mods(
                     original_size,
                     new_size: Some(new_size),
                     error: None,
+                    original_compression,
+                    new_compression: Some(Compression::Oodle.to_string()),
                 });
             }
             Err(e) => {
@@ -3971,19 +9836,27 @@ async fn recompress_mods(
                     original_size,
                     new_size: None,
                     error: Some(e),
+                    original_compression,
+                    new_compression: None,
                 });
             }
         }
     }
     
+    // A cancel that arrived while the last pak was processing still counts
+    if CANCEL_RECOMPRESS.swap(false, Ordering::SeqCst) {
+        result.cancelled = true;
+    }
+
     // Emit completion
     let _ = window.emit("recompress_progress", serde_json::json!({
-        "current": pak_files.len(),
+        "current": if result.cancelled { result.details.len() } else { pak_files.len() },
         "total": pak_files.len(),
-        "status": "Complete"
+        "status": if result.cancelled { "Cancelled" } else { "Complete" }
     }));
     
-    info!("Recompression complete: {} scanned, {} already Oodle, {} recompressed, {} failed",
+    info!("Recompression {}: {} scanned, {} already Oodle, {} recompressed, {} failed",
+        if result.cancelled { "cancelled" } else { "complete" },
         result.total_scanned, result.already_oodle, result.recompressed, result.failed);
     
     Ok(result)
@@ -3995,8 +9868,10 @@ fn recompress_pak_file(pak_path: &Path, pak_reader: &repak::PakReader) -> Result
     use std::io::{BufReader, BufWriter};
     use tempfile::NamedTempFile;
     
-    // Create a temporary file for the new PAK
-    let temp_file = NamedTempFile::new()
+    // Create the temporary file next to the original so it can be renamed over it atomically;
+    // on any error it is dropped and deleted, leaving the original untouched
+    let temp_dir = pak_path.parent().unwrap_or_else(|| Path::new("."));
+    let temp_file = NamedTempFile::new_in(temp_dir)
         .map_err(|e| format!("Failed to create temp file: {}", e))?;
     
     let temp_path = temp_file.path().to_path_buf();
@@ -4012,7 +9887,8 @@ fn recompress_pak_file(pak_path: &Path, pak_reader: &repak::PakReader) -> Result
     
     let builder = repak::PakBuilder::new()
         .compression(vec![Compression::Oodle])
-        .key(install_mod::AES_KEY.clone().0);
+        .key(install_mod::current_aes_key().0)
+        .encrypt_index_only(install_mod::is_index_only_encrypted(pak_reader));
     
     let mut pak_writer = builder.writer(
         BufWriter::new(output_file),
@@ -4042,21 +9918,22 @@ fn recompress_pak_file(pak_path: &Path, pak_reader: &repak::PakReader) -> Result
             .map_err(|e| format!("Failed to write entry {}: {}", file_path, e))?;
     }
     
-    // Finalize the PAK (write_index consumes pak_writer)
-    let _writer = pak_writer.write_index()
+    // Finalize the PAK (write_index consumes pak_writer), then flush and close our handle
+    // so the rename below isn't blocked by it
+    let writer = pak_writer.write_index()
         .map_err(|e| format!("Failed to write index: {}", e))?;
+    writer.into_inner()
+        .map_err(|e| format!("Failed to flush PAK: {}", e))?;
     
     // Get new file size
     let new_size = std::fs::metadata(&temp_path)
         .map(|m| m.len())
         .unwrap_or(0);
     
-    // Replace original file with recompressed version
-    std::fs::copy(&temp_path, pak_path)
+    // Replace original file with recompressed version in a single rename
+    temp_file.persist(pak_path)
         .map_err(|e| format!("Failed to replace original PAK: {}", e))?;
     
-    // Clean up temp file (it will be deleted when temp_file is dropped)
-    
     Ok(new_size)
 }
 
@@ -4068,14 +9945,26 @@ async fn get_app_version() -> Result<String, String> {
 /// Check for application updates and emit update_available event when found
 #[tauri::command]
 async fn check_for_updates(window: Window) -> Result<Option<UpdateInfo>, String> {
-    let client = reqwest::Client::new();
+    let client = reqwest::Client::builder()
+        .timeout(UPDATE_REQUEST_TIMEOUT)
+        .connect_timeout(UPDATE_CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
     let url = "https://api.github.com/repos/XzantGaming/Repak-X/releases/latest";
     
-    let res = client.get(url)
-        .header("User-Agent", "RepakX")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    // One automatic retry on connection errors/timeouts so a flaky network doesn't skip the check
+    let mut attempt = 0;
+    let res = loop {
+        attempt += 1;
+        match client.get(url).header("User-Agent", "RepakX").send().await {
+            Ok(res) => break res,
+            Err(e) if attempt < 2 && (e.is_connect() || e.is_timeout()) => {
+                info!("Update check failed ({}), retrying once...", e);
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+            Err(e) => return Err(format!("Request failed: {}", e)),
+        }
+    };
         
     if !res.status().is_success() {
         return Ok(None);
@@ -4141,12 +10030,28 @@ async fn check_for_updates(window: Window) -> Result<Option<UpdateInfo>, String>
                  }
              }
              
+             // Published size (and SHA-256 if available) so download_update can verify the file
+             let selected_asset = asset_name.as_ref().and_then(|name| {
+                 assets?.iter().find(|a| a["name"].as_str() == Some(name.as_str()))
+             });
+             let asset_size = selected_asset.and_then(|a| a["size"].as_u64());
+             let asset_sha256 = selected_asset
+                 .and_then(|a| a["digest"].as_str())
+                 .and_then(|d| d.strip_prefix("sha256:"))
+                 .map(|d| d.to_lowercase())
+                 .or_else(|| {
+                     let name = asset_name.as_deref()?;
+                     find_published_sha256(changelog.as_deref()?, name)
+                 });
+             
              let update_info = UpdateInfo {
                  latest: tag_name.to_string(),
                  url,
                  asset_url,
                  asset_name,
                  changelog,
+                 asset_size,
+                 asset_sha256,
              };
              
              // Emit update_available event
@@ -4167,8 +10072,31 @@ struct UpdateInfo {
     asset_url: Option<String>,
     asset_name: Option<String>,
     changelog: Option<String>,
+    /// Size of the release asset in bytes as reported by GitHub
+    #[serde(default)]
+    asset_size: Option<u64>,
+    /// Lowercase hex SHA-256 of the asset, from GitHub's digest or the release notes
+    #[serde(default)]
+    asset_sha256: Option<String>,
+}
+
+/// Find a SHA-256 published for `asset_name` in release notes,
+/// e.g. a line like "RepakX_Windows.zip: 3f2a...".
+fn find_published_sha256(body: &str, asset_name: &str) -> Option<String> {
+    body.lines()
+        .filter(|line| line.contains(asset_name))
+        .flat_map(|line| line.split(|c: char| !c.is_ascii_hexdigit()))
+        .find(|token| token.len() == 64)
+        .map(|token| token.to_lowercase())
 }
 
+/// Overall timeout for update metadata requests
+const UPDATE_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+/// Timeout for establishing a connection to the update server
+const UPDATE_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// Max time to wait for the next chunk before a download is considered stalled
+const UPDATE_CHUNK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Progress information for update download
 #[derive(Clone, Serialize, Deserialize)]
 struct UpdateDownloadProgress {
@@ -4176,6 +10104,9 @@ struct UpdateDownloadProgress {
     total_bytes: Option<u64>,
     percentage: f32,
     status: String, // "downloading", "extracting", "ready", "error"
+    /// Human-readable reason when status is "error"
+    #[serde(default)]
+    error: Option<String>,
 }
 
 /// Download an update from the given URL
@@ -4184,7 +10115,37 @@ struct UpdateDownloadProgress {
 async fn download_update(
     asset_url: String,
     asset_name: String,
+    expected_size: Option<u64>,
+    expected_sha256: Option<String>,
     window: Window,
+) -> Result<String, String> {
+    let result = download_update_inner(
+        &asset_url,
+        &asset_name,
+        expected_size,
+        expected_sha256.as_deref(),
+        &window,
+    ).await;
+    if let Err(ref e) = result {
+        error!("Update download failed: {}", e);
+        let progress = UpdateDownloadProgress {
+            downloaded_bytes: 0,
+            total_bytes: None,
+            percentage: 0.0,
+            status: "error".to_string(),
+            error: Some(e.clone()),
+        };
+        let _ = window.emit("update_download_progress", &progress);
+    }
+    result
+}
+
+async fn download_update_inner(
+    asset_url: &str,
+    asset_name: &str,
+    expected_size: Option<u64>,
+    expected_sha256: Option<&str>,
+    window: &Window,
 ) -> Result<String, String> {
     use tokio::io::AsyncWriteExt;
     
@@ -4198,14 +10159,18 @@ async fn download_update(
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
     
-    let download_path = temp_dir.join(&asset_name);
+    let download_path = temp_dir.join(asset_name);
     
-    // Download the file with progress reporting and retry logic
-    let client = reqwest::Client::new();
+    // Download the file with progress reporting and retry logic.
+    // No overall timeout here (large downloads); stalls are caught per chunk below.
+    let client = reqwest::Client::builder()
+        .connect_timeout(UPDATE_CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
     let mut response = None;
     let max_retries = 3;
     for attempt in 1..=max_retries {
-        let res = client.get(&asset_url)
+        let res = client.get(asset_url)
             .header("User-Agent", "RepakX")
             .send()
             .await;
@@ -4248,7 +10213,10 @@ async fn download_update(
     let mut body = response;
     
     loop {
-        let n = body.chunk().await.map_err(|e| format!("Download stream error: {}", e))?;
+        let n = tokio::time::timeout(UPDATE_CHUNK_TIMEOUT, body.chunk())
+            .await
+            .map_err(|_| format!("Download stalled: no data received for {} seconds", UPDATE_CHUNK_TIMEOUT.as_secs()))?
+            .map_err(|e| format!("Download stream error: {}", e))?;
         let chunk = match n {
             Some(c) => c,
             None => break,
@@ -4280,6 +10248,7 @@ async fn download_update(
                     total_bytes: total_size,
                     percentage,
                     status: "downloading".to_string(),
+                    error: None,
                 };
                 let _ = window.emit("update_download_progress", &progress);
             }
@@ -4291,12 +10260,19 @@ async fn download_update(
     
     info!("Update downloaded to: {:?}", download_path);
     
+    // Verify before anything can run or extract the file
+    if let Err(e) = verify_update_download(&download_path, downloaded, expected_size, expected_sha256) {
+        let _ = std::fs::remove_file(&download_path);
+        return Err(e);
+    }
+    
     // Emit completion progress
     let progress = UpdateDownloadProgress {
         downloaded_bytes: downloaded,
         total_bytes: total_size,
         percentage: 100.0,
         status: "ready".to_string(),
+        error: None,
     };
     let _ = window.emit("update_download_progress", &progress);
     
@@ -4311,6 +10287,36 @@ async fn download_update(
     Ok(download_path.to_string_lossy().to_string())
 }
 
+/// Check a downloaded update against the size and SHA-256 published with the release
+fn verify_update_download(
+    path: &Path,
+    downloaded: u64,
+    expected_size: Option<u64>,
+    expected_sha256: Option<&str>,
+) -> Result<(), String> {
+    if let Some(expected) = expected_size {
+        if downloaded != expected {
+            return Err(format!(
+                "Downloaded update size mismatch (expected {} bytes, got {})",
+                expected, downloaded
+            ));
+        }
+    }
+    if let Some(expected) = expected_sha256 {
+        let actual = p2p_sharing::hash_file(path).map_err(|e| e.to_string())?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!(
+                "Downloaded update failed integrity check (expected SHA-256 {}, got {})",
+                expected, actual
+            ));
+        }
+        info!("Update SHA-256 verified: {}", actual);
+    } else {
+        warn!("No SHA-256 published for this update; verified size only");
+    }
+    Ok(())
+}
+
 /// Apply a downloaded update
 /// This creates an updater script and schedules it to run after the app closes
 #[tauri::command]
@@ -4659,8 +10665,8 @@ rm -f "$0"
 
 /// Get auto-update preference from settings
 #[tauri::command]
-async fn get_auto_update_enabled(state: State<'_, Arc<Mutex<AppState>>>) -> Result<bool, String> {
-    let state = state.lock().unwrap();
+async fn get_auto_update_enabled(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
     Ok(state.auto_check_updates)
 }
 
@@ -4668,9 +10674,9 @@ async fn get_auto_update_enabled(state: State<'_, Arc<Mutex<AppState>>>) -> Resu
 #[tauri::command]
 async fn set_auto_update_enabled(
     enabled: bool,
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
 ) -> Result<(), String> {
-    let mut state = state.lock().unwrap();
+    let mut state = state.write().await;
     state.auto_check_updates = enabled;
     save_state(&state).map_err(|e| e.to_string())?;
     Ok(())
@@ -4819,108 +10825,155 @@ async fn monitor_game_for_crashes(
     }
     
     // Game just stopped - check for crashes that occurred during THIS session
-    if !game_running && game_start_time.is_some() {
-        let session_start = game_start_time.unwrap();
-        info!("Game stopped - checking for crashes since session start: {:?}", session_start);
-        
-        // Check for crashes created AFTER the game started
-        let new_crashes = crash_monitor::check_for_new_crashes(session_start);
-        
-        // Reset state for next session
-        *game_start_time = None;
+    if !game_running {
+        if let Some(session_start) = game_start_time.take() {
+            return Ok(report_session_crashes(session_start, &mut last_checked, &window));
+        }
+    }
+    
+    Ok(None)
+}
+
+/// Look for crash folders written since `session_start` and report the newest one (log + toast)
+fn report_session_crashes(
+    session_start: std::time::SystemTime,
+    last_checked: &mut Option<std::time::SystemTime>,
+    window: &Window,
+) -> Option<crash_monitor::CrashInfo> {
+    info!("Game stopped - checking for crashes since session start: {:?}", session_start);
+    
+    // Check for crashes created AFTER the game started
+    let new_crashes = crash_monitor::check_for_new_crashes(session_start);
+    
+    if !new_crashes.is_empty() {
+        error!("⚠️ ═══════════════════════════════════════════════════════════════");
+        error!("⚠️ CRASH DETECTED! Marvel Rivals crashed during this session!");
+        error!("⚠️ ═══════════════════════════════════════════════════════════════");
+        error!("⚠️ Found {} crash folder(s) from this session", new_crashes.len());
         
-        if !new_crashes.is_empty() {
-            error!("⚠️ ═══════════════════════════════════════════════════════════════");
-            error!("⚠️ CRASH DETECTED! Marvel Rivals crashed during this session!");
-            error!("⚠️ ═══════════════════════════════════════════════════════════════");
-            error!("⚠️ Found {} crash folder(s) from this session", new_crashes.len());
+        // Parse the most recent crash
+        if let Some(crash_folder) = new_crashes.first() {
+            let crash_info = crash_monitor::parse_crash_info(crash_folder, Vec::new());
             
-            // Parse the most recent crash
-            if let Some(crash_folder) = new_crashes.first() {
-                let crash_info = crash_monitor::parse_crash_info(crash_folder, Vec::new());
+            if let Some(ref info) = crash_info {
+                let unknown_error = "Unknown error".to_string();
+                let error_msg = info.error_message.as_ref().unwrap_or(&unknown_error);
                 
-                if let Some(ref info) = crash_info {
-                    let unknown_error = "Unknown error".to_string();
-                    let error_msg = info.error_message.as_ref().unwrap_or(&unknown_error);
-                    
-                    error!("⚠️ Crash Details:");
-                    error!("⚠️   Type: {}", info.crash_type.as_ref().unwrap_or(&"Unknown".to_string()));
-                    
-                    // Parse and display detailed error information
-                    let (asset_path, error_type, details) = crash_monitor::parse_error_details(error_msg);
-                    
-                    if let Some(err_type) = error_type {
-                        error!("⚠️   Error Type: {}", err_type);
-                    }
-                    
-                    if let Some(asset) = asset_path {
-                        error!("⚠️   Affected Asset: {}", asset);
-                        
-                        // Extract character ID if present
-                        if let Some(char_id) = crash_monitor::extract_character_id(error_msg) {
-                            error!("⚠️   Character ID: {}", char_id);
-                        }
-                    }
-                    
-                    if let Some(detail) = details {
-                        error!("⚠️   Details: {}", detail);
-                    }
-                    
-                    // Check if it's a mesh-related crash
-                    if crash_monitor::is_mesh_related_crash(error_msg) {
-                        error!("⚠️   ⚡ MESH LOADING ERROR detected");
-                    }
-                    
-                    if let Some(seconds) = info.seconds_since_start {
-                        let minutes = seconds / 60;
-                        let secs = seconds % 60;
-                        error!("⚠️   Time in game: {}m {}s", minutes, secs);
-                    }
-                    
-                    error!("⚠️   Crash folder: {:?}", crash_folder);
-                    error!("⚠️   Mods enabled: {} mod(s)", info.enabled_mods.len());
-                    
-                    if !info.enabled_mods.is_empty() {
-                        error!("⚠️   Active mods:");
-                        for mod_name in &info.enabled_mods {
-                            error!("⚠️     - {}", mod_name);
-                        }
-                    }
-                    
-                    // Show full error message at the end for reference
-                    error!("⚠️");
-                    error!("⚠️   Full Error Message:");
-                    error!("⚠️   {}", error_msg);
-                    error!("⚠️ ═══════════════════════════════════════════════════════════════");
-                    
-                    // Update last checked time to avoid re-reporting this crash
-                    *last_checked = Some(info.timestamp);
+                error!("⚠️ Crash Details:");
+                error!("⚠️   Type: {}", info.crash_type.as_ref().unwrap_or(&"Unknown".to_string()));
+                
+                // Parse and display detailed error information
+                let (asset_path, error_type, details) = crash_monitor::parse_error_details(error_msg);
+                
+                if let Some(err_type) = error_type {
+                    error!("⚠️   Error Type: {}", err_type);
+                }
+                
+                if let Some(asset) = asset_path {
+                    error!("⚠️   Affected Asset: {}", asset);
                     
-                    // Emit toast notification with crash details
-                    toast_events::emit_crash_from_info(&window, info);
+                    // Extract character ID if present
+                    if let Some(char_id) = crash_monitor::extract_character_id(error_msg) {
+                        error!("⚠️   Character ID: {}", char_id);
+                    }
+                }
+                
+                if let Some(detail) = details {
+                    error!("⚠️   Details: {}", detail);
+                }
+                
+                // Check if it's a mesh-related crash
+                if crash_monitor::is_mesh_related_crash(error_msg) {
+                    error!("⚠️   ⚡ MESH LOADING ERROR detected");
+                }
+                
+                if let Some(seconds) = info.seconds_since_start {
+                    let minutes = seconds / 60;
+                    let secs = seconds % 60;
+                    error!("⚠️   Time in game: {}m {}s", minutes, secs);
+                }
+                
+                error!("⚠️   Crash folder: {:?}", crash_folder);
+                error!("⚠️   Mods enabled: {} mod(s)", info.enabled_mods.len());
+                
+                if !info.enabled_mods.is_empty() {
+                    error!("⚠️   Active mods:");
+                    for mod_name in &info.enabled_mods {
+                        error!("⚠️     - {}", mod_name);
+                    }
                 }
                 
-                return Ok(crash_info);
+                // Show full error message at the end for reference
+                error!("⚠️");
+                error!("⚠️   Full Error Message:");
+                error!("⚠️   {}", error_msg);
+                error!("⚠️ ═══════════════════════════════════════════════════════════════");
+                
+                // Update last checked time to avoid re-reporting this crash
+                *last_checked = Some(info.timestamp);
+                
+                // Emit toast notification with crash details
+                toast_events::emit_crash_from_info(window, info);
             }
-        } else {
-            info!("✓ ═══════════════════════════════════════════════════════════════");
-            info!("✓ Game closed normally - no crashes detected this session");
-            info!("✓ ═══════════════════════════════════════════════════════════════");
+            
+            return crash_info;
         }
+    } else {
+        info!("✓ ═══════════════════════════════════════════════════════════════");
+        info!("✓ Game closed normally - no crashes detected this session");
+        info!("✓ ═══════════════════════════════════════════════════════════════");
     }
-    
-    Ok(None)
+    None
+}
+
+
+/// How often the background game monitor checks for the game process
+const GAME_MONITOR_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Watch for the game starting and stopping, emitting `game_started`/`game_stopped` on
+/// transitions. Session start times go into CrashMonitorState and crash detection runs
+/// as soon as the game exits, so the frontend doesn't have to poll for either.
+fn spawn_game_monitor(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut was_running = false;
+        loop {
+            let running = is_game_process_running();
+            if running != was_running {
+                let crash_state = app_handle.state::<CrashMonitorState>();
+                if running {
+                    let now = std::time::SystemTime::now();
+                    let mut game_start_time = crash_state.game_start_time.lock().unwrap();
+                    if game_start_time.is_none() {
+                        *game_start_time = Some(now);
+                        *crash_state.last_checked_crash.lock().unwrap() = Some(now);
+                    }
+                    info!("Game started - monitoring for crashes from: {:?}", now);
+                    let _ = app_handle.emit("game_started", ());
+                } else {
+                    let _ = app_handle.emit("game_stopped", ());
+                    let session_start = crash_state.game_start_time.lock().unwrap().take();
+                    if let (Some(session_start), Some(webview)) = (session_start, app_handle.get_webview_window("main")) {
+                        let window = webview.as_ref().window();
+                        let mut last_checked = crash_state.last_checked_crash.lock().unwrap();
+                        report_session_crashes(session_start, &mut last_checked, &window);
+                    }
+                }
+                was_running = running;
+            }
+            std::thread::sleep(GAME_MONITOR_INTERVAL);
+        }
+    });
 }
 
 /// Check for crashes that occurred in previous sessions (when app wasn't running)
 /// This should be called once on app startup to detect crashes from the last game session
 #[tauri::command]
 async fn check_for_previous_crash(
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
     window: Window,
 ) -> Result<Option<crash_monitor::CrashInfo>, String> {
     let last_known = {
-        let state_guard = state.lock().unwrap();
+        let state_guard = state.read().await;
         state_guard.last_known_crash_folder.clone()
     };
     
@@ -4943,7 +10996,7 @@ async fn check_for_previous_crash(
     
     // Update last known crash folder to the newest one (whether crash detected or not)
     if let Some((newest_name, _)) = crash_monitor::get_newest_crash_folder() {
-        let mut state_guard = state.lock().unwrap();
+        let mut state_guard = state.write().await;
         state_guard.last_known_crash_folder = Some(newest_name);
         let _ = save_state(&state_guard);
     }
@@ -5084,6 +11137,36 @@ fn app_dir() -> PathBuf {
         .join("Repak-X")
 }
 
+/// Directory for persistent archive extractions (keep_extracted mode)
+fn extraction_dir() -> PathBuf {
+    app_dir().join("Extracted")
+}
+
+/// Staging directory for paks produced by split_pak_by_folder
+fn split_dir() -> PathBuf {
+    app_dir().join("Split")
+}
+
+/// Output directory for paks produced by merge_paks
+fn merged_output_dir() -> PathBuf {
+    app_dir().join("Merged")
+}
+
+/// Old mod files are parked here while update_mod installs the new version
+fn update_backup_dir() -> PathBuf {
+    app_dir().join("UpdateBackups")
+}
+
+/// Directory for per-session install logs
+fn install_log_dir() -> PathBuf {
+    app_dir().join("InstallLogs")
+}
+
+/// On-disk cache for the library-wide asset index
+fn asset_index_path() -> PathBuf {
+    app_dir().join("asset_index.json")
+}
+
 /// Directory for USMAP files - stored in roaming folder
 fn usmap_dir() -> PathBuf {
     app_dir().join("Usmap")
@@ -5114,22 +11197,40 @@ fn log_dir() -> PathBuf {
     app_dir()
 }
 
+const STATE_FILE: &str = "state.json";
+const STATE_BACKUP_FILE: &str = "state.json.bak";
+
 fn save_state(state: &AppState) -> std::io::Result<()> {
-    let dir = app_dir();
-    std::fs::create_dir_all(&dir)?;
-    let path = dir.join("state.json");
-    let file = File::create(path)?;
-    serde_json::to_writer_pretty(file, state)?;
+    save_state_to(&app_dir(), state)
+}
+
+/// Write the state to a temp file next to state.json and rename it into place, so a crash
+/// mid-write can never leave a truncated config. The previous config is kept as state.json.bak.
+fn save_state_to(dir: &Path, state: &AppState) -> std::io::Result<()> {
+    use std::io::Write;
+
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(STATE_FILE);
+    let mut temp_file = tempfile::NamedTempFile::new_in(dir)?;
+    {
+        let mut writer = std::io::BufWriter::new(temp_file.as_file_mut());
+        serde_json::to_writer_pretty(&mut writer, state)?;
+        writer.flush()?;
+    }
+    temp_file.as_file().sync_all()?;
+
+    // state.json is only ever replaced whole, so whatever is there is a complete previous save
+    if path.exists() {
+        if let Err(e) = std::fs::rename(&path, dir.join(STATE_BACKUP_FILE)) {
+            warn!("Failed to back up previous state: {}", e);
+        }
+    }
+    temp_file.persist(&path).map_err(|e| e.error)?;
     Ok(())
 }
 
 fn load_state() -> AppState {
-    let path = app_dir().join("state.json");
-    let mut state = if let Ok(file) = File::open(path) {
-        serde_json::from_reader(file).unwrap_or_default()
-    } else {
-        AppState::default()
-    };
+    let mut state = load_state_from(&app_dir());
     
     // Auto-detect USMAP file from roaming folder on startup
     // This ensures the app always uses whatever USMAP is actually in the folder
@@ -5151,6 +11252,108 @@ fn load_state() -> AppState {
     state
 }
 
+/// Current layout version of state.json; bump it and add a step to `migrate_state` when the layout changes
+const STATE_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrade a raw state.json written by an older version to the current layout, one version at a time
+fn migrate_state(value: &mut serde_json::Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    let mut version = obj.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+    if version > STATE_SCHEMA_VERSION {
+        warn!(
+            "state.json has schema version {} but this build only knows {}; loading it as-is",
+            version, STATE_SCHEMA_VERSION
+        );
+        return;
+    }
+
+    if version == 0 {
+        info!("Migrating state.json from schema 0 to 1 (fill required fields, derive folder hierarchy)");
+        // These fields predate #[serde(default)]; a config missing any of them used to reset everything
+        for (key, default) in [
+            ("folders", serde_json::json!([])),
+            ("mod_metadata", serde_json::json!([])),
+            ("usmap_path", serde_json::json!("")),
+            ("auto_check_updates", serde_json::json!(false)),
+            ("hide_internal_suffix", serde_json::json!(false)),
+            ("custom_tag_catalog", serde_json::json!([])),
+            ("game_path", serde_json::json!("")),
+        ] {
+            obj.entry(key).or_insert(default);
+        }
+
+        // Older versions kept a flat folder list keyed by path; derive depth/parent from the id
+        let root_name = obj.get("game_path")
+            .and_then(|p| p.as_str())
+            .and_then(|p| Path::new(p).file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "~mods".to_string());
+        if let Some(folders) = obj.get_mut("folders").and_then(|f| f.as_array_mut()) {
+            for folder in folders.iter_mut().filter_map(|f| f.as_object_mut()) {
+                let Some(id) = folder.get("id").and_then(|i| i.as_str()).map(|i| i.replace('\\', "/")) else {
+                    continue;
+                };
+                let is_root = id == root_name;
+                folder.entry("is_root").or_insert(serde_json::json!(is_root));
+                if !folder.contains_key("depth") {
+                    let depth = if is_root { 0 } else { id.split('/').count() };
+                    folder.insert("depth".to_string(), serde_json::json!(depth));
+                }
+                if !folder.contains_key("parent_id") {
+                    let parent_id = if is_root {
+                        None
+                    } else {
+                        Some(id.rsplit_once('/').map(|(parent, _)| parent.to_string()).unwrap_or_else(|| root_name.clone()))
+                    };
+                    folder.insert("parent_id".to_string(), serde_json::json!(parent_id));
+                }
+                folder.insert("id".to_string(), serde_json::json!(id));
+            }
+        }
+        if let Some(metadata) = obj.get_mut("mod_metadata").and_then(|m| m.as_array_mut()) {
+            for entry in metadata.iter_mut().filter_map(|m| m.as_object_mut()) {
+                if let Some(folder_id) = entry.get("folder_id").and_then(|f| f.as_str()).map(|f| f.replace('\\', "/")) {
+                    entry.insert("folder_id".to_string(), serde_json::json!(folder_id));
+                }
+            }
+        }
+        version = 1;
+    }
+
+    obj.insert("schema_version".to_string(), serde_json::json!(version));
+}
+
+/// Read state.json, falling back to state.json.bak when the main file is missing or unreadable
+fn load_state_from(dir: &Path) -> AppState {
+    let read = |name: &str| -> Result<AppState, String> {
+        let file = File::open(dir.join(name)).map_err(|e| e.to_string())?;
+        let mut value: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(file))
+            .map_err(|e| e.to_string())?;
+        migrate_state(&mut value);
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    };
+
+    match read(STATE_FILE) {
+        Ok(state) => state,
+        Err(e) => {
+            if dir.join(STATE_FILE).exists() {
+                error!("Failed to parse {}: {} - trying {}", STATE_FILE, e, STATE_BACKUP_FILE);
+            }
+            match read(STATE_BACKUP_FILE) {
+                Ok(state) => {
+                    warn!("Restored settings from {}", STATE_BACKUP_FILE);
+                    state
+                }
+                Err(_) => AppState {
+                    schema_version: STATE_SCHEMA_VERSION,
+                    ..Default::default()
+                },
+            }
+        }
+    }
+}
+
 fn setup_logging() {
     // Try exe-relative Logs folder first
     let log_dir = log_dir();
@@ -5190,11 +11393,172 @@ fn setup_logging() {
     ]);
 }
 
+/// Whether an installed IoStore mod is obfuscated, i.e. its container only opens with the
+/// game AES key. Legacy paks are never obfuscated.
+#[tauri::command]
+async fn is_mod_obfuscated(mod_path: String) -> Result<bool, String> {
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+    let utoc_path = path.with_extension("utoc");
+    if !utoc_path.exists() {
+        return Ok(false);
+    }
+    let utoc = utoc_path.to_string_lossy();
+    if !uasset_toolkit::is_iostore_encrypted_header(&utoc).map_err(|e| e.to_string())? {
+        return Ok(false);
+    }
+    let probe = uasset_toolkit::probe_iostore_encryption(
+        &utoc,
+        &install_mod::current_aes_key_hex(),
+    ).map_err(|e| format!("Failed to check IoStore encryption: {}", e))?;
+    info!("is_mod_obfuscated {}: {:?}", mod_path, probe);
+    Ok(probe.readable_with_key && !probe.readable_without_key)
+}
+
+/// One directory or file in the pak viewer tree
+#[derive(Debug, Clone, Serialize)]
+struct PakViewerNode {
+    name: String,
+    /// Full internal path (directories without a trailing slash, "" for the root)
+    path: String,
+    is_dir: bool,
+    /// Uncompressed size; for directories the sum of their files. None for IoStore mods
+    size: Option<u64>,
+    /// Stored (compressed) size, summed the same way. None for IoStore mods
+    compressed_size: Option<u64>,
+    /// Compression method of a file ("None" when stored uncompressed); unset for directories
+    compression: Option<String>,
+    children: Vec<PakViewerNode>,
+}
+
+/// Tree view of a mod's contents for the pak viewer
+#[derive(Debug, Clone, Serialize)]
+struct PakViewerModel {
+    mod_name: String,
+    is_iostore: bool,
+    file_count: usize,
+    root: PakViewerNode,
+}
+
+impl PakViewerNode {
+    fn dir(name: &str, path: String) -> Self {
+        PakViewerNode {
+            name: name.to_string(),
+            path,
+            is_dir: true,
+            size: Some(0),
+            compressed_size: Some(0),
+            compression: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Insert a file below this directory, creating intermediate directories as needed
+    fn insert(&mut self, parts: &[&str], mut file: PakViewerNode) {
+        if let [name, rest @ ..] = parts {
+            if rest.is_empty() {
+                file.name = name.to_string();
+                self.children.push(file);
+                return;
+            }
+            let idx = match self.children.iter().position(|c| c.is_dir && c.name == *name) {
+                Some(idx) => idx,
+                None => {
+                    let path = if self.path.is_empty() { name.to_string() } else { format!("{}/{}", self.path, name) };
+                    self.children.push(PakViewerNode::dir(name, path));
+                    self.children.len() - 1
+                }
+            };
+            self.children[idx].insert(rest, file);
+        }
+    }
+
+    /// Sort directories before files, by name, and roll file sizes up into directories
+    fn finish(&mut self) {
+        if !self.is_dir {
+            return;
+        }
+        for child in self.children.iter_mut() {
+            child.finish();
+        }
+        self.children.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())));
+        self.size = self.children.iter().map(|c| c.size).sum();
+        self.compressed_size = self.children.iter().map(|c| c.compressed_size).sum();
+    }
+}
+
+/// Build the pak viewer tree for an installed mod (enabled, disabled or IoStore).
+/// Legacy paks include per-file sizes and compression; IoStore mods only have the file tree.
+#[tauri::command]
+async fn open_pak_viewer(mod_path: String) -> Result<PakViewerModel, String> {
+    use std::io::BufReader;
+
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+    info!("open_pak_viewer called: {}", mod_path);
+
+    let mod_name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Unknown").to_string();
+    let is_iostore = path.with_extension("utoc").exists();
+    let mut files: Vec<(String, PakViewerNode)> = Vec::new();
+
+    if is_iostore {
+        for file_path in list_mod_files(&path).unwrap_or_default() {
+            files.push((file_path, PakViewerNode {
+                name: String::new(),
+                path: String::new(),
+                is_dir: false,
+                size: None,
+                compressed_size: None,
+                compression: None,
+                children: Vec::new(),
+            }));
+        }
+    } else {
+        let file = File::open(&path).map_err(|e| format!("Failed to open PAK file: {}", e))?;
+        let pak = repak::PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut BufReader::new(file))
+            .map_err(|e| format!("Failed to read PAK (bad AES key or corrupted file): {}", e))?;
+        for file_path in pak.files() {
+            let entry = pak.get_file_entry(&file_path).map_err(|e| e.to_string())?;
+            let compression = pak.entry_compression(&entry)
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "None".to_string());
+            files.push((file_path, PakViewerNode {
+                name: String::new(),
+                path: String::new(),
+                is_dir: false,
+                size: Some(entry.uncompressed),
+                compressed_size: Some(entry.compressed),
+                compression: Some(compression),
+                children: Vec::new(),
+            }));
+        }
+    }
+
+    let file_count = files.len();
+    let mut root = PakViewerNode::dir(&mod_name, String::new());
+    for (file_path, mut node) in files {
+        let parts: Vec<&str> = file_path.split('/').filter(|p| !p.is_empty()).collect();
+        node.path = file_path.clone();
+        root.insert(&parts, node);
+    }
+    root.finish();
+
+    Ok(PakViewerModel { mod_name, is_iostore, file_count, root })
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 struct ModDetails {
     mod_name: String,
     mod_type: String,
     character_name: String,
+    /// Skin display name resolved from skin IDs in asset paths (e.g. "Symbiote")
+    skin_name: Option<String>,
     category: String,
     file_count: usize,
     total_size: u64,
@@ -5237,7 +11601,7 @@ async fn get_mod_details(mod_path: String, _detect_blueprint: Option<bool>) -> R
             .collect()
     } else {
         // For regular PAK, open with AES key
-        let aes_key = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
+        let aes_key = AesKey::from_str(&install_mod::current_aes_key_hex())
             .map_err(|e| format!("Failed to create AES key: {}", e))?;
         
         let file = File::open(&path)
@@ -5267,6 +11631,20 @@ async fn get_mod_details(mod_path: String, _detect_blueprint: Option<bool>) -> R
     info!("Detected mod type: {}", characteristics.mod_type);
     info!("Character name: {}", characteristics.character_name);
     info!("Category: {}", characteristics.category);
+
+    // Resolve character/skin from asset path IDs (e.g. /1011/ or 1011001) via the cached character table
+    let (character_name, skin_name) = match character_data::identify_mod_from_paths(&files) {
+        Some((character, skin)) => {
+            info!("Resolved character/skin: {} - {}", character, skin);
+            let character_name = if characteristics.character_name.is_empty() {
+                character
+            } else {
+                characteristics.character_name
+            };
+            (character_name, Some(skin))
+        }
+        None => (characteristics.character_name, None),
+    };
     
     // Run fast Blueprint detection using filename heuristics
     let has_blueprint = files.iter().any(|f| {
@@ -5317,7 +11695,8 @@ async fn get_mod_details(mod_path: String, _detect_blueprint: Option<bool>) -> R
     Ok(ModDetails {
         mod_name,
         mod_type: characteristics.mod_type,
-        character_name: characteristics.character_name,
+        character_name,
+        skin_name,
         category: characteristics.category,
         file_count,
         total_size,
@@ -5328,6 +11707,345 @@ async fn get_mod_details(mod_path: String, _detect_blueprint: Option<bool>) -> R
     })
 }
 
+/// SHA-256 fingerprint of a mod: each internal file plus the container files themselves.
+/// Used to certify a "known good" build and verify P2P-shared or re-downloaded copies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModManifest {
+    mod_name: String,
+    is_iostore: bool,
+    /// Container hashes keyed by role ("pak", "utoc", "ucas") so renamed/disabled copies still compare
+    containers: std::collections::BTreeMap<String, String>,
+    /// Internal file path -> SHA-256 of its decompressed content
+    files: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct VerifyResult {
+    matching: Vec<String>,
+    differing: Vec<String>,
+    missing: Vec<String>,
+    extra: Vec<String>,
+    containers_match: bool,
+    /// True when every internal file matches and nothing is missing or extra
+    is_match: bool,
+}
+
+fn build_mod_manifest(path: &Path) -> Result<ModManifest, String> {
+    use sha2::{Digest, Sha256};
+    use std::collections::BTreeMap;
+    use std::io::BufReader;
+
+    if !path.exists() {
+        return Err(format!("Mod file does not exist: {}", path.display()));
+    }
+
+    let utoc_path = path.with_extension("utoc");
+    let ucas_path = path.with_extension("ucas");
+    let is_iostore = utoc_path.exists();
+
+    let mut containers = BTreeMap::new();
+    containers.insert(
+        "pak".to_string(),
+        p2p_sharing::hash_file(path).map_err(|e| e.to_string())?,
+    );
+    if is_iostore {
+        containers.insert("utoc".to_string(), p2p_sharing::hash_file(&utoc_path).map_err(|e| e.to_string())?);
+        if ucas_path.exists() {
+            containers.insert("ucas".to_string(), p2p_sharing::hash_file(&ucas_path).map_err(|e| e.to_string())?);
+        }
+    }
+
+    let mut files = BTreeMap::new();
+    if is_iostore {
+        // IoStore content has to be extracted before individual files can be hashed
+        let extract_dir = tempfile::tempdir()
+            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+        uasset_toolkit::extract_iostore(
+            &utoc_path.to_string_lossy(),
+            &extract_dir.path().to_string_lossy(),
+            Some(&install_mod::current_aes_key_hex()),
+        ).map_err(|e| format!("Failed to extract IoStore: {}", e))?;
+
+        for entry in WalkDir::new(extract_dir.path()).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry.path()
+                .strip_prefix(extract_dir.path())
+                .map_err(|e| e.to_string())?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let hash = p2p_sharing::hash_file(entry.path()).map_err(|e| e.to_string())?;
+            files.insert(rel, hash);
+        }
+    } else {
+        let file = File::open(path)
+            .map_err(|e| format!("Failed to open PAK file: {}", e))?;
+        let mut reader = BufReader::new(file);
+        let pak = repak::PakBuilder::new()
+            .key(install_mod::current_aes_key().0)
+            .reader(&mut reader)
+            .map_err(|e| format!("Failed to read PAK: {}", e))?;
+
+        for file_path in pak.files() {
+            let data = pak.get(&file_path, &mut reader)
+                .map_err(|e| format!("Failed to read entry {}: {}", file_path, e))?;
+            files.insert(file_path, hex::encode(Sha256::digest(&data)));
+        }
+    }
+
+    let mod_name = path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown")
+        .to_string();
+
+    Ok(ModManifest { mod_name, is_iostore, containers, files })
+}
+
+/// Hash every internal file and container of a mod into a shareable manifest
+#[tauri::command]
+async fn generate_mod_manifest(mod_path: String) -> Result<ModManifest, String> {
+    info!("generate_mod_manifest called: {}", mod_path);
+    let manifest = build_mod_manifest(Path::new(&mod_path))?;
+    info!("Manifest for {}: {} files, {} containers", manifest.mod_name, manifest.files.len(), manifest.containers.len());
+    Ok(manifest)
+}
+
+/// Compare a mod against a previously generated manifest
+#[tauri::command]
+async fn verify_against_manifest(mod_path: String, manifest: ModManifest) -> Result<VerifyResult, String> {
+    info!("verify_against_manifest called: {} (against {})", mod_path, manifest.mod_name);
+    let current = build_mod_manifest(Path::new(&mod_path))?;
+
+    let mut matching = Vec::new();
+    let mut differing = Vec::new();
+    let mut missing = Vec::new();
+    for (file_path, expected) in &manifest.files {
+        match current.files.get(file_path) {
+            Some(actual) if actual == expected => matching.push(file_path.clone()),
+            Some(_) => differing.push(file_path.clone()),
+            None => missing.push(file_path.clone()),
+        }
+    }
+    let extra: Vec<String> = current.files.keys()
+        .filter(|f| !manifest.files.contains_key(*f))
+        .cloned()
+        .collect();
+
+    let containers_match = current.containers == manifest.containers;
+    let is_match = differing.is_empty() && missing.is_empty() && extra.is_empty();
+    info!(
+        "Verify {}: {} matching, {} differing, {} missing, {} extra, containers_match={}",
+        mod_path, matching.len(), differing.len(), missing.len(), extra.len(), containers_match
+    );
+
+    Ok(VerifyResult { matching, differing, missing, extra, containers_match, is_match })
+}
+
+/// A single problem found by run_diagnostics
+#[derive(Debug, Clone, Serialize)]
+struct DiagnosticFinding {
+    severity: String, // "error", "warning", "info"
+    category: String,
+    message: String,
+    suggestion: Option<String>,
+    path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DiagnosticsReport {
+    findings: Vec<DiagnosticFinding>,
+    mods_scanned: usize,
+}
+
+impl DiagnosticsReport {
+    fn add(&mut self, severity: &str, category: &str, message: String, suggestion: Option<&str>, path: Option<&Path>) {
+        self.findings.push(DiagnosticFinding {
+            severity: severity.to_string(),
+            category: category.to_string(),
+            message,
+            suggestion: suggestion.map(|s| s.to_string()),
+            path: path.map(|p| p.to_string_lossy().to_string()),
+        });
+    }
+}
+
+/// Priority markers of a mod file stem: whether it has the "!" prefix, and how many nines
+/// its "_999..._P" suffix has (0 if none)
+fn mod_priority_markers(stem: &str) -> (bool, usize) {
+    let bang = stem.starts_with('!');
+    let base = stem.strip_prefix('!').unwrap_or(stem);
+    let nines = base.strip_suffix("_P")
+        .and_then(|b| b.rsplit_once('_'))
+        .filter(|(_, digits)| !digits.is_empty() && digits.chars().all(|c| c == '9'))
+        .map(|(_, digits)| digits.len())
+        .unwrap_or(0);
+    (bang, nines)
+}
+
+/// Priority of base paks (no nines suffix): they are mounted before every patch pak, so any
+/// patch pak overrides them
+const BASE_PAK_PRIORITY: usize = usize::MAX;
+
+/// UI priority of a mod file, as shown by get_pak_files and set by set_mod_priority:
+/// 0 = "!" prefix (highest priority), 1-N = 7 nines → 1, 8 nines → 2, etc.
+/// Mods without a nines suffix get BASE_PAK_PRIORITY (lowest).
+fn mod_priority(path: &Path) -> usize {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    match mod_priority_markers(stem) {
+        (true, _) => 0,
+        (false, nines) if nines >= 7 => nines - 6,
+        (false, 0) => BASE_PAK_PRIORITY,
+        _ => 0,
+    }
+}
+
+/// Trailing "_999..." run left once the "_P" is stripped from a mod stem
+static PRIORITY_NINES_REGEX: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"^(.*)_(9+)$").unwrap());
+
+/// Mod file stem with the "!" prefix and "_9999999_P" priority suffix removed
+fn mod_base_name_without_priority(stem: &str) -> String {
+    let stem = stem.strip_prefix('!').unwrap_or(stem);
+    let base = stem.strip_suffix("_P").unwrap_or(stem);
+    match PRIORITY_NINES_REGEX.captures(base) {
+        Some(caps) => caps[1].to_string(),
+        None => base.to_string(),
+    }
+}
+
+/// Run the common support checks in one go: game path, ~mods writability, UAssetTool,
+/// USMAP, orphaned IoStore files, non-Oodle mods and naming/priority collisions
+#[tauri::command]
+async fn run_diagnostics(state: State<'_, Arc<RwLock<AppState>>>) -> Result<DiagnosticsReport, String> {
+    use std::collections::HashMap;
+    use std::io::{BufReader, Read};
+
+    info!("run_diagnostics called");
+    let (game_path, usmap_filename) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.usmap_path.clone())
+    };
+    let mut report = DiagnosticsReport { findings: Vec::new(), mods_scanned: 0 };
+
+    // UAssetTool
+    let toolkit_ok = match uasset_toolkit::ping_toolkit() {
+        Ok(()) => true,
+        Err(e) => {
+            report.add("error", "uasset_tool", format!("UAssetTool is not responding: {}", e),
+                Some("Reinstall Repak-X or check that antivirus hasn't quarantined UAssetTool"), None);
+            false
+        }
+    };
+
+    // USMAP
+    if usmap_filename.is_empty() {
+        report.add("warning", "usmap", "No USMAP file is configured".to_string(),
+            Some("Import the game's .usmap in Settings; texture and mesh fixes need it"), None);
+    } else {
+        match get_usmap_full_path(&usmap_filename) {
+            None => report.add("error", "usmap", format!("USMAP file '{}' is missing", usmap_filename),
+                Some("Re-import the .usmap file in Settings"), None),
+            Some(usmap_path) => {
+                // .usmap files start with the 0x30C4 magic
+                let mut magic = [0u8; 2];
+                let valid = File::open(&usmap_path)
+                    .and_then(|mut f| f.read_exact(&mut magic))
+                    .map(|_| u16::from_le_bytes(magic) == 0x30C4)
+                    .unwrap_or(false);
+                if !valid {
+                    report.add("error", "usmap", format!("USMAP file '{}' is not a valid .usmap", usmap_filename),
+                        Some("Re-export the mappings for the current game version and import them again"), Some(&usmap_path));
+                }
+            }
+        }
+    }
+
+    // Game path
+    if !game_path.exists() {
+        report.add("error", "game_path", format!("Mods folder does not exist: {}", game_path.display()),
+            Some("Set the game path in Settings (auto-detect usually finds it)"), Some(&game_path));
+        return Ok(report);
+    }
+    if game_path.file_name().and_then(|n| n.to_str()) != Some("~mods") {
+        report.add("warning", "game_path", "Mods folder is not named ~mods; the game may not load mods from it".to_string(),
+            Some("Point the game path at .../Content/Paks/~mods"), Some(&game_path));
+    }
+    if let Err(e) = tempfile::NamedTempFile::new_in(&game_path) {
+        report.add("error", "game_path", format!("Mods folder is not writable: {}", e),
+            Some("Run Repak-X with permission to write to the game folder, or move the game out of a protected location"), Some(&game_path));
+    }
+
+    // Walk the mods folder once for all file-based checks
+    let mut containers: Vec<PathBuf> = Vec::new();
+    let mut iostore_files: Vec<PathBuf> = Vec::new();
+    for entry in WalkDir::new(&game_path).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("pak") | Some("bak_repak") | Some("pak_disabled") => containers.push(path.to_path_buf()),
+            Some("utoc") | Some("ucas") => iostore_files.push(path.to_path_buf()),
+            _ => {}
+        }
+    }
+    report.mods_scanned = containers.len();
+
+    // Orphaned IoStore files
+    for path in &iostore_files {
+        let has_pak = ["pak", "bak_repak", "pak_disabled"].iter().any(|ext| path.with_extension(ext).exists());
+        let other = if path.extension().and_then(|s| s.to_str()) == Some("utoc") { "ucas" } else { "utoc" };
+        if !has_pak {
+            report.add("warning", "orphaned_iostore", "IoStore file has no matching .pak".to_string(),
+                Some("Delete it, or reinstall the mod it belongs to"), Some(path));
+        } else if !path.with_extension(other).exists() {
+            report.add("error", "orphaned_iostore", format!("IoStore file is missing its .{} companion", other),
+                Some("Reinstall this mod"), Some(path));
+        }
+    }
+
+    // Compression (enabled mods only, like recompress_mods)
+    for path in containers.iter().filter(|p| p.extension().and_then(|s| s.to_str()) == Some("pak")) {
+        let utoc_path = path.with_extension("utoc");
+        if utoc_path.exists() {
+            if toolkit_ok {
+                if let Ok(false) = uasset_toolkit::is_iostore_compressed(&utoc_path.to_string_lossy()) {
+                    report.add("warning", "compression", "IoStore mod is not Oodle-compressed".to_string(),
+                        Some("Run 'Recompress mods' to shrink it"), Some(path));
+                }
+            }
+        } else if let Ok(file) = File::open(path) {
+            let mut reader = BufReader::new(file);
+            match repak::PakBuilder::new().key(install_mod::current_aes_key().0).reader(&mut reader) {
+                Ok(pak) if !pak.compression().contains(&repak::Compression::Oodle) => {
+                    report.add("warning", "compression", "PAK mod is not Oodle-compressed".to_string(),
+                        Some("Run 'Recompress mods' to shrink it"), Some(path));
+                }
+                Ok(_) => {}
+                Err(e) => report.add("error", "unreadable", format!("PAK could not be read: {}", e),
+                    Some("The file may be corrupt or use a different AES key; reinstall it"), Some(path)),
+            }
+        }
+    }
+
+    // Naming/priority collisions: the same mod installed more than once
+    let mut by_base: HashMap<String, Vec<&PathBuf>> = HashMap::new();
+    for path in &containers {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        by_base.entry(mod_base_name_without_priority(stem).to_lowercase()).or_default().push(path);
+    }
+    for (base, paths) in by_base.iter().filter(|(_, p)| p.len() > 1) {
+        let list = paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", ");
+        report.add("warning", "name_collision",
+            format!("'{}' is installed {} times: {}", base, paths.len(), list),
+            Some("Keep one copy; use 'Find duplicates' to remove identical ones"), Some(paths[0]));
+    }
+
+    info!("Diagnostics complete: {} finding(s) across {} mod(s)", report.findings.len(), report.mods_scanned);
+    Ok(report)
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 struct ModClash {
     file_path: String,
@@ -5344,7 +12062,7 @@ struct SingleModConflict {
 }
 
 #[tauri::command]
-async fn check_mod_clashes(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec<ModClash>, String> {
+async fn check_mod_clashes(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<ModClash>, String> {
     use repak::PakBuilder;
     use repak::utils::AesKey;
     use std::str::FromStr;
@@ -5352,8 +12070,7 @@ async fn check_mod_clashes(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec
     use std::io::BufReader;
     use std::collections::HashMap;
     
-    let state = state.lock().unwrap();
-    let game_path = &state.game_path;
+    let game_path = state.read().await.game_path.clone();
     
     info!("Checking for mod clashes...");
     
@@ -5362,7 +12079,7 @@ async fn check_mod_clashes(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec
     }
 
     // Get AES key
-    let aes_key = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
+    let aes_key = AesKey::from_str(&install_mod::current_aes_key_hex())
         .map_err(|e| format!("Failed to create AES key: {}", e))?;
 
     // Structure to hold mod info for clash detection
@@ -5394,26 +12111,7 @@ async fn check_mod_clashes(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec
         }
 
         // Calculate priority (same as get_pak_files)
-        let mut priority = 0;
-        let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-
-        // Check for "!" prefix (highest priority)
-        if file_stem.starts_with("!") {
-            priority = 0; // Highest priority
-        } else if file_stem.ends_with("_P") {
-            let base_no_p = file_stem.strip_suffix("_P").unwrap();
-            let re_nums = Regex::new(r"_(\d+)$").unwrap();
-            if let Some(caps) = re_nums.captures(base_no_p) {
-                let nums = &caps[1];
-                if nums.chars().all(|c| c == '9') {
-                    let actual_nines = nums.len();
-                    // Convert actual nines count to UI priority (1-based)
-                    if actual_nines >= 7 {
-                        priority = actual_nines - 6;
-                    }
-                }
-            }
-        }
+        let priority = mod_priority(path);
 
         // Open PAK file to analyze contents
         let file = match File::open(&path) {
@@ -5552,10 +12250,102 @@ async fn check_mod_clashes(state: State<'_, Arc<Mutex<AppState>>>) -> Result<Vec
     Ok(clashes)
 }
 
+/// An installed, enabled mod whose files would be overwritten by a mod about to be installed
+#[derive(Clone, Serialize)]
+struct ModConflict {
+    /// Name of the incoming mod (as it will be installed)
+    incoming_mod: String,
+    existing_mod_path: String,
+    existing_mod_name: String,
+    overlapping_files: Vec<String>,
+}
+
+/// Internal file paths of a not-yet-installed mod, in the same form `list_mod_files` reports
+fn list_incoming_mod_files(installable: &crate::install_mod::InstallableMod) -> Vec<String> {
+    if installable.iostore {
+        return crate::utoc_utils::read_utoc(&installable.mod_path.with_extension("utoc"))
+            .iter()
+            .map(|entry| entry.file_path.clone())
+            .collect();
+    }
+    if let Some(reader) = &installable.reader {
+        return reader.files();
+    }
+    if installable.is_dir {
+        return walkdir::WalkDir::new(&installable.mod_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                e.path().strip_prefix(&installable.mod_path).ok()
+                    .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            })
+            .collect();
+    }
+    Vec::new()
+}
+
+/// Before installing, find enabled installed mods that change the same files as the incoming
+/// mods at `source_paths` (paks, IoStore bundles, folders or archives)
+#[tauri::command]
+async fn preview_install_conflicts(
+    source_paths: Vec<String>,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<Vec<ModConflict>, String> {
+    use crate::install_mod::map_paths_to_mods;
+    use std::collections::HashSet;
+
+    let game_path = state.read().await.game_path.clone();
+    if !game_path.exists() {
+        return Err("Game path does not exist".to_string());
+    }
+
+    info!("preview_install_conflicts called for {} path(s)", source_paths.len());
+    let paths: Vec<PathBuf> = source_paths.iter().map(PathBuf::from).collect();
+    let incoming = map_paths_to_mods(&paths, None);
+
+    // Union of enabled installed mods' files, remembered per mod so owners can be reported
+    let installed: Vec<(PathBuf, HashSet<String>)> = WalkDir::new(&game_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().and_then(|s| s.to_str()) == Some("pak"))
+        .filter_map(|e| {
+            list_mod_files(e.path()).map(|files| (e.path().to_path_buf(), files.into_iter().collect()))
+        })
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for installable in &incoming {
+        let incoming_files: HashSet<String> = list_incoming_mod_files(installable)
+            .into_iter()
+            // Metadata written by the install pipeline, present in every repacked mod
+            .filter(|f| !f.ends_with("patched_files"))
+            .collect();
+        for (existing_path, existing_files) in &installed {
+            let mut overlapping: Vec<String> = incoming_files.intersection(existing_files).cloned().collect();
+            if overlapping.is_empty() {
+                continue;
+            }
+            overlapping.sort();
+            conflicts.push(ModConflict {
+                incoming_mod: installable.mod_name.clone(),
+                existing_mod_path: existing_path.to_string_lossy().to_string(),
+                existing_mod_name: existing_path.file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                overlapping_files: overlapping,
+            });
+        }
+    }
+
+    info!("preview_install_conflicts found {} conflict(s)", conflicts.len());
+    Ok(conflicts)
+}
+
 #[tauri::command]
 async fn check_single_mod_conflicts(
     mod_path: String,
-    state: State<'_, Arc<Mutex<AppState>>>
+    state: State<'_, Arc<RwLock<AppState>>>
 ) -> Result<Vec<SingleModConflict>, String> {
     use repak::PakBuilder;
     use repak::utils::AesKey;
@@ -5571,7 +12361,7 @@ async fn check_single_mod_conflicts(
     }
     
     let game_path = {
-        let state = state.lock().unwrap();
+        let state = state.read().await;
         state.game_path.clone()
     };
     
@@ -5581,32 +12371,9 @@ async fn check_single_mod_conflicts(
     
     info!("Checking conflicts for mod: {}", target_path.display());
     
-    let aes_key = AesKey::from_str("0C263D8C22DCB085894899C3A3796383E9BF9DE0CBFB08C9BF2DEF2E84F29D74")
+    let aes_key = AesKey::from_str(&install_mod::current_aes_key_hex())
         .map_err(|e| format!("Failed to create AES key: {}", e))?;
     
-    // Helper to calculate priority from filename
-    fn calculate_priority(path: &Path) -> usize {
-        let mut priority = 0;
-        let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-        
-        if file_stem.starts_with("!") {
-            priority = 0;
-        } else if file_stem.ends_with("_P") {
-            let base_no_p = file_stem.strip_suffix("_P").unwrap();
-            let re_nums = Regex::new(r"_(\d+)$").unwrap();
-            if let Some(caps) = re_nums.captures(base_no_p) {
-                let nums = &caps[1];
-                if nums.chars().all(|c| c == '9') {
-                    let actual_nines = nums.len();
-                    if actual_nines >= 7 {
-                        priority = actual_nines - 6;
-                    }
-                }
-            }
-        }
-        priority
-    }
-    
     // Helper to get files from a PAK
     fn get_pak_files(path: &Path, aes_key: &AesKey) -> Result<Vec<String>, String> {
         let file = File::open(path).map_err(|e| format!("Failed to open PAK: {}", e))?;
@@ -5631,7 +12398,7 @@ async fn check_single_mod_conflicts(
     }
     
     // Get target mod info
-    let target_priority = calculate_priority(&target_path);
+    let target_priority = mod_priority(&target_path);
     let target_files: HashSet<String> = get_pak_files(&target_path, &aes_key)?
         .into_iter()
         .collect();
@@ -5683,7 +12450,7 @@ async fn check_single_mod_conflicts(
         }
         
         // Calculate priority comparison
-        let other_priority = calculate_priority(path);
+        let other_priority = mod_priority(path);
         let priority_comparison = if target_priority == other_priority {
             "Same priority (conflict!)".to_string()
         } else if target_priority < other_priority {
@@ -5726,6 +12493,223 @@ async fn check_single_mod_conflicts(
     Ok(conflicts)
 }
 
+/// A file that more than one enabled mod provides
+#[derive(Clone, Serialize)]
+struct LoadOrderConflict {
+    file_path: String,
+    /// Mod whose copy the game ends up using
+    winner: String,
+    /// The top mods for this file share a priority, so which one wins isn't defined
+    ambiguous: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct LoadOrderEntry {
+    path: String,
+    name: String,
+    priority: usize,
+    /// 1-based position in the order mods are applied (later entries win)
+    position: usize,
+    conflicts: Vec<LoadOrderConflict>,
+}
+
+/// Sort enabled mods into the order the game applies them: lowest priority first, so the
+/// highest priority ("!" / priority 0) comes last and wins. Ties keep path order.
+fn effective_load_order(mut mods: Vec<(PathBuf, Vec<String>)>) -> Vec<LoadOrderEntry> {
+    use std::collections::HashMap;
+
+    mods.sort_by(|(a, _), (b, _)| mod_priority(b).cmp(&mod_priority(a)).then_with(|| a.cmp(b)));
+
+    // internal file -> indices (into the sorted list) of the mods providing it
+    let mut providers: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, (_, files)) in mods.iter().enumerate() {
+        for file in files {
+            if !file.ends_with("patched_files") {
+                providers.entry(file.as_str()).or_default().push(idx);
+            }
+        }
+    }
+
+    let mut conflicts: Vec<Vec<LoadOrderConflict>> = vec![Vec::new(); mods.len()];
+    for (file, idxs) in &providers {
+        if idxs.len() < 2 {
+            continue;
+        }
+        let winner = *idxs.last().unwrap();
+        let winner_priority = mod_priority(&mods[winner].0);
+        let ambiguous = idxs.iter().filter(|&&i| mod_priority(&mods[i].0) == winner_priority).count() > 1;
+        for &idx in idxs {
+            conflicts[idx].push(LoadOrderConflict {
+                file_path: file.to_string(),
+                winner: mods[winner].0.to_string_lossy().to_string(),
+                ambiguous,
+            });
+        }
+    }
+
+    mods.into_iter()
+        .zip(conflicts)
+        .enumerate()
+        .map(|(idx, ((path, _), mut conflicts))| {
+            conflicts.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+            LoadOrderEntry {
+                name: path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+                priority: mod_priority(&path),
+                path: path.to_string_lossy().to_string(),
+                position: idx + 1,
+                conflicts,
+            }
+        })
+        .collect()
+}
+
+/// Enabled mods in the order the game applies them (last wins), with the files each one
+/// shares with other mods and which mod actually wins them
+#[tauri::command]
+async fn get_effective_load_order(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<LoadOrderEntry>, String> {
+    use rayon::prelude::*;
+
+    let (game_path, mod_metadata) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone())
+    };
+    let entries = list_mod_entries(&game_path, &mod_metadata)?;
+
+    let mods: Vec<(PathBuf, Vec<String>)> = entries.par_iter()
+        .filter(|e| e.enabled)
+        .map(|e| {
+            let files = list_mod_files(&e.path).unwrap_or_else(|| {
+                warn!("Failed to read files of {}", e.path.display());
+                Vec::new()
+            });
+            (e.path.clone(), files)
+        })
+        .collect();
+    let mut order = effective_load_order(mods);
+
+    // Prefer the user's display names
+    for entry in &mut order {
+        if let Some(name) = entries.iter()
+            .find(|e| e.path.to_string_lossy() == entry.path)
+            .and_then(|e| e.custom_name.clone())
+        {
+            entry.name = name;
+        }
+    }
+    info!("Effective load order: {} enabled mod(s)", order.len());
+    Ok(order)
+}
+
+/// An enabled mod some or all of whose files are overridden by higher-priority mods
+#[derive(Debug, Clone, Serialize)]
+struct ShadowedMod {
+    path: String,
+    name: String,
+    /// Distinct assets the mod provides
+    total_files: usize,
+    /// Assets a higher-priority mod wins
+    shadowed_files: usize,
+    /// Every asset is overridden, so enabling the mod changes nothing in-game
+    fully_shadowed: bool,
+    /// Mods winning the shadowed assets
+    shadowed_by: Vec<String>,
+    /// A few of the shadowed assets, for display
+    sample_files: Vec<String>,
+}
+
+/// Shadowed assets listed per mod in ShadowedMod::sample_files
+const SHADOWED_SAMPLE_FILES: usize = 10;
+
+/// Work out which enabled mods lose some (or all) of their assets to higher-priority mods.
+/// Files are compared per package, so a legacy pak and an IoStore mod replacing the same
+/// asset count as overlapping. Ties of equal priority are left out since their winner isn't defined.
+fn find_shadowed(mods: Vec<(PathBuf, Vec<String>)>) -> Vec<ShadowedMod> {
+    let keyed: Vec<(PathBuf, Vec<String>)> = mods.into_iter()
+        .map(|(path, files)| {
+            let keys: std::collections::BTreeSet<String> = files.iter()
+                .filter(|f| !f.ends_with("patched_files"))
+                .filter(|f| !matches!(
+                    Path::new(f.as_str()).extension().and_then(|e| e.to_str()),
+                    Some("uexp" | "ubulk" | "uptnl")
+                ))
+                .map(|f| asset_override_key(f).unwrap_or_else(|| content_relative_path(f)))
+                .collect();
+            (path, keys.into_iter().collect())
+        })
+        .collect();
+    let totals: std::collections::HashMap<String, usize> = keyed.iter()
+        .map(|(path, keys)| (path.to_string_lossy().to_string(), keys.len()))
+        .collect();
+
+    let mut shadowed: Vec<ShadowedMod> = effective_load_order(keyed)
+        .into_iter()
+        .filter_map(|entry| {
+            let lost: Vec<&LoadOrderConflict> = entry.conflicts.iter()
+                .filter(|c| c.winner != entry.path && !c.ambiguous)
+                .collect();
+            if lost.is_empty() {
+                return None;
+            }
+            let total_files = totals.get(&entry.path).copied().unwrap_or_default();
+            let shadowed_by: std::collections::BTreeSet<String> = lost.iter().map(|c| c.winner.clone()).collect();
+            Some(ShadowedMod {
+                total_files,
+                shadowed_files: lost.len(),
+                fully_shadowed: lost.len() == total_files,
+                shadowed_by: shadowed_by.into_iter().collect(),
+                sample_files: lost.iter().take(SHADOWED_SAMPLE_FILES).map(|c| c.file_path.clone()).collect(),
+                path: entry.path,
+                name: entry.name,
+            })
+        })
+        .collect();
+    shadowed.sort_by(|a, b| b.fully_shadowed.cmp(&a.fully_shadowed).then_with(|| a.path.cmp(&b.path)));
+    shadowed
+}
+
+/// Enabled mods that never (or only partly) take effect because higher-priority mods
+/// override their files, fully shadowed mods first
+#[tauri::command]
+async fn find_shadowed_mods(state: State<'_, Arc<RwLock<AppState>>>) -> Result<Vec<ShadowedMod>, String> {
+    info!("find_shadowed_mods called");
+    let (game_path, mod_metadata) = {
+        let state = state.read().await;
+        (state.game_path.clone(), state.mod_metadata.clone())
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        use rayon::prelude::*;
+
+        let entries = list_mod_entries(&game_path, &mod_metadata)?;
+        let mods: Vec<(PathBuf, Vec<String>)> = entries.par_iter()
+            .filter(|e| e.enabled)
+            .map(|e| {
+                let files = list_mod_files(&e.path).unwrap_or_else(|| {
+                    warn!("Failed to read files of {}", e.path.display());
+                    Vec::new()
+                });
+                (e.path.clone(), files)
+            })
+            .collect();
+        let mut shadowed = find_shadowed(mods);
+        for mod_entry in &mut shadowed {
+            if let Some(name) = entries.iter()
+                .find(|e| e.path.to_string_lossy() == mod_entry.path)
+                .and_then(|e| e.custom_name.clone())
+            {
+                mod_entry.name = name;
+            }
+        }
+        info!(
+            "{} shadowed mod(s), {} fully",
+            shadowed.len(),
+            shadowed.iter().filter(|m| m.fully_shadowed).count()
+        );
+        Ok(shadowed)
+    })
+    .await
+    .map_err(|e| format!("Shadowed mod check failed: {}", e))?
+}
+
 // ============================================================================
 // P2P SHARING COMMANDS
 // ============================================================================
@@ -5737,12 +12721,35 @@ async fn p2p_start_sharing(
     description: String,
     mod_paths: Vec<String>,
     creator: Option<String>,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
     p2p_state: State<'_, P2PState>,
 ) -> Result<p2p_libp2p::ShareInfo, String> {
     let paths: Vec<PathBuf> = mod_paths.iter().map(PathBuf::from).collect();
+
+    if !ip_obfuscation::is_enabled() {
+        toast_events::emit_toast(&window, toast_events::ToastPayload::warning(
+            "IP Obfuscation Off",
+            "Your public and local IP addresses will appear unmasked in logs and connection status. Anyone you send logs or screenshots to can see them.",
+        ));
+    }
+
+    // Send our tags along so receivers that auto-install can apply them
+    let tags: Vec<String> = {
+        let state_guard = state.read().await;
+        let mut tags: Vec<String> = Vec::new();
+        for metadata in state_guard.mod_metadata.iter().filter(|m| paths.contains(&m.path)) {
+            for tag in &metadata.custom_tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+        tags
+    };
     
     p2p_state.manager
-        .start_sharing(name, description, paths, creator)
+        .start_sharing(name, description, paths, creator, tags)
         .await
         .map_err(|e| e.to_string())
 }
@@ -5777,7 +12784,7 @@ async fn p2p_start_receiving(
     connection_string: String,
     client_name: Option<String>,
     window: Window,
-    state: State<'_, Arc<Mutex<AppState>>>,
+    state: State<'_, Arc<RwLock<AppState>>>,
     p2p_state: State<'_, P2PState>,
     watcher_state: State<'_, WatcherState>,
 ) -> Result<(), String> {
@@ -5785,13 +12792,52 @@ async fn p2p_start_receiving(
     watcher_state.paused.store(true, Ordering::Relaxed);
     info!("[P2P] File watcher paused for transfer");
 
-    let output_dir = {
-        let state_guard = state.lock().unwrap();
-        state_guard.game_path.clone()
+    let (output_dir, auto_install) = {
+        let state_guard = state.read().await;
+        (state_guard.game_path.clone(), state_guard.auto_install_received)
     };
     
     p2p_state.manager
-        .start_receiving(&connection_string, output_dir, client_name, window)
+        .start_receiving(&connection_string, output_dir, client_name, auto_install, window)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Share a single mod and return a short share code for it
+#[tauri::command]
+async fn p2p_generate_share_code(
+    mod_path: String,
+    creator: Option<String>,
+    p2p_state: State<'_, P2PState>,
+) -> Result<String, String> {
+    p2p_state.manager
+        .generate_share_code(PathBuf::from(mod_path), creator)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Start receiving the mod a share code points at
+#[tauri::command]
+async fn p2p_receive_from_share_code(
+    code: String,
+    client_name: Option<String>,
+    window: Window,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    p2p_state: State<'_, P2PState>,
+    watcher_state: State<'_, WatcherState>,
+) -> Result<(), String> {
+    // Validate before pausing the watcher so a bad code doesn't leave it paused
+    p2p_manager::decode_share_code(&code).map_err(|e| e.to_string())?;
+    watcher_state.paused.store(true, Ordering::Relaxed);
+    info!("[P2P] File watcher paused for transfer");
+
+    let (output_dir, auto_install) = {
+        let state_guard = state.read().await;
+        (state_guard.game_path.clone(), state_guard.auto_install_received)
+    };
+
+    p2p_state.manager
+        .receive_from_share_code(&code, output_dir, client_name, auto_install, window)
         .await
         .map_err(|e| e.to_string())
 }
@@ -5827,6 +12873,79 @@ async fn p2p_get_receive_progress(p2p_state: State<'_, P2PState>) -> Result<Opti
     }
 }
 
+/// Queued, active and finished P2P transfers for the transfer list
+#[tauri::command]
+async fn p2p_get_transfer_queue(p2p_state: State<'_, P2PState>) -> Result<Vec<p2p_manager::TransferQueueEntry>, String> {
+    Ok(p2p_state.manager.get_transfer_queue())
+}
+
+/// Remove a queued transfer or abort an active one
+#[tauri::command]
+async fn p2p_cancel_transfer(id: String, p2p_state: State<'_, P2PState>) -> Result<(), String> {
+    p2p_state.manager.cancel_transfer(&id).map_err(|e| e.to_string())
+}
+
+/// Set how many P2P downloads may run at once (0 = default)
+#[tauri::command]
+async fn set_p2p_max_concurrent(
+    max: usize,
+    state: State<'_, Arc<RwLock<AppState>>>,
+    p2p_state: State<'_, P2PState>,
+) -> Result<(), String> {
+    info!("set_p2p_max_concurrent called: max={}", max);
+    p2p_state.manager.set_max_concurrent(max);
+    let mut state = state.write().await;
+    state.p2p_max_concurrent = max;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Get the configured P2P concurrent download limit (0 = default)
+#[tauri::command]
+async fn get_p2p_max_concurrent(state: State<'_, Arc<RwLock<AppState>>>) -> Result<usize, String> {
+    let state = state.read().await;
+    Ok(state.p2p_max_concurrent)
+}
+
+/// Enable or disable installing received P2P mods automatically
+#[tauri::command]
+async fn set_auto_install_received(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_auto_install_received called: enabled={}", enabled);
+    let mut state = state.write().await;
+    state.auto_install_received = enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_auto_install_received(state: State<'_, Arc<RwLock<AppState>>>) -> Result<bool, String> {
+    let state = state.read().await;
+    Ok(state.auto_install_received)
+}
+
+/// Enable or disable obfuscation of P2P addresses in logs and connection events
+#[tauri::command]
+async fn set_ip_obfuscation(
+    enabled: bool,
+    state: State<'_, Arc<RwLock<AppState>>>,
+) -> Result<(), String> {
+    info!("set_ip_obfuscation called: enabled={}", enabled);
+    ip_obfuscation::set_enabled(enabled);
+    let mut state = state.write().await;
+    state.ip_obfuscation_disabled = !enabled;
+    save_state(&state).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Whether P2P addresses are currently obfuscated
+#[tauri::command]
+async fn get_ip_obfuscation_status() -> Result<bool, String> {
+    Ok(ip_obfuscation::is_enabled())
+}
+
 /// Check if currently receiving
 #[tauri::command]
 async fn p2p_is_receiving(p2p_state: State<'_, P2PState>) -> Result<bool, String> {
@@ -6022,6 +13141,12 @@ fn main() {
 
     setup_logging();
     info!("Starting RepakX v{}", env!("CARGO_PKG_VERSION"));
+
+    // Per-session install log so failed installs leave a trace after the window closes
+    match install_mod::install_mod_logic::session_log::init_session_log(&install_log_dir()) {
+        Ok(path) => info!("Install log: {}", path.display()),
+        Err(e) => warn!("Failed to create install log: {}", e),
+    }
     
     // Register protocol handler for portable app support (self-healing registry)
     if let Err(e) = register_protocol_handler() {
@@ -6041,7 +13166,21 @@ fn main() {
     info!("Initializing character data cache...");
     character_data::refresh_cache();
     
-    let state = Arc::new(Mutex::new(load_state()));
+    let state = Arc::new(RwLock::new(load_state()));
+    {
+        let mut state_guard = state.blocking_write();
+        if state_guard.repair_config_on_startup {
+            let summary = repair_app_state(&mut state_guard);
+            info!("Startup config repair: {:?}", summary);
+            if let Err(e) = save_state(&state_guard) {
+                warn!("Failed to save repaired state: {}", e);
+            }
+        }
+        install_mod::install_mod_logic::archives::set_extraction_limits(
+            state_guard.max_extracted_bytes,
+            state_guard.max_entries,
+        );
+    }
     let watcher_state = WatcherState { 
         watcher: Mutex::new(None),
         last_event_time: Mutex::new(std::time::Instant::now()),
@@ -6055,6 +13194,24 @@ fn main() {
         .expect("Failed to create tokio runtime")
         .block_on(p2p_manager::UnifiedP2PManager::new())
         .expect("Failed to initialize P2P network");
+    p2p_manager.set_max_concurrent(state.blocking_read().p2p_max_concurrent);
+    ip_obfuscation::set_enabled(!state.blocking_read().ip_obfuscation_disabled);
+    if let Some(key) = state.blocking_read().aes_key.clone() {
+        if let Err(e) = install_mod::set_aes_key(&key) {
+            warn!("Ignoring saved AES key: {}", e);
+        }
+    }
+    if let Some(tool_path) = state.blocking_read().uasset_tool_path.clone() {
+        if let Err(e) = uasset_toolkit::reinit_global_toolkit(tool_path) {
+            warn!("Ignoring saved UAssetTool path: {}", e);
+        }
+    }
+    let max_tool_response_bytes = state.blocking_read().max_tool_response_bytes;
+    if max_tool_response_bytes != 0 {
+        if let Err(e) = uasset_toolkit::set_max_response_bytes(max_tool_response_bytes) {
+            warn!("Failed to apply saved UAssetTool response limit: {}", e);
+        }
+    }
     let p2p_state = P2PState { manager: Arc::new(p2p_manager) };
     
     // Initialize Discord Rich Presence manager
@@ -6062,7 +13219,7 @@ fn main() {
 
     // Check saved state to see if DRP should be enabled
     {
-        let state_guard = state.lock().unwrap();
+        let state_guard = state.blocking_read();
         if state_guard.enable_drp {
              if let Err(e) = discord_manager.connect() {
                  warn!("Failed to auto-connect Discord RPC: {}", e);
@@ -6096,6 +13253,8 @@ fn main() {
     tauri::Builder::default()
         .manage(state)
         .manage(watcher_state)
+        .manage(DevWatchState::default())
+        .manage(Arc::new(InstallLogBuffer::default()))
         .manage(crash_state)
         .manage(p2p_state)
         .manage(discord_state)
@@ -6133,6 +13292,19 @@ fn main() {
                 }
             }
             
+            // Mirror install output into the exportable log buffer
+            let install_log_buffer = app.state::<Arc<InstallLogBuffer>>().inner().clone();
+            let log_buffer = install_log_buffer.clone();
+            app.listen_any("install_log", move |event| {
+                let line = serde_json::from_str::<String>(event.payload())
+                    .unwrap_or_else(|_| event.payload().to_string());
+                log_buffer.push(line);
+            });
+            let log_buffer = install_log_buffer;
+            app.listen_any("install_stage", move |event| {
+                log_buffer.push(format!("[Stage] {}", event.payload()));
+            });
+
             let app_handle = app.handle().clone();
             app.listen("deep-link://new-url", move |event| {
                 let payload = event.payload();
@@ -6149,6 +13321,8 @@ fn main() {
             // 
             // Note: We use a small delay to ensure the frontend is ready to receive events.
             // ============================================================
+            spawn_game_monitor(app.handle().clone());
+
             let startup_app_handle = app.handle().clone();
             std::thread::spawn(move || {
                 // Wait for the frontend to be ready
@@ -6172,26 +13346,49 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             get_game_path,
             set_game_path,
+            migrate_mods_to_path,
+            detect_game_aes_key,
+            set_aes_key,
+            set_uasset_tool_path,
+            get_uasset_tool_path,
+            get_uasset_tool_stderr,
             auto_detect_game_path,
             start_file_watcher,
+            watch_source_for_dev,
+            stop_dev_watch,
             get_pak_files,
+            query_mods,
             parse_dropped_files,
+            cancel_detection,
             install_mods,
+            install_mod_from_url,
             quick_organize,
             delete_mod,
+            find_duplicate_mods,
+            merge_duplicates,
             update_mod,
             rename_mod,
+            get_mods_drive_space,
+            fix_mod_companion_names,
+            batch_rename_mods,
             open_in_explorer,
             copy_to_clipboard,
             create_folder,
             get_folders,
             get_root_folder_info,
             update_folder,
+            repair_config,
+            set_repair_config_on_startup,
+            get_repair_config_on_startup,
             delete_folder,
             rename_folder,
             assign_mod_to_folder,
             add_custom_tag,
             remove_custom_tag,
+            add_tag_to_mods,
+            remove_tag_from_mods,
+            rename_tag,
+            delete_tag,
             // USMAP management commands
             copy_usmap_to_folder,
             set_usmap_path,
@@ -6201,13 +13398,32 @@ fn main() {
             get_current_usmap_file,
             get_current_usmap_full_path,
             delete_current_usmap,
+            delete_all_usmaps,
+            set_active_usmap,
             get_all_tags,
+            set_mod_source_url,
+            set_mod_thumbnail,
+            open_mod_source,
+            export_mod_bundle,
+            import_mod_bundle,
             toggle_mod,
+            isolate_mod,
+            restore_from_isolation,
+            get_active_isolation,
+            start_crash_bisection,
+            bisection_result,
+            cancel_crash_bisection,
+            get_crash_bisection,
             check_game_running,
             launch_game,
+            launch_game_direct,
+            get_mount_status,
             skip_launcher_patch,
             get_skip_launcher_status,
+            get_launcher_skip_mode,
+            set_launcher_skip_mode,
             recompress_mods,
+            cancel_recompress,
             get_app_version,
             check_for_updates,
             download_update,
@@ -6223,11 +13439,42 @@ fn main() {
             dismiss_crash_dialog,
             get_crash_log_path,
             get_mod_details,
+            open_pak_viewer,
+            is_mod_obfuscated,
             set_mod_priority,
+            reset_mod_priority,
+            audit_pak_suffixes,
+            fix_pak_suffix,
+            diff_mod_against_base,
             check_mod_clashes,
+            run_diagnostics,
             check_single_mod_conflicts,
+            get_effective_load_order,
+            find_shadowed_mods,
+            preview_install_conflicts,
             extract_pak_to_destination,
+            cleanup_redundant_ubulk,
             extract_mod_assets,
+            convert_to_legacy_pak,
+            get_pak_mount_point,
+            read_pak_entry,
+            extract_mod_configs,
+            preview_mod_texture,
+            get_pak_metadata,
+            inspect_pak_encryption,
+            list_supported_versions,
+            detect_pak_version,
+            is_iostore_stub_pak,
+            set_pak_mount_point,
+            split_pak_by_folder,
+            merge_paks,
+            refix_installed_mod,
+            convert_directory_to_iostore,
+            fix_serialsize_for_mod,
+            backup_game_paks,
+            verify_game_paks,
+            generate_mod_manifest,
+            verify_against_manifest,
             // Character data commands
             get_character_data,
             get_character_by_skin_id,
@@ -6236,6 +13483,9 @@ fn main() {
             identify_mod_character,
             get_character_data_path,
             refresh_character_cache,
+            build_asset_index,
+            get_library_stats,
+            find_mods_touching_path,
             // P2P sharing commands
             p2p_start_sharing,
             p2p_stop_sharing,
@@ -6243,6 +13493,16 @@ fn main() {
             p2p_is_sharing,
             p2p_start_receiving,
             p2p_stop_receiving,
+            p2p_generate_share_code,
+            p2p_get_transfer_queue,
+            p2p_cancel_transfer,
+            set_p2p_max_concurrent,
+            get_p2p_max_concurrent,
+            set_auto_install_received,
+            get_auto_install_received,
+            set_ip_obfuscation,
+            get_ip_obfuscation_status,
+            p2p_receive_from_share_code,
             p2p_get_receive_progress,
             p2p_is_receiving,
             p2p_create_mod_pack_preview,
@@ -6252,6 +13512,7 @@ fn main() {
             check_lod_disabler_deployed,
             get_lod_disabler_path,
             deploy_lod_disabler,
+            set_lod_disabler_enabled,
             // Discord Rich Presence commands
             discord_connect,
             discord_disconnect,
@@ -6272,7 +13533,28 @@ fn main() {
             get_parallel_processing,
             // Obfuscation
             set_obfuscate,
-            get_obfuscate
+            get_obfuscate,
+            set_recompress_on_organize,
+            get_recompress_on_organize,
+            set_fast_detection,
+            get_fast_detection,
+            set_auto_strip_mipmaps,
+            get_auto_strip_mipmaps,
+            set_iostore_version,
+            get_iostore_version,
+            detect_iostore_version,
+            check_mod_compatibility,
+            estimate_mod_impact,
+            set_archive_limits,
+            get_archive_limits,
+            set_max_tool_response_bytes,
+            get_max_tool_response_bytes,
+            set_keep_extracted,
+            get_keep_extracted,
+            reveal_last_extraction,
+            open_install_logs,
+            get_last_install_log,
+            save_install_log
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
@@ -6287,3 +13569,596 @@ fn main() {
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Listing ~mods while other tasks toggle mods and edit metadata must never stall on the state lock
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_listing_and_toggling_does_not_deadlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let mod_paths: Vec<PathBuf> = (0..8)
+            .map(|i| {
+                let path = dir.path().join(format!("Mod{}_9999999_P.pak", i));
+                std::fs::write(&path, b"not a real pak").unwrap();
+                path
+            })
+            .collect();
+        let state = Arc::new(RwLock::new(AppState {
+            game_path: dir.path().to_path_buf(),
+            ..Default::default()
+        }));
+
+        let mut tasks = Vec::new();
+        for _ in 0..4 {
+            let state = state.clone();
+            tasks.push(tokio::spawn(async move {
+                for _ in 0..50 {
+                    let (game_path, mod_metadata) = {
+                        let state = state.read().await;
+                        (state.game_path.clone(), state.mod_metadata.clone())
+                    };
+                    list_mod_entries(&game_path, &mod_metadata).unwrap();
+                    tokio::task::yield_now().await;
+                }
+            }));
+        }
+        for path in mod_paths {
+            let state = state.clone();
+            tasks.push(tokio::spawn(async move {
+                let mut current = path;
+                for i in 0..50 {
+                    let enabled = toggle_mod_file(&current).unwrap();
+                    current = current.with_extension(if enabled { "pak" } else { "bak_repak" });
+                    state.write().await.mod_metadata.push(ModMetadata {
+                        path: current.clone(),
+                        custom_name: Some(format!("Toggle {}", i)),
+                        folder_id: None,
+                        custom_tags: Vec::new(),
+                        source_url: None,
+                        thumbnail_path: None,
+                    });
+                    tokio::task::yield_now().await;
+                }
+            }));
+        }
+
+        tokio::time::timeout(Duration::from_secs(30), async {
+            for task in tasks {
+                task.await.unwrap();
+            }
+        })
+        .await
+        .expect("state lock deadlocked");
+
+        // Every mod was toggled an even number of times, so all end up enabled again
+        let entries = list_mod_entries(dir.path(), &state.read().await.mod_metadata).unwrap();
+        assert_eq!(entries.len(), 8);
+        assert!(entries.iter().all(|m| m.enabled));
+    }
+
+    #[test]
+    fn strip_priority_markers_removes_stacked_suffixes() {
+        assert_eq!(strip_priority_markers("!CoolSkin_9999999_P"), "CoolSkin");
+        assert_eq!(strip_priority_markers("CoolSkin_999999999_P_9999999_P"), "CoolSkin");
+        assert_eq!(strip_priority_markers("CoolSkin_v2_P"), "CoolSkin_v2");
+        assert_eq!(strip_priority_markers("CoolSkin_1080"), "CoolSkin_1080");
+    }
+
+    #[test]
+    fn load_order_applies_highest_priority_last() {
+        let mods = vec![
+            (PathBuf::from("/mods/!Top_9999999_P.pak"), vec!["a.uasset".to_string()]),
+            (PathBuf::from("/mods/Low_999999999_P.pak"), vec!["a.uasset".to_string(), "b.uasset".to_string()]),
+            (PathBuf::from("/mods/MidA_9999999_P.pak"), vec!["b.uasset".to_string()]),
+            (PathBuf::from("/mods/MidB_9999999_P.pak"), vec!["b.uasset".to_string()]),
+        ];
+        let order = effective_load_order(mods);
+        let names: Vec<&str> = order.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Low_999999999_P", "MidA_9999999_P", "MidB_9999999_P", "!Top_9999999_P"]);
+        assert_eq!(order.iter().map(|e| e.priority).collect::<Vec<_>>(), [3, 1, 1, 0]);
+
+        let low = &order[0].conflicts;
+        let a = low.iter().find(|c| c.file_path == "a.uasset").unwrap();
+        assert!(a.winner.ends_with("!Top_9999999_P.pak") && !a.ambiguous);
+        let b = low.iter().find(|c| c.file_path == "b.uasset").unwrap();
+        assert!(b.winner.ends_with("MidB_9999999_P.pak") && b.ambiguous);
+    }
+
+    #[test]
+    fn base_paks_load_before_patch_paks() {
+        let mods = vec![
+            (PathBuf::from("/mods/Patch_9999999_P.pak"), vec!["a.uasset".to_string()]),
+            (PathBuf::from("/mods/Base.pak"), vec!["a.uasset".to_string()]),
+        ];
+        let order = effective_load_order(mods);
+        let names: Vec<&str> = order.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Base", "Patch_9999999_P"]);
+        assert_eq!(order[0].priority, BASE_PAK_PRIORITY);
+        let a = &order[0].conflicts[0];
+        assert!(a.winner.ends_with("Patch_9999999_P.pak") && !a.ambiguous);
+    }
+
+    #[test]
+    fn thumbnail_prefers_base_color_texture() {
+        let files: Vec<String> = [
+            "Marvel/Content/Marvel/Characters/1011/1011001/Meshes/SK_1011_1011001.uasset",
+            "Marvel/Content/Marvel/Characters/1011/1011001/Textures/T_1011_Body_N.uasset",
+            "Marvel/Content/Marvel/Characters/1011/1011001/Textures/T_1011_Body_D.uasset",
+            "Marvel/Content/Marvel/Characters/1011/1011001/Textures/T_1011_Body_D.ubulk",
+        ].iter().map(|s| s.to_string()).collect();
+        assert!(pick_thumbnail_texture(&files).unwrap().ends_with("T_1011_Body_D.uasset"));
+        assert!(pick_thumbnail_texture(&files[1..2]).unwrap().ends_with("T_1011_Body_N.uasset"));
+        assert!(pick_thumbnail_texture(&files[..1]).is_none());
+    }
+
+    #[test]
+    fn mount_status_flags_mods_changed_since_launch() {
+        let entry = |name: &str, enabled: bool| ModEntry {
+            path: PathBuf::from(format!("/mods/{}.{}", name, if enabled { "pak" } else { "bak_repak" })),
+            enabled,
+            custom_name: None,
+            folder_id: None,
+            custom_tags: Vec::new(),
+            file_size: 0,
+            priority: 0,
+            character_name: None,
+            skin_name: None,
+            source_url: None,
+            mod_type: None,
+            file_count: None,
+            obfuscated: false,
+            thumbnail_path: None,
+        };
+        let entries = vec![entry("Kept_P", true), entry("Disabled_P", false), entry("New_P", true)];
+        let mounted = vec![
+            PathBuf::from("/mods/Kept_P.pak"),
+            PathBuf::from("/mods/Disabled_P.pak"),
+            PathBuf::from("/mods/Deleted_P.pak"),
+        ];
+
+        let status = compute_mount_status(&entries, Some(&mounted));
+        let pending: Vec<_> = status.iter()
+            .filter(|s| s.pending_restart)
+            .map(|s| s.path.file_stem().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(pending, ["Disabled_P", "New_P", "Deleted_P"]);
+        assert!(status.iter().find(|s| s.path.ends_with("Disabled_P.bak_repak")).unwrap().mounted);
+
+        assert!(compute_mount_status(&entries, None).iter().all(|s| !s.pending_restart && s.mounted == s.enabled));
+    }
+
+    #[test]
+    fn toggling_stub_pak_mod_also_toggles_iostore_companions() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("IoStoreMod_9999999_P.pak");
+        std::fs::write(&pak, b"stub pak").unwrap();
+        std::fs::write(pak.with_extension("utoc"), b"toc").unwrap();
+        std::fs::write(pak.with_extension("ucas"), b"cas").unwrap();
+
+        assert!(!toggle_mod_file(&pak).unwrap());
+        let disabled = pak.with_extension("bak_repak");
+        assert!(disabled.exists());
+        for ext in ["utoc", "ucas"] {
+            assert!(!pak.with_extension(ext).exists(), ".{} still mountable", ext);
+            assert!(pak.with_extension(format!("{}_disabled", ext)).exists());
+            assert_eq!(iostore_companion_path(&pak, ext), pak.with_extension(format!("{}_disabled", ext)));
+        }
+
+        assert!(toggle_mod_file(&disabled).unwrap());
+        assert!(pak.exists());
+        for ext in ["utoc", "ucas"] {
+            assert!(pak.with_extension(ext).exists());
+            assert!(!pak.with_extension(format!("{}_disabled", ext)).exists());
+        }
+    }
+
+    #[test]
+    fn save_state_keeps_backup_and_load_falls_back_to_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = AppState { usmap_path: "first.usmap".to_string(), ..Default::default() };
+        let second = AppState { usmap_path: "second.usmap".to_string(), ..Default::default() };
+
+        save_state_to(dir.path(), &first).unwrap();
+        save_state_to(dir.path(), &second).unwrap();
+        assert_eq!(load_state_from(dir.path()).usmap_path, "second.usmap");
+
+        // Simulate a config truncated by a crash from an older version
+        std::fs::write(dir.path().join(STATE_FILE), b"{\"game_path\": \"C:/Ga").unwrap();
+        assert_eq!(load_state_from(dir.path()).usmap_path, "first.usmap");
+
+        // No stray temp files are left behind
+        let files = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(files, 2);
+    }
+
+    #[test]
+    fn override_keys_match_across_pak_and_iostore_paths() {
+        let legacy = asset_override_key("Marvel/Content/Marvel/Characters/1011/Body.uasset");
+        let iostore = asset_override_key("/Game/Marvel/Characters/1011/Body.uasset");
+        assert!(legacy.is_some());
+        assert_eq!(legacy, iostore);
+        assert_eq!(asset_override_key("Marvel/Content/Marvel/Characters/1011/Body.uexp"), None);
+        assert!(has_patch_suffix("Mod_9999999_P"));
+        assert!(has_patch_suffix("mod_p"));
+        assert!(!has_patch_suffix("Mod_9999999"));
+    }
+
+    #[test]
+    fn game_root_is_found_at_any_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("Games").join("MarvelRivals");
+        let mods = root.join("MarvelGame/Marvel/Content/Paks/~mods");
+        std::fs::create_dir_all(&mods).unwrap();
+        assert_eq!(find_game_root(&mods), None);
+
+        std::fs::write(root.join("MarvelRivals_Launcher.exe"), b"").unwrap();
+        assert_eq!(find_game_root(&mods), Some(root.clone()));
+
+        // Shallower layouts work too
+        let shallow_mods = dir.path().join("Epic/Paks/~mods");
+        std::fs::create_dir_all(&shallow_mods).unwrap();
+        std::fs::write(dir.path().join("Epic/launch_record"), b"6").unwrap();
+        assert_eq!(find_game_root(&shallow_mods), Some(dir.path().join("Epic")));
+    }
+
+    #[test]
+    fn base_companions_match_through_their_package() {
+        let mut base = std::collections::HashMap::new();
+        base.insert(content_relative_path("/Game/Marvel/Characters/1011/Body.uasset"), BaseGameFile::IoStore);
+        assert!(is_base_package_companion(&content_relative_path("Marvel/Content/Marvel/Characters/1011/Body.uexp"), &base));
+        assert!(!is_base_package_companion(&content_relative_path("Marvel/Content/Marvel/Characters/1011/New.uexp"), &base));
+        assert!(!is_base_package_companion(&content_relative_path("Marvel/Content/Marvel/Characters/1011/Body.uasset"), &base));
+    }
+
+    #[test]
+    fn auto_strip_mipmaps_covers_texture_mods_either_way_they_are_found() {
+        assert!(auto_strip_mipmaps_applies("Hulk - Texture", true, false));
+        assert!(auto_strip_mipmaps_applies("Hulk - Skin", true, true));
+        assert!(!auto_strip_mipmaps_applies("Hulk - Skin", true, false));
+        assert!(!auto_strip_mipmaps_applies("Hulk - Texture", false, false));
+    }
+
+    #[test]
+    fn impact_flags_4k_mipmapped_textures_and_high_poly_meshes() {
+        let texture = |width, height, mips: &str| uasset_toolkit::TextureInfo {
+            mip_gen_settings: Some(mips.to_string()),
+            width: Some(width),
+            height: Some(height),
+            format: Some("PF_DXT5".to_string()),
+        };
+        let textures = vec![
+            ("T_Body_D".to_string(), texture(4096, 4096, "FromTextureGroup")),
+            ("T_Icon".to_string(), texture(256, 256, "NoMipmaps")),
+        ];
+        let meshes = vec![("SK_Body".to_string(), uasset_toolkit::MeshInfo {
+            material_count: Some(3),
+            vertex_count: Some(120_000),
+            triangle_count: Some(250_000),
+            is_skeletal_mesh: Some(true),
+        })];
+
+        let report = ImpactReport::from_assets(&textures, &meshes);
+        assert_eq!(report.largest_texture, Some((4096, 4096)));
+        assert_eq!(report.max_triangles, Some(250_000));
+        assert!(report.flags.contains(&"Contains 1 4K texture(s) with mipmaps".to_string()));
+        assert!(report.flags.iter().any(|f| f.starts_with("High-poly mesh: SK_Body")));
+        assert_eq!(report.level, "medium");
+
+        let light = ImpactReport::from_assets(&textures[1..], &[]);
+        assert!(light.flags.is_empty());
+        assert_eq!(light.level, "low");
+    }
+
+    #[test]
+    fn asset_index_maps_paths_back_to_owning_mods() {
+        let mut index = AssetIndex::default();
+        let indexed = |files: &[&str]| IndexedMod {
+            mtime: std::time::SystemTime::UNIX_EPOCH,
+            files: files.iter().map(|f| f.to_string()).collect(),
+        };
+        index.mods.insert(PathBuf::from("MenuBg.pak"), indexed(&["/Game/Marvel/UI/Textures/T_MainMenu_Bg.uasset"]));
+        index.mods.insert(PathBuf::from("Hulk.pak"), indexed(&[
+            "/Game/Marvel/Characters/1011/Meshes/SK_1011.uasset",
+            "/Game/Marvel/UI/Textures/T_MainMenu_Bg.uasset",
+        ]));
+        index.rebuild_paths();
+
+        assert_eq!(index.paths["/Game/Marvel/UI/Textures/T_MainMenu_Bg.uasset"].len(), 2);
+        assert_eq!(index.mods_touching("ui\\textures\\t_mainmenu").len(), 2);
+        let meshes = index.mods_touching("SK_1011");
+        assert_eq!(meshes.len(), 1);
+        assert!(meshes.contains(Path::new("Hulk.pak")));
+        assert!(index.mods_touching("Audio").is_empty());
+    }
+
+    #[test]
+    fn bisection_narrows_down_to_the_crashing_mod() {
+        let mods: Vec<PathBuf> = (0..5).map(|i| PathBuf::from(format!("Mod{}.pak", i))).collect();
+        let culprit = &mods[3];
+
+        let mut session = BisectionSession::new("1".to_string(), mods.clone());
+        assert_eq!(session.remaining_steps(), 3);
+        while session.culprit.is_none() {
+            assert!(session.step < mods.len(), "bisection did not converge");
+            let crashed = session.testing.contains(culprit);
+            session.record(crashed);
+        }
+        assert_eq!(session.culprit.as_ref(), Some(culprit));
+        assert!(session.step <= 3);
+        assert_eq!(session.remaining_steps(), 0);
+    }
+
+    #[test]
+    fn renaming_keeps_base_paks_suffix_less() {
+        assert_eq!(renamed_mod_stem("Hulk_99999999_P", "Green"), "Green_99999999_P");
+        assert_eq!(renamed_mod_stem("Hulk_P", "Green"), "Green_9999999_P");
+        assert_eq!(renamed_mod_stem("Hulk", "Green"), "Green");
+        assert_eq!(renamed_mod_stem("Hulk", "Green_9999999_P"), "Green_9999999_P");
+    }
+
+    #[test]
+    fn renaming_a_disabled_iostore_mod_moves_its_disabled_companions() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["Hulk_9999999_P.bak_repak", "Hulk_9999999_P.utoc_disabled", "Hulk_9999999_P.ucas_disabled"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        let renamed = rename_mod_files(&dir.path().join("Hulk_9999999_P.bak_repak"), "Green").unwrap();
+        assert_eq!(renamed, dir.path().join("Green_9999999_P.bak_repak"));
+        for name in ["Green_9999999_P.bak_repak", "Green_9999999_P.utoc_disabled", "Green_9999999_P.ucas_disabled"] {
+            assert!(dir.path().join(name).exists(), "{} missing", name);
+        }
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn drifted_companions_are_matched_to_the_pak_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("Hulk_Skin_9999999_P.pak");
+        for name in ["Hulk_Skin_9999999_P.pak", "Hulk_Skin.utoc", "Hulk_Skin.ucas", "Other.pak", "Other.utoc"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        let mut renames = find_drifted_companions(&pak).unwrap();
+        renames.sort();
+        assert_eq!(renames, vec![
+            (dir.path().join("Hulk_Skin.ucas"), dir.path().join("Hulk_Skin_9999999_P.ucas")),
+            (dir.path().join("Hulk_Skin.utoc"), dir.path().join("Hulk_Skin_9999999_P.utoc")),
+        ]);
+
+        // Disabled paks get disabled companions, and a utoc owned by its own pak is never taken
+        let disabled = dir.path().join("Other_9999999_P.bak_repak");
+        std::fs::write(&disabled, b"").unwrap();
+        assert!(find_drifted_companions(&disabled).unwrap().is_empty());
+        std::fs::remove_file(dir.path().join("Other.pak")).unwrap();
+        assert_eq!(
+            find_drifted_companions(&disabled).unwrap(),
+            vec![(dir.path().join("Other.utoc"), dir.path().join("Other_9999999_P.utoc_disabled"))]
+        );
+
+        // Same stem, wrong enabled state
+        let toggled = dir.path().join("Thor_9999999_P.pak");
+        for name in ["Thor_9999999_P.pak", "Thor_9999999_P.utoc_disabled", "Thor_9999999_P.ucas"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        assert_eq!(
+            find_drifted_companions(&toggled).unwrap(),
+            vec![(dir.path().join("Thor_9999999_P.utoc_disabled"), dir.path().join("Thor_9999999_P.utoc"))]
+        );
+    }
+
+    #[test]
+    fn changing_the_mount_point_keeps_encryption_and_entry_compression() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak_path = dir.path().join("Plain_9999999_P.pak");
+        let mut writer = repak::PakBuilder::new().writer(
+            std::io::BufWriter::new(File::create(&pak_path).unwrap()),
+            repak::Version::V11,
+            "../../../".to_string(),
+            None,
+        );
+        writer.write_file("Marvel/Content/Test.uasset", false, b"stored").unwrap();
+        writer.write_index().unwrap();
+
+        tauri::async_runtime::block_on(set_pak_mount_point(
+            pak_path.to_string_lossy().to_string(),
+            "../../../Marvel/".to_string(),
+        )).unwrap();
+
+        let pak = repak::PakBuilder::new()
+            .reader(&mut std::io::BufReader::new(File::open(&pak_path).unwrap()))
+            .unwrap();
+        assert_eq!(pak.mount_point(), "../../../Marvel/");
+        assert_eq!(install_mod::pak_encryption_mode(&pak), "none");
+        let entry = pak.get_file_entry("Marvel/Content/Test.uasset").unwrap();
+        assert_eq!(pak.entry_compression(&entry), None);
+        // Only the rewritten pak is left, no temp file
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn install_space_covers_conversion_overhead() {
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(required_install_space(0), INSTALL_SPACE_HEADROOM);
+        assert!(required_install_space(2 * gib) >= 3 * gib + INSTALL_SPACE_HEADROOM);
+        // Whatever drive the temp dir is on, it is found and can't be fuller than it is big
+        let dir = tempfile::tempdir().unwrap();
+        if let Some(space) = disk_space_for(dir.path()) {
+            assert!(space.free_bytes <= space.total_bytes);
+        }
+
+        // Archives count what they unpack to, not their compressed size
+        let zip_path = dir.path().join("Mod.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        zip.start_file("Mod.pak", zip::write::SimpleFileOptions::default()).unwrap();
+        std::io::Write::write_all(&mut zip, &vec![0u8; 1024 * 1024]).unwrap();
+        zip.finish().unwrap();
+        assert!(std::fs::metadata(&zip_path).unwrap().len() < 1024 * 1024);
+        assert_eq!(source_content_size(&zip_path), 1024 * 1024);
+    }
+
+    #[test]
+    fn install_log_buffer_keeps_the_latest_lines() {
+        let buffer = InstallLogBuffer::default();
+        for i in 0..INSTALL_LOG_BUFFER_LINES + 10 {
+            buffer.push(format!("line {}", i));
+        }
+        let lines = buffer.snapshot();
+        assert_eq!(lines.len(), INSTALL_LOG_BUFFER_LINES);
+        assert!(lines[0].ends_with("line 10"));
+        assert!(lines.last().unwrap().ends_with(&format!("line {}", INSTALL_LOG_BUFFER_LINES + 9)));
+    }
+
+    #[test]
+    fn shadowed_mods_lose_assets_to_higher_priority() {
+        let mods = vec![
+            (PathBuf::from("Skin_9999999_P.pak"), vec![
+                "Marvel/Content/Marvel/Characters/1011/SK_1011.uasset".to_string(),
+                "Marvel/Content/Marvel/Characters/1011/SK_1011.uexp".to_string(),
+            ]),
+            (PathBuf::from("Partial_9999999_P.pak"), vec![
+                "Marvel/Content/Marvel/Characters/1011/T_1011_D.uasset".to_string(),
+                "Marvel/Content/Marvel/UI/T_Icon.uasset".to_string(),
+            ]),
+            // IoStore paths, priority 0 ("!"), wins everything it provides
+            (PathBuf::from("!Override_9999999_P.pak"), vec![
+                "/Game/Marvel/Characters/1011/SK_1011.uasset".to_string(),
+                "/Game/Marvel/Characters/1011/T_1011_D.uasset".to_string(),
+            ]),
+        ];
+
+        let shadowed = find_shadowed(mods);
+        assert_eq!(shadowed.len(), 2);
+        assert_eq!(shadowed[0].path, "Skin_9999999_P.pak");
+        assert!(shadowed[0].fully_shadowed);
+        assert_eq!(shadowed[0].total_files, 1);
+        assert_eq!(shadowed[0].shadowed_by, vec!["!Override_9999999_P.pak".to_string()]);
+        assert_eq!(shadowed[1].path, "Partial_9999999_P.pak");
+        assert!(!shadowed[1].fully_shadowed);
+        assert_eq!((shadowed[1].shadowed_files, shadowed[1].total_files), (1, 2));
+    }
+
+    #[test]
+    fn repair_prunes_missing_mods_folders_and_duplicate_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let mods_dir = dir.path().join("~mods");
+        std::fs::create_dir_all(mods_dir.join("Skins")).unwrap();
+        std::fs::write(mods_dir.join("Kept_9999999_P.bak_repak"), b"").unwrap();
+        let metadata = |name: &str| ModMetadata {
+            path: mods_dir.join(name),
+            custom_name: None,
+            folder_id: None,
+            custom_tags: Vec::new(),
+            source_url: None,
+            thumbnail_path: None,
+        };
+        let folder = |id: &str, is_root: bool| ModFolder {
+            id: id.to_string(),
+            name: id.to_string(),
+            enabled: true,
+            expanded: true,
+            color: None,
+            depth: 1,
+            parent_id: None,
+            is_root,
+            mod_count: 0,
+        };
+        let mut state = AppState {
+            game_path: mods_dir.clone(),
+            // Saved as enabled, now disabled on disk: still a live mod
+            mod_metadata: vec![metadata("Kept_9999999_P.pak"), metadata("Deleted_9999999_P.pak")],
+            folders: vec![folder("~mods", true), folder("Skins", false), folder("Gone", false)],
+            custom_tag_catalog: vec!["Skin".into(), "UI".into(), "Skin".into(), " ".into()],
+            ..Default::default()
+        };
+
+        let summary = repair_app_state(&mut state);
+        assert_eq!((summary.metadata_pruned, summary.folders_pruned, summary.tags_deduped), (1, 1, 2));
+        assert_eq!(state.mod_metadata.len(), 1);
+        assert_eq!(state.folders.iter().map(|f| f.id.as_str()).collect::<Vec<_>>(), vec!["~mods", "Skins"]);
+        assert_eq!(state.custom_tag_catalog, vec!["Skin".to_string(), "UI".to_string()]);
+
+        // A missing mods directory must not wipe the library
+        state.game_path = dir.path().join("unplugged");
+        state.mod_metadata.push(metadata("Deleted_9999999_P.pak"));
+        assert_eq!(repair_app_state(&mut state).metadata_pruned, 0);
+    }
+
+    #[test]
+    fn split_keeps_a_skins_meshes_textures_and_materials_together() {
+        let single_skin: Vec<String> = [
+            "Marvel/Content/Marvel/Characters/1011/1011001/Meshes/SK_1011_1011001.uasset",
+            "Marvel/Content/Marvel/Characters/1011/1011001/Textures/T_1011001_D.uasset",
+            "Marvel/Content/Marvel/Characters/1011/1011001/Materials/MI_1011001_Body.uasset",
+        ].iter().map(|s| s.to_string()).collect();
+        assert!(split_groups(&single_skin).is_err());
+
+        let mut bundle = single_skin.clone();
+        bundle.push("Marvel/Content/Marvel/Characters/1014/1014500/Meshes/SK_1014_1014500.uasset".to_string());
+        let groups = split_groups(&bundle).unwrap();
+        assert_eq!(groups.keys().collect::<Vec<_>>(), ["1011/1011001", "1014/1014500"]);
+        assert_eq!(groups["1011/1011001"].len(), 3);
+
+        // Without character content, entries still split by top-level folder
+        let ui: Vec<String> = [
+            "Marvel/Content/Marvel/UI/Textures/Loading/T_Loading.uasset",
+            "Marvel/Content/Marvel/UI/Fonts/F_Main.uasset",
+        ].iter().map(|s| s.to_string()).collect();
+        assert_eq!(split_groups(&ui).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn extracted_entry_prefers_the_matching_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        for rel in ["Marvel/Content/Marvel/UI/T_Icon.uasset", "Marvel/Content/Marvel/Characters/1011/T_Icon.uasset"] {
+            let path = dir.path().join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, b"x").unwrap();
+        }
+        let found = find_extracted_entry(dir.path(), "/Game/Marvel/Characters/1011/T_Icon.uasset").unwrap();
+        assert!(found.ends_with("Characters/1011/T_Icon.uasset"));
+        assert!(find_extracted_entry(dir.path(), "Marvel/Content/Missing.uasset").is_none());
+    }
+
+    #[test]
+    fn migrate_state_upgrades_unversioned_config() {
+        let mut value = serde_json::json!({
+            "game_path": "C:/Game/Paks/~mods",
+            "folders": [
+                { "id": "Skins", "name": "Skins", "enabled": true, "expanded": false, "color": null },
+                { "id": "Skins\\Hulk", "name": "Hulk", "enabled": true, "expanded": true, "color": [1, 2, 3] }
+            ],
+            "mod_metadata": [],
+            "usmap_path": ""
+        });
+        migrate_state(&mut value);
+        let state: AppState = serde_json::from_value(value).unwrap();
+
+        assert_eq!(state.schema_version, STATE_SCHEMA_VERSION);
+        assert!(state.custom_tag_catalog.is_empty());
+        assert_eq!(state.folders[0].depth, 1);
+        assert_eq!(state.folders[0].parent_id.as_deref(), Some("~mods"));
+        assert_eq!(state.folders[1].id, "Skins/Hulk");
+        assert_eq!(state.folders[1].depth, 2);
+        assert_eq!(state.folders[1].parent_id.as_deref(), Some("Skins"));
+    }
+
+    #[test]
+    fn metadata_matches_the_mod_under_any_enabled_state() {
+        let metadata = ModMetadata {
+            path: PathBuf::from("/game/~mods/Hulk_9999999_P.pak"),
+            custom_name: None,
+            folder_id: None,
+            custom_tags: Vec::new(),
+            source_url: Some("https://example.com/hulk".to_string()),
+            thumbnail_path: None,
+        };
+        for ext in ["pak", "bak_repak", "pak_disabled"] {
+            assert!(metadata.matches(&PathBuf::from(format!("/game/~mods/Hulk_9999999_P.{}", ext))), "{}", ext);
+        }
+        assert!(!metadata.matches(Path::new("/game/~mods/Thor_9999999_P.pak")));
+    }
+}
@@ -11,6 +11,9 @@ pub struct ShareInfo {
     pub addresses: Vec<String>,
     pub encryption_key: String,
     pub share_code: String,
+    /// Tags the sharer had on the shared mods, applied by receivers that auto-install
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl ShareInfo {
@@ -25,4 +28,10 @@ impl ShareInfo {
         let share_info: ShareInfo = serde_json::from_str(&json_str)?;
         Ok(share_info)
     }
+
+    /// Addresses as they should appear in logs and the UI. The real addresses stay in
+    /// `addresses` because receivers need them to connect.
+    pub fn display_addresses(&self) -> Vec<String> {
+        self.addresses.iter().map(|a| crate::ip_obfuscation::display_address(a)).collect()
+    }
 }
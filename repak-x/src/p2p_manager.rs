@@ -9,11 +9,11 @@ use crate::p2p_libp2p::ShareInfo;
 use crate::p2p_sharing::{ShareSession, TransferProgress, TransferStatus, P2PError, P2PResult};
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use log::{info, error, warn};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::Duration;
 use parking_lot::Mutex;
 use tauri::{Emitter, Window};
@@ -46,7 +46,7 @@ async fn try_upnp_port_mapping(local_ip: Ipv4Addr, local_port: u16) -> Option<(s
     // Get external IP from router
     let external_ip = match gateway.get_external_ip().await {
         Ok(ip) => {
-            info!("[P2P/UPnP] External IP from router: {}", ip);
+            info!("[P2P/UPnP] External IP from router: {}", crate::ip_obfuscation::display_address(&ip.to_string()));
             ip
         }
         Err(e) => {
@@ -55,6 +55,8 @@ async fn try_upnp_port_mapping(local_ip: Ipv4Addr, local_port: u16) -> Option<(s
         }
     };
 
+    let shown_ip = crate::ip_obfuscation::display_address(&external_ip.to_string());
+
     // Add port mapping (TCP, same external port, 2 hour lease)
     let internal_addr = SocketAddr::V4(SocketAddrV4::new(local_ip, local_port));
     match gateway.add_port(
@@ -65,7 +67,7 @@ async fn try_upnp_port_mapping(local_ip: Ipv4Addr, local_port: u16) -> Option<(s
         "Repak-X P2P Mod Sharing",
     ).await {
         Ok(()) => {
-            info!("[P2P/UPnP] Port mapping created: external {}:{} -> internal {}", external_ip, local_port, internal_addr);
+            info!("[P2P/UPnP] Port mapping created: external {}:{} -> internal {}", shown_ip, local_port, internal_addr);
             Some((external_ip, local_port))
         }
         Err(e) => {
@@ -78,7 +80,7 @@ async fn try_upnp_port_mapping(local_ip: Ipv4Addr, local_port: u16) -> Option<(s
                 "Repak-X P2P Mod Sharing",
             ).await {
                 Ok(ext_port) => {
-                    info!("[P2P/UPnP] Port mapping created (any port): external {}:{} -> internal {}", external_ip, ext_port, internal_addr);
+                    info!("[P2P/UPnP] Port mapping created (any port): external {}:{} -> internal {}", shown_ip, ext_port, internal_addr);
                     Some((external_ip, ext_port))
                 }
                 Err(e2) => {
@@ -186,6 +188,406 @@ async fn try_bore_tunnel(local_port: u16) -> Option<(String, u16, tokio::task::J
     Some(("bore.pub".to_string(), remote_port, handle))
 }
 
+// ============================================================================
+// CONNECTION RETRY
+// ============================================================================
+
+/// Attempts per address before moving on to the next one
+pub const MAX_CONNECT_ATTEMPTS: u32 = 3;
+
+/// Exponential backoff before retry `retry` (1-based): 1s, 2s, 4s... plus up to 500ms jitter
+fn retry_delay(retry: u32) -> Duration {
+    use rand::Rng;
+    let base = Duration::from_secs(1 << (retry.saturating_sub(1)).min(4));
+    base + Duration::from_millis(rand::thread_rng().gen_range(0..500))
+}
+
+/// Whether a failed attempt is worth retrying. Protocol, key and validation errors won't change
+/// on a retry, and a refused direct connection means the peer is offline or blocking us;
+/// relay addresses are the exception since the tunnel may still be warming up.
+fn is_transient_failure(err: &P2PError, addr: &str) -> bool {
+    match err {
+        P2PError::NetworkError(msg) | P2PError::ConnectionError(msg) => {
+            !msg.to_lowercase().contains("refused") || addr.contains("bore.pub")
+        }
+        _ => false,
+    }
+}
+
+/// Tell the UI where a connection attempt stands ("connecting", "retrying", "failed")
+fn emit_connection_state(window: &Window, code: &str, addr: &str, attempt: u32, state: &str) {
+    let _ = window.emit("p2p_connection_state", serde_json::json!({
+        "share_code": code,
+        "address": addr,
+        "attempt": attempt,
+        "max_attempts": MAX_CONNECT_ATTEMPTS,
+        "state": state,
+    }));
+}
+
+// ============================================================================
+// SHARE CODES
+// ============================================================================
+
+/// Share codes look like "RPX1-<base64url payload>"; the digit is the format version
+const SHARE_CODE_PREFIX: &str = "RPX";
+/// Bump when ShareCodePayload changes so older builds reject codes they can't read
+const SHARE_CODE_VERSION: u32 = 1;
+/// Trailing SHA256 bytes used to catch typos / truncated copies
+const SHARE_CODE_CHECKSUM_LEN: usize = 4;
+
+/// Everything a receiver needs to reach a single shared mod, in compact binary form
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShareCodePayload {
+    pub peer_id: String,
+    pub addresses: Vec<String>,
+    pub key: [u8; 32],
+    pub share_code: String,
+    /// Leading hex of the shared file's SHA256, identifies the mod
+    pub mod_hash: String,
+}
+
+pub fn encode_share_code(payload: &ShareCodePayload) -> P2PResult<String> {
+    use sha2::{Digest, Sha256};
+    let mut bytes = bincode::serialize(payload)
+        .map_err(|e| P2PError::ValidationError(format!("Failed to encode share code: {}", e)))?;
+    let checksum = Sha256::digest(&bytes);
+    bytes.extend_from_slice(&checksum[..SHARE_CODE_CHECKSUM_LEN]);
+    Ok(format!("{}{}-{}", SHARE_CODE_PREFIX, SHARE_CODE_VERSION, URL_SAFE_NO_PAD.encode(bytes)))
+}
+
+pub fn decode_share_code(code: &str) -> P2PResult<ShareCodePayload> {
+    use sha2::{Digest, Sha256};
+    let invalid = |msg: &str| P2PError::ValidationError(format!("Invalid share code: {}", msg));
+
+    let rest = code.trim().strip_prefix(SHARE_CODE_PREFIX).ok_or_else(|| invalid("missing prefix"))?;
+    let (version, data) = rest.split_once('-').ok_or_else(|| invalid("missing version"))?;
+    let version: u32 = version.parse().map_err(|_| invalid("bad version"))?;
+    if version != SHARE_CODE_VERSION {
+        return Err(P2PError::ValidationError(format!(
+            "Share code format v{} is not supported by this version of Repak-X (expected v{})",
+            version, SHARE_CODE_VERSION
+        )));
+    }
+
+    let bytes = URL_SAFE_NO_PAD.decode(data).map_err(|_| invalid("not valid base64"))?;
+    if bytes.len() <= SHARE_CODE_CHECKSUM_LEN {
+        return Err(invalid("too short"));
+    }
+    let (body, checksum) = bytes.split_at(bytes.len() - SHARE_CODE_CHECKSUM_LEN);
+    if Sha256::digest(body)[..SHARE_CODE_CHECKSUM_LEN] != *checksum {
+        return Err(invalid("checksum mismatch (incomplete or mistyped code?)"));
+    }
+    let payload: ShareCodePayload = bincode::deserialize(body).map_err(|_| invalid("corrupt payload"))?;
+    if payload.addresses.is_empty() {
+        return Err(invalid("no addresses"));
+    }
+    Ok(payload)
+}
+
+// ============================================================================
+// TRANSFER QUEUE
+// ============================================================================
+
+/// Concurrent downloads allowed when no limit is configured
+pub const DEFAULT_MAX_CONCURRENT_TRANSFERS: usize = 2;
+
+/// A receive request waiting for a free transfer slot
+pub struct PendingTransfer {
+    share_info: ShareInfo,
+    key: [u8; 32],
+    out: PathBuf,
+    client_name: Option<String>,
+    /// Install the received files into `out` once verified instead of dropping them there raw
+    auto_install: bool,
+    window: Window,
+}
+
+/// One row of the transfer list shown in the UI
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TransferQueueEntry {
+    pub id: String,
+    /// "queued", "active", "completed", "failed" or "cancelled"
+    pub state: String,
+    pub progress: Option<TransferProgress>,
+}
+
+fn is_finished(status: &TransferStatus) -> bool {
+    matches!(status, TransferStatus::Completed | TransferStatus::Failed(_) | TransferStatus::Cancelled)
+}
+
+/// Shared handles download workers need to report progress and start queued transfers
+#[derive(Clone)]
+struct TransferSlots {
+    downloads: Arc<Mutex<HashMap<String, ActiveDownload>>>,
+    queue: Arc<Mutex<VecDeque<PendingTransfer>>>,
+    max_concurrent: Arc<AtomicUsize>,
+}
+
+impl TransferSlots {
+    fn max_concurrent(&self) -> usize {
+        self.max_concurrent.load(Ordering::SeqCst).max(1)
+    }
+
+    fn active_count(&self) -> usize {
+        self.downloads.lock().values().filter(|d| !is_finished(&d.progress.status)).count()
+    }
+
+    /// Start queued transfers in FIFO order while slots are free. The queue stays locked while
+    /// slots are checked and filled, so concurrent pumps can't start more than the limit
+    fn pump(&self, rt: &tokio::runtime::Handle) {
+        let mut queue = self.queue.lock();
+        while self.active_count() < self.max_concurrent() {
+            let Some(job) = queue.pop_front() else {
+                break;
+            };
+            info!("[P2P] Starting queued transfer {}", job.share_info.share_code);
+            let _ = job.window.emit("p2p_transfer_queue_changed", ());
+            launch_download(self.clone(), job, rt.clone());
+        }
+    }
+}
+
+/// Start a download now: insert its progress entry, spawn the worker thread that tries each
+/// address, and a progress sync task. When the worker ends it starts the next queued transfer.
+fn launch_download(slots: TransferSlots, job: PendingTransfer, rt: tokio::runtime::Handle) {
+    let PendingTransfer { share_info, key, out, client_name, auto_install, window } = job;
+    let code = share_info.share_code.clone();
+    let addresses = share_info.addresses.clone();
+    info!(
+        "[P2P] Will try {} address(es): {:?}",
+        addresses.len(),
+        share_info.display_addresses()
+    );
+
+    // Initial stop flag (replaced per-client once a working address is found)
+    let initial_stop = Arc::new(AtomicBool::new(false));
+
+    // Insert initial progress
+    slots.downloads.lock().insert(
+        code.clone(),
+        ActiveDownload {
+            share_info: share_info.clone(),
+            progress: TransferProgress {
+                current_file: "Connecting...".into(),
+                files_completed: 0,
+                total_files: 1,
+                bytes_transferred: 0,
+                total_bytes: 0,
+                status: TransferStatus::Connecting,
+            },
+            output_dir: out.clone(),
+            stop_flag: initial_stop.clone(),
+        },
+    );
+
+    let dl = slots.downloads.clone();
+    let thread_slots = slots.clone();
+    let thread_rt = rt.clone();
+    let c = code.clone();
+    let sender_tags = share_info.tags.clone();
+
+    // Shared slot for the active progress handle so the sync task can
+    // pick it up once the working client is determined.
+    let active_progress: Arc<std::sync::Mutex<Option<Arc<std::sync::Mutex<TransferProgress>>>>> =
+        Arc::new(std::sync::Mutex::new(None));
+    let active_progress_for_sync = active_progress.clone();
+
+    // Spawn download thread - tries each address in order, then hands its slot to the next queued transfer
+    std::thread::spawn(move || {
+        let run = move || {
+            info!(
+                "[P2P] Download thread started - trying {} address(es)",
+                addresses.len()
+            );
+            let mut last_error = String::from("No addresses to try");
+
+            // Auto-installed transfers are staged outside ~mods so only verified files get installed there
+            let mut staging = if auto_install {
+                match tempfile::tempdir() {
+                    Ok(dir) => Some(dir),
+                    Err(e) => {
+                        error!("[P2P] Failed to create staging folder for auto-install: {}", e);
+                        if let Some(d) = dl.lock().get_mut(&c) {
+                            d.progress.status =
+                                TransferStatus::Failed(format!("Failed to create staging folder: {}", e));
+                        }
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            let target = staging
+                .as_ref()
+                .map(|dir| dir.path().to_path_buf())
+                .unwrap_or_else(|| out.clone());
+
+            for (i, addr) in addresses.iter().enumerate() {
+                let shown = crate::ip_obfuscation::display_address(addr);
+                // Check cancellation
+                if initial_stop.load(Ordering::SeqCst) {
+                    info!("[P2P] Download cancelled by user");
+                    if let Some(d) = dl.lock().get_mut(&c) {
+                        d.progress.status = TransferStatus::Cancelled;
+                    }
+                    return;
+                }
+
+                info!(
+                    "[P2P] Trying address {}/{}: {}",
+                    i + 1,
+                    addresses.len(),
+                    shown
+                );
+                if let Some(d) = dl.lock().get_mut(&c) {
+                    d.progress.current_file =
+                        format!("Trying {} ({}/{})...", shown, i + 1, addresses.len());
+                    d.progress.status = TransferStatus::Connecting;
+                }
+
+                for attempt in 1..=MAX_CONNECT_ATTEMPTS {
+                    if attempt > 1 {
+                        let delay = retry_delay(attempt - 1);
+                        info!("[P2P] Retrying {} in {:?} (attempt {}/{})", shown, delay, attempt, MAX_CONNECT_ATTEMPTS);
+                        emit_connection_state(&window, &c, &shown, attempt, "retrying");
+                        crate::toast_events::emit_toast(&window, crate::toast_events::ToastPayload::info(
+                            "Connecting",
+                            format!("Connecting (attempt {}/{})...", attempt, MAX_CONNECT_ATTEMPTS),
+                        ));
+                        if let Some(d) = dl.lock().get_mut(&c) {
+                            d.progress.current_file =
+                                format!("Retrying {} ({}/{})...", shown, attempt, MAX_CONNECT_ATTEMPTS);
+                        }
+                        std::thread::sleep(delay);
+                        if initial_stop.load(Ordering::SeqCst) {
+                            info!("[P2P] Download cancelled by user");
+                            if let Some(d) = dl.lock().get_mut(&c) {
+                                d.progress.status = TransferStatus::Cancelled;
+                            }
+                            return;
+                        }
+                    } else {
+                        emit_connection_state(&window, &c, &shown, attempt, "connecting");
+                    }
+
+                    // Build a fresh client for this address (no probe — go directly)
+                    let client = crate::p2p_sharing::P2PClient::new(key, addr.clone());
+
+                    // Update stop flag so cancellation works on this client
+                    let client_stop = client.get_stop_flag();
+                    if let Some(d) = dl.lock().get_mut(&c) {
+                        d.stop_flag = client_stop.clone();
+                    }
+
+                    // Publish progress handle for the sync task
+                    if let Ok(mut guard) = active_progress.lock() {
+                        *guard = Some(client.progress_handle());
+                    }
+
+                    match client.download_pack(&target, client_name.clone()) {
+                        Ok(pack) => {
+                            info!(
+                                "[P2P] Download complete! {} mods received via {}",
+                                pack.mods.len(),
+                                addr
+                            );
+                            if let Some(d) = dl.lock().get_mut(&c) {
+                                d.progress.status = TransferStatus::Completed;
+                                d.progress.files_completed = d.progress.total_files;
+                            }
+                            // download_pack only returns Ok once every file's SHA256 matched the sender's
+                            if let Some(dir) = staging.take() {
+                                crate::install_received_mods(dir, &pack, &sender_tags, &out, &window);
+                            }
+                            let _ = window.emit("mods_dir_changed", ());
+                            return; // success
+                        }
+                        Err(crate::p2p_sharing::P2PError::Cancelled) => {
+                            info!("[P2P] Download cancelled by user");
+                            if let Some(d) = dl.lock().get_mut(&c) {
+                                d.progress.status = TransferStatus::Cancelled;
+                            }
+                            return;
+                        }
+                        Err(e) => {
+                            warn!("[P2P] Transfer via {} failed (attempt {}/{}): {}", shown, attempt, MAX_CONNECT_ATTEMPTS, e);
+                            last_error = format!("{}: {}", shown, e);
+                            if !is_transient_failure(&e, addr) {
+                                info!("[P2P] Not retrying {}: failure is permanent", shown);
+                                break; // try next address
+                            }
+                        }
+                    }
+                }
+            }
+
+            // All addresses exhausted
+            error!("[P2P] All addresses failed. Last error: {}", last_error);
+            emit_connection_state(&window, &c, "", MAX_CONNECT_ATTEMPTS, "failed");
+            crate::toast_events::emit_toast(&window, crate::toast_events::ToastPayload::error(
+                "Connection Failed",
+                format!("Could not connect to host: {}", last_error),
+            ));
+            if let Some(d) = dl.lock().get_mut(&c) {
+                d.progress.status =
+                    TransferStatus::Failed(format!("Could not connect to host: {}", last_error));
+            }
+        };
+        run();
+        thread_slots.pump(&thread_rt);
+    });
+
+    // Spawn progress sync task
+    let dl2 = slots.downloads.clone();
+    let c2 = code.clone();
+    rt.spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+
+            // Try to read from the active client's progress handle
+            let handle = active_progress_for_sync
+                .lock()
+                .ok()
+                .and_then(|g| g.clone());
+            if let Some(h) = handle {
+                if let Ok(prog) = h.lock().map(|p| p.clone()) {
+                    let is_done = matches!(
+                        prog.status,
+                        TransferStatus::Completed
+                            | TransferStatus::Failed(_)
+                            | TransferStatus::Cancelled
+                    );
+                    if let Some(d) = dl2.lock().get_mut(&c2) {
+                        d.progress = prog;
+                    }
+                    if is_done {
+                        break;
+                    }
+                }
+            } else {
+                // No client yet - check if download already finished/failed
+                let done = dl2
+                    .lock()
+                    .get(&c2)
+                    .map(|d| {
+                        matches!(
+                            d.progress.status,
+                            TransferStatus::Completed
+                                | TransferStatus::Failed(_)
+                                | TransferStatus::Cancelled
+                        )
+                    })
+                    .unwrap_or(true);
+                if done {
+                    break;
+                }
+            }
+        }
+    });
+
+}
+
 // ============================================================================
 // MANAGER
 // ============================================================================
@@ -194,6 +596,8 @@ pub struct UnifiedP2PManager {
     instance_id: String,
     pub active_shares: Arc<Mutex<HashMap<String, ActiveShare>>>,
     pub active_downloads: Arc<Mutex<HashMap<String, ActiveDownload>>>,
+    queue: Arc<Mutex<VecDeque<PendingTransfer>>>,
+    max_concurrent: Arc<AtomicUsize>,
 }
 
 pub struct ActiveShare {
@@ -218,6 +622,8 @@ impl UnifiedP2PManager {
             instance_id: id,
             active_shares: Arc::new(Mutex::new(HashMap::new())),
             active_downloads: Arc::new(Mutex::new(HashMap::new())),
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+            max_concurrent: Arc::new(AtomicUsize::new(DEFAULT_MAX_CONCURRENT_TRANSFERS)),
         })
     }
 
@@ -227,6 +633,7 @@ impl UnifiedP2PManager {
         desc: String,
         paths: Vec<PathBuf>,
         creator: Option<String>,
+        tags: Vec<String>,
     ) -> P2PResult<ShareInfo> {
         info!("[P2P] Starting share: {} ({} files)", name, paths.len());
 
@@ -251,7 +658,10 @@ impl UnifiedP2PManager {
         // 1) Try UPnP automatic port forwarding
         match try_upnp_port_mapping(local_ip_parsed, local_port).await {
             Some((ext_ip, ext_port)) => {
-                info!("[P2P] UPnP success - public address: {}:{}", ext_ip, ext_port);
+                info!(
+                    "[P2P] UPnP success - public address: {}",
+                    crate::ip_obfuscation::display_address(&format!("{}:{}", ext_ip, ext_port))
+                );
                 addresses.push(format!("{}:{}", ext_ip, ext_port));
                 upnp_external_port = Some(ext_port);
             }
@@ -273,8 +683,6 @@ impl UnifiedP2PManager {
         // 3) Always include local address as LAN fallback
         addresses.push(format!("{}:{}", session.local_ip, local_port));
 
-        info!("[P2P] Share addresses: {:?}", addresses);
-
         // Build ShareInfo for frontend (base64 JSON format)
         let key_b64 = URL_SAFE_NO_PAD.encode(key_bytes);
         let share_info = ShareInfo {
@@ -282,7 +690,9 @@ impl UnifiedP2PManager {
             addresses,
             encryption_key: key_b64,
             share_code: session.share_code.clone(),
+            tags,
         };
+        info!("[P2P] Share addresses: {:?}", share_info.display_addresses());
 
         // Encode as base64 JSON connection string
         let conn = share_info
@@ -359,6 +769,7 @@ impl UnifiedP2PManager {
         conn: &str,
         out: PathBuf,
         client_name: Option<String>,
+        auto_install: bool,
         window: Window,
     ) -> P2PResult<()> {
         info!("[P2P] Starting receive to: {}", out.display());
@@ -385,191 +796,142 @@ impl UnifiedP2PManager {
         let mut key = [0u8; 32];
         key.copy_from_slice(&key_bytes);
 
-        let addresses = share_info.addresses.clone();
-        info!(
-            "[P2P] Will try {} address(es): {:?}",
-            addresses.len(),
-            addresses
-        );
-
-        // Initial stop flag (replaced per-client once a working address is found)
-        let initial_stop = Arc::new(AtomicBool::new(false));
-
-        // Insert initial progress
-        self.active_downloads.lock().insert(
-            code.clone(),
-            ActiveDownload {
-                share_info: share_info.clone(),
-                progress: TransferProgress {
-                    current_file: "Connecting...".into(),
-                    files_completed: 0,
-                    total_files: 1,
-                    bytes_transferred: 0,
-                    total_bytes: 0,
-                    status: TransferStatus::Connecting,
-                },
-                output_dir: out.clone(),
-                stop_flag: initial_stop.clone(),
-            },
-        );
-
-        let dl = self.active_downloads.clone();
-        let c = code.clone();
-
-        // Shared slot for the active progress handle so the sync task can
-        // pick it up once the working client is determined.
-        let active_progress: Arc<std::sync::Mutex<Option<Arc<std::sync::Mutex<TransferProgress>>>>> =
-            Arc::new(std::sync::Mutex::new(None));
-        let active_progress_for_sync = active_progress.clone();
-
-        // Spawn download thread - tries each address in order
-        std::thread::spawn(move || {
-            info!(
-                "[P2P] Download thread started - trying {} address(es)",
-                addresses.len()
-            );
-            let mut last_error = String::from("No addresses to try");
-
-            for (i, addr) in addresses.iter().enumerate() {
-                // Check cancellation
-                if initial_stop.load(Ordering::SeqCst) {
-                    info!("[P2P] Download cancelled by user");
-                    if let Some(d) = dl.lock().get_mut(&c) {
-                        d.progress.status = TransferStatus::Cancelled;
-                    }
-                    return;
-                }
-
-                info!(
-                    "[P2P] Trying address {}/{}: {}",
-                    i + 1,
-                    addresses.len(),
-                    addr
-                );
-                if let Some(d) = dl.lock().get_mut(&c) {
-                    d.progress.current_file =
-                        format!("Trying {} ({}/{})...", addr, i + 1, addresses.len());
-                    d.progress.status = TransferStatus::Connecting;
-                }
-
-                // Relay addresses get extra retries (bore tunnel may need warmup)
-                let max_attempts = if addr.contains("bore.pub") { 3 } else { 1 };
-
-                for attempt in 0..max_attempts {
-                    if attempt > 0 {
-                        info!("[P2P] Retrying {} (attempt {}/{})", addr, attempt + 1, max_attempts);
-                        std::thread::sleep(Duration::from_secs(2));
-                        if let Some(d) = dl.lock().get_mut(&c) {
-                            d.progress.current_file =
-                                format!("Retrying {} ({}/{})...", addr, attempt + 1, max_attempts);
-                        }
-                    }
+        // Always enqueue and let pump decide: checking for a free slot first would strand the
+        // job if the last running worker finished (and pumped) in between
+        let slots = self.slots();
+        slots.queue.lock().push_back(PendingTransfer { share_info, key, out, client_name, auto_install, window: window.clone() });
+        slots.pump(&tokio::runtime::Handle::current());
+        if slots.queue.lock().iter().any(|job| job.share_info.share_code == code) {
+            info!("[P2P] All {} transfer slot(s) busy, queued {}", slots.max_concurrent(), code);
+            let _ = window.emit("p2p_transfer_queue_changed", ());
+        }
+        Ok(())
+    }
 
-                    // Build a fresh client for this address (no probe — go directly)
-                    let client = crate::p2p_sharing::P2PClient::new(key, addr.clone());
+    fn slots(&self) -> TransferSlots {
+        TransferSlots {
+            downloads: self.active_downloads.clone(),
+            queue: self.queue.clone(),
+            max_concurrent: self.max_concurrent.clone(),
+        }
+    }
 
-                    // Update stop flag so cancellation works on this client
-                    let client_stop = client.get_stop_flag();
-                    if let Some(d) = dl.lock().get_mut(&c) {
-                        d.stop_flag = client_stop.clone();
-                    }
+    /// Limit how many downloads run at once (0 = default); queued transfers start as slots free
+    pub fn set_max_concurrent(&self, max: usize) {
+        let max = if max == 0 { DEFAULT_MAX_CONCURRENT_TRANSFERS } else { max };
+        info!("[P2P] Max concurrent transfers: {}", max);
+        self.max_concurrent.store(max, Ordering::SeqCst);
+        if let Ok(rt) = tokio::runtime::Handle::try_current() {
+            self.slots().pump(&rt);
+        }
+    }
 
-                    // Publish progress handle for the sync task
-                    if let Ok(mut guard) = active_progress.lock() {
-                        *guard = Some(client.progress_handle());
-                    }
+    /// Queued, active and finished transfers, queued ones in the order they will start
+    pub fn get_transfer_queue(&self) -> Vec<TransferQueueEntry> {
+        let mut entries: Vec<TransferQueueEntry> = self.queue.lock()
+            .iter()
+            .map(|job| TransferQueueEntry {
+                id: job.share_info.share_code.clone(),
+                state: "queued".to_string(),
+                progress: None,
+            })
+            .collect();
+        for (id, download) in self.active_downloads.lock().iter() {
+            let state = match download.progress.status {
+                TransferStatus::Completed => "completed",
+                TransferStatus::Failed(_) => "failed",
+                TransferStatus::Cancelled => "cancelled",
+                _ => "active",
+            };
+            entries.push(TransferQueueEntry {
+                id: id.clone(),
+                state: state.to_string(),
+                progress: Some(download.progress.clone()),
+            });
+        }
+        entries
+    }
 
-                    match client.download_pack(&out, client_name.clone()) {
-                        Ok(pack) => {
-                            info!(
-                                "[P2P] Download complete! {} mods received via {}",
-                                pack.mods.len(),
-                                addr
-                            );
-                            if let Some(d) = dl.lock().get_mut(&c) {
-                                d.progress.status = TransferStatus::Completed;
-                                d.progress.files_completed = d.progress.total_files;
-                            }
-                            let _ = window.emit("mods_dir_changed", ());
-                            return; // success
-                        }
-                        Err(crate::p2p_sharing::P2PError::Cancelled) => {
-                            info!("[P2P] Download cancelled by user");
-                            if let Some(d) = dl.lock().get_mut(&c) {
-                                d.progress.status = TransferStatus::Cancelled;
-                            }
-                            return;
-                        }
-                        Err(e) => {
-                            warn!("[P2P] Transfer via {} failed (attempt {}/{}): {}", addr, attempt + 1, max_attempts, e);
-                            last_error = format!("{}: {}", addr, e);
-                            // retry or try next address
-                        }
-                    }
-                }
+    /// Remove a queued transfer, or abort an active one
+    pub fn cancel_transfer(&self, id: &str) -> P2PResult<()> {
+        {
+            let mut queue = self.queue.lock();
+            if let Some(pos) = queue.iter().position(|job| job.share_info.share_code == id) {
+                info!("[P2P] Removed queued transfer {}", id);
+                queue.remove(pos);
+                return Ok(());
             }
-
-            // All addresses exhausted
-            error!("[P2P] All addresses failed. Last error: {}", last_error);
-            if let Some(d) = dl.lock().get_mut(&c) {
-                d.progress.status =
-                    TransferStatus::Failed(format!("Could not connect to host: {}", last_error));
+        }
+        match self.active_downloads.lock().get(id) {
+            Some(download) if !is_finished(&download.progress.status) => {
+                info!("[P2P] Cancelling active transfer {}", id);
+                download.stop_flag.store(true, Ordering::SeqCst);
+                Ok(())
             }
-        });
+            Some(_) => Err(P2PError::ValidationError(format!("Transfer {} has already finished", id))),
+            None => Err(P2PError::ValidationError(format!("No transfer with id {}", id))),
+        }
+    }
 
-        // Spawn progress sync task
-        let dl2 = self.active_downloads.clone();
-        let c2 = code.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(Duration::from_millis(250)).await;
+    /// Share a single mod and return a short, copyable share code for it
+    pub async fn generate_share_code(
+        &self,
+        mod_path: PathBuf,
+        creator: Option<String>,
+    ) -> P2PResult<String> {
+        let name = mod_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Mod")
+            .to_string();
+        let hash = crate::p2p_sharing::hash_file(&mod_path)?;
+        let share_info = self.start_sharing(name, String::new(), vec![mod_path], creator, Vec::new()).await?;
 
-                // Try to read from the active client's progress handle
-                let handle = active_progress_for_sync
-                    .lock()
-                    .ok()
-                    .and_then(|g| g.clone());
-                if let Some(h) = handle {
-                    if let Ok(prog) = h.lock().map(|p| p.clone()) {
-                        let is_done = matches!(
-                            prog.status,
-                            TransferStatus::Completed
-                                | TransferStatus::Failed(_)
-                                | TransferStatus::Cancelled
-                        );
-                        if let Some(d) = dl2.lock().get_mut(&c2) {
-                            d.progress = prog;
-                        }
-                        if is_done {
-                            break;
-                        }
-                    }
-                } else {
-                    // No client yet - check if download already finished/failed
-                    let done = dl2
-                        .lock()
-                        .get(&c2)
-                        .map(|d| {
-                            matches!(
-                                d.progress.status,
-                                TransferStatus::Completed
-                                    | TransferStatus::Failed(_)
-                                    | TransferStatus::Cancelled
-                            )
-                        })
-                        .unwrap_or(true);
-                    if done {
-                        break;
-                    }
-                }
-            }
-        });
+        let key_bytes = URL_SAFE_NO_PAD
+            .decode(&share_info.encryption_key)
+            .map_err(|e| P2PError::ValidationError(format!("Invalid encryption key: {}", e)))?;
+        let key: [u8; 32] = key_bytes
+            .try_into()
+            .map_err(|_| P2PError::ValidationError("Invalid encryption key length".into()))?;
+
+        let code = encode_share_code(&ShareCodePayload {
+            peer_id: share_info.peer_id,
+            addresses: share_info.addresses,
+            key,
+            share_code: share_info.share_code,
+            mod_hash: hash.chars().take(16).collect(),
+        })?;
+        info!("[P2P] Generated share code ({} chars)", code.len());
+        Ok(code)
+    }
 
-        Ok(())
+    /// Decode a share code and start downloading the mod it points at
+    pub async fn receive_from_share_code(
+        &self,
+        code: &str,
+        out: PathBuf,
+        client_name: Option<String>,
+        auto_install: bool,
+        window: Window,
+    ) -> P2PResult<()> {
+        let payload = decode_share_code(code)?;
+        info!("[P2P] Share code for mod {} from peer {}", payload.mod_hash, payload.peer_id);
+        let share_info = ShareInfo {
+            peer_id: payload.peer_id,
+            addresses: payload.addresses,
+            encryption_key: URL_SAFE_NO_PAD.encode(payload.key),
+            share_code: payload.share_code,
+            tags: Vec::new(),
+        };
+        let conn = share_info
+            .encode()
+            .map_err(|e| P2PError::ValidationError(format!("{}", e)))?;
+        self.start_receiving(&conn, out, client_name, auto_install, window).await
     }
 
     /// Stop all active downloads
     pub fn stop_all_downloads(&self) {
+        self.queue.lock().clear();
         let mut downloads = self.active_downloads.lock();
         for (code, download) in downloads.iter() {
             info!("[P2P] Stopping download: {}", code);
@@ -642,4 +1004,37 @@ pub fn validate_connection_string(s: &str) -> P2PResult<bool> {
     Err(P2PError::ValidationError(
         "Invalid connection string".to_string(),
     ))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_payload() -> ShareCodePayload {
+        ShareCodePayload {
+            peer_id: "repak-1234abcd".into(),
+            addresses: vec!["bore.pub:40123".into(), "192.168.1.10:47820".into()],
+            key: [7u8; 32],
+            share_code: "ABCD-1234".into(),
+            mod_hash: "0123456789abcdef".into(),
+        }
+    }
+
+    #[test]
+    fn test_share_code_roundtrip() {
+        let code = encode_share_code(&sample_payload()).unwrap();
+        assert!(code.starts_with("RPX1-"));
+        assert_eq!(decode_share_code(&code).unwrap(), sample_payload());
+    }
+
+    #[test]
+    fn test_share_code_rejects_tampering_and_unknown_versions() {
+        let code = encode_share_code(&sample_payload()).unwrap();
+        let mut tampered = code.clone().into_bytes();
+        let last = tampered.len() - 6;
+        tampered[last] = if tampered[last] == b'A' { b'B' } else { b'A' };
+        assert!(decode_share_code(&String::from_utf8(tampered).unwrap()).is_err());
+        assert!(decode_share_code(&code.replacen("RPX1-", "RPX2-", 1)).is_err());
+        assert!(decode_share_code("not a code").is_err());
+    }
+}
@@ -592,7 +592,7 @@ impl P2PServer {
         while !self.stop_flag.load(Ordering::SeqCst) {
             match listener.accept() {
                 Ok((stream, addr)) => {
-                    info!("New connection from: {}", addr);
+                    info!("New connection from: {}", crate::ip_obfuscation::display_address(&addr.to_string()));
                     if let Err(e) = self.handle_connection(stream, addr) {
                         warn!("Connection handler error: {} — continuing to accept", e);
                     }
@@ -646,10 +646,11 @@ impl P2PServer {
             *count = count.saturating_sub(1);
         }
 
+        let shown = crate::ip_obfuscation::display_address(&addr.to_string());
         if let Err(ref e) = result {
-            warn!("Connection from {} ended with error: {}", addr, e);
+            warn!("Connection from {} ended with error: {}", shown, e);
         } else {
-            info!("Connection from {} completed successfully", addr);
+            info!("Connection from {} completed successfully", shown);
         }
 
         result
@@ -2,11 +2,13 @@
 //! 
 //! All detection is done via UAssetAPI - no heuristic fallbacks.
 //! If UAssetAPI fails (e.g., missing USMAP), detection returns false.
+//! The one exception is the opt-in `fast_detection` texture path, which classifies
+//! obvious cases from the uasset header and only sends ambiguous files to UAssetAPI.
 //!
 //! Uses the global UAssetToolkit singleton for optimal performance -
 //! the UAssetTool process is started once and reused for all operations.
 
-use log::info;
+use log::{info, warn};
 use uasset_toolkit::get_global_toolkit;
 
 /// Detects SKELETAL mesh files using UAssetAPI batch detection
@@ -59,10 +61,61 @@ pub async fn detect_mesh_files_async(mod_contents: &[String]) -> bool {
     false
 }
 
+/// Legacy package file magic (PACKAGE_FILE_TAG), little-endian
+const PACKAGE_FILE_TAG: [u8; 4] = [0xC1, 0x83, 0x2A, 0x9E];
+
+/// How much of a .uasset to read when sniffing; the summary and name map sit at the start
+const HEADER_SNIFF_BYTES: u64 = 64 * 1024;
+
+/// Class names that mark a package as a texture in its name map
+const TEXTURE_CLASS_NAMES: &[&str] = &["Texture2D", "TextureCube", "Texture2DArray", "VolumeTexture"];
+
+/// Name-map entries are FStrings: an i32 length (including the null) followed by the chars
+fn name_map_contains(header: &[u8], name: &str) -> bool {
+    let mut needle = ((name.len() + 1) as i32).to_le_bytes().to_vec();
+    needle.extend_from_slice(name.as_bytes());
+    needle.push(0);
+    header.windows(needle.len()).any(|w| w == needle.as_slice())
+}
+
+/// Classify a legacy .uasset from its package summary without a UAssetTool round-trip.
+/// Some(true): the name map has Texture2D and the file follows the T_ naming convention.
+/// Some(false): the whole header was read and names no texture class at all.
+/// None: ambiguous (e.g. a material that references textures) or not a readable legacy package.
+pub fn sniff_uasset_texture(path: &std::path::Path) -> Option<bool> {
+    use std::io::Read;
+
+    let file = std::fs::File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    let mut header = Vec::new();
+    file.take(HEADER_SNIFF_BYTES).read_to_end(&mut header).ok()?;
+    if !header.starts_with(&PACKAGE_FILE_TAG) {
+        return None;
+    }
+
+    let names_texture_class = TEXTURE_CLASS_NAMES.iter().any(|n| name_map_contains(&header, n));
+    if !names_texture_class {
+        // Only conclusive if the name map can't have continued past what we read
+        return (file_len <= HEADER_SNIFF_BYTES).then_some(false);
+    }
+
+    let is_texture_named = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.to_lowercase().starts_with("t_"));
+    if is_texture_named && name_map_contains(&header, "Texture2D") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
 /// Detects texture files that need the texture fix (Texture2D with .ubulk companion)
-/// Uses UAssetAPI to find Texture2D assets, then checks if they have a matching .ubulk file
+/// Uses UAssetAPI to find Texture2D assets, then checks if they have a matching .ubulk file.
+/// With `fast` set, obvious cases are decided from the uasset header and only the
+/// ambiguous ones go to UAssetAPI.
 /// Async version for use in Tauri commands
-pub async fn detect_texture_files_async(mod_contents: &[String]) -> bool {
+pub async fn detect_texture_files_async(mod_contents: &[String], fast: bool) -> bool {
     info!("[Detection] Texture detection received {} files to check", mod_contents.len());
     
     // Collect all .ubulk file stems (without extension) for quick lookup
@@ -112,6 +165,27 @@ pub async fn detect_texture_files_async(mod_contents: &[String]) -> bool {
         return false;
     }
     
+    let uasset_files_with_ubulk = if fast {
+        let mut ambiguous = Vec::new();
+        for file in uasset_files_with_ubulk {
+            match sniff_uasset_texture(std::path::Path::new(&file)) {
+                Some(true) => {
+                    info!("[Detection] Fast path: {} is a Texture2D - texture fix ENABLED", file);
+                    return true;
+                }
+                Some(false) => {}
+                None => ambiguous.push(file),
+            }
+        }
+        info!("[Detection] Fast path left {} ambiguous uasset(s) for UAssetAPI", ambiguous.len());
+        if ambiguous.is_empty() {
+            return false;
+        }
+        ambiguous
+    } else {
+        uasset_files_with_ubulk
+    };
+
     info!("[Detection] Found {} uassets with matching .ubulk, batch checking for Texture2D", uasset_files_with_ubulk.len());
     
     // Use global UAssetToolkit singleton with TRUE batch detection (all files in ONE request)
@@ -246,10 +320,8 @@ pub fn detect_mesh_files(mod_contents: &[String]) -> bool {
     }
 }
 
-/// Detects texture files that need the texture fix (Texture2D with .ubulk companion)
-/// Uses UAssetAPI to find Texture2D assets, then checks if they have a matching .ubulk file
-/// Sync version for use in install_mod.rs
-pub fn detect_texture_files(mod_contents: &[String]) -> bool {
+/// .uasset files that have a matching .ubulk, i.e. the only candidates for the texture fix
+fn uassets_with_ubulk(mod_contents: &[String]) -> Vec<String> {
     // Collect all .ubulk file stems (without extension) for quick lookup
     let ubulk_stems: std::collections::HashSet<String> = mod_contents.iter()
         .filter(|f| f.to_lowercase().ends_with(".ubulk"))
@@ -262,11 +334,11 @@ pub fn detect_texture_files(mod_contents: &[String]) -> bool {
         .collect();
     
     if ubulk_stems.is_empty() {
-        return false;
+        return Vec::new();
     }
     
     // Filter to only uassets that have matching .ubulk files
-    let uasset_files_with_ubulk: Vec<String> = mod_contents.iter()
+    mod_contents.iter()
         .filter(|f| f.to_lowercase().ends_with(".uasset"))
         .filter(|f| {
             std::path::Path::new(f)
@@ -276,45 +348,43 @@ pub fn detect_texture_files(mod_contents: &[String]) -> bool {
                 .unwrap_or(false)
         })
         .cloned()
-        .collect();
-    
-    if uasset_files_with_ubulk.is_empty() {
-        return false;
-    }
-    
-    // Use global singleton with TRUE batch detection (all candidates in ONE request)
-    match uasset_toolkit::get_global_toolkit() {
-        Ok(toolkit) => {
-            match toolkit.batch_detect_texture(&uasset_files_with_ubulk) {
-                Ok(result) => result,
-                Err(_) => false,
-            }
-        }
-        Err(_) => false,
-    }
+        .collect()
 }
 
-/// Detects Static Mesh files using UAssetAPI
+/// Detects Texture2D assets with a .ubulk companion (texture fix) and Static Mesh assets
+/// in a single UAssetTool batch request, returning (needs_texture_fix, has_static_mesh)
 /// Sync version for use in install_mod.rs
-pub fn detect_static_mesh_files(mod_contents: &[String]) -> bool {
+pub fn detect_texture_and_static_mesh_files(mod_contents: &[String]) -> (bool, bool) {
+    use uasset_toolkit::UAssetRequest;
+
     let uasset_files: Vec<String> = mod_contents.iter()
         .filter(|f| f.to_lowercase().ends_with(".uasset"))
         .cloned()
         .collect();
-    
     if uasset_files.is_empty() {
-        return false;
+        return (false, false);
     }
-    
-    // Use global singleton with TRUE batch detection (all files in ONE request)
-    match uasset_toolkit::get_global_toolkit() {
-        Ok(toolkit) => {
-            match toolkit.batch_detect_static_mesh(&uasset_files) {
-                Ok(result) => result,
-                Err(_) => false,
-            }
-        }
-        Err(_) => false,
+    let uasset_files_with_ubulk = uassets_with_ubulk(mod_contents);
+    let check_textures = !uasset_files_with_ubulk.is_empty();
+
+    let mut requests = Vec::new();
+    if check_textures {
+        requests.push(UAssetRequest::BatchDetectTexture { file_paths: uasset_files_with_ubulk });
     }
-}
+    requests.push(UAssetRequest::BatchDetectStaticMesh { file_paths: uasset_files });
 
+    let responses = match uasset_toolkit::get_global_toolkit().and_then(|toolkit| toolkit.batch(requests)) {
+        Ok(responses) => responses,
+        Err(e) => {
+            warn!("[Detection] Batch texture/static mesh detection failed: {}", e);
+            return (false, false);
+        }
+    };
+    let detected = |response: &uasset_toolkit::UAssetResponse| {
+        response.success && response.data.as_ref().and_then(|d| d.as_bool()).unwrap_or(false)
+    };
+    let mut responses = responses.iter();
+    let has_texture = check_textures && responses.next().is_some_and(detected);
+    let has_static_mesh = responses.next().is_some_and(detected);
+    (has_texture, has_static_mesh)
+}
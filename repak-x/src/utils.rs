@@ -29,6 +29,18 @@ static FILENAME_CHAR_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 
+/// Character/skin a content path belongs to: "1011/1011001" for skin folders, "1011" for
+/// character folders without a skin, None for anything outside a character folder
+pub fn skin_group_key(path: &str) -> Option<String> {
+    if let Some(skin) = SKIN_REGEX.find(path) {
+        return Some(skin.as_str().replace('\\', "/"));
+    }
+    CHAR_ID_REGEX
+        .captures(path)
+        .and_then(|caps| caps.get(1))
+        .map(|id| id.as_str().to_string())
+}
+
 /// Result of mod characteristics detection, includes mod type and detected heroes
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ModCharacteristics {
@@ -127,6 +139,7 @@ pub fn get_pak_characteristics_detailed(mod_contents: Vec<String>) -> ModCharact
     let mut has_ui = false;
     let mut has_blueprint = false;
     let mut has_text = false;
+    let mut has_map = false;
     let mut character_name: Option<String> = None;  // Full skin-specific name (e.g., "Hawkeye - Default")
     let mut hero_names: HashSet<String> = HashSet::new();  // All detected hero names
 
@@ -153,6 +166,10 @@ pub fn get_pak_characteristics_detailed(mod_contents: Vec<String>) -> ModCharact
         if filename_lower.starts_with("t_") && is_uasset {
             has_texture = true;
         }
+        // Levels: the .umap itself, or any package under the game's Maps folder
+        if filename_lower.ends_with(".umap") || path_lower.starts_with("maps/") {
+            has_map = true;
+        }
         
         // VFX: MI_ files in VFX path (e.g. /Game/Marvel/VFX/Materials/...)
         // Check both original file path and stripped path
@@ -243,7 +260,7 @@ pub fn get_pak_characteristics_detailed(mod_contents: Vec<String>) -> ModCharact
     heroes.sort();
 
     // Determine the pure category (without character name)
-    // Priority order: Audio/Movies/UI (pure) > Mesh > Static Mesh > VFX > Audio (mixed) > Texture
+    // Priority order: Audio/Movies/UI (pure) > Map/Level > Mesh > Static Mesh > VFX > Audio (mixed) > Texture
     // Note: Blueprint and Text are now additive categories and handled separately
     let category = if has_audio && !has_skeletal_mesh && !has_static_mesh && !has_texture && !has_material {
         "Audio"
@@ -251,6 +268,8 @@ pub fn get_pak_characteristics_detailed(mod_contents: Vec<String>) -> ModCharact
         "Movies"
     } else if has_ui && !has_skeletal_mesh && !has_static_mesh && !has_texture && !has_material {
         "UI"
+    } else if has_map {
+        "Map/Level"
     } else if has_skeletal_mesh {
         "Mesh"
     } else if has_static_mesh {
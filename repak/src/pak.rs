@@ -19,6 +19,7 @@ impl std::fmt::Debug for Hash {
 #[derive(Debug)]
 pub struct PakBuilder {
     key: super::Key,
+    encrypt_index_only: bool,
     allowed_compression: Vec<Compression>,
 }
 
@@ -32,6 +33,7 @@ impl PakBuilder {
     pub fn new() -> Self {
         Self {
             key: Default::default(),
+            encrypt_index_only: false,
             allowed_compression: Default::default(),
         }
     }
@@ -40,6 +42,12 @@ impl PakBuilder {
         self.key = super::Key::Some(key);
         self
     }
+    /// When writing, only encrypt the index and leave entry data unencrypted
+    #[cfg(feature = "encryption")]
+    pub fn encrypt_index_only(mut self, encrypt_index_only: bool) -> Self {
+        self.encrypt_index_only = encrypt_index_only;
+        self
+    }
     #[cfg(feature = "compression")]
     pub fn compression(mut self, compression: impl IntoIterator<Item = Compression>) -> Self {
         self.allowed_compression = compression.into_iter().collect();
@@ -62,9 +70,15 @@ impl PakBuilder {
         mount_point: String,
         path_hash_seed: Option<u64>,
     ) -> PakWriter<W> {
+        let data_key = if self.encrypt_index_only {
+            super::Key::None
+        } else {
+            self.key.clone()
+        };
         PakWriter::new_inner(
             writer,
             self.key,
+            data_key,
             version,
             mount_point,
             path_hash_seed,
@@ -73,6 +87,41 @@ impl PakBuilder {
     }
 }
 
+/// Footer-level information about a pak, readable without the AES key
+#[derive(Debug, Clone, Copy)]
+pub struct PakFooterInfo {
+    pub version: super::Version,
+    pub encrypted_index: bool,
+    /// GUID of the key the pak was encrypted with, if the version stores one (zero when unset)
+    pub encryption_guid: Option<u128>,
+}
+
+impl PakFooterInfo {
+    /// Read only the footer, trying each known version like `PakBuilder::reader`
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, super::Error> {
+        use std::fmt::Write;
+        let mut log = "\n".to_owned();
+
+        for ver in Version::iter() {
+            let footer = reader
+                .seek(io::SeekFrom::End(-ver.size()))
+                .map_err(super::Error::from)
+                .and_then(|_| super::footer::Footer::read(&mut *reader, ver));
+            match footer {
+                Ok(footer) => {
+                    return Ok(Self {
+                        version: ver,
+                        encrypted_index: footer.encrypted,
+                        encryption_guid: footer.encryption_uuid,
+                    })
+                }
+                Err(err) => writeln!(log, "trying version {} failed: {}", ver, err)?,
+            }
+        }
+        Err(super::Error::UnsupportedOrEncrypted(log))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PakReader {
     pak: Pak,
@@ -84,6 +133,8 @@ pub struct PakWriter<W: Write + Seek> {
     pak: Pak,
     writer: W,
     key: super::Key,
+    /// Key used for entry data; `None` when only the index is encrypted
+    data_key: super::Key,
     allowed_compression: Vec<Compression>,
 }
 
@@ -188,6 +239,11 @@ impl PakReader {
         self.pak.encryption_guid
     }
 
+    /// Whether any entry's data is encrypted (as opposed to only the index)
+    pub fn encrypted_data(&self) -> bool {
+        self.pak.index.entries().values().any(|e| e.is_encrypted())
+    }
+
     pub fn path_hash_seed(&self) -> Option<u64> {
         self.pak.index.path_hash_seed
     }
@@ -232,15 +288,28 @@ impl PakReader {
     pub fn compression(&self) -> Vec<Compression> {
         self.pak.compression.iter().filter_map(|c| *c).collect()
     }
+    /// Returns the compression method an entry was stored with, or None if uncompressed
+    pub fn entry_compression(&self, entry: &Entry) -> Option<Compression> {
+        entry
+            .compression_slot
+            .and_then(|slot| self.pak.compression.get(slot as usize).copied().flatten())
+    }
     pub fn into_pakwriter<W: Write + Seek>(
         self,
         mut writer: W,
     ) -> Result<PakWriter<W>, super::Error> {
         writer.seek(io::SeekFrom::Start(self.pak.index_offset.unwrap()))?;
+        // Keep the existing encryption mode for appended entries
+        let data_key = if self.encrypted_data() {
+            self.key.clone()
+        } else {
+            super::Key::None
+        };
         Ok(PakWriter {
             allowed_compression: self.pak.compression.iter().filter_map(|c| *c).collect(),
             pak: self.pak,
             key: self.key,
+            data_key,
             writer,
         })
     }
@@ -251,6 +320,7 @@ impl<W: Write + Seek> PakWriter<W> {
     fn new_inner(
         writer: W,
         key: super::Key,
+        data_key: super::Key,
         version: Version,
         mount_point: String,
         path_hash_seed: Option<u64>,
@@ -260,6 +330,7 @@ impl<W: Write + Seek> PakWriter<W> {
             pak: Pak::new(version, mount_point, path_hash_seed),
             writer,
             key,
+            data_key,
             allowed_compression,
         }
     }
@@ -286,7 +357,7 @@ impl<W: Write + Seek> PakWriter<W> {
                     &[]
                 },
                 data.as_ref(),
-                &self.key,
+                &self.data_key,
                 &root_path(&self.pak.mount_point, path),
             )?,
         );
@@ -297,7 +368,7 @@ impl<W: Write + Seek> PakWriter<W> {
     pub fn entry_builder(&self) -> EntryBuilder {
         EntryBuilder {
             allowed_compression: self.allowed_compression.clone(),
-            key: self.key.clone(),
+            key: self.data_key.clone(),
             mount_point: self.pak.mount_point.clone(),
         }
     }
@@ -379,6 +450,22 @@ impl EntryBuilder {
             &root_path(&self.mount_point, path),
         )
     }
+
+    /// Builds an entry with exactly `compression` (None = stored), e.g. to keep an entry's
+    /// method when rewriting a pak. The method must be one the writer was configured with.
+    pub fn build_entry_with_compression<D: AsRef<[u8]> + Send + Sync>(
+        &self,
+        compression: Option<Compression>,
+        data: D,
+        path: &str,
+    ) -> Result<PartialEntry<D>, Error> {
+        build_partial_entry(
+            compression.as_slice(),
+            data,
+            &self.key,
+            &root_path(&self.mount_point, path),
+        )
+    }
 }
 
 impl Pak {
@@ -826,4 +913,32 @@ mod test {
         assert_eq!(split_path_child("/"), None);
         assert_eq!(split_path_child(""), None);
     }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encrypt_index_only() {
+        use aes::cipher::KeyInit;
+
+        let key = aes::Aes256::new_from_slice(&[7u8; 32]).unwrap();
+        let mut writer = PakBuilder::new()
+            .key(key.clone())
+            .encrypt_index_only(true)
+            .writer(
+                io::Cursor::new(vec![]),
+                Version::V11,
+                "../../../".to_owned(),
+                Some(0x205C5A7D),
+            );
+        writer.write_file("test.txt", false, b"hello").unwrap();
+        let mut bytes = writer.write_index().unwrap();
+
+        let footer = PakFooterInfo::read(&mut bytes).unwrap();
+        assert!(footer.encrypted_index);
+        assert_eq!(footer.version, Version::V11);
+
+        let pak = PakBuilder::new().key(key).reader(&mut bytes).unwrap();
+        assert!(pak.encrypted_index());
+        assert!(!pak.encrypted_data());
+        assert_eq!(pak.get("test.txt", &mut bytes).unwrap(), b"hello");
+    }
 }
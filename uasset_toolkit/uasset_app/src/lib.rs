@@ -2,7 +2,9 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Stdio;
-use std::sync::{OnceLock, Mutex as StdMutex, mpsc};
+use std::collections::VecDeque;
+use std::sync::{Arc, OnceLock, Mutex as StdMutex, mpsc};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::io::{BufRead, BufReader as StdBufReader, Write};
 use std::process::{Command as StdCommand, Child as StdChild, ChildStdin as StdChildStdin, ChildStdout as StdChildStdout};
 use std::time::Duration;
@@ -23,17 +25,31 @@ use std::os::windows::process::CommandExt;
 /// Global singleton for the synchronous UAssetToolkit
 static GLOBAL_TOOLKIT_SYNC: OnceLock<SyncToolkit> = OnceLock::new();
 
+/// Lines of UAssetTool stderr kept for diagnostics
+const STDERR_TAIL_LINES: usize = 200;
+/// Lines of captured stderr appended to errors when the tool dies mid-request
+const STDERR_ERROR_LINES: usize = 20;
+/// Default cap on a single stdout line (one response) from UAssetTool
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 64 * 1024 * 1024;
+
 /// Synchronous child process handle using channel-based communication for timeout support
 struct SyncChildProcess {
-    _child: StdChild,
+    child: StdChild,
     stdin: StdChildStdin,
     response_rx: mpsc::Receiver<std::io::Result<String>>,
 }
 
 /// Synchronous toolkit that manages a persistent UAssetTool process
 pub struct SyncToolkit {
-    tool_path: String,
+    /// Swappable at runtime via `reinit_global_toolkit`; takes effect on the next process start
+    tool_path: StdMutex<String>,
     process: StdMutex<Option<SyncChildProcess>>,
+    /// Cleared the first time the tool rejects a `batch` request (older UAssetTool builds)
+    batch_supported: AtomicBool,
+    /// Most recent stderr lines across process restarts, drained by a reader thread
+    stderr_tail: Arc<StdMutex<VecDeque<String>>>,
+    /// Longest stdout line the reader thread buffers before giving up on the process
+    max_response_bytes: Arc<AtomicUsize>,
 }
 
 impl SyncToolkit {
@@ -44,11 +60,60 @@ impl SyncToolkit {
         };
         
         Ok(Self {
-            tool_path,
+            tool_path: StdMutex::new(tool_path),
             process: StdMutex::new(None),
+            batch_supported: AtomicBool::new(true),
+            stderr_tail: Arc::new(StdMutex::new(VecDeque::new())),
+            max_response_bytes: Arc::new(AtomicUsize::new(DEFAULT_MAX_RESPONSE_BYTES)),
         })
     }
+
+    /// Cap the size of a single response line. A tool exceeding it is killed and restarted
+    /// on the next request instead of being buffered until the app runs out of memory.
+    pub fn set_max_response_bytes(&self, bytes: usize) {
+        self.max_response_bytes.store(bytes.max(1), Ordering::Relaxed);
+    }
     
+    /// Path of the UAssetTool executable this toolkit launches
+    pub fn tool_path(&self) -> String {
+        self.tool_path.lock().map(|p| p.clone()).unwrap_or_default()
+    }
+
+    /// Point the toolkit at a different executable. The running process (if any) is
+    /// dropped so the next request starts the new one.
+    pub fn set_tool_path(&self, tool_path: String) -> Result<()> {
+        let mut process_guard = self.process.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire process lock: {}", e))?;
+        *self.tool_path.lock()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire tool path lock: {}", e))? = tool_path;
+        *process_guard = None;
+        Ok(())
+    }
+
+    /// The captured stderr tail, oldest line first
+    pub fn stderr_tail(&self) -> String {
+        self.stderr_lines(STDERR_TAIL_LINES).join("\n")
+    }
+
+    fn stderr_lines(&self, count: usize) -> Vec<String> {
+        let Ok(tail) = self.stderr_tail.lock() else {
+            return Vec::new();
+        };
+        tail.iter().skip(tail.len().saturating_sub(count)).cloned().collect()
+    }
+
+    /// Error for a request that died with the process, with the tool's last stderr output attached
+    fn error_with_stderr(&self, message: String) -> anyhow::Error {
+        // Give the stderr reader a moment to drain what the tool printed before exiting
+        thread::sleep(Duration::from_millis(100));
+        let lines = self.stderr_lines(STDERR_ERROR_LINES);
+        if lines.is_empty() {
+            anyhow::anyhow!(message)
+        } else {
+            anyhow::anyhow!("{}\nUAssetTool stderr:\n{}", message, lines.join("\n"))
+        }
+    }
+
     fn find_tool_path() -> Result<String> {
         let exe_name = Self::get_tool_executable_name();
         let exe_path = std::env::current_exe()?;
@@ -100,21 +165,28 @@ impl SyncToolkit {
     }
     
     fn send_request(&self, request: &UAssetRequest) -> Result<UAssetResponse> {
+        self.send_request_with_progress(request, &|_| {})
+    }
+    
+    /// Send a request, forwarding any NDJSON progress lines the tool emits before its
+    /// final response to `on_progress`. Each progress line also extends the timeout.
+    fn send_request_with_progress(&self, request: &UAssetRequest, on_progress: &dyn Fn(ToolProgress)) -> Result<UAssetResponse> {
         let mut process_guard = self.process.lock()
             .map_err(|e| anyhow::anyhow!("Failed to acquire process lock: {}", e))?;
         
         // Start process if not running
         if process_guard.is_none() {
-            log::info!("[SyncToolkit] Starting new UAssetTool process: {}", self.tool_path);
+            let tool_path = self.tool_path();
+            log::info!("[SyncToolkit] Starting new UAssetTool process: {}", tool_path);
             
-            if !Path::new(&self.tool_path).exists() {
-                anyhow::bail!("UAssetTool executable not found at: {}", self.tool_path);
+            if !Path::new(&tool_path).exists() {
+                anyhow::bail!("UAssetTool executable not found at: {}", tool_path);
             }
             
-            let mut cmd = StdCommand::new(&self.tool_path);
+            let mut cmd = StdCommand::new(&tool_path);
             cmd.stdin(Stdio::piped())
                 .stdout(Stdio::piped())
-                .stderr(Stdio::inherit()); // MUST inherit stderr to avoid deadlock from buffer filling
+                .stderr(Stdio::piped()); // Drained continuously below so the pipe buffer never fills
             
             // Pass USMAP_PATH to child process
             if let Ok(usmap_path) = std::env::var("USMAP_PATH") {
@@ -130,21 +202,50 @@ impl SyncToolkit {
             
             let stdin = child.stdin.take().context("Failed to get stdin")?;
             let stdout = child.stdout.take().context("Failed to get stdout")?;
+            let stderr = child.stderr.take().context("Failed to get stderr")?;
+
+            let stderr_tail = Arc::clone(&self.stderr_tail);
+            thread::spawn(move || {
+                let push = |line: String| {
+                    if let Ok(mut tail) = stderr_tail.lock() {
+                        if tail.len() >= STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(line);
+                    }
+                };
+                push("--- UAssetTool process started ---".to_string());
+                for line in StdBufReader::new(stderr).lines() {
+                    let Ok(line) = line else { break };
+                    log::debug!("[UAssetTool] {}", line);
+                    push(line);
+                }
+            });
             
             // Create channel for timeout-safe reading
             let (tx, rx) = mpsc::channel();
             
             // Spawn reader thread that sends lines through channel
+            let max_response_bytes = Arc::clone(&self.max_response_bytes);
             thread::spawn(move || {
-                let reader = StdBufReader::new(stdout);
-                for line in reader.lines() {
-                    if tx.send(line).is_err() {
-                        break; // Channel closed, stop reading
+                let mut reader = StdBufReader::new(stdout);
+                loop {
+                    match read_bounded_line(&mut reader, max_response_bytes.load(Ordering::Relaxed)) {
+                        Ok(Some(line)) => {
+                            if tx.send(Ok(line)).is_err() {
+                                break; // Channel closed, stop reading
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            let _ = tx.send(Err(e));
+                            break;
+                        }
                     }
                 }
             });
             
-            *process_guard = Some(SyncChildProcess { _child: child, stdin, response_rx: rx });
+            *process_guard = Some(SyncChildProcess { child, stdin, response_rx: rx });
             log::info!("[SyncToolkit] UAssetTool process started successfully");
         }
         
@@ -156,12 +257,12 @@ impl SyncToolkit {
         // Write request
         if let Err(e) = writeln!(proc.stdin, "{}", request_json) {
             *process_guard = None;
-            anyhow::bail!("Failed to write to UAssetTool: {}", e);
+            return Err(self.error_with_stderr(format!("Failed to write to UAssetTool: {}", e)));
         }
         
         if let Err(e) = proc.stdin.flush() {
             *process_guard = None;
-            anyhow::bail!("Failed to flush to UAssetTool: {}", e);
+            return Err(self.error_with_stderr(format!("Failed to flush to UAssetTool: {}", e)));
         }
         
         log::info!("[SyncToolkit] Request sent, waiting for response (timeout: 5 min)...");
@@ -169,7 +270,7 @@ impl SyncToolkit {
         // Read response with timeout (5 minutes for large batch operations)
         // Skip non-JSON lines (e.g. log output that leaked to stdout) until we get a valid JSON response
         let timeout = Duration::from_secs(300);
-        let deadline = std::time::Instant::now() + timeout;
+        let mut deadline = std::time::Instant::now() + timeout;
         loop {
             let remaining = deadline.saturating_duration_since(std::time::Instant::now());
             if remaining.is_zero() {
@@ -188,18 +289,28 @@ impl SyncToolkit {
                         log::warn!("[SyncToolkit] Skipping non-JSON stdout line: {}", &trimmed[..std::cmp::min(200, trimmed.len())]);
                         continue;
                     }
+                    if let Ok(ProgressLine::Progress(progress)) = serde_json::from_str::<ProgressLine>(trimmed) {
+                        deadline = std::time::Instant::now() + timeout;
+                        on_progress(progress);
+                        continue;
+                    }
                     log::info!("[SyncToolkit] Got response: {} bytes", line.len());
                     match serde_json::from_str::<UAssetResponse>(&line) {
                         Ok(response) => return Ok(response),
                         Err(e) => {
                             *process_guard = None;
-                            anyhow::bail!("Failed to parse response: {} (Line: {})", e, &line[..std::cmp::min(500, line.len())]);
+                            return Err(self.error_with_stderr(format!(
+                                "Failed to parse response: {} (Line: {})", e, &line[..std::cmp::min(500, line.len())]
+                            )));
                         }
                     }
                 }
                 Ok(Err(e)) => {
-                    *process_guard = None;
-                    anyhow::bail!("Failed to read from UAssetTool: {}", e);
+                    // The reader thread has stopped, so a tool still writing would block forever
+                    if let Some(mut proc) = process_guard.take() {
+                        let _ = proc.child.kill();
+                    }
+                    return Err(self.error_with_stderr(format!("Failed to read from UAssetTool: {}", e)));
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
                     log::error!("[SyncToolkit] TIMEOUT waiting for UAssetTool response after {:?}", timeout);
@@ -208,12 +319,60 @@ impl SyncToolkit {
                 }
                 Err(mpsc::RecvTimeoutError::Disconnected) => {
                     *process_guard = None;
-                    anyhow::bail!("UAssetTool process closed connection (channel disconnected)");
+                    return Err(self.error_with_stderr("UAssetTool process closed connection (channel disconnected)".to_string()));
                 }
             }
         }
     }
     
+    /// Send several requests in one write/read cycle. UAssetTool runs them in order and answers
+    /// with one response per request; tools without batch support get one round trip per request.
+    pub fn batch(&self, requests: Vec<UAssetRequest>) -> Result<Vec<UAssetResponse>> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+        let requests = if self.batch_supported.load(Ordering::Relaxed) {
+            let count = requests.len();
+            let request = UAssetRequest::Batch { requests };
+            let response = self.send_request(&request)?;
+            let responses = if response.success {
+                response.data.and_then(|d| serde_json::from_value::<Vec<UAssetResponse>>(d).ok())
+            } else {
+                None
+            };
+            match responses {
+                Some(responses) if responses.len() == count => return Ok(responses),
+                _ => {
+                    log::warn!("[SyncToolkit] UAssetTool doesn't support batch requests ({}), sending them one by one", response.message);
+                    self.batch_supported.store(false, Ordering::Relaxed);
+                }
+            }
+            let UAssetRequest::Batch { requests } = request else {
+                unreachable!()
+            };
+            requests
+        } else {
+            requests
+        };
+        requests.iter().map(|request| self.send_request(request)).collect()
+    }
+
+    /// Texture info for each file, `None` where the file isn't a readable texture
+    pub fn batch_get_texture_info(&self, file_paths: &[String]) -> Result<Vec<Option<TextureInfo>>> {
+        let requests = file_paths.iter()
+            .map(|file_path| UAssetRequest::GetTextureInfo { file_path: file_path.clone() })
+            .collect();
+        Ok(self.batch(requests)?.into_iter().map(parse_response_data).collect())
+    }
+
+    /// Mesh info for each file, `None` where the file isn't a readable mesh
+    pub fn batch_get_mesh_info(&self, file_paths: &[String]) -> Result<Vec<Option<MeshInfo>>> {
+        let requests = file_paths.iter()
+            .map(|file_path| UAssetRequest::GetMeshInfo { file_path: file_path.clone() })
+            .collect();
+        Ok(self.batch(requests)?.into_iter().map(parse_response_data).collect())
+    }
+
     pub fn batch_detect_skeletal_mesh(&self, file_paths: &[String]) -> Result<bool> {
         let request = UAssetRequest::BatchDetectSkeletalMesh { file_paths: file_paths.to_vec() };
         let response = self.send_request(&request)?;
@@ -277,6 +436,20 @@ impl SyncToolkit {
         Ok(true)
     }
     
+    pub fn export_texture_png(&self, file_path: &str, output_path: &str, max_size: Option<u32>, usmap_path: Option<&str>) -> Result<()> {
+        let request = UAssetRequest::ExportTexturePng {
+            file_path: file_path.to_string(),
+            output_path: output_path.to_string(),
+            max_size,
+            usmap_path: usmap_path.map(|s| s.to_string()),
+        };
+        let response = self.send_request(&request)?;
+        if !response.success {
+            anyhow::bail!("Failed to export texture to PNG: {}", response.message);
+        }
+        Ok(())
+    }
+    
     pub fn set_no_mipmaps(&self, file_path: &str) -> Result<()> {
         let request = UAssetRequest::SetMipGen {
             file_path: file_path.to_string(),
@@ -374,16 +547,17 @@ impl SyncToolkit {
         Ok(IoStoreListResult { package_count, container_name, files })
     }
     
-    pub fn create_mod_iostore(&self, output_path: &str, input_dir: &str, usmap_path: Option<&str>, mount_point: Option<&str>, compress: Option<bool>, aes_key: Option<&str>, parallel: bool, obfuscate: bool) -> Result<IoStoreResult> {
+    pub fn create_mod_iostore(&self, output_path: &str, input_dir: &str, options: &ModIoStoreOptions) -> Result<IoStoreResult> {
         let request = UAssetRequest::CreateModIoStore {
             output_path: output_path.to_string(),
             input_dir: input_dir.to_string(),
-            usmap_path: usmap_path.map(|s| s.to_string()),
-            mount_point: mount_point.map(|s| s.to_string()),
-            compress,
-            aes_key: aes_key.map(|s| s.to_string()),
-            parallel,
-            obfuscate,
+            usmap_path: options.usmap_path.map(|s| s.to_string()),
+            mount_point: options.mount_point.map(|s| s.to_string()),
+            compress: options.compress,
+            aes_key: options.aes_key.map(|s| s.to_string()),
+            parallel: options.parallel,
+            obfuscate: options.obfuscate,
+            container_version: options.container_version,
         };
         
         let response = self.send_request(&request)?;
@@ -399,26 +573,56 @@ impl SyncToolkit {
         let converted_count = data.get("converted_count").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
         let file_count = data.get("file_count").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
         
+        if utoc_path.is_empty() {
+            ensure_container_version(&format!("{}.utoc", output_path), options.container_version)?;
+        } else {
+            ensure_container_version(&utoc_path, options.container_version)?;
+        }
         Ok(IoStoreResult { utoc_path, ucas_path, pak_path, converted_count, file_count })
     }
 }
 
-/// Get or initialize the global synchronous toolkit
+/// Get or initialize the global synchronous toolkit.
+/// A failed initialization is not cached, so the next call tries again.
 pub fn get_global_toolkit() -> Result<&'static SyncToolkit> {
-    let toolkit = GLOBAL_TOOLKIT_SYNC.get_or_init(|| {
-        log::info!("[SyncToolkit] Initializing global singleton...");
-        match SyncToolkit::new(None) {
-            Ok(t) => {
-                log::info!("[SyncToolkit] Global singleton created successfully");
-                t
-            }
-            Err(e) => {
-                log::error!("[SyncToolkit] Failed to create singleton: {}", e);
-                panic!("Failed to initialize SyncToolkit: {}", e);
-            }
+    if let Some(toolkit) = GLOBAL_TOOLKIT_SYNC.get() {
+        return Ok(toolkit);
+    }
+    log::info!("[SyncToolkit] Initializing global singleton...");
+    let toolkit = SyncToolkit::new(None).map_err(|e| {
+        log::error!("[SyncToolkit] Failed to create singleton: {}", e);
+        e.context("Failed to initialize SyncToolkit")
+    })?;
+    log::info!("[SyncToolkit] Global singleton created successfully");
+    // Another thread may have won the race; its instance is kept and ours is dropped
+    Ok(GLOBAL_TOOLKIT_SYNC.get_or_init(|| toolkit))
+}
+
+/// Rebuild the global toolkit around an explicit UAssetTool path, replacing whatever
+/// path was guessed (or failed) at startup. Any running tool process is restarted.
+pub fn reinit_global_toolkit(tool_path: String) -> Result<()> {
+    if !Path::new(&tool_path).is_file() {
+        anyhow::bail!("UAssetTool executable not found at: {}", tool_path);
+    }
+    let toolkit = match GLOBAL_TOOLKIT_SYNC.get() {
+        Some(toolkit) => toolkit,
+        None => {
+            let toolkit = SyncToolkit::new(Some(tool_path.clone()))?;
+            GLOBAL_TOOLKIT_SYNC.get_or_init(|| toolkit)
         }
-    });
-    Ok(toolkit)
+    };
+    toolkit.set_tool_path(tool_path.clone())?;
+    // A different build may support batching even if the previous one didn't
+    toolkit.batch_supported.store(true, Ordering::Relaxed);
+    log::info!("[SyncToolkit] Global singleton now uses {}", tool_path);
+    Ok(())
+}
+
+/// Cap the size of a single UAssetTool response (using global singleton)
+pub fn set_max_response_bytes(bytes: usize) -> Result<()> {
+    let toolkit = get_global_toolkit()?;
+    toolkit.set_max_response_bytes(bytes);
+    Ok(())
 }
 
 /// Initialize the global toolkit at app startup
@@ -447,6 +651,9 @@ pub enum UAssetRequest {
     PatchMesh { file_path: String, uexp_path: String },
     #[serde(rename = "get_mesh_info")]
     GetMeshInfo { file_path: String },
+    // Read the engine object versions from a legacy .uasset summary
+    #[serde(rename = "get_package_version")]
+    GetPackageVersion { file_path: String },
     // Batch detection - sends all files at once, returns first match
     #[serde(rename = "batch_detect_skeletal_mesh")]
     BatchDetectSkeletalMesh { file_paths: Vec<String> },
@@ -459,6 +666,9 @@ pub enum UAssetRequest {
     // Texture conversion using UE4-DDS-Tools (export -> re-inject with no_mipmaps)
     #[serde(rename = "convert_texture")]
     ConvertTexture { file_path: String },
+    // Decode a texture (BC/DXT via UE4-DDS-Tools) to PNG, downscaled so neither side exceeds max_size
+    #[serde(rename = "export_texture_png")]
+    ExportTexturePng { file_path: String, output_path: String, max_size: Option<u32>, usmap_path: Option<String> },
     #[serde(rename = "strip_mipmaps")]
     StripMipmaps { file_path: String },
     // Native C# mipmap stripping using UAssetAPI TextureExport
@@ -496,13 +706,34 @@ pub enum UAssetRequest {
     #[serde(rename = "is_iostore_encrypted")]
     IsIoStoreEncrypted { file_path: String },
     #[serde(rename = "recompress_iostore")]
-    RecompressIoStore { file_path: String },
+    RecompressIoStore { file_path: String, #[serde(default, skip_serializing_if = "Option::is_none")] container_version: Option<u8> },
     #[serde(rename = "extract_iostore")]
-    ExtractIoStore { file_path: String, output_path: String, aes_key: Option<String> },
+    ExtractIoStore { file_path: String, output_path: String, aes_key: Option<String>, #[serde(default, skip_serializing_if = "std::ops::Not::not")] progress: bool },
     #[serde(rename = "extract_script_objects")]
     ExtractScriptObjects { file_path: String, output_path: String },
     #[serde(rename = "create_mod_iostore")]
-    CreateModIoStore { output_path: String, input_dir: String, usmap_path: Option<String>, mount_point: Option<String>, compress: Option<bool>, aes_key: Option<String>, #[serde(default)] parallel: bool, #[serde(default)] obfuscate: bool },
+    CreateModIoStore { output_path: String, input_dir: String, usmap_path: Option<String>, mount_point: Option<String>, compress: Option<bool>, aes_key: Option<String>, #[serde(default)] parallel: bool, #[serde(default)] obfuscate: bool, #[serde(default, skip_serializing_if = "Option::is_none")] container_version: Option<u8> },
+
+    // Several requests in one round trip - processed in order, data is an array of responses
+    #[serde(rename = "batch")]
+    Batch { requests: Vec<UAssetRequest> },
+}
+
+/// Progress update emitted by UAssetTool as an NDJSON line while a long request runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolProgress {
+    /// 1-based index of the file being processed
+    pub current: usize,
+    pub total: usize,
+    #[serde(default)]
+    pub file: String,
+}
+
+/// Non-response stdout line: `{"type":"progress","current":..,"total":..,"file":".."}`
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ProgressLine {
+    Progress(ToolProgress),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -528,6 +759,48 @@ pub struct MeshInfo {
     pub is_skeletal_mesh: Option<bool>,
 }
 
+fn parse_response_data<T: serde::de::DeserializeOwned>(response: UAssetResponse) -> Option<T> {
+    if !response.success {
+        return None;
+    }
+    response.data.and_then(|d| serde_json::from_value(d).ok())
+}
+
+/// Read one newline-terminated line (without the `\n` / `\r\n`), buffering at most `max_bytes`
+/// of it. Returns `Ok(None)` at end of stream and an `InvalidData` error for longer lines.
+fn read_bounded_line<R: BufRead>(reader: &mut R, max_bytes: usize) -> std::io::Result<Option<String>> {
+    let mut line = Vec::new();
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            if line.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        let newline = available.iter().position(|&b| b == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        if line.len() + chunk.len() > max_bytes {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("UAssetTool response exceeded {} bytes without a newline", max_bytes),
+            ));
+        }
+        line.extend_from_slice(chunk);
+        let consumed = chunk.len() + usize::from(newline.is_some());
+        reader.consume(consumed);
+        if newline.is_some() {
+            break;
+        }
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
 // ============================================================================
 // GLOBAL SYNC API - Module-level functions using the global singleton
 // ============================================================================
@@ -567,28 +840,50 @@ pub fn is_static_mesh_uasset(file_path: &str) -> Result<bool> {
     toolkit.batch_detect_static_mesh(&[file_path.to_string()])
 }
 
+/// Check that the UAssetTool process is reachable (using global singleton).
+/// Sends a no-op batch request; any reply, even a failure, means the tool is alive.
+pub fn ping_toolkit() -> Result<()> {
+    let toolkit = get_global_toolkit()?;
+    let request = UAssetRequest::BatchDetectTexture { file_paths: Vec::new() };
+    toolkit.send_request(&request)?;
+    Ok(())
+}
+
 /// Recompress an IoStore file
-pub fn recompress_iostore(file_path: &str) -> Result<()> {
+/// container_version: target .utoc TOC version (None = the tool's built-in default)
+pub fn recompress_iostore(file_path: &str, container_version: Option<u8>) -> Result<()> {
     let toolkit = get_global_toolkit()?;
     let request = UAssetRequest::RecompressIoStore {
         file_path: file_path.to_string(),
+        container_version,
     };
     let response = toolkit.send_request(&request)?;
     if !response.success {
         anyhow::bail!("Failed to recompress IoStore: {}", response.message);
     }
-    Ok(())
+    ensure_container_version(file_path, container_version)
 }
 
 /// Extract files from an IoStore to legacy format
 pub fn extract_iostore(file_path: &str, output_path: &str, aes_key: Option<&str>) -> Result<usize> {
+    extract_iostore_with_progress(file_path, output_path, aes_key, &|_| {})
+}
+
+/// Extract files from an IoStore to legacy format, reporting each extracted file to `on_progress`
+pub fn extract_iostore_with_progress(
+    file_path: &str,
+    output_path: &str,
+    aes_key: Option<&str>,
+    on_progress: &dyn Fn(ToolProgress),
+) -> Result<usize> {
     let toolkit = get_global_toolkit()?;
     let request = UAssetRequest::ExtractIoStore {
         file_path: file_path.to_string(),
         output_path: output_path.to_string(),
         aes_key: aes_key.map(|s| s.to_string()),
+        progress: true,
     };
-    let response = toolkit.send_request(&request)?;
+    let response = toolkit.send_request_with_progress(&request, on_progress)?;
     if !response.success {
         anyhow::bail!("Failed to extract IoStore: {}", response.message);
     }
@@ -653,20 +948,99 @@ pub struct IoStoreResult {
     pub file_count: usize,
 }
 
+/// Magic at the start of every .utoc (FIoStoreTocHeader::TocMagicImg)
+const UTOC_MAGIC: &[u8; 16] = b"-==--==--==--==-";
+
+/// Read the TOC version byte from an existing .utoc header, so output can match the
+/// container format of the installed game build
+pub fn detect_iostore_version(utoc_path: &str) -> Result<u8> {
+    use std::io::Read;
+    let mut header = [0u8; 17];
+    std::fs::File::open(utoc_path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .with_context(|| format!("Failed to read IoStore header: {}", utoc_path))?;
+    if &header[..16] != UTOC_MAGIC {
+        anyhow::bail!("Not an IoStore container (bad .utoc magic): {}", utoc_path);
+    }
+    Ok(header[16])
+}
+
+/// Offset of ContainerFlags in FIoStoreTocHeader (after magic, version/reserved, nine u32
+/// counts/sizes, the u64 container id and the 16-byte encryption key guid)
+const UTOC_CONTAINER_FLAGS_OFFSET: usize = 80;
+
+/// EIoContainerFlags::Encrypted
+const IO_CONTAINER_FLAG_ENCRYPTED: u8 = 0x2;
+
+/// Read the Encrypted container flag straight from the .utoc header (no UAssetTool round-trip)
+pub fn is_iostore_encrypted_header(utoc_path: &str) -> Result<bool> {
+    use std::io::Read;
+    let mut header = [0u8; UTOC_CONTAINER_FLAGS_OFFSET + 1];
+    std::fs::File::open(utoc_path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .with_context(|| format!("Failed to read IoStore header: {}", utoc_path))?;
+    if &header[..16] != UTOC_MAGIC {
+        anyhow::bail!("Not an IoStore container (bad .utoc magic): {}", utoc_path);
+    }
+    Ok(header[UTOC_CONTAINER_FLAGS_OFFSET] & IO_CONTAINER_FLAG_ENCRYPTED != 0)
+}
+
+/// Result of probing an IoStore container with and without an AES key
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IoStoreEncryptionProbe {
+    /// The header has the Encrypted container flag set
+    pub encrypted: bool,
+    /// The container's file list could be read without any key
+    pub readable_without_key: bool,
+    /// The container's file list could be read with the given key
+    pub readable_with_key: bool,
+}
+
+/// Check whether an IoStore container is encrypted and whether `aes_key` unlocks it, by
+/// listing it once without a key and once with the key
+pub fn probe_iostore_encryption(utoc_path: &str, aes_key: &str) -> Result<IoStoreEncryptionProbe> {
+    let encrypted = is_iostore_encrypted_header(utoc_path)?;
+    let toolkit = get_global_toolkit()?;
+    let readable_without_key = toolkit.list_iostore_files(utoc_path, None).is_ok();
+    let readable_with_key = readable_without_key || toolkit.list_iostore_files(utoc_path, Some(aes_key)).is_ok();
+    Ok(IoStoreEncryptionProbe { encrypted, readable_without_key, readable_with_key })
+}
+
+/// Settings for `create_mod_iostore`
+#[derive(Debug, Clone, Default)]
+pub struct ModIoStoreOptions<'a> {
+    pub usmap_path: Option<&'a str>,
+    pub mount_point: Option<&'a str>,
+    pub compress: Option<bool>,
+    pub aes_key: Option<&'a str>,
+    /// When true, uses 75% of CPU threads; when false, uses 50%
+    pub parallel: bool,
+    /// Encrypt the container with `aes_key` to block extraction tools
+    pub obfuscate: bool,
+    /// Target .utoc TOC version (None = the tool's built-in default)
+    pub container_version: Option<u8>,
+}
+
+/// Fail when a container didn't come out at the requested TOC version. UAssetTool builds that
+/// don't know `container_version` ignore it and write their default version
+fn ensure_container_version(utoc_path: &str, requested: Option<u8>) -> Result<()> {
+    let Some(requested) = requested else {
+        return Ok(());
+    };
+    let written = detect_iostore_version(utoc_path)?;
+    if written != requested {
+        anyhow::bail!(
+            "Container version {} is unsupported by this UAssetTool (it wrote version {}); update UAssetTool or use the default version",
+            requested, written
+        );
+    }
+    Ok(())
+}
+
 /// Create mod IoStore
-/// parallel: when true, uses 75% of CPU threads; when false, uses 50%
-pub fn create_mod_iostore(
-    output_path: &str,
-    input_dir: &str,
-    usmap_path: Option<&str>,
-    mount_point: Option<&str>,
-    compress: Option<bool>,
-    aes_key: Option<&str>,
-    parallel: bool,
-    obfuscate: bool,
-) -> Result<IoStoreResult> {
+pub fn create_mod_iostore(output_path: &str, input_dir: &str, options: &ModIoStoreOptions) -> Result<IoStoreResult> {
     let toolkit = get_global_toolkit()?;
-    toolkit.create_mod_iostore(output_path, input_dir, usmap_path, mount_point, compress, aes_key, parallel, obfuscate)
+    toolkit.create_mod_iostore(output_path, input_dir, options)
 }
 
 /// Patch mesh materials
@@ -683,6 +1057,48 @@ pub fn patch_mesh(file_path: &str, uexp_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Engine object versions a package was cooked with (from its package file summary)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageVersion {
+    pub file_version_ue4: i32,
+    #[serde(default)]
+    pub file_version_ue5: i32,
+    #[serde(default)]
+    pub file_version_licensee_ue: i32,
+}
+
+/// Read the package file version / UE object versions from a .uasset header
+pub fn get_package_version(file_path: &str) -> Result<PackageVersion> {
+    let toolkit = get_global_toolkit()?;
+    let request = UAssetRequest::GetPackageVersion {
+        file_path: file_path.to_string(),
+    };
+    let response = toolkit.send_request(&request)?;
+    if !response.success {
+        anyhow::bail!("Failed to read package version: {}", response.message);
+    }
+    let data = response.data.ok_or_else(|| anyhow::anyhow!("No package version in response"))?;
+    Ok(serde_json::from_value(data)?)
+}
+
+/// Send several requests to UAssetTool in one round trip, one response per request
+pub fn batch(requests: Vec<UAssetRequest>) -> Result<Vec<UAssetResponse>> {
+    let toolkit = get_global_toolkit()?;
+    toolkit.batch(requests)
+}
+
+/// Texture info for several files in one round trip (using global singleton)
+pub fn batch_get_texture_info(file_paths: &[String]) -> Result<Vec<Option<TextureInfo>>> {
+    let toolkit = get_global_toolkit()?;
+    toolkit.batch_get_texture_info(file_paths)
+}
+
+/// Mesh info for several files in one round trip (using global singleton)
+pub fn batch_get_mesh_info(file_paths: &[String]) -> Result<Vec<Option<MeshInfo>>> {
+    let toolkit = get_global_toolkit()?;
+    toolkit.batch_get_mesh_info(file_paths)
+}
+
 /// List files in IoStore
 pub fn list_iostore_files(file_path: &str, aes_key: Option<&str>) -> Result<IoStoreListResult> {
     let toolkit = get_global_toolkit()?;
@@ -696,3 +1112,30 @@ pub struct IoStoreListResult {
     pub container_name: String,
     pub files: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn bounded_line_reads_lines_and_strips_line_endings() {
+        let mut reader = Cursor::new(b"{\"success\":true}\n{\"a\":1}\r\nlast".to_vec());
+        assert_eq!(read_bounded_line(&mut reader, 64).unwrap().as_deref(), Some("{\"success\":true}"));
+        assert_eq!(read_bounded_line(&mut reader, 64).unwrap().as_deref(), Some("{\"a\":1}"));
+        // A final line without a newline is still returned, then EOF
+        assert_eq!(read_bounded_line(&mut reader, 64).unwrap().as_deref(), Some("last"));
+        assert_eq!(read_bounded_line(&mut reader, 64).unwrap(), None);
+    }
+
+    #[test]
+    fn bounded_line_rejects_lines_over_the_cap() {
+        // Small buffer capacity so the line arrives across several fill_buf calls
+        let mut reader = std::io::BufReader::with_capacity(4, Cursor::new(b"0123456789\nok\n".to_vec()));
+        let err = read_bounded_line(&mut reader, 8).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let mut reader = Cursor::new(b"01234567\n".to_vec());
+        assert_eq!(read_bounded_line(&mut reader, 8).unwrap().as_deref(), Some("01234567"));
+    }
+}